@@ -0,0 +1,35 @@
+use ark_bn254::Fr;
+use multilinear::multilinear::MultilinearPolynomial;
+use std::time::{Duration, Instant};
+
+// Times the eq table builds against each other for growing numbers of variables, e.g.
+//   cargo run --release -p multilinear --example eq_table --features parallel -- 24
+// The argument is the largest number of variables, 20 by default. Each build runs three times and the fastest counts
+fn main() {
+    let max_vars: usize = std::env::args().nth(1).and_then(|arg| arg.parse().ok()).unwrap_or(20);
+
+    println!("{:>5} {:>12} {:>12} {:>12}", "vars", "naive", "in place", "parallel");
+    for no_of_vars in (12..=max_vars).step_by(2) {
+        let point: Vec<Fr> = (0..no_of_vars as u64).map(|i| Fr::from(i * 7919 + 13)).collect();
+
+        let naive = fastest(|| MultilinearPolynomial::naive_eq_evaluations(&point));
+        let in_place = fastest(|| MultilinearPolynomial::eq_evaluations_in_place(&point));
+        #[cfg(feature = "parallel")]
+        let parallel = format!("{:?}", fastest(|| MultilinearPolynomial::parallel_eq_evaluations(&point)));
+        #[cfg(not(feature = "parallel"))]
+        let parallel = String::from("-");
+
+        println!("{:>5} {:>12?} {:>12?} {:>12}", no_of_vars, naive, in_place, parallel);
+    }
+}
+
+fn fastest(build: impl Fn() -> Vec<Fr>) -> Duration {
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(build());
+            start.elapsed()
+        })
+        .min()
+        .expect("three runs")
+}
@@ -0,0 +1,36 @@
+use crate::multilinear::MultilinearPolynomial;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use proptest::prelude::*;
+
+// proptest generators, built for the tests of this crate and for other crates with the proptest feature.
+// Field elements are 32 random bytes reduced modulo p, the same as univariate_poly::arbitrary
+
+pub fn field_element<F: PrimeField>() -> impl Strategy<Value = F> {
+    any::<[u8; 32]>().prop_map(|bytes| F::from_le_bytes_mod_order(&bytes))
+}
+
+// A polynomial in exactly no_of_vars variables
+pub fn polynomial<F: PrimeField>(no_of_vars: usize) -> impl Strategy<Value = MultilinearPolynomial<F>> {
+    prop::collection::vec(field_element::<F>(), 1 << no_of_vars).prop_map(|values| MultilinearPolynomial::new(&values))
+}
+
+// A point to evaluate a polynomial in no_of_vars variables at
+pub fn point<F: PrimeField>(no_of_vars: usize) -> impl Strategy<Value = Vec<F>> {
+    prop::collection::vec(field_element::<F>(), no_of_vars)
+}
+
+// A polynomial in 1 to 4 variables and a point with as many coordinates
+pub fn polynomial_and_point<F: PrimeField>() -> impl Strategy<Value = (MultilinearPolynomial<F>, Vec<F>)> {
+    (1..=4usize).prop_flat_map(|no_of_vars| (polynomial(no_of_vars), point(no_of_vars)))
+}
+
+// Polynomials in 1 to 4 variables
+impl <F: PrimeField>Arbitrary for MultilinearPolynomial<F> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (1..=4usize).prop_flat_map(polynomial).boxed()
+    }
+}
@@ -0,0 +1,89 @@
+use crate::multilinear::MultilinearPolynomial;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use ark_poly::DenseMultilinearExtension;
+
+// Conversions to and from arkworks' DenseMultilinearExtension, for its commitment schemes and sumcheck tooling.
+//
+// Both store the 2^n evaluations over the hypercube, but in a different order. Here variable 0 is the most significant
+// bit of the index, so fixing it folds the low half of the table into the high half; arkworks takes variable 0 to be
+// the least significant bit. The same table read by both would be the same polynomial with its variables in reverse
+// order, so the conversions move entry i to the index with the n bits of i reversed, and the two sides agree at every
+// point: f(r) here is g(r) there
+impl <F: PrimeField>From<&MultilinearPolynomial<F>> for DenseMultilinearExtension<F> {
+    fn from(polynomial: &MultilinearPolynomial<F>) -> Self {
+        DenseMultilinearExtension::from_evaluations_vec(polynomial.no_of_vars, bit_reversed(&polynomial.evaluated_values, polynomial.no_of_vars))
+    }
+}
+
+impl <F: PrimeField>From<MultilinearPolynomial<F>> for DenseMultilinearExtension<F> {
+    fn from(polynomial: MultilinearPolynomial<F>) -> Self {
+        Self::from(&polynomial)
+    }
+}
+
+impl <F: PrimeField>From<&DenseMultilinearExtension<F>> for MultilinearPolynomial<F> {
+    fn from(extension: &DenseMultilinearExtension<F>) -> Self {
+        MultilinearPolynomial::new(&bit_reversed(&extension.evaluations, extension.num_vars))
+    }
+}
+
+impl <F: PrimeField>From<DenseMultilinearExtension<F>> for MultilinearPolynomial<F> {
+    fn from(extension: DenseMultilinearExtension<F>) -> Self {
+        Self::from(&extension)
+    }
+}
+
+// The table with entry i at the index whose no_of_vars low bits are those of i reversed. Reversing twice is the
+// identity, so the same function converts both ways
+fn bit_reversed<F: PrimeField>(values: &[F], no_of_vars: usize) -> Vec<F> {
+    if no_of_vars == 0 {
+        return values.to_vec();
+    }
+    let shift = usize::BITS as usize - no_of_vars;
+    (0..values.len()).map(|i| values[i.reverse_bits() >> shift]).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrary::polynomial_and_point;
+    use ark_bn254::Fr;
+    use ark_poly::{MultilinearExtension, Polynomial};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_variable_order() {
+        // f(x0, x1) = 3 * x0 + x1 here, with x0 the high bit
+        let polynomial = MultilinearPolynomial::new(&[Fr::from(0), Fr::from(1), Fr::from(3), Fr::from(4)]);
+        let extension = DenseMultilinearExtension::from(&polynomial);
+
+        // arkworks lists the table with x0 as the low bit
+        assert_eq!(extension.evaluations, vec![Fr::from(0), Fr::from(3), Fr::from(1), Fr::from(4)]);
+        // fixing x0 to 1 on either side leaves 3 + x1
+        assert_eq!(
+            MultilinearPolynomial::from(extension.fix_variables(&[Fr::from(1)])),
+            MultilinearPolynomial::partial_evaluate(&polynomial.evaluated_values, 0, Fr::from(1))
+        );
+    }
+
+    #[test]
+    fn test_constant_polynomial() {
+        let polynomial = MultilinearPolynomial::new(&[Fr::from(7)]);
+        let extension = DenseMultilinearExtension::from(&polynomial);
+
+        assert_eq!(extension.num_vars, 0);
+        assert_eq!(MultilinearPolynomial::from(extension), polynomial);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_conversions_agree_everywhere((polynomial, point) in polynomial_and_point::<Fr>()) {
+            let extension = DenseMultilinearExtension::from(&polynomial);
+
+            prop_assert_eq!(extension.evaluate(&point), polynomial.evaluate(&point));
+            prop_assert_eq!(MultilinearPolynomial::from(extension), polynomial);
+        }
+    }
+}
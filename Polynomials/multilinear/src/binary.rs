@@ -0,0 +1,283 @@
+use alloc::vec::Vec;
+use ark_ff::{One, Zero};
+use core::fmt;
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// GF(2^64), the binary field of polynomials over GF(2) modulo x^64 + x^4 + x^3 + x + 1, for boolean circuit experiments.
+//
+// An element is a polynomial of degree < 64 with its coefficients as the bits of a u64, bit i for x^i. Addition is
+// XOR, so every element is its own negative and subtraction is addition too, and multiplication is the carryless
+// product reduced modulo the pentanomial. A bit of a boolean circuit is 0 or 1 here with no encoding, and XOR gates
+// are additions, which is why hash-like circuits are so much cheaper over binary fields than over a prime one.
+//
+// The field has characteristic 2, so it is not an ark_ff PrimeField and none of the generic code takes it: 2 = 0, and
+// a protocol that reads a round polynomial from its values at 0, 1, 2, ... has to pick other points. Multilinear
+// rounds only need 0 and 1, so the fold and the sumcheck work unchanged, see BinaryMultilinearPolynomial and
+// sumcheck::binary
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Gf2_64(pub u64);
+
+// x^4 + x^3 + x + 1, what x^64 reduces to
+const REDUCTION: u64 = 0x1b;
+
+impl Gf2_64 {
+    // The element with the first 8 bytes as its bits, little endian, e.g. to read a challenge out of a hash
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        let mut limb = [0u8; 8];
+        limb.copy_from_slice(&bytes[..8]);
+        Self(u64::from_le_bytes(limb))
+    }
+
+    pub fn to_bytes(self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        let mut result = Self::one();
+        for bit in (0..64).rev() {
+            result = result.square();
+            if (exponent >> bit) & 1 == 1 {
+                result *= self;
+            }
+        }
+        result
+    }
+
+    // a^(2^64 - 2), the inverse by Fermat, None for zero
+    pub fn inverse(self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(self.pow(u64::MAX - 1))
+    }
+}
+
+// Carryless multiplication of the two polynomials, then reduction of the bits above 63 from the top down
+fn multiply(left: u64, right: u64) -> u64 {
+    let mut product: u128 = 0;
+    for bit in 0..64 {
+        if (right >> bit) & 1 == 1 {
+            product ^= (left as u128) << bit;
+        }
+    }
+
+    for bit in (64..128).rev() {
+        if (product >> bit) & 1 == 1 {
+            product ^= (1u128 << bit) ^ ((REDUCTION as u128) << (bit - 64));
+        }
+    }
+    product as u64
+}
+
+impl From<u64> for Gf2_64 {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<bool> for Gf2_64 {
+    fn from(bit: bool) -> Self {
+        Self(bit as u64)
+    }
+}
+
+impl Add for Gf2_64 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+}
+
+impl Sub for Gf2_64 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+}
+
+impl Mul for Gf2_64 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(multiply(self.0, other.0))
+    }
+}
+
+impl Neg for Gf2_64 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl AddAssign for Gf2_64 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Gf2_64 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Gf2_64 {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl Zero for Gf2_64 {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl One for Gf2_64 {
+    fn one() -> Self {
+        Self(1)
+    }
+}
+
+impl Sum for Gf2_64 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Gf2_64> for Gf2_64 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl Product for Gf2_64 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Mul::mul)
+    }
+}
+
+impl fmt::Debug for Gf2_64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Gf2_64({:#018x})", self.0)
+    }
+}
+
+impl fmt::Display for Gf2_64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#018x}", self.0)
+    }
+}
+
+// A multilinear polynomial over GF(2^64) as its evaluations on the boolean hypercube, with the layout of
+// MultilinearPolynomial: variable 0 is the most significant bit of the index.
+//
+// The fold is the same y1 + r * (y2 - y1), and over GF(2) inputs the evaluations of a boolean circuit's wires are
+// 0 and 1 with no cost to encode them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryMultilinearPolynomial {
+    pub evaluated_values: Vec<Gf2_64>,
+    pub no_of_vars: usize,
+}
+
+impl BinaryMultilinearPolynomial {
+    pub fn new(evaluated_values: &[Gf2_64]) -> Self {
+        assert!(evaluated_values.len().is_power_of_two(), "the number of evaluations must be a power of two");
+        Self { evaluated_values: evaluated_values.to_vec(), no_of_vars: evaluated_values.len().ilog2() as usize }
+    }
+
+    // The polynomial of a table of bits, e.g. one wire of a boolean circuit over every input
+    pub fn from_bits(bits: &[bool]) -> Self {
+        Self::new(&bits.iter().map(|bit| Gf2_64::from(*bit)).collect::<Vec<_>>())
+    }
+
+    // Binds variable 0 to value: the low half is the table at x_0 = 0 and the high half at x_0 = 1
+    pub fn fold(&self, value: Gf2_64) -> Self {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("binary_mle_fold", size = self.evaluated_values.len()).entered();
+
+        let (low, high) = self.evaluated_values.split_at(self.evaluated_values.len() / 2);
+        let folded: Vec<Gf2_64> = low.iter().zip(high).map(|(y1, y2)| *y1 + value * (*y2 - *y1)).collect();
+        Self { evaluated_values: folded, no_of_vars: self.no_of_vars.saturating_sub(1) }
+    }
+
+    pub fn evaluate(&self, point: &[Gf2_64]) -> Gf2_64 {
+        assert_eq!(point.len(), self.no_of_vars, "the point must have one coordinate per variable");
+        point.iter().fold(self.clone(), |polynomial, value| polynomial.fold(*value)).evaluated_values[0]
+    }
+
+    pub fn sum(&self) -> Gf2_64 {
+        self.evaluated_values.iter().sum()
+    }
+
+    // The round polynomial of a sumcheck over this table at 0 and 1: the sums of the low and high halves
+    pub fn round_evaluations(&self) -> [Gf2_64; 2] {
+        let (low, high) = self.evaluated_values.split_at(self.evaluated_values.len() / 2);
+        [low.iter().sum(), high.iter().sum()]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_arithmetic() {
+        let a = Gf2_64(0x0123_4567_89ab_cdef);
+        let b = Gf2_64(0xfedc_ba98_7654_3210);
+
+        // characteristic 2: everything is its own negative, and 1 + 1 = 0
+        assert_eq!(a + a, Gf2_64::zero());
+        assert_eq!(Gf2_64::one() + Gf2_64::one(), Gf2_64::zero());
+        assert_eq!(a - b, a + b);
+
+        // x * x^63 = x^64 reduces to x^4 + x^3 + x + 1
+        assert_eq!(Gf2_64(2) * Gf2_64(1 << 63), Gf2_64(0x1b));
+        assert_eq!(a * b, b * a);
+        assert_eq!(a * (b + Gf2_64(7)), a * b + a * Gf2_64(7));
+        assert_eq!(a * a.inverse().unwrap(), Gf2_64::one());
+        assert_eq!(Gf2_64::zero().inverse(), None);
+    }
+
+    #[test]
+    fn test_modulus_is_irreducible() {
+        // x^(2^64) = x holds when every factor of the modulus has a degree dividing 64, x^(2^32) != x rules out all
+        // of them dividing 32, so the modulus is irreducible and every nonzero element has an inverse
+        let square_times = |times: usize| (0..times).fold(Gf2_64(2), |value, _| value.square());
+        assert_eq!(square_times(64), Gf2_64(2));
+        assert_ne!(square_times(32), Gf2_64(2));
+    }
+
+    #[test]
+    fn test_fold_and_evaluate_over_bits() {
+        // the AND of two bits: 1 only at (1, 1)
+        let and = BinaryMultilinearPolynomial::from_bits(&[false, false, false, true]);
+        let (r0, r1) = (Gf2_64(0x1234), Gf2_64(0xbeef));
+
+        // AND extends to x_0 * x_1
+        assert_eq!(and.evaluate(&[r0, r1]), r0 * r1);
+        assert_eq!(and.evaluate(&[Gf2_64::one(), Gf2_64::one()]), Gf2_64::one());
+        assert_eq!(and.fold(r0).evaluated_values, vec![Gf2_64::zero(), r0]);
+
+        // one set bit sums to 1, and the round polynomial adds up to the sum
+        let [at_0, at_1] = and.round_evaluations();
+        assert_eq!(and.sum(), Gf2_64::one());
+        assert_eq!(at_0 + at_1, and.sum());
+    }
+}
@@ -0,0 +1,276 @@
+use crate::multilinear::MultilinearPolynomial;
+use alloc::vec::Vec;
+use ark_ff::{BigInteger, PrimeField};
+use core::marker::PhantomData;
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+// Evaluation tables too big for memory, kept in a file and memory-mapped. A 2^28 table over a 256 bit field is 8 GiB,
+// more than a laptop wants to allocate, but the operating system pages a mapped file in and out on its own, so the
+// table can be read in chunks of a size that fits and folded one chunk at a time.
+//
+// The file holds the 2^n evaluations in hypercube order and nothing else, each the little-endian bytes of its canonical
+// integer padded to the limbs of the field, 32 bytes for BN254, the order the limbs are in memory rather than
+// transcript::encoding's big-endian, since these bytes are storage and never hashed. EvaluationWriter streams a table out in that format,
+// so a table can be produced without ever being in memory as a whole either
+pub struct EvaluationWriter<F: PrimeField> {
+    writer: BufWriter<File>,
+    len: usize,
+    _field: PhantomData<F>,
+}
+
+pub struct MappedMultilinear<F: PrimeField> {
+    map: Mmap,
+    pub no_of_vars: usize,
+    _field: PhantomData<F>,
+}
+
+fn element_bytes<F: PrimeField>() -> usize {
+    <F::BigInt as BigInteger>::NUM_LIMBS * 8
+}
+
+fn is_canonical<F: PrimeField>(bytes: &[u8]) -> bool {
+    F::from_le_bytes_mod_order(bytes).into_bigint().to_bytes_le() == bytes
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// The file is fine but the caller asked for something the table does not have
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+impl <F: PrimeField>EvaluationWriter<F> {
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self { writer: BufWriter::new(File::create(path)?), len: 0, _field: PhantomData })
+    }
+
+    pub fn write(&mut self, values: &[F]) -> io::Result<()> {
+        for value in values {
+            self.writer.write_all(&value.into_bigint().to_bytes_le())?;
+        }
+        self.len += values.len();
+        Ok(())
+    }
+
+    // Flushes the file and returns the number of evaluations written, which has to be a power of two
+    pub fn finish(mut self) -> io::Result<usize> {
+        self.writer.flush()?;
+        if !self.len.is_power_of_two() {
+            return Err(invalid_data("a multilinear table holds a power of two evaluations"));
+        }
+        Ok(self.len)
+    }
+}
+
+impl <F: PrimeField>MappedMultilinear<F> {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the table is only read through the map, and nothing in this crate writes to a file while it is
+        // mapped. Another process truncating the file underneath would make reads fault, as with any mapped file
+        let map = unsafe { Mmap::map(&file)? };
+
+        if map.len() % element_bytes::<F>() != 0 || !(map.len() / element_bytes::<F>()).is_power_of_two() {
+            return Err(invalid_data("the file is not a power of two field elements"));
+        }
+        // one pass over the file, a page at a time, so chunk never has to reduce an integer at or above the modulus:
+        // such an entry would read back as another element than the one the bytes spell
+        if !map.chunks_exact(element_bytes::<F>()).all(is_canonical::<F>) {
+            return Err(invalid_data("an evaluation is not the canonical encoding of a field element"));
+        }
+        let no_of_vars = (map.len() / element_bytes::<F>()).ilog2() as usize;
+        Ok(Self { map, no_of_vars, _field: PhantomData })
+    }
+
+    pub fn len(&self) -> usize {
+        1 << self.no_of_vars
+    }
+
+    // open refuses a file without a whole table, so there is always at least one evaluation
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    // The evaluations start..start + len. open checked every entry is canonical, so reading them back reduces nothing
+    pub fn chunk(&self, start: usize, len: usize) -> io::Result<Vec<F>> {
+        if start.checked_add(len).is_none_or(|end| end > self.len()) {
+            return Err(invalid_input("the chunk goes past the end of the table"));
+        }
+
+        let size = element_bytes::<F>();
+        Ok(self.map[start * size..(start + len) * size].chunks_exact(size).map(F::from_le_bytes_mod_order).collect())
+    }
+
+    // The whole table in memory, for once folding has made it small enough
+    pub fn to_polynomial(&self) -> io::Result<MultilinearPolynomial<F>> {
+        Ok(MultilinearPolynomial::new(&self.chunk(0, self.len())?))
+    }
+
+    // f(point) with chunks of 2^chunk_vars evaluations. A chunk fixes the first n - chunk_vars variables, which are
+    // the high bits of the index, to the chunk's number c, so
+    //   f(r) = sum over c of eq(r_high, c) * f_c(r_low)
+    // where f_c is the chunk as a polynomial in the last chunk_vars variables. That needs one chunk and the
+    // 2^(n - chunk_vars) eq values in memory at a time, rather than the table
+    pub fn evaluate(&self, point: &[F], chunk_vars: usize) -> io::Result<F> {
+        if point.len() != self.no_of_vars {
+            return Err(invalid_input("the point must have one coordinate per variable"));
+        }
+
+        let chunk_vars = chunk_vars.min(self.no_of_vars);
+        let (high, low) = point.split_at(self.no_of_vars - chunk_vars);
+        let chunk_len = 1 << chunk_vars;
+
+        MultilinearPolynomial::eq_evaluations(high)
+            .into_iter()
+            .enumerate()
+            .map(|(c, eq)| Ok(eq * MultilinearPolynomial::new(&self.chunk(c * chunk_len, chunk_len)?).evaluate(low)))
+            .sum()
+    }
+
+    // Fixes variable 0 to value, the same fold as partial_evaluate(.., 0, value), streamed into writer. Variable 0 is
+    // the top bit of the index, so the fold pairs the low and the high half of the table entry by entry and reads
+    // both halves chunk_len entries at a time
+    pub fn partial_evaluate_into(&self, value: F, writer: &mut EvaluationWriter<F>, chunk_len: usize) -> io::Result<()> {
+        if self.no_of_vars == 0 {
+            return Err(invalid_input("a constant has no variable to fix"));
+        }
+        if chunk_len == 0 {
+            return Err(invalid_input("chunks hold at least one evaluation"));
+        }
+
+        let half = self.len() / 2;
+        let mut start = 0;
+        while start < half {
+            let len = chunk_len.min(half - start);
+            let folded: Vec<F> = self
+                .chunk(start, len)?
+                .into_iter()
+                .zip(self.chunk(half + start, len)?)
+                .map(|(low, high)| low + value * (high - low))
+                .collect();
+            writer.write(&folded)?;
+            start += len;
+        }
+        Ok(())
+    }
+
+    // The fold above into a new file at path, mapped back in for the next round
+    pub fn partial_evaluate_to_file<P: AsRef<Path>>(&self, value: F, path: P, chunk_len: usize) -> io::Result<Self> {
+        let mut writer = EvaluationWriter::create(&path)?;
+        self.partial_evaluate_into(value, &mut writer, chunk_len)?;
+        writer.finish()?;
+        Self::open(path)
+    }
+}
+
+impl <F: PrimeField>MultilinearPolynomial<F> {
+    // Maps a table written by EvaluationWriter or write_to_file without reading it into memory
+    pub fn from_mmap<P: AsRef<Path>>(path: P) -> io::Result<MappedMultilinear<F>> {
+        MappedMultilinear::open(path)
+    }
+
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = EvaluationWriter::create(path)?;
+        writer.write(&self.evaluated_values)?;
+        writer.finish().map(|_| ())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use std::path::PathBuf;
+
+    // A file in the temp directory that is removed again when the test is done with it
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(format!("mle-{}-{}.bin", name, std::process::id())))
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn polynomial(no_of_vars: usize) -> MultilinearPolynomial<Fr> {
+        let values: Vec<Fr> = (0..1u64 << no_of_vars).map(|i| Fr::from(i * i + 7) - Fr::from(100)).collect();
+        MultilinearPolynomial::new(&values)
+    }
+
+    #[test]
+    fn test_mapped_table_evaluates_like_the_table() {
+        let file = TempFile::new("evaluate");
+        let polynomial = polynomial(6);
+        polynomial.write_to_file(&file.0).unwrap();
+
+        let mapped = MultilinearPolynomial::<Fr>::from_mmap(&file.0).unwrap();
+        assert_eq!(mapped.no_of_vars, 6);
+        assert_eq!(mapped.to_polynomial().unwrap(), polynomial);
+
+        let point: Vec<Fr> = (0..6u64).map(|i| Fr::from(3 * i + 2)).collect();
+        // chunks of one evaluation up to the whole table, and a chunk size larger than the table
+        for chunk_vars in [0, 2, 6, 9] {
+            assert_eq!(mapped.evaluate(&point, chunk_vars).unwrap(), polynomial.evaluate(&point));
+        }
+
+        // the caller's mistakes are errors, the table is left as it is
+        let invalid_input = Some(io::ErrorKind::InvalidInput);
+        assert_eq!(mapped.evaluate(&point[1..], 2).err().map(|error| error.kind()), invalid_input);
+        assert_eq!(mapped.chunk(60, 5).err().map(|error| error.kind()), invalid_input);
+        assert_eq!(mapped.chunk(1, usize::MAX).err().map(|error| error.kind()), invalid_input);
+        let fold_file = TempFile::new("evaluate_fold");
+        let mut writer = EvaluationWriter::<Fr>::create(&fold_file.0).unwrap();
+        assert_eq!(mapped.partial_evaluate_into(Fr::from(1), &mut writer, 0).err().map(|error| error.kind()), invalid_input);
+    }
+
+    #[test]
+    fn test_chunked_fold_matches_partial_evaluate() {
+        let (file, folded_file) = (TempFile::new("fold"), TempFile::new("folded"));
+        let polynomial = polynomial(5);
+        polynomial.write_to_file(&file.0).unwrap();
+        let mapped = MappedMultilinear::<Fr>::open(&file.0).unwrap();
+
+        // a chunk length that does not divide the half exercises the last short chunk
+        let folded = mapped.partial_evaluate_to_file(Fr::from(11), &folded_file.0, 3).unwrap();
+        assert_eq!(folded.no_of_vars, 4);
+        assert_eq!(
+            folded.to_polynomial().unwrap(),
+            MultilinearPolynomial::partial_evaluate(&polynomial.evaluated_values, 0, Fr::from(11))
+        );
+    }
+
+    #[test]
+    fn test_malformed_files_are_rejected() {
+        let file = TempFile::new("malformed");
+
+        // three field elements are not a table
+        let mut writer = EvaluationWriter::<Fr>::create(&file.0).unwrap();
+        writer.write(&[Fr::from(1), Fr::from(2), Fr::from(3)]).unwrap();
+        assert_eq!(writer.finish().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert!(MappedMultilinear::<Fr>::open(&file.0).is_err());
+
+        // nor is a number of bytes that does not split into field elements
+        std::fs::write(&file.0, [0u8; 40]).unwrap();
+        assert_eq!(MappedMultilinear::<Fr>::open(&file.0).err().map(|error| error.kind()), Some(io::ErrorKind::InvalidData));
+
+        // nor an entry at or above the modulus, the modulus itself here, which would read back as zero
+        let mut bytes = [0u8; 64];
+        bytes[32..].copy_from_slice(&Fr::MODULUS.to_bytes_le());
+        std::fs::write(&file.0, bytes).unwrap();
+        assert_eq!(MappedMultilinear::<Fr>::open(&file.0).err().map(|error| error.kind()), Some(io::ErrorKind::InvalidData));
+        // p - 1, the largest canonical entry, is accepted
+        bytes[32..].copy_from_slice(&(-Fr::from(1)).into_bigint().to_bytes_le());
+        std::fs::write(&file.0, bytes).unwrap();
+        assert!(MappedMultilinear::<Fr>::open(&file.0).is_ok());
+    }
+}
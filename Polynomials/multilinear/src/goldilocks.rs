@@ -0,0 +1,57 @@
+use ark_ff::fields::{Fp64, MontBackend, MontConfig};
+
+// The Goldilocks field, p = 2^64 - 2^32 + 1, the 64-bit field of Plonky2.
+// Elements fit in one limb, so the small-field paths get tested with a field where challenges and sums wrap
+// quickly, and p - 1 = 2^32 * (2^32 - 1) gives it two-adicity 32, enough for radix-2 domains of size up to 2^32.
+// 7 generates the multiplicative group
+#[derive(MontConfig)]
+#[modulus = "18446744069414584321"]
+#[generator = "7"]
+pub struct GoldilocksConfig;
+
+pub type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
+
+// The same tests for each field, e.g. field_tests!(bn254: Fr, goldilocks: Goldilocks => { proptest! { ... } }): the
+// tests go in a module per field that sees its parent's imports, written against the alias F for that module's field.
+// Exported so the tests of crates above this one instantiate theirs the same way
+#[macro_export]
+macro_rules! field_tests {
+    (@module $module:ident, $field:ty, { $($tests:tt)* }) => {
+        mod $module {
+            use super::*;
+
+            type F = $field;
+
+            $($tests)*
+        }
+    };
+    ($($module:ident: $field:ty),+ $(,)? => $tests:tt) => {
+        $($crate::field_tests!(@module $module, $field, $tests);)+
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{FftField, Field, PrimeField};
+
+    #[test]
+    fn test_goldilocks_arithmetic_wraps_at_the_modulus() {
+        let minus_one = Goldilocks::from(0u64) - Goldilocks::from(1u64);
+
+        assert_eq!(minus_one.into_bigint().0[0], 18446744069414584320);
+        assert_eq!(minus_one + Goldilocks::from(1u64), Goldilocks::from(0u64));
+        assert_eq!(Goldilocks::from(u64::MAX), Goldilocks::from((1u64 << 32) - 2));
+        assert_eq!(Goldilocks::from(3u64).inverse().unwrap() * Goldilocks::from(3u64), Goldilocks::from(1u64));
+    }
+
+    #[test]
+    fn test_goldilocks_has_two_adicity_32() {
+        let root = Goldilocks::get_root_of_unity(1 << 32).unwrap();
+
+        assert_eq!(Goldilocks::TWO_ADICITY, 32);
+        assert_eq!(root.pow([1u64 << 32]), Goldilocks::from(1u64));
+        assert_ne!(root.pow([1u64 << 31]), Goldilocks::from(1u64));
+    }
+}
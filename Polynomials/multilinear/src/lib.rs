@@ -1 +1,16 @@
-pub mod multilinear;
\ No newline at end of file
+// Builds without std when the default std feature is off, the polynomials only need alloc
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod multilinear;
+pub mod binary;
+pub mod goldilocks;
+pub mod virtual_poly;
+pub mod serialize;
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
+#[cfg(any(test, feature = "mmap"))]
+pub mod disk;
+#[cfg(any(test, feature = "ark-poly"))]
+pub mod ark;
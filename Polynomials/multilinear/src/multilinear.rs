@@ -1,4 +1,7 @@
-use ark_ff::{PrimeField, BigInteger};
+use alloc::vec;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use transcript::encoding::field_to_bytes;
 
 // This implementation of Multi linear interpolation uses an evaluation over the boolean hypercube
 // then the values from the boolean hypercube evaluation is used as the polynomial
@@ -11,7 +14,7 @@ pub struct MultilinearPolynomial<F: PrimeField> {
 
 
 impl <F: PrimeField>MultilinearPolynomial<F> {
-    pub fn new(evaluated_values: &Vec<F>) -> Self {
+    pub fn new(evaluated_values: &[F]) -> Self {
         let no_of_vars = evaluated_values.len().ilog2() as usize;
         Self {
             evaluated_values: evaluated_values.to_vec(),
@@ -20,7 +23,7 @@ impl <F: PrimeField>MultilinearPolynomial<F> {
     }
 
     // The evaluate function calls the partial evaluate multiple times
-    pub fn evaluate(&self, values: &Vec<F>) -> F {
+    pub fn evaluate(&self, values: &[F]) -> F {
         let mut r_polynomial = self.clone();
         let expected_number_of_partial_eval = values.len();
 
@@ -34,11 +37,12 @@ impl <F: PrimeField>MultilinearPolynomial<F> {
         r_polynomial.evaluated_values[0]
     }
 
+    // The evaluations one after the other, each in the shared encoding transcripts absorb
     pub fn convert_to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
 
         for value in &self.evaluated_values {
-            bytes.extend(value.into_bigint().to_bytes_be());
+            bytes.extend(field_to_bytes(*value));
         }
 
         bytes
@@ -55,7 +59,10 @@ impl <F: PrimeField>MultilinearPolynomial<F> {
 
     // This function will receive a polynomial in it's evaluated form
     // That means the polynomial it will receive has already been evaluated over a boolean hypercube
-    pub fn partial_evaluate(polynomial: &Vec<F>, evaluating_variable: usize, value: F) -> Self {
+    pub fn partial_evaluate(polynomial: &[F], evaluating_variable: usize, value: F) -> Self {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("mle_fold", size = polynomial.len(), variable = evaluating_variable).entered();
+
         let polynomial_size = polynomial.len();
         let expected_polynomial_size = polynomial_size / 2;
         let mut result_polynomial: Vec<F> = Vec::with_capacity(expected_polynomial_size);
@@ -101,26 +108,178 @@ impl <F: PrimeField>MultilinearPolynomial<F> {
         MultilinearPolynomial::new(&result_polynomial)
     }
 
+    // Evaluations of eq(x, point) over the boolean hypercube, where
+    // eq(x, r) = product of (x_i * r_i + (1 - x_i)(1 - r_i)) is 1 when x = r and 0 on every other boolean x.
+    // The table is built one variable at a time, each step doubling it: entry e becomes [e * (1 - r_i), e * r_i].
+    // Variable 0 is bound first so it ends up as the most significant bit, the same layout partial_evaluate uses.
+    // With the parallel feature the table is built by parallel_eq_evaluations, otherwise in place
+    pub fn eq_evaluations(point: &[F]) -> Vec<F> {
+        #[cfg(feature = "parallel")]
+        return Self::parallel_eq_evaluations(point);
+
+        #[cfg(not(feature = "parallel"))]
+        Self::eq_evaluations_in_place(point)
+    }
+
+    // The doubling with a new table every step, the reference the faster builds are tested and benchmarked against
+    pub fn naive_eq_evaluations(point: &[F]) -> Vec<F> {
+        let mut table = vec![F::one()];
+
+        for r in point {
+            let mut next_table = Vec::with_capacity(table.len() * 2);
+            for value in &table {
+                let high = *value * r;
+                next_table.push(*value - high);
+                next_table.push(high);
+            }
+            table = next_table;
+        }
+
+        table
+    }
+
+    // The same doubling in one buffer of the final size: a step reads the first 2^i entries from the back and writes
+    // entry j to 2j and 2j + 1, which never overwrites an entry it has yet to read. One allocation instead of one per
+    // variable, and one multiplication per entry
+    pub fn eq_evaluations_in_place(point: &[F]) -> Vec<F> {
+        let mut table = vec![F::zero(); 1 << point.len()];
+        table[0] = F::one();
+        Self::expand_eq(&mut table, point);
+        table
+    }
+
+    // Doubles table[..1] up to the full table for point, see eq_evaluations_in_place
+    fn expand_eq(table: &mut [F], point: &[F]) {
+        for (i, r) in point.iter().enumerate() {
+            for j in (0..1 << i).rev() {
+                let value = table[j];
+                let high = value * r;
+                table[2 * j] = value - high;
+                table[2 * j + 1] = high;
+            }
+        }
+    }
+
+    // Block by block on rayon's thread pool. Variable 0 is the most significant bit, so the table is 2^k blocks, block
+    // b being the eq table of the last n - k variables scaled by entry b of the eq table of the first k. Both small
+    // tables are built in place, and the blocks are filled in parallel, each one a contiguous run that stays in the
+    // cache of the thread writing it
+    #[cfg(feature = "parallel")]
+    pub fn parallel_eq_evaluations(point: &[F]) -> Vec<F> {
+        use rayon::prelude::*;
+
+        // a few blocks per thread to even out the load, and no splitting of tables too small to be worth it
+        const MIN_BLOCK_VARS: usize = 10;
+        let threads = rayon::current_num_threads().max(1);
+        let high_vars = (4 * threads).next_power_of_two().ilog2() as usize;
+        let high_vars = high_vars.min(point.len().saturating_sub(MIN_BLOCK_VARS));
+        if high_vars == 0 {
+            return Self::eq_evaluations_in_place(point);
+        }
+
+        let (high, low) = point.split_at(high_vars);
+        let scales = Self::eq_evaluations_in_place(high);
+        let block = Self::eq_evaluations_in_place(low);
+
+        let mut table = vec![F::zero(); 1 << point.len()];
+        table.par_chunks_mut(block.len()).zip(scales.par_iter()).for_each(|(chunk, scale)| {
+            for (entry, value) in chunk.iter_mut().zip(&block) {
+                *entry = *value * scale;
+            }
+        });
+        table
+    }
+
+    // eq(left, right) for two points of the same length, the value the table above holds at right when built for left
+    pub fn eq_evaluate(left: &[F], right: &[F]) -> F {
+        assert_eq!(left.len(), right.len(), "points must have the same number of variables");
+
+        left.iter()
+            .zip(right.iter())
+            .map(|(l, r)| *l * r + (F::one() - l) * (F::one() - r))
+            .product()
+    }
+
+    // The multilinear extension of a table that is zero outside entries, at a point, in O(entries * no_of_vars)
+    // instead of the O(2^no_of_vars) of building the table and folding it. An entry (index, value) contributes value
+    // times eq(index, point), the product over the bits of index of r_j where the bit is set and 1 - r_j where it is
+    // not. The indices must be distinct and below 2^point.len(); a repeated index counts once per entry
+    pub fn evaluate_sparse(entries: &[(usize, F)], point: &[F]) -> F {
+        let no_of_vars = point.len();
+        let complements: Vec<F> = point.iter().map(|r| F::one() - r).collect();
+
+        entries
+            .iter()
+            .map(|(index, value)| {
+                assert!(no_of_vars >= usize::BITS as usize || index >> no_of_vars == 0, "index {} needs more than {} bits", index, no_of_vars);
+                // variable 0 is the most significant bit of the index
+                (0..no_of_vars).fold(*value, |product, j| {
+                    let bit = (index >> (no_of_vars - 1 - j)) & 1;
+                    product * if bit == 1 { point[j] } else { complements[j] }
+                })
+            })
+            .sum()
+    }
+
+    // The table padded with zeros to the next power of two, so 3 * 2^k values become a polynomial in k + 2 variables
+    // whose sum over the hypercube is still the sum of the values. new() takes the table as it is and reads only the
+    // first 2^floor(log2(len)) values of one that is not a power of two
+    pub fn padded(evaluated_values: &[F]) -> Self {
+        let mut values = evaluated_values.to_vec();
+        values.resize(evaluated_values.len().max(1).next_power_of_two(), F::zero());
+        Self::new(&values)
+    }
+
+    // The table of the indicator of the first `length` points of the hypercube in no_of_vars variables, 1 at every
+    // index below length and 0 above. Multiplying a padded table by it zeroes the padding whatever it holds
+    pub fn prefix_indicator_evaluations(length: usize, no_of_vars: usize) -> Vec<F> {
+        (0..1usize << no_of_vars).map(|index| if index < length { F::one() } else { F::zero() }).collect()
+    }
+
+    // The multilinear extension of prefix_indicator_evaluations at a point, in O(no_of_vars) instead of O(2^n).
+    // The indices below length are, for every set bit j of length, those agreeing with it above bit j and having a
+    // 0 at bit j, so the value is the sum over the set bits of eq of the bits above times (1 - r_j)
+    pub fn evaluate_prefix_indicator(length: usize, point: &[F]) -> F {
+        let no_of_vars = point.len();
+        if no_of_vars < usize::BITS as usize && length >= 1 << no_of_vars {
+            return F::one();
+        }
+
+        let mut prefix_eq = F::one();
+        let mut sum = F::zero();
+        for (j, r) in point.iter().enumerate() {
+            // variable 0 is the most significant bit of the index
+            if (length >> (no_of_vars - 1 - j)) & 1 == 1 {
+                sum += prefix_eq * (F::one() - r);
+                prefix_eq *= r;
+            } else {
+                prefix_eq *= F::one() - r;
+            }
+        }
+        sum
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::arbitrary::polynomial_and_point;
     use ark_bn254::Fq;
+    use proptest::prelude::*;
 
     #[test]
     fn test_partial_evaluate() {
         let polynomial = vec![Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(8)];
 
-        assert_eq!(MultilinearPolynomial::partial_evaluate(&polynomial, 0, Fq::from(6)), MultilinearPolynomial::new(&vec![Fq::from(18), Fq::from(48)]));
-        assert_eq!(MultilinearPolynomial::partial_evaluate(&polynomial, 1, Fq::from(2)), MultilinearPolynomial::new(&vec![Fq::from(0), Fq::from(13)]));
+        assert_eq!(MultilinearPolynomial::partial_evaluate(&polynomial, 0, Fq::from(6)), MultilinearPolynomial::new(&[Fq::from(18), Fq::from(48)]));
+        assert_eq!(MultilinearPolynomial::partial_evaluate(&polynomial, 1, Fq::from(2)), MultilinearPolynomial::new(&[Fq::from(0), Fq::from(13)]));
 
         let small_polynomial = vec![Fq::from(18), Fq::from(48)];
-        assert_eq!(MultilinearPolynomial::partial_evaluate(&small_polynomial, 0, Fq::from(2)), MultilinearPolynomial::new(&vec![Fq::from(78)]));
+        assert_eq!(MultilinearPolynomial::partial_evaluate(&small_polynomial, 0, Fq::from(2)), MultilinearPolynomial::new(&[Fq::from(78)]));
 
         let bigger_polynomial = vec![Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(0), Fq::from(0), Fq::from(2), Fq::from(5)];
-        assert_eq!(MultilinearPolynomial::partial_evaluate(&bigger_polynomial, 2, Fq::from(3)), MultilinearPolynomial::new(&vec![Fq::from(0), Fq::from(9), Fq::from(0), Fq::from(11)]));
+        assert_eq!(MultilinearPolynomial::partial_evaluate(&bigger_polynomial, 2, Fq::from(3)), MultilinearPolynomial::new(&[Fq::from(0), Fq::from(9), Fq::from(0), Fq::from(11)]));
     }
 
     #[test]
@@ -132,7 +291,93 @@ mod tests {
         assert_eq!(polynomial.evaluate(&values), Fq::from(78));
     }
 
-    
+    #[test]
+    fn test_eq_evaluations() {
+        let evaluated_values = vec![Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(8)];
+        let polynomial = MultilinearPolynomial::new(&evaluated_values);
+        let point = vec![Fq::from(6), Fq::from(2)];
 
+        // f(r) = sum over the hypercube of f(b) * eq(b, r)
+        let eq_table = MultilinearPolynomial::eq_evaluations(&point);
+        let evaluation: Fq = evaluated_values.iter().zip(eq_table.iter()).map(|(f, eq)| *f * eq).sum();
 
-}
\ No newline at end of file
+        assert_eq!(eq_table.len(), 4);
+        assert_eq!(evaluation, polynomial.evaluate(&point));
+        assert_eq!(MultilinearPolynomial::eq_evaluations(&[Fq::from(1), Fq::from(0)]), vec![Fq::from(0), Fq::from(0), Fq::from(1), Fq::from(0)]);
+        assert_eq!(MultilinearPolynomial::eq_evaluate(&point, &[Fq::from(1), Fq::from(0)]), eq_table[2]);
+    }
+
+    #[test]
+    fn test_eq_table_builds_agree() {
+        for no_of_vars in 0..=13u64 {
+            let point: Vec<Fq> = (0..no_of_vars).map(|i| Fq::from(i * i + 5)).collect();
+            let expected = MultilinearPolynomial::naive_eq_evaluations(&point);
+
+            assert_eq!(MultilinearPolynomial::eq_evaluations_in_place(&point), expected);
+            assert_eq!(MultilinearPolynomial::eq_evaluations(&point), expected);
+            #[cfg(feature = "parallel")]
+            assert_eq!(MultilinearPolynomial::parallel_eq_evaluations(&point), expected);
+        }
+    }
+
+    #[test]
+    fn test_padded_tables_and_prefix_indicator() {
+        let values: Vec<Fq> = (1..=6u64).map(Fq::from).collect();
+        let padded = MultilinearPolynomial::padded(&values);
+
+        assert_eq!(padded.no_of_vars, 3);
+        assert_eq!(padded.evaluated_values.iter().sum::<Fq>(), Fq::from(21));
+        assert_eq!(MultilinearPolynomial::padded(&values[..4]).evaluated_values, values[..4].to_vec());
+
+        let indicator = MultilinearPolynomial::new(&MultilinearPolynomial::<Fq>::prefix_indicator_evaluations(6, 3));
+        let point = [Fq::from(5), Fq::from(9), Fq::from(13)];
+        assert_eq!(indicator.evaluated_values.iter().filter(|value| **value == Fq::from(1)).count(), 6);
+        for length in 0..=8 {
+            let indicator = MultilinearPolynomial::new(&MultilinearPolynomial::<Fq>::prefix_indicator_evaluations(length, 3));
+            assert_eq!(MultilinearPolynomial::evaluate_prefix_indicator(length, &point), indicator.evaluate(&point));
+        }
+        assert_eq!(MultilinearPolynomial::evaluate_prefix_indicator(9, &point), Fq::from(1));
+    }
+
+    // The properties below for every field the crate is tested over: bn254's base field and the 64-bit Goldilocks
+    // field, where the table's values and the points are reduced modulo a much smaller p
+    crate::field_tests!(bn254: Fq, goldilocks: crate::goldilocks::Goldilocks => {
+        proptest! {
+            #[test]
+            fn prop_evaluations_on_the_hypercube_are_the_table(polynomial in any::<MultilinearPolynomial<F>>()) {
+                for (index, value) in polynomial.evaluated_values.iter().enumerate() {
+                    // variable 0 is the most significant bit of the index
+                    let boolean_point: Vec<F> = (0..polynomial.no_of_vars)
+                        .map(|i| F::from(((index >> (polynomial.no_of_vars - 1 - i)) & 1) as u64))
+                        .collect();
+                    prop_assert_eq!(polynomial.evaluate(&boolean_point), *value);
+                }
+            }
+
+            #[test]
+            fn prop_evaluation_is_the_eq_weighted_sum((polynomial, point) in polynomial_and_point::<F>()) {
+                let eq_table = MultilinearPolynomial::eq_evaluations(&point);
+                let sum: F = polynomial.evaluated_values.iter().zip(eq_table.iter()).map(|(f, eq)| *f * eq).sum();
+
+                prop_assert_eq!(polynomial.evaluate(&point), sum);
+            }
+
+            #[test]
+            fn prop_sparse_evaluation_is_the_dense_one((polynomial, point) in polynomial_and_point::<F>()) {
+                // the nonzero entries of the table, the rest of it being what the sparse path skips
+                let entries: Vec<(usize, F)> = polynomial.evaluated_values.iter().copied().enumerate()
+                    .filter(|(index, _)| index % 3 == 0)
+                    .collect();
+                let mut values = vec![F::from(0u64); polynomial.evaluated_values.len()];
+                for (index, value) in &entries {
+                    values[*index] = *value;
+                }
+
+                prop_assert_eq!(
+                    MultilinearPolynomial::evaluate_sparse(&entries, &point),
+                    MultilinearPolynomial::new(&values).evaluate(&point)
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,139 @@
+use crate::multilinear::MultilinearPolynomial;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate, Write};
+use digest::{Digest, Output};
+
+// The canonical encoding of a multilinear polynomial: a version byte, the number of variables as a u8 and the 2^n
+// evaluations in hypercube order, each in the field's own canonical form. The length of the table follows from the
+// number of variables, so an encoding is always a whole table and there is no separate length to disagree with it.
+//
+// digest hashes exactly these bytes. A transcript that absorbs the digest instead of the table is bound to the same
+// polynomial while appending 32 bytes rather than 32 * 2^n, which is what keeps the transcript cheap once tables
+// reach millions of entries. The version comes first so a later encoding hashes to something else
+pub const SERIALIZATION_VERSION: u8 = 1;
+
+// 2^63 evaluations would not fit in a usize index on any machine this runs on
+const MAX_VARS: u8 = 63;
+
+impl <F: PrimeField>CanonicalSerialize for MultilinearPolynomial<F> {
+    fn serialize_with_mode<W: Write>(&self, mut writer: W, compress: Compress) -> Result<(), SerializationError> {
+        SERIALIZATION_VERSION.serialize_with_mode(&mut writer, compress)?;
+        (self.no_of_vars as u8).serialize_with_mode(&mut writer, compress)?;
+        for value in &self.evaluated_values {
+            value.serialize_with_mode(&mut writer, compress)?;
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        2 + self.evaluated_values.len() * F::zero().serialized_size(compress)
+    }
+}
+
+// The table is read element by element rather than reserved up front, so a forged number of variables fails at the
+// end of the input instead of allocating 2^63 elements
+impl <F: PrimeField>CanonicalDeserialize for MultilinearPolynomial<F> {
+    fn deserialize_with_mode<R: Read>(mut reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != SERIALIZATION_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+        let no_of_vars = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        if no_of_vars > MAX_VARS {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let mut evaluated_values = Vec::new();
+        for _ in 0..1u64 << no_of_vars {
+            evaluated_values.push(F::deserialize_with_mode(&mut reader, compress, validate)?);
+        }
+        Ok(Self { evaluated_values, no_of_vars: no_of_vars as usize })
+    }
+}
+
+impl <F: PrimeField>Valid for MultilinearPolynomial<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        if self.evaluated_values.len() != 1 << self.no_of_vars {
+            return Err(SerializationError::InvalidData);
+        }
+        self.evaluated_values.check()
+    }
+}
+
+impl <F: PrimeField>MultilinearPolynomial<F> {
+    // The hash of the compressed encoding under the given hasher, which may already hold a domain separator.
+    // The table is hashed one element at a time, so it is never copied into a buffer of its own
+    pub fn digest<D: Digest>(&self, mut hasher: D) -> Output<D> {
+        hasher.update([SERIALIZATION_VERSION, self.no_of_vars as u8]);
+
+        let mut bytes = Vec::with_capacity(F::zero().compressed_size());
+        for value in &self.evaluated_values {
+            bytes.clear();
+            value.serialize_compressed(&mut bytes).expect("serializing into a Vec cannot fail");
+            hasher.update(&bytes);
+        }
+        hasher.finalize()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use proptest::prelude::*;
+    use sha3::Keccak256;
+
+    fn polynomial(values: &[u64]) -> MultilinearPolynomial<Fr> {
+        MultilinearPolynomial::new(&values.iter().map(|value| Fr::from(*value)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_encoding_layout() {
+        let polynomial = polynomial(&[1, 2, 3, 4]);
+        let mut bytes = Vec::new();
+        polynomial.serialize_compressed(&mut bytes).unwrap();
+
+        assert_eq!(bytes.len(), polynomial.compressed_size());
+        assert_eq!(bytes.len(), 2 + 4 * 32);
+        assert_eq!(bytes[..3], [SERIALIZATION_VERSION, 2, 1]);
+        assert_eq!(MultilinearPolynomial::<Fr>::deserialize_compressed(&bytes[..]).unwrap(), polynomial);
+
+        // the digest is the hash of the encoding
+        assert_eq!(polynomial.digest(Keccak256::new()), Keccak256::digest(&bytes));
+    }
+
+    #[test]
+    fn test_malformed_encodings_are_rejected() {
+        let mut bytes = Vec::new();
+        polynomial(&[1, 2, 3, 4]).serialize_compressed(&mut bytes).unwrap();
+
+        // another version, a short table and a number of variables no table has
+        let mut other_version = bytes.clone();
+        other_version[0] = SERIALIZATION_VERSION + 1;
+        assert!(MultilinearPolynomial::<Fr>::deserialize_compressed(&other_version[..]).is_err());
+        assert!(MultilinearPolynomial::<Fr>::deserialize_compressed(&bytes[..bytes.len() - 32]).is_err());
+        assert!(MultilinearPolynomial::<Fr>::deserialize_compressed(&[SERIALIZATION_VERSION, 64][..]).is_err());
+        // 2^60 claimed evaluations run out of input rather than memory
+        assert!(MultilinearPolynomial::<Fr>::deserialize_compressed(&[SERIALIZATION_VERSION, 60, 0][..]).is_err());
+    }
+
+    #[test]
+    fn test_digest_binds_the_table() {
+        let digest = polynomial(&[1, 2, 3, 4]).digest(Keccak256::new());
+
+        assert_ne!(polynomial(&[1, 2, 3, 5]).digest(Keccak256::new()), digest);
+        // a hasher that already holds a domain separator gives another digest for the same table
+        assert_ne!(polynomial(&[1, 2, 3, 4]).digest(Keccak256::new_with_prefix(b"label")), digest);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_serialization_round_trips(polynomial in any::<MultilinearPolynomial<Fr>>()) {
+            let mut bytes = Vec::new();
+            polynomial.serialize_uncompressed(&mut bytes).unwrap();
+
+            prop_assert_eq!(MultilinearPolynomial::<Fr>::deserialize_uncompressed(&bytes[..]).unwrap(), polynomial);
+        }
+    }
+}
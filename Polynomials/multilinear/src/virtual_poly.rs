@@ -0,0 +1,429 @@
+use crate::multilinear::MultilinearPolynomial;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Mul};
+use ark_ff::PrimeField;
+
+// A composition of multilinear polynomials, the expression a composed sumcheck sums, kept symbolically so it can be
+// printed the way it is written in the notes.
+//
+// The polynomials are named and share the same variables, and the expression combines them with sums, products and
+// constants, e.g. the GKR layer polynomial add(b, c)·(w_b(b, c) + w_c(b, c)) + mul(b, c)·w_b(b, c)·w_c(b, c).
+// fix_first_variable binds variables one at a time as the sumcheck rounds do, folding every table, and the bound
+// ones print as r_1, r_2, ..., so the expression printed after a round is the polynomial the next round sums.
+// combine is the closure ComposedSumcheckProof::prove takes, and tables the polynomials it takes with it.
+//
+// A polynomial can also advertise a closed form, so a prover that knows it can compute its part of the round sums
+// without the table; add_eq registers eq(r, x) that way, see eq_factor
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualPolynomial<F: PrimeField> {
+    pub mles: Vec<(String, MultilinearPolynomial<F>)>,
+    // one entry per polynomial, Some for those with a known structure
+    pub closed_forms: Vec<Option<ClosedForm<F>>>,
+    pub expression: Expression<F>,
+    // one name per variable, x_1, x_2, ... unless given
+    pub variable_names: Vec<String>,
+    // the number of variables bound so far, the first ones
+    pub bound: usize,
+}
+
+// The expression over the polynomials of a VirtualPolynomial, Mle(i) standing for the i-th of them. Sums and
+// products of expressions flatten into one Sum or Product
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression<F: PrimeField> {
+    Mle(usize),
+    Constant(F),
+    Sum(Vec<Expression<F>>),
+    Product(Vec<Expression<F>>),
+}
+
+// What is known about a polynomial beyond its table.
+//
+// Eq { point, scale } is scale * eq(point, x) over the free variables. Its sum over any hypercube is scale, and binding
+// its first variable to r leaves scale * eq1(point_1, r) times eq over the rest of the point, so it keeps the form with
+// one coordinate less and needs no table to fold
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClosedForm<F: PrimeField> {
+    Eq { point: Vec<F>, scale: F },
+}
+
+// Everything that can go wrong building a VirtualPolynomial or binding its variables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VirtualPolynomialError {
+    // the polynomial has `expected` variables to name
+    WrongNumberOfNames { expected: usize, provided: usize },
+    // polynomials are added before any variable is bound, so they all share the same free variables
+    AddedAfterBinding,
+    // a polynomial in `provided` variables added to one in `expected`
+    WrongNumberOfVariables { expected: usize, provided: usize },
+    EveryVariableBound,
+}
+
+impl fmt::Display for VirtualPolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VirtualPolynomialError::WrongNumberOfNames { expected, provided } => {
+                write!(f, "{} names for {} variables", provided, expected)
+            }
+            VirtualPolynomialError::AddedAfterBinding => write!(f, "polynomial added after a variable was bound"),
+            VirtualPolynomialError::WrongNumberOfVariables { expected, provided } => {
+                write!(f, "polynomial in {} variables added to one in {}", provided, expected)
+            }
+            VirtualPolynomialError::EveryVariableBound => write!(f, "every variable is already bound"),
+        }
+    }
+}
+
+impl core::error::Error for VirtualPolynomialError {}
+
+impl <F: PrimeField>Expression<F> {
+    // The degree in every variable, which bounds the degree of a sumcheck round polynomial
+    pub fn degree(&self) -> usize {
+        match self {
+            Expression::Mle(_) => 1,
+            Expression::Constant(_) => 0,
+            Expression::Sum(terms) => terms.iter().map(Self::degree).max().unwrap_or(0),
+            Expression::Product(factors) => factors.iter().map(Self::degree).sum(),
+        }
+    }
+
+    // The value of the expression when the polynomials take these values
+    pub fn evaluate(&self, values: &[F]) -> F {
+        match self {
+            Expression::Mle(index) => values[*index],
+            Expression::Constant(constant) => *constant,
+            Expression::Sum(terms) => terms.iter().map(|term| term.evaluate(values)).sum(),
+            Expression::Product(factors) => factors.iter().map(|factor| factor.evaluate(values)).product(),
+        }
+    }
+
+    // Whether Mle(index) appears anywhere in the expression
+    pub fn reads(&self, index: usize) -> bool {
+        match self {
+            Expression::Mle(other) => *other == index,
+            Expression::Constant(_) => false,
+            Expression::Sum(parts) | Expression::Product(parts) => parts.iter().any(|part| part.reads(index)),
+        }
+    }
+
+    fn render(&self, latex: bool, mle: &dyn Fn(usize) -> String) -> String {
+        match self {
+            Expression::Mle(index) => mle(*index),
+            Expression::Constant(constant) => constant.to_string(),
+            Expression::Sum(terms) => terms.iter().map(|term| term.render(latex, mle)).collect::<Vec<_>>().join(" + "),
+            Expression::Product(factors) => factors
+                .iter()
+                .map(|factor| match (factor, latex) {
+                    // a sum inside a product keeps its parentheses
+                    (Expression::Sum(_), false) => format!("({})", factor.render(latex, mle)),
+                    (Expression::Sum(_), true) => format!("\\left({}\\right)", factor.render(latex, mle)),
+                    _ => factor.render(latex, mle),
+                })
+                .collect::<Vec<_>>()
+                .join(if latex { " \\cdot " } else { "·" }),
+        }
+    }
+}
+
+impl <F: PrimeField>Add for Expression<F> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut terms = Vec::new();
+        for expression in [self, other] {
+            match expression {
+                Expression::Sum(inner) => terms.extend(inner),
+                expression => terms.push(expression),
+            }
+        }
+        Expression::Sum(terms)
+    }
+}
+
+impl <F: PrimeField>Mul for Expression<F> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let mut factors = Vec::new();
+        for expression in [self, other] {
+            match expression {
+                Expression::Product(inner) => factors.extend(inner),
+                expression => factors.push(expression),
+            }
+        }
+        Expression::Product(factors)
+    }
+}
+
+impl <F: PrimeField>VirtualPolynomial<F> {
+    // No polynomials yet and the expression 0, over no_of_vars variables named x_1, ..., x_n
+    pub fn new(no_of_vars: usize) -> Self {
+        Self {
+            mles: Vec::new(),
+            closed_forms: Vec::new(),
+            expression: Expression::Constant(F::zero()),
+            variable_names: (1..=no_of_vars).map(|i| format!("x_{}", i)).collect(),
+            bound: 0,
+        }
+    }
+
+    // Names the variables, e.g. b and c for the two halves of a GKR layer's input
+    pub fn with_variable_names(mut self, names: &[&str]) -> Result<Self, VirtualPolynomialError> {
+        if names.len() != self.variable_names.len() {
+            return Err(VirtualPolynomialError::WrongNumberOfNames { expected: self.variable_names.len(), provided: names.len() });
+        }
+        self.variable_names = names.iter().map(|name| name.to_string()).collect();
+        Ok(self)
+    }
+
+    pub fn no_of_vars(&self) -> usize {
+        self.variable_names.len()
+    }
+
+    // Adds a polynomial and returns the expression that stands for it
+    pub fn add_mle(&mut self, name: &str, polynomial: MultilinearPolynomial<F>) -> Result<Expression<F>, VirtualPolynomialError> {
+        if self.bound > 0 {
+            return Err(VirtualPolynomialError::AddedAfterBinding);
+        }
+        if polynomial.no_of_vars != self.no_of_vars() {
+            return Err(VirtualPolynomialError::WrongNumberOfVariables { expected: self.no_of_vars(), provided: polynomial.no_of_vars });
+        }
+        self.mles.push((name.to_string(), polynomial));
+        self.closed_forms.push(None);
+        Ok(Expression::Mle(self.mles.len() - 1))
+    }
+
+    // Adds eq(point, x) with its closed form. The table is still built, for provers that only read tables
+    pub fn add_eq(&mut self, name: &str, point: &[F]) -> Result<Expression<F>, VirtualPolynomialError> {
+        let expression = self.add_mle(name, MultilinearPolynomial::new(&MultilinearPolynomial::eq_evaluations(point)))?;
+        self.closed_forms[self.mles.len() - 1] = Some(ClosedForm::Eq { point: point.to_vec(), scale: F::one() });
+        Ok(expression)
+    }
+
+    // When the expression is a product with an eq factor of known form, e.g. the zerocheck eq(r, x)·gate(x), the index
+    // of that polynomial, its closed form and the product of the other factors. A prover can then send each round as
+    // the eq factor's linear part times the sum of the rest against the eq table of the remaining coordinates, one
+    // degree less to evaluate and no eq table to fold, see ComposedSumcheckProof::prove_virtual. An eq polynomial the
+    // rest reads as well, as in eq·eq or eq·(eq + g), is not a factor of that form
+    pub fn eq_factor(&self) -> Option<(usize, &ClosedForm<F>, Expression<F>)> {
+        let factors: &[Expression<F>] = match &self.expression {
+            Expression::Product(factors) => factors,
+            expression => core::slice::from_ref(expression),
+        };
+
+        factors.iter().enumerate().find_map(|(position, factor)| match factor {
+            Expression::Mle(index) => self.closed_forms[*index].as_ref().map(|closed_form| {
+                let rest: Vec<Expression<F>> =
+                    factors.iter().enumerate().filter(|(other, _)| *other != position).map(|(_, factor)| factor.clone()).collect();
+                let rest = match rest.len() {
+                    0 => Expression::Constant(F::one()),
+                    1 => rest.into_iter().next().expect("one factor"),
+                    _ => Expression::Product(rest),
+                };
+                (*index, closed_form, rest)
+            }).filter(|(index, _, rest)| !rest.reads(*index)),
+            _ => None,
+        })
+    }
+
+    pub fn set_expression(&mut self, expression: Expression<F>) {
+        self.expression = expression;
+    }
+
+    pub fn degree(&self) -> usize {
+        self.expression.degree()
+    }
+
+    // The expression on one value per polynomial, the combine closure of ComposedSumcheckProof::prove
+    pub fn combine(&self, values: &[F]) -> F {
+        self.expression.evaluate(values)
+    }
+
+    pub fn tables(&self) -> Vec<MultilinearPolynomial<F>> {
+        self.mles.iter().map(|(_, polynomial)| polynomial.clone()).collect()
+    }
+
+    // Binds the first free variable to r in every polynomial, as a sumcheck round does with its challenge
+    pub fn fix_first_variable(&mut self, r: F) -> Result<(), VirtualPolynomialError> {
+        if self.bound == self.no_of_vars() {
+            return Err(VirtualPolynomialError::EveryVariableBound);
+        }
+        for (_, polynomial) in &mut self.mles {
+            *polynomial = MultilinearPolynomial::partial_evaluate(&polynomial.evaluated_values, 0, r);
+        }
+        for closed_form in self.closed_forms.iter_mut().flatten() {
+            let ClosedForm::Eq { point, scale } = closed_form;
+            *scale *= eq1(point.remove(0), r);
+        }
+        self.bound += 1;
+        Ok(())
+    }
+
+    // The sum of the expression over the hypercube of the free variables
+    pub fn sum(&self) -> F {
+        let size = 1usize << (self.no_of_vars() - self.bound);
+        let mut values = Vec::with_capacity(self.mles.len());
+        (0..size)
+            .map(|index| {
+                values.clear();
+                values.extend(self.mles.iter().map(|(_, polynomial)| polynomial.evaluated_values[index]));
+                self.combine(&values)
+            })
+            .sum()
+    }
+
+    // The expression in LaTeX, e.g. \mathrm{add}(r_{1}, c) \cdot \left(\mathrm{w_b}(r_{1}, c) + ...\right)
+    pub fn to_latex(&self) -> String {
+        self.expression.render(true, &|index| {
+            format!("\\mathrm{{{}}}({})", self.mles[index].0, self.arguments(true))
+        })
+    }
+
+    // The variables as a polynomial is applied to them, the bound ones as the challenges r_i
+    fn arguments(&self, latex: bool) -> String {
+        self.variable_names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| match (i < self.bound, latex) {
+                (true, false) => format!("r_{}", i + 1),
+                (true, true) => format!("r_{{{}}}", i + 1),
+                (false, true) if name.starts_with("x_") => format!("x_{{{}}}", &name[2..]),
+                (false, _) => name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+// eq(a, t) in one variable, a·t + (1 - a)(1 - t)
+pub fn eq1<F: PrimeField>(a: F, t: F) -> F {
+    a * t + (F::one() - a) * (F::one() - t)
+}
+
+// The expression as written in the notes, e.g. add(b, c)·(w_b(b, c) + w_c(b, c)), bound variables as r_i
+impl <F: PrimeField>fmt::Display for VirtualPolynomial<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arguments = self.arguments(false);
+        let rendered = self.expression.render(false, &|index| format!("{}({})", self.mles[index].0, arguments));
+        f.write_str(&rendered)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fq;
+
+    fn table(values: &[u64]) -> MultilinearPolynomial<Fq> {
+        MultilinearPolynomial::new(&values.iter().map(|value| Fq::from(*value)).collect::<Vec<_>>())
+    }
+
+    // the GKR layer polynomial over one bit of b and one of c
+    fn gkr_layer() -> VirtualPolynomial<Fq> {
+        let mut polynomial = VirtualPolynomial::new(2).with_variable_names(&["b", "c"]).unwrap();
+        let add = polynomial.add_mle("add", table(&[0, 1, 0, 0])).unwrap();
+        let mul = polynomial.add_mle("mul", table(&[0, 0, 0, 1])).unwrap();
+        let w_b = polynomial.add_mle("w_b", table(&[3, 3, 5, 5])).unwrap();
+        let w_c = polynomial.add_mle("w_c", table(&[3, 5, 3, 5])).unwrap();
+        polynomial.set_expression(add * (w_b.clone() + w_c.clone()) + mul * w_b * w_c);
+        polynomial
+    }
+
+    #[test]
+    fn test_expression_prints_as_written() {
+        let mut polynomial = gkr_layer();
+        assert_eq!(polynomial.to_string(), "add(b, c)·(w_b(b, c) + w_c(b, c)) + mul(b, c)·w_b(b, c)·w_c(b, c)");
+        assert_eq!(polynomial.degree(), 3);
+
+        polynomial.fix_first_variable(Fq::from(7)).unwrap();
+        assert_eq!(polynomial.to_string(), "add(r_1, c)·(w_b(r_1, c) + w_c(r_1, c)) + mul(r_1, c)·w_b(r_1, c)·w_c(r_1, c)");
+
+        let constant = VirtualPolynomial::<Fq>::new(1);
+        assert_eq!(constant.to_string(), "0");
+    }
+
+    #[test]
+    fn test_latex_export() {
+        let mut polynomial = VirtualPolynomial::new(2);
+        let f = polynomial.add_mle("f", table(&[1, 2, 3, 4])).unwrap();
+        let g = polynomial.add_mle("g", table(&[1, 1, 2, 2])).unwrap();
+        polynomial.set_expression(Expression::Constant(Fq::from(2)) * (f + g.clone()) * g);
+
+        assert_eq!(
+            polynomial.to_latex(),
+            "2 \\cdot \\left(\\mathrm{f}(x_{1}, x_{2}) + \\mathrm{g}(x_{1}, x_{2})\\right) \\cdot \\mathrm{g}(x_{1}, x_{2})"
+        );
+        polynomial.fix_first_variable(Fq::from(3)).unwrap();
+        assert!(polynomial.to_latex().contains("\\mathrm{f}(r_{1}, x_{2})"));
+    }
+
+    #[test]
+    fn test_expression_values_follow_the_tables() {
+        let mut polynomial = gkr_layer();
+        // add at (0, 1) with 3 + 5, mul at (1, 1) with 5 * 5
+        assert_eq!(polynomial.sum(), Fq::from(8 + 25));
+
+        let values = [Fq::from(2), Fq::from(3), Fq::from(4), Fq::from(5)];
+        assert_eq!(polynomial.combine(&values), Fq::from(2 * (4 + 5) + 3 * 4 * 5));
+
+        // the sum after binding b to r is the round polynomial at r
+        let r = Fq::from(9);
+        let at = |t: Fq| {
+            let mut bound = gkr_layer();
+            bound.fix_first_variable(t).unwrap();
+            bound.sum()
+        };
+        polynomial.fix_first_variable(r).unwrap();
+        assert_eq!(polynomial.sum(), at(r));
+        assert_eq!(at(Fq::from(0)) + at(Fq::from(1)), gkr_layer().sum());
+    }
+
+    #[test]
+    fn test_eq_factor_keeps_its_closed_form() {
+        let point = [Fq::from(3), Fq::from(5)];
+        let mut polynomial = VirtualPolynomial::new(2);
+        let f = polynomial.add_mle("f", table(&[1, 2, 3, 4])).unwrap();
+        let g = polynomial.add_mle("g", table(&[2, 7, 1, 8])).unwrap();
+        let eq = polynomial.add_eq("eq", &point).unwrap();
+        polynomial.set_expression(f.clone() * eq.clone() * (f.clone() + g.clone()));
+
+        let (index, _, rest) = polynomial.eq_factor().unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(rest, f.clone() * (f + g.clone()));
+
+        // the closed form follows the table as variables are bound
+        polynomial.fix_first_variable(Fq::from(11)).unwrap();
+        let Some(ClosedForm::Eq { point: rest_point, scale }) = &polynomial.closed_forms[2] else { panic!("eq stays eq") };
+        let table = &polynomial.mles[2].1.evaluated_values;
+        assert_eq!(table, &MultilinearPolynomial::eq_evaluations(rest_point).iter().map(|value| *value * scale).collect::<Vec<_>>());
+        assert_eq!(*scale, eq1(point[0], Fq::from(11)));
+
+        // no eq factor in a sum, nor without a closed form, nor when the rest reads eq too
+        assert!(gkr_layer().eq_factor().is_none());
+        polynomial.set_expression(eq.clone() * (eq.clone() + g));
+        assert!(polynomial.eq_factor().is_none());
+        polynomial.set_expression(eq.clone() * eq);
+        assert!(polynomial.eq_factor().is_none());
+    }
+
+    #[test]
+    fn test_misuse_is_an_error() {
+        assert_eq!(
+            VirtualPolynomial::<Fq>::new(2).with_variable_names(&["b"]),
+            Err(VirtualPolynomialError::WrongNumberOfNames { expected: 2, provided: 1 })
+        );
+
+        let mut polynomial = VirtualPolynomial::new(1);
+        assert_eq!(
+            polynomial.add_mle("f", table(&[1, 2, 3, 4])),
+            Err(VirtualPolynomialError::WrongNumberOfVariables { expected: 1, provided: 2 })
+        );
+        polynomial.add_mle("f", table(&[1, 2])).unwrap();
+        polynomial.fix_first_variable(Fq::from(3)).unwrap();
+        assert_eq!(polynomial.add_eq("eq", &[Fq::from(5)]), Err(VirtualPolynomialError::AddedAfterBinding));
+        assert_eq!(polynomial.fix_first_variable(Fq::from(3)), Err(VirtualPolynomialError::EveryVariableBound));
+    }
+}
@@ -0,0 +1,9 @@
+use ark_bn254::Fr;
+use univariate_poly::univariate::UnivariatePoly;
+
+// 2x + 5, its degree and its value at x = 3
+fn main() {
+    let poly = UnivariatePoly::new(vec![Fr::from(5), Fr::from(2)]);
+    println!("Degree: {}", poly.degree());
+    println!("Evaluation at x=3: {}", poly.evaluate(Fr::from(3)));
+}
@@ -0,0 +1,38 @@
+use ark_bn254::Fr;
+use std::time::{Duration, Instant};
+use univariate_poly::univariate::UnivariatePoly;
+
+// Times the multiplication algorithms against each other for growing numbers of coefficients, e.g.
+//   cargo run --release -p univariatePoly --example multiplication -- 1024
+// The argument is the largest number of coefficients, 512 by default. Each product runs three times and the fastest
+// counts; Mul is what the operator picks, see KARATSUBA_THRESHOLD and TOOM3_THRESHOLD in multiplication.rs
+fn main() {
+    let max_length: usize = std::env::args().nth(1).and_then(|arg| arg.parse().ok()).unwrap_or(512);
+
+    println!("{:>6} {:>12} {:>12} {:>12} {:>12} {:>12}", "coeffs", "schoolbook", "karatsuba", "toom-3", "ntt", "mul");
+    let mut length = 8;
+    while length <= max_length {
+        let a = UnivariatePoly::new((0..length as u64).map(|i| Fr::from(i * 7919 + 13)).collect());
+        let b = UnivariatePoly::new((0..length as u64).map(|i| Fr::from(i * 104729 + 7)).collect());
+
+        let schoolbook = fastest(|| a.schoolbook_mul(&b));
+        let karatsuba = fastest(|| a.karatsuba_mul(&b));
+        let toom3 = fastest(|| a.toom3_mul(&b));
+        let ntt = fastest(|| a.ntt_mul(&b).expect("BN254 has a large enough domain"));
+        let mul = fastest(|| &a * &b);
+
+        println!("{:>6} {:>12?} {:>12?} {:>12?} {:>12?} {:>12?}", length, schoolbook, karatsuba, toom3, ntt, mul);
+        length *= 2;
+    }
+}
+
+fn fastest(multiply: impl Fn() -> UnivariatePoly<Fr>) -> Duration {
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(multiply());
+            start.elapsed()
+        })
+        .min()
+        .expect("three runs")
+}
@@ -0,0 +1,33 @@
+use crate::univariate::UnivariatePoly;
+use ark_ff::PrimeField;
+use proptest::prelude::*;
+
+// proptest generators, built for the tests of this crate and for other crates with the proptest feature.
+// Field elements are 32 random bytes reduced modulo p, so every element can come up
+
+pub fn field_element<F: PrimeField>() -> impl Strategy<Value = F> {
+    any::<[u8; 32]>().prop_map(|bytes| F::from_le_bytes_mod_order(&bytes))
+}
+
+// len pairwise distinct field elements, e.g. interpolation points
+pub fn distinct_field_elements<F: PrimeField>(len: usize) -> impl Strategy<Value = Vec<F>> {
+    prop::collection::vec(field_element::<F>(), len)
+        .prop_filter("elements must be distinct", |elements| {
+            elements.iter().enumerate().all(|(i, element)| !elements[..i].contains(element))
+        })
+}
+
+// Polynomials with fewer than `max_coefficients` coefficients, including the zero polynomial
+pub fn polynomial<F: PrimeField>(max_coefficients: usize) -> impl Strategy<Value = UnivariatePoly<F>> {
+    prop::collection::vec(field_element::<F>(), 0..max_coefficients).prop_map(UnivariatePoly::new)
+}
+
+// Polynomials of degree at most 7
+impl <F: PrimeField>Arbitrary for UnivariatePoly<F> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        polynomial(9).boxed()
+    }
+}
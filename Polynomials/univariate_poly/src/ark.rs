@@ -0,0 +1,101 @@
+use ark_ff::PrimeField;
+use ark_poly::univariate::{DensePolynomial, SparsePolynomial as ArkSparsePolynomial};
+use ark_poly::{DenseUVPolynomial, EvaluationDomain as _, Radix2EvaluationDomain};
+use crate::domain::EvaluationDomain;
+use crate::sparse::SparsePolynomial;
+use crate::univariate::UnivariatePoly;
+
+// Conversions to and from arkworks' univariate polynomials and radix-2 domains, for its commitment schemes and FFTs.
+// Both sides keep coefficients lowest power first and trim trailing zeros, so a dense polynomial converts by moving
+// its coefficient vector, and a sparse one by moving its (exponent, coefficient) terms
+impl <F: PrimeField>From<UnivariatePoly<F>> for DensePolynomial<F> {
+    fn from(polynomial: UnivariatePoly<F>) -> Self {
+        DensePolynomial::from_coefficients_vec(polynomial.coefficients)
+    }
+}
+
+impl <F: PrimeField>From<DensePolynomial<F>> for UnivariatePoly<F> {
+    fn from(polynomial: DensePolynomial<F>) -> Self {
+        UnivariatePoly::new(polynomial.coeffs)
+    }
+}
+
+impl <F: PrimeField>From<SparsePolynomial<F>> for ArkSparsePolynomial<F> {
+    fn from(polynomial: SparsePolynomial<F>) -> Self {
+        ArkSparsePolynomial::from_coefficients_vec(polynomial.terms)
+    }
+}
+
+impl <F: PrimeField>From<ArkSparsePolynomial<F>> for SparsePolynomial<F> {
+    fn from(polynomial: ArkSparsePolynomial<F>) -> Self {
+        SparsePolynomial::new(polynomial.to_vec())
+    }
+}
+
+// Both domains take the primitive root of unity F::get_root_of_unity(n), so the elements come in the same order and
+// an FFT over either gives the same evaluations
+impl <F: PrimeField>From<&EvaluationDomain<F>> for Radix2EvaluationDomain<F> {
+    fn from(domain: &EvaluationDomain<F>) -> Self {
+        let subgroup = Radix2EvaluationDomain::new(domain.size).expect("the field has a subgroup of the domain's size");
+        if domain.is_coset() {
+            subgroup.get_coset(domain.offset).expect("a coset offset is nonzero")
+        } else {
+            subgroup
+        }
+    }
+}
+
+impl <F: PrimeField>From<&Radix2EvaluationDomain<F>> for EvaluationDomain<F> {
+    fn from(domain: &Radix2EvaluationDomain<F>) -> Self {
+        let subgroup = EvaluationDomain::new(domain.size()).expect("the field has a subgroup of the domain's size");
+        if domain.coset_offset().is_one() {
+            subgroup
+        } else {
+            subgroup.get_coset(domain.coset_offset()).expect("a coset offset is nonzero")
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrary::polynomial;
+    use ark_bn254::Fr;
+    use ark_poly::Polynomial;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_sparse_conversion() {
+        let polynomial = SparsePolynomial::new(vec![(0, -Fr::from(1)), (1024, Fr::from(1))]);
+        let ark = ArkSparsePolynomial::from(polynomial.clone());
+
+        assert_eq!(ark.degree(), 1024);
+        assert_eq!(ark.evaluate(&Fr::from(1)), Fr::from(0));
+        assert_eq!(SparsePolynomial::from(ark), polynomial);
+    }
+
+    #[test]
+    fn test_domains_agree() {
+        let domain = EvaluationDomain::<Fr>::new(8).unwrap();
+        let coset = domain.get_coset(Fr::from(7)).unwrap();
+        let coefficients: Vec<Fr> = (1..=8u64).map(Fr::from).collect();
+
+        for domain in [domain, coset] {
+            let ark = Radix2EvaluationDomain::from(&domain);
+            assert_eq!(ark.elements().collect::<Vec<_>>(), domain.elements());
+            assert_eq!(ark.fft(&coefficients), domain.fft(&coefficients));
+            assert_eq!(EvaluationDomain::from(&ark), domain);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_dense_conversion_keeps_the_polynomial(polynomial in polynomial::<Fr>(6), x in crate::arbitrary::field_element::<Fr>()) {
+            let ark = DensePolynomial::from(polynomial.clone());
+
+            prop_assert_eq!(ark.evaluate(&x), polynomial.evaluate(x));
+            prop_assert_eq!(UnivariatePoly::from(ark), polynomial);
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use ark_ff::{FftField, PrimeField};
+use std::sync::OnceLock;
+use crate::sparse::SparsePolynomial;
+
+// A radix-2 evaluation domain: the multiplicative subgroup {1, w, w^2, ..., w^(n-1)} of size n = 2^k
+// where w is a primitive n-th root of unity of the field.
+// Evaluating a polynomial over this subgroup (and interpolating back) can be done in O(n log n)
+// with the number theoretic transform (NTT), the finite field version of the FFT.
+// A domain can also be a coset h*H = {h, h*w, ..., h*w^(n-1)} of the subgroup, where h is the offset.
+// The twiddle factors of the transforms are computed on the first fft or ifft and kept, so a domain that is built
+// once and reused, e.g. by a Reed-Solomon code, pays for them once; a coset shares those of its subgroup
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationDomain<F: FftField> {
+    pub size: usize,
+    pub log_size: u32,
+    pub generator: F,
+    pub generator_inv: F,
+    pub size_inv: F,
+    pub offset: F,
+    pub offset_inv: F,
+    twiddles: TwiddleCache<F>,
+}
+
+// w^0, ..., w^(n/2 - 1) for the forward transform and the same powers of w^-1 for the inverse. The cache is not part
+// of the domain's value, two domains are equal whether or not either has filled it
+#[derive(Debug, Clone, Default)]
+struct TwiddleCache<F> {
+    forward: OnceLock<Vec<F>>,
+    inverse: OnceLock<Vec<F>>,
+}
+
+impl <F>PartialEq for TwiddleCache<F> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl <F: FftField>EvaluationDomain<F> {
+    // Creates the smallest power-of-two domain that holds at least min_size elements.
+    // Returns None when the field's two-adicity is too small for a subgroup of that size
+    // (e.g. bn254's base field Fq only has a subgroup of size 2, while the scalar field Fr has one of size 2^28)
+    pub fn new(min_size: usize) -> Option<Self> {
+        let size = min_size.max(1).checked_next_power_of_two()?;
+        let log_size = size.trailing_zeros();
+
+        if log_size > F::TWO_ADICITY {
+            return None;
+        }
+
+        let generator = F::get_root_of_unity(size as u64)?;
+
+        Some(Self {
+            size,
+            log_size,
+            generator,
+            generator_inv: generator.inverse()?,
+            size_inv: F::from(size as u64).inverse()?,
+            offset: F::one(),
+            offset_inv: F::one(),
+            twiddles: TwiddleCache::default(),
+        })
+    }
+
+    // Returns the same domain shifted by `offset`: {offset, offset*w, ..., offset*w^(n-1)}.
+    // Returns None for a zero offset since the coset would collapse to a single point
+    pub fn get_coset(&self, offset: F) -> Option<Self> {
+        Some(Self {
+            offset,
+            offset_inv: offset.inverse()?,
+            ..self.clone()
+        })
+    }
+
+    pub fn is_coset(&self) -> bool {
+        !self.offset.is_one()
+    }
+
+    // Returns offset * w^i
+    pub fn element(&self, i: usize) -> F {
+        self.offset * self.generator.pow([i as u64])
+    }
+
+    // Returns all the elements of the domain in order: offset, offset*w, ..., offset*w^(n-1)
+    pub fn elements(&self) -> Vec<F> {
+        let mut elements = Vec::with_capacity(self.size);
+        let mut current = self.offset;
+
+        for _ in 0..self.size {
+            elements.push(current);
+            current *= self.generator;
+        }
+
+        elements
+    }
+
+    // The vanishing polynomial of the domain evaluated at z: Z(z) = z^n - offset^n.
+    // It is zero exactly on the domain elements since (offset * w^i)^n = offset^n
+    pub fn evaluate_vanishing(&self, z: F) -> F {
+        z.pow([self.size as u64]) - self.offset.pow([self.size as u64])
+    }
+
+    // Forward NTT: takes at most `size` coefficients and returns the evaluations of the polynomial
+    // at every element of the domain. Missing coefficients are treated as zero.
+    // On a coset, p(offset * x) is evaluated over the subgroup instead, which is the same as
+    // scaling the i-th coefficient by offset^i before the transform
+    pub fn fft(&self, coefficients: &[F]) -> Vec<F> {
+        assert!(coefficients.len() <= self.size, "too many coefficients for the domain size");
+
+        let mut values = coefficients.to_vec();
+        values.resize(self.size, F::zero());
+        if self.is_coset() {
+            scale_by_powers(&mut values, self.offset);
+        }
+        ntt_in_place(&mut values, self.forward_twiddles());
+
+        values
+    }
+
+    // Inverse NTT: takes the evaluations over the domain and returns the coefficients.
+    // Running the NTT with w^-1 instead of w and dividing by n undoes the forward transform
+    pub fn ifft(&self, evaluations: &[F]) -> Vec<F> {
+        assert!(evaluations.len() <= self.size, "too many evaluations for the domain size");
+
+        let mut values = evaluations.to_vec();
+        values.resize(self.size, F::zero());
+        ntt_in_place(&mut values, self.inverse_twiddles());
+
+        for value in values.iter_mut() {
+            *value *= self.size_inv;
+        }
+        if self.is_coset() {
+            scale_by_powers(&mut values, self.offset_inv);
+        }
+
+        values
+    }
+
+    fn forward_twiddles(&self) -> &[F] {
+        self.twiddles.forward.get_or_init(|| powers(self.generator, self.size / 2))
+    }
+
+    fn inverse_twiddles(&self) -> &[F] {
+        self.twiddles.inverse.get_or_init(|| powers(self.generator_inv, self.size / 2))
+    }
+}
+
+impl <F: PrimeField>EvaluationDomain<F> {
+    // Z(x) = x^n - offset^n, the polynomial that is zero on every element of the domain.
+    // It only has two terms, so it is returned in sparse form
+    pub fn vanishing_poly(&self) -> SparsePolynomial<F> {
+        SparsePolynomial::new(vec![
+            (0, -self.offset.pow([self.size as u64])),
+            (self.size, F::one()),
+        ])
+    }
+}
+
+// Multiplies values[i] by factor^i
+fn scale_by_powers<F: FftField>(values: &mut [F], factor: F) {
+    let mut power = F::one();
+    for value in values.iter_mut() {
+        *value *= power;
+        power *= factor;
+    }
+}
+
+// 1, factor, ..., factor^(count - 1)
+fn powers<F: FftField>(factor: F, count: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(count);
+    let mut power = F::one();
+    for _ in 0..count {
+        powers.push(power);
+        power *= factor;
+    }
+    powers
+}
+
+// Iterative Cooley-Tukey NTT. The input is first put in bit-reversed order, then
+// every stage combines pairs of half-size transforms with the butterfly:
+// (u, v) => (u + w*v, u - w*v)
+// twiddles holds the first n / 2 powers of the root, a stage of size s reads every (n / s)-th of them
+fn ntt_in_place<F: FftField>(values: &mut [F], twiddles: &[F]) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+    let log_n = n.trailing_zeros();
+
+    // bit-reversal permutation
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut half_size = 1;
+    while half_size < n {
+        let size = half_size * 2;
+        // the powers of the root of unity for transforms of this stage's size
+        let stride = n / size;
+
+        for chunk in values.chunks_mut(size) {
+            for j in 0..half_size {
+                let u = chunk[j];
+                let v = chunk[j + half_size] * twiddles[j * stride];
+                chunk[j] = u + v;
+                chunk[j + half_size] = u - v;
+            }
+        }
+
+        half_size = size;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fq, Fr};
+    use ark_ff::Field;
+
+    #[test]
+    fn test_domain_size_and_generator() {
+        let domain = EvaluationDomain::<Fr>::new(5).unwrap();
+
+        assert_eq!(domain.size, 8);
+        assert_eq!(domain.log_size, 3);
+        assert_eq!(domain.generator.pow([8]), Fr::from(1));
+        assert_ne!(domain.generator.pow([4]), Fr::from(1));
+        assert_eq!(domain.elements()[3], domain.element(3));
+    }
+
+    #[test]
+    fn test_domain_too_large_for_field() {
+        assert!(EvaluationDomain::<Fq>::new(2).is_some());
+        assert!(EvaluationDomain::<Fq>::new(4).is_none());
+    }
+
+    #[test]
+    fn test_fft_matches_naive_evaluation() {
+        let domain = EvaluationDomain::<Fr>::new(8).unwrap();
+        let coefficients: Vec<Fr> = (1..=6u64).map(Fr::from).collect();
+
+        let naive: Vec<Fr> = domain
+            .elements()
+            .iter()
+            .map(|x| coefficients.iter().rev().fold(Fr::from(0), |acc, c| acc * x + c))
+            .collect();
+
+        assert_eq!(domain.fft(&coefficients), naive);
+    }
+
+    #[test]
+    fn test_fft_over_goldilocks() {
+        use ark_ff::fields::{Fp64, MontBackend, MontConfig};
+
+        // p = 2^64 - 2^32 + 1, the same field as multilinear::goldilocks, declared here so the tests need no crate
+        // above this one
+        #[derive(MontConfig)]
+        #[modulus = "18446744069414584321"]
+        #[generator = "7"]
+        pub struct GoldilocksConfig;
+        type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
+
+        // a 64-bit field with two-adicity 32, so domains far beyond bn254's 2^28 exist and small ones wrap quickly
+        let domain = EvaluationDomain::<Goldilocks>::new(64).unwrap();
+        let coefficients: Vec<Goldilocks> = (0..64u64).map(|i| Goldilocks::from(u64::MAX - i * i)).collect();
+
+        let naive: Vec<Goldilocks> = domain
+            .elements()
+            .iter()
+            .map(|x| coefficients.iter().rev().fold(Goldilocks::from(0u64), |acc, c| acc * x + c))
+            .collect();
+
+        assert_eq!(domain.fft(&coefficients), naive);
+        assert_eq!(domain.ifft(&naive), coefficients);
+        assert_eq!(EvaluationDomain::<Goldilocks>::new(1 << 32).map(|domain| domain.log_size), Some(32));
+        assert!(EvaluationDomain::<Goldilocks>::new((1 << 32) + 1).is_none());
+    }
+
+    #[test]
+    fn test_twiddles_are_cached_and_shared_by_cosets() {
+        let domain = EvaluationDomain::<Fr>::new(16).unwrap();
+        let fresh = domain.clone();
+        assert!(domain.twiddles.forward.get().is_none());
+
+        let coefficients: Vec<Fr> = (0..16u64).map(|i| Fr::from(i + 1)).collect();
+        let evaluations = domain.fft(&coefficients);
+        assert_eq!(domain.twiddles.forward.get().map(Vec::len), Some(8));
+        assert!(domain.twiddles.inverse.get().is_none());
+        // a filled cache does not change the domain's value, nor the transform
+        assert_eq!(domain, fresh);
+        assert_eq!(fresh.fft(&coefficients), evaluations);
+
+        let coset = domain.get_coset(Fr::from(5)).unwrap();
+        assert!(coset.twiddles.forward.get().is_some());
+        assert_eq!(coset.ifft(&coset.fft(&coefficients)), coefficients);
+    }
+
+    #[test]
+    fn test_ifft_inverts_fft() {
+        let domain = EvaluationDomain::<Fr>::new(16).unwrap();
+        let coefficients: Vec<Fr> = (0..16u64).map(|i| Fr::from(i * 7 + 3)).collect();
+
+        assert_eq!(domain.ifft(&domain.fft(&coefficients)), coefficients);
+    }
+
+    #[test]
+    fn test_vanishing_poly() {
+        let domain = EvaluationDomain::<Fr>::new(8).unwrap();
+        let vanishing = domain.vanishing_poly();
+
+        assert_eq!(vanishing.degree(), 8);
+        for element in domain.elements() {
+            assert_eq!(vanishing.evaluate(element), Fr::from(0));
+            assert_eq!(domain.evaluate_vanishing(element), Fr::from(0));
+        }
+        assert_eq!(domain.evaluate_vanishing(Fr::from(3)), vanishing.evaluate(Fr::from(3)));
+        assert_ne!(domain.evaluate_vanishing(Fr::from(3)), Fr::from(0));
+    }
+
+    #[test]
+    fn test_coset_fft() {
+        let domain = EvaluationDomain::<Fr>::new(8).unwrap();
+        let coset = domain.get_coset(Fr::from(7)).unwrap();
+        let coefficients: Vec<Fr> = (1..=8u64).map(Fr::from).collect();
+
+        let naive: Vec<Fr> = coset
+            .elements()
+            .iter()
+            .map(|x| coefficients.iter().rev().fold(Fr::from(0), |acc, c| acc * x + c))
+            .collect();
+
+        assert_eq!(coset.fft(&coefficients), naive);
+        assert_eq!(coset.ifft(&naive), coefficients);
+
+        // the coset is disjoint from the subgroup, so the subgroup's vanishing polynomial never vanishes on it
+        for element in coset.elements() {
+            assert_ne!(domain.evaluate_vanishing(element), Fr::from(0));
+            assert_eq!(coset.evaluate_vanishing(element), Fr::from(0));
+        }
+        assert!(domain.get_coset(Fr::from(0)).is_none());
+    }
+}
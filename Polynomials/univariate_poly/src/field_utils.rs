@@ -0,0 +1,74 @@
+use ark_ff::Field;
+
+// Montgomery's batch inversion trick: inverts every element of `values` in place using
+// a single field inversion and about 3(n - 1) multiplications.
+//
+// 1. Compute the running products p_i = v_0 * v_1 * ... * v_i
+// 2. Invert the total product once: inv = (v_0 * ... * v_(n-1))^-1
+// 3. Walk backwards: v_i^-1 = inv * p_(i-1), then inv = inv * v_i removes v_i from the inverse
+//
+// Zero elements have no inverse, they are skipped and left as zero
+pub fn batch_inversion<F: Field>(values: &mut [F]) {
+    let mut running_products = Vec::with_capacity(values.len());
+    let mut product = F::one();
+
+    for value in values.iter() {
+        if !value.is_zero() {
+            running_products.push(product);
+            product *= value;
+        }
+    }
+
+    let mut inverse = product.inverse().expect("product of non-zero elements is non-zero");
+
+    for (value, running_product) in values
+        .iter_mut()
+        .rev()
+        .filter(|value| !value.is_zero())
+        .zip(running_products.into_iter().rev())
+    {
+        let value_inverse = inverse * running_product;
+        inverse *= *value;
+        *value = value_inverse;
+    }
+}
+
+// Same as batch_inversion but leaves the input untouched and returns the inverses
+pub fn batch_inverse<F: Field>(values: &[F]) -> Vec<F> {
+    let mut inverses = values.to_vec();
+    batch_inversion(&mut inverses);
+    inverses
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_batch_inversion() {
+        let values: Vec<Fr> = (1..=20u64).map(|i| Fr::from(i * 13 + 2)).collect();
+        let inverses = batch_inverse(&values);
+
+        for (value, inverse) in values.iter().zip(inverses.iter()) {
+            assert_eq!(*value * inverse, Fr::from(1));
+            assert_eq!(value.inverse().unwrap(), *inverse);
+        }
+    }
+
+    #[test]
+    fn test_batch_inversion_skips_zeros() {
+        let mut values = vec![Fr::from(2), Fr::from(0), Fr::from(5), Fr::from(0)];
+        batch_inversion(&mut values);
+
+        assert_eq!(values[0], Fr::from(2).inverse().unwrap());
+        assert_eq!(values[1], Fr::from(0));
+        assert_eq!(values[2], Fr::from(5).inverse().unwrap());
+        assert_eq!(values[3], Fr::from(0));
+
+        let mut empty: Vec<Fr> = Vec::new();
+        batch_inversion(&mut empty);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+use ark_ff::PrimeField;
+use std::fmt;
+use std::str::FromStr;
+use crate::sparse::SparsePolynomial;
+use crate::univariate::UnivariatePoly;
+
+// Polynomials are written the way they are on paper, highest power first: 4x^3 + 8x^2 + 2.
+// A coefficient above (p - 1) / 2 is written as the negative of p - c, so x^2 - 1 reads x^2 - 1 rather than
+// x^2 + 21888...616. Coefficients of 1 are left out, and the zero polynomial is 0.
+//
+// Parsing takes the same form back, with any spacing and an optional * between coefficient and x, so
+// "2x + 5", "2*x+5" and "-x^2 + 3" all parse. Terms can come in any order and repeated powers are added up
+const MAX_PARSED_DEGREE: usize = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePolynomialError {
+    Empty,
+    // a term that is not [coefficient][*][x[^exponent]], e.g. "2y" or "3x^"
+    InvalidTerm(String),
+    // dense polynomials store every coefficient, so a typo like x^100000000 is refused instead of allocated
+    DegreeTooLarge { degree: usize, max: usize },
+}
+
+impl fmt::Display for ParsePolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePolynomialError::Empty => write!(f, "empty polynomial"),
+            ParsePolynomialError::InvalidTerm(term) => write!(f, "invalid term \"{}\"", term),
+            ParsePolynomialError::DegreeTooLarge { degree, max } => {
+                write!(f, "degree {} is above the maximum of {} for a dense polynomial", degree, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParsePolynomialError {}
+
+impl <F: PrimeField>fmt::Display for UnivariatePoly<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms = self.coefficients.iter().enumerate().rev().map(|(exponent, coefficient)| (exponent, *coefficient));
+        write_terms(f, terms)
+    }
+}
+
+impl <F: PrimeField>fmt::Display for SparsePolynomial<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_terms(f, self.terms.iter().rev().copied())
+    }
+}
+
+impl <F: PrimeField>FromStr for UnivariatePoly<F> {
+    type Err = ParsePolynomialError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = parse_terms::<F>(s)?;
+        let degree = terms.iter().map(|(exponent, _)| *exponent).max().unwrap_or(0);
+        if degree > MAX_PARSED_DEGREE {
+            return Err(ParsePolynomialError::DegreeTooLarge { degree, max: MAX_PARSED_DEGREE });
+        }
+
+        let mut coefficients = vec![F::zero(); degree + 1];
+        for (exponent, coefficient) in terms {
+            coefficients[exponent] += coefficient;
+        }
+        Ok(Self::new(coefficients))
+    }
+}
+
+impl <F: PrimeField>FromStr for SparsePolynomial<F> {
+    type Err = ParsePolynomialError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(parse_terms(s)?))
+    }
+}
+
+// Writes the nonzero terms in the order given, which is highest power first for both representations
+fn write_terms<F: PrimeField>(f: &mut fmt::Formatter, terms: impl Iterator<Item = (usize, F)>) -> fmt::Result {
+    let mut first = true;
+    for (exponent, coefficient) in terms.filter(|(_, coefficient)| !coefficient.is_zero()) {
+        let negative = coefficient.into_bigint() > F::MODULUS_MINUS_ONE_DIV_TWO;
+        let magnitude = if negative { -coefficient } else { coefficient };
+
+        match (first, negative) {
+            (true, true) => write!(f, "-")?,
+            (true, false) => {}
+            (false, true) => write!(f, " - ")?,
+            (false, false) => write!(f, " + ")?,
+        }
+        first = false;
+
+        if !magnitude.is_one() || exponent == 0 {
+            write!(f, "{}", magnitude)?;
+        }
+        match exponent {
+            0 => {}
+            1 => write!(f, "x")?,
+            _ => write!(f, "x^{}", exponent)?,
+        }
+    }
+
+    if first {
+        write!(f, "0")?;
+    }
+    Ok(())
+}
+
+// Splits at every + or - that starts a term and reads each term as (exponent, signed coefficient)
+fn parse_terms<F: PrimeField>(s: &str) -> Result<Vec<(usize, F)>, ParsePolynomialError> {
+    let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if s.is_empty() {
+        return Err(ParsePolynomialError::Empty);
+    }
+
+    let mut terms = Vec::new();
+    let mut start = 0;
+    for (i, c) in s.char_indices().skip(1) {
+        if c == '+' || c == '-' {
+            terms.push(parse_term(&s[start..i])?);
+            start = i;
+        }
+    }
+    terms.push(parse_term(&s[start..])?);
+    Ok(terms)
+}
+
+fn parse_term<F: PrimeField>(term: &str) -> Result<(usize, F), ParsePolynomialError> {
+    let invalid = || ParsePolynomialError::InvalidTerm(term.to_string());
+
+    let (negative, unsigned) = match term.as_bytes().first() {
+        Some(b'-') => (true, &term[1..]),
+        Some(b'+') => (false, &term[1..]),
+        _ => (false, term),
+    };
+
+    let digits_end = unsigned.find(|c: char| !c.is_ascii_digit()).unwrap_or(unsigned.len());
+    let (digits, rest) = unsigned.split_at(digits_end);
+
+    let exponent = match rest {
+        "" if !digits.is_empty() => 0,
+        "" => return Err(invalid()),
+        _ => {
+            // the * only goes between a coefficient and x
+            let rest = match rest.strip_prefix('*') {
+                Some(rest) if !digits.is_empty() => rest,
+                Some(_) => return Err(invalid()),
+                None => rest,
+            };
+            match rest.strip_prefix('x').ok_or_else(invalid)? {
+                "" => 1,
+                power => {
+                    let exponent = power.strip_prefix('^').ok_or_else(invalid)?;
+                    if exponent.is_empty() || !exponent.bytes().all(|b| b.is_ascii_digit()) {
+                        return Err(invalid());
+                    }
+                    exponent.parse().map_err(|_| invalid())?
+                }
+            }
+        }
+    };
+
+    let coefficient = if digits.is_empty() { F::one() } else { F::from_str(digits).map_err(|_| invalid())? };
+    Ok((exponent, if negative { -coefficient } else { coefficient }))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrary::polynomial;
+    use ark_bn254::Fr;
+    use proptest::prelude::*;
+
+    fn poly(coefficients: &[i64]) -> UnivariatePoly<Fr> {
+        UnivariatePoly::new(coefficients.iter().map(|c| Fr::from(*c)).collect())
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(poly(&[2, 0, 8, 4]).to_string(), "4x^3 + 8x^2 + 2");
+        assert_eq!(poly(&[5, 2]).to_string(), "2x + 5");
+        assert_eq!(poly(&[-1, 0, 1]).to_string(), "x^2 - 1");
+        assert_eq!(poly(&[0, -1]).to_string(), "-x");
+        assert_eq!(poly(&[7]).to_string(), "7");
+        assert_eq!(UnivariatePoly::<Fr>::zero().to_string(), "0");
+
+        let sparse = SparsePolynomial::new(vec![(0, -Fr::from(1)), (1024, Fr::from(1))]);
+        assert_eq!(sparse.to_string(), "x^1024 - 1");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2x+5".parse::<UnivariatePoly<Fr>>(), Ok(poly(&[5, 2])));
+        assert_eq!(" 4x^3 + 8 x^2 +2 ".parse::<UnivariatePoly<Fr>>(), Ok(poly(&[2, 0, 8, 4])));
+        assert_eq!("-x^2 + 3*x - 1".parse::<UnivariatePoly<Fr>>(), Ok(poly(&[-1, 3, -1])));
+        // unordered and repeated powers add up
+        assert_eq!("1 + x + 2x - 1".parse::<UnivariatePoly<Fr>>(), Ok(poly(&[0, 3])));
+        assert_eq!("0".parse::<UnivariatePoly<Fr>>(), Ok(UnivariatePoly::zero()));
+        assert_eq!(
+            "x^1024 - 1".parse::<SparsePolynomial<Fr>>(),
+            Ok(SparsePolynomial::new(vec![(0, -Fr::from(1)), (1024, Fr::from(1))]))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<UnivariatePoly<Fr>>(), Err(ParsePolynomialError::Empty));
+        for bad in ["2y", "3x^", "x^-2", "2x+", "*x", "x2", "++1", "2**x"] {
+            assert!(matches!(bad.parse::<UnivariatePoly<Fr>>(), Err(ParsePolynomialError::InvalidTerm(_))), "{}", bad);
+        }
+        assert_eq!(
+            "x^100000000".parse::<UnivariatePoly<Fr>>(),
+            Err(ParsePolynomialError::DegreeTooLarge { degree: 100000000, max: MAX_PARSED_DEGREE })
+        );
+        // a sparse polynomial stores only its terms, so the same input is fine there
+        assert!("x^100000000".parse::<SparsePolynomial<Fr>>().is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_display_parses_back(polynomial in polynomial::<Fr>(6)) {
+            prop_assert_eq!(polynomial.to_string().parse::<UnivariatePoly<Fr>>(), Ok(polynomial));
+        }
+    }
+}
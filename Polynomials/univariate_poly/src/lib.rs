@@ -0,0 +1,11 @@
+pub mod univariate;
+pub mod sparse;
+pub mod multiplication;
+pub mod domain;
+pub mod field_utils;
+pub mod format;
+pub mod reed_solomon;
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
+#[cfg(any(test, feature = "ark-poly"))]
+pub mod ark;
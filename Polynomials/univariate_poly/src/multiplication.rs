@@ -0,0 +1,227 @@
+use ark_ff::PrimeField;
+use crate::univariate::UnivariatePoly;
+
+// Below this many coefficients in the shorter operand Karatsuba's extra additions cost more than the multiplications
+// it saves, and the recursion bottoms out in the schoolbook product
+pub const KARATSUBA_THRESHOLD: usize = 16;
+// From this many coefficients in the shorter operand Toom-3's five products of a third of the size beat Karatsuba's
+// three of half the size, once its interpolation is paid for. Over BN254 the two are within a few percent of each
+// other up to here, see the multiplication example
+pub const TOOM3_THRESHOLD: usize = 512;
+
+// Multiplication without an evaluation domain, for sizes below the NTT threshold and for fields without a large
+// enough power-of-two subgroup, where ntt_mul returns None.
+//
+// Karatsuba splits both operands in halves, a = a_0 + x^m a_1, and gets the product from three half-size products,
+// a_0 b_0, a_1 b_1 and (a_0 + a_1)(b_0 + b_1), in O(n^1.585). Toom-3 splits them in thirds, evaluates the quadratics
+// in x^k at 0, 1, -1, -2 and infinity and interpolates the product from five third-size products, in O(n^1.465),
+// dividing by 2 and 3 on the way, so it falls back to Karatsuba in a field of characteristic 2 or 3. Both recurse
+// into themselves and into the schoolbook product below KARATSUBA_THRESHOLD, and split the longer operand alone
+// when the other is too short to split. The Mul impl picks between them and the NTT by size
+impl <F: PrimeField>UnivariatePoly<F> {
+    pub fn karatsuba_mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        UnivariatePoly::new(karatsuba(&self.coefficients, &other.coefficients))
+    }
+
+    pub fn toom3_mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        UnivariatePoly::new(toom3(&self.coefficients, &other.coefficients))
+    }
+}
+
+// The product of two non-empty coefficient slices, a.len() + b.len() - 1 coefficients
+pub(crate) fn schoolbook<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    let mut result = vec![F::zero(); a.len() + b.len() - 1];
+    for (i, left) in a.iter().enumerate() {
+        for (j, right) in b.iter().enumerate() {
+            result[i + j] += *left * right;
+        }
+    }
+    result
+}
+
+fn karatsuba<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+
+    let m = a.len().max(b.len()).div_ceil(2);
+    if a.len() <= m || b.len() <= m {
+        return split_longer(a, b, m, karatsuba);
+    }
+
+    let (a_0, a_1) = a.split_at(m);
+    let (b_0, b_1) = b.split_at(m);
+    let low = karatsuba(a_0, b_0);
+    let high = karatsuba(a_1, b_1);
+    let mut middle = karatsuba(&add(a_0, a_1), &add(b_0, b_1));
+    subtract_into(&mut middle, &low);
+    subtract_into(&mut middle, &high);
+
+    let mut result = vec![F::zero(); a.len() + b.len() - 1];
+    add_into(&mut result, &low, 0);
+    add_into(&mut result, &middle, m);
+    add_into(&mut result, &high, 2 * m);
+    result
+}
+
+fn toom3<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.len().min(b.len()) < TOOM3_THRESHOLD {
+        return karatsuba(a, b);
+    }
+    let (Some(half), Some(third)) = (F::from(2u64).inverse(), F::from(3u64).inverse()) else {
+        return karatsuba(a, b);
+    };
+
+    let k = a.len().max(b.len()).div_ceil(3);
+    if a.len() <= 2 * k || b.len() <= 2 * k {
+        return split_longer(a, b, k, toom3);
+    }
+
+    // a(y) = a_0 + a_1 y + a_2 y^2 with y = x^k, at 0, 1, -1, -2 and infinity
+    let evaluate = |coefficients: &[F]| {
+        let (part_0, rest) = coefficients.split_at(k);
+        let (part_1, part_2) = rest.split_at(k);
+        let even = add(part_0, part_2);
+        let at_1 = add(&even, part_1);
+        let at_minus_1 = sub(&even, part_1);
+        let at_minus_2 = sub(&scale(&add(&at_minus_1, part_2), F::from(2u64)), part_0);
+        [part_0.to_vec(), at_1, at_minus_1, at_minus_2, part_2.to_vec()]
+    };
+    let [a_0, a_1, a_minus_1, a_minus_2, a_infinity] = evaluate(a);
+    let [b_0, b_1, b_minus_1, b_minus_2, b_infinity] = evaluate(b);
+
+    let r_0 = toom3(&a_0, &b_0);
+    let r_1 = toom3(&a_1, &b_1);
+    let r_minus_1 = toom3(&a_minus_1, &b_minus_1);
+    let r_minus_2 = toom3(&a_minus_2, &b_minus_2);
+    let r_infinity = toom3(&a_infinity, &b_infinity);
+
+    // Bodrato's interpolation sequence, c_i the coefficient of y^i in the product
+    let c_3 = scale(&sub(&r_minus_2, &r_1), third);
+    let c_1 = scale(&sub(&r_1, &r_minus_1), half);
+    let c_2 = sub(&r_minus_1, &r_0);
+    let c_3 = add(&scale(&sub(&c_2, &c_3), half), &scale(&r_infinity, F::from(2u64)));
+    let c_2 = sub(&add(&c_2, &c_1), &r_infinity);
+    let c_1 = sub(&c_1, &c_3);
+
+    // the middle parts can reach a few coefficients past the end of the product, where they are all zero
+    let mut result = vec![F::zero(); a.len() + b.len() - 1];
+    for (i, part) in [r_0, c_1, c_2, c_3, r_infinity].iter().enumerate() {
+        add_into(&mut result, part, i * k);
+    }
+    result.truncate(a.len() + b.len() - 1);
+    result
+}
+
+// a * b with the longer operand split at m and the shorter whole: lower * shorter + x^m (upper * shorter)
+fn split_longer<F: PrimeField>(a: &[F], b: &[F], m: usize, multiply: fn(&[F], &[F]) -> Vec<F>) -> Vec<F> {
+    let (longer, shorter) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let (lower, upper) = longer.split_at(m);
+
+    let mut result = vec![F::zero(); a.len() + b.len() - 1];
+    add_into(&mut result, &multiply(lower, shorter), 0);
+    add_into(&mut result, &multiply(upper, shorter), m);
+    result
+}
+
+fn add<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    let mut result = a.to_vec();
+    add_into(&mut result, b, 0);
+    result
+}
+
+fn sub<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    let mut result = a.to_vec();
+    subtract_into(&mut result, b);
+    result
+}
+
+fn scale<F: PrimeField>(a: &[F], scalar: F) -> Vec<F> {
+    a.iter().map(|coefficient| *coefficient * scalar).collect()
+}
+
+// result += x^offset * part, growing result if part reaches past its end
+fn add_into<F: PrimeField>(result: &mut Vec<F>, part: &[F], offset: usize) {
+    if result.len() < offset + part.len() {
+        result.resize(offset + part.len(), F::zero());
+    }
+    for (target, coefficient) in result[offset..].iter_mut().zip(part) {
+        *target += coefficient;
+    }
+}
+
+fn subtract_into<F: PrimeField>(result: &mut Vec<F>, part: &[F]) {
+    if result.len() < part.len() {
+        result.resize(part.len(), F::zero());
+    }
+    for (target, coefficient) in result.iter_mut().zip(part) {
+        *target -= coefficient;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use proptest::prelude::*;
+
+    fn poly(length: u64, seed: u64) -> UnivariatePoly<Fr> {
+        UnivariatePoly::new((0..length).map(|i| Fr::from(i * i * seed + 3 * i + seed)).collect())
+    }
+
+    #[test]
+    fn test_karatsuba_and_toom3_match_schoolbook() {
+        // balanced, unbalanced and lengths that do not split evenly, around both thresholds
+        for (left, right) in [(15, 15), (16, 16), (17, 40), (200, 97), (500, 20), (1, 400), (511, 512), (512, 512), (1100, 530), (1601, 800)] {
+            let (a, b) = (poly(left, 5), poly(right, 11));
+            let expected = a.schoolbook_mul(&b);
+            assert_eq!(a.karatsuba_mul(&b), expected, "karatsuba {} x {}", left, right);
+            assert_eq!(a.toom3_mul(&b), expected, "toom3 {} x {}", left, right);
+            assert_eq!(&a * &b, expected, "mul {} x {}", left, right);
+        }
+    }
+
+    #[test]
+    fn test_zero_and_constant_operands() {
+        let a = poly(120, 3);
+        assert!(a.karatsuba_mul(&UnivariatePoly::zero()).is_zero());
+        assert!(UnivariatePoly::zero().toom3_mul(&a).is_zero());
+        assert_eq!(a.toom3_mul(&UnivariatePoly::new(vec![Fr::from(2)])), a.scalar_mul(Fr::from(2)));
+    }
+
+    #[test]
+    fn test_fields_without_a_large_domain_still_multiply() {
+        use ark_ff::fields::{Fp64, MontBackend, MontConfig};
+
+        // 193 - 1 = 64 * 3, so no domain holds more than 64 points and ntt_mul refuses these products
+        #[derive(MontConfig)]
+        #[modulus = "193"]
+        #[generator = "5"]
+        pub struct SmallConfig;
+        type Small = Fp64<MontBackend<SmallConfig, 1>>;
+
+        for length in [300u64, 600] {
+            let a = UnivariatePoly::new((0..length).map(|i| Small::from(i * 7 + 1)).collect());
+            let b = UnivariatePoly::new((0..length).map(|i| Small::from(i * 13 + 2)).collect());
+            assert!(a.ntt_mul(&b).is_none());
+            assert_eq!(&a * &b, a.schoolbook_mul(&b));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_fallbacks_match_schoolbook(left in 1u64..260, right in 1u64..260, seed in 1u64..1000) {
+            let (a, b) = (poly(left, seed), poly(right, seed + 1));
+            let expected = a.schoolbook_mul(&b);
+            prop_assert_eq!(a.karatsuba_mul(&b), expected.clone());
+            prop_assert_eq!(a.toom3_mul(&b), expected);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use ark_ff::PrimeField;
+use std::fmt;
+use crate::domain::EvaluationDomain;
+use crate::univariate::UnivariatePoly;
+
+// Reed-Solomon codes over an evaluation domain: a message of k field elements is a polynomial of degree < k, and its
+// codeword is the polynomial's evaluations over the n elements of the domain, n >= k. Two polynomials of degree < k
+// agree on at most k - 1 points, so any k positions of a codeword determine it: up to n - k positions can be erased.
+//
+// The encoding is systematic: the message polynomial is the one that takes the message values at the first k domain
+// elements, so the codeword starts with the message itself. Shamir sharing is the same code read the other way round,
+// the secret is one evaluation and the shares are the codeword, and FRI proves that a committed vector is close to a
+// codeword of this code
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReedSolomon<F: PrimeField> {
+    pub message_len: usize,
+    pub domain: EvaluationDomain<F>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReedSolomonError {
+    WrongLength { expected: usize, provided: usize },
+    // fewer than message_len positions survived
+    TooManyErasures { needed: usize, available: usize },
+    // the positions that survived are not all on one polynomial of degree < message_len
+    Inconsistent,
+}
+
+impl fmt::Display for ReedSolomonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReedSolomonError::WrongLength { expected, provided } => write!(f, "expected {} symbols, got {}", expected, provided),
+            ReedSolomonError::TooManyErasures { needed, available } => {
+                write!(f, "{} symbols are needed to decode, only {} are left", needed, available)
+            }
+            ReedSolomonError::Inconsistent => write!(f, "the received symbols are not a codeword"),
+        }
+    }
+}
+
+impl std::error::Error for ReedSolomonError {}
+
+impl <F: PrimeField>ReedSolomon<F> {
+    // A code for messages of message_len elements with codewords of at least codeword_len elements, rounded up to
+    // the power of two domain holding them. None for an empty message, or when the field has no domain that large
+    pub fn new(message_len: usize, codeword_len: usize) -> Option<Self> {
+        if message_len == 0 {
+            return None;
+        }
+        let domain = EvaluationDomain::new(codeword_len.max(message_len))?;
+        Some(Self { message_len, domain })
+    }
+
+    pub fn codeword_len(&self) -> usize {
+        self.domain.size
+    }
+
+    // The polynomial of degree < message_len through the message values at the first message_len domain elements
+    pub fn message_polynomial(&self, message: &[F]) -> UnivariatePoly<F> {
+        assert_eq!(message.len(), self.message_len, "the message must have message_len elements");
+
+        let xs: Vec<F> = self.domain.elements().into_iter().take(self.message_len).collect();
+        UnivariatePoly::interpolate(&xs, message)
+    }
+
+    pub fn encode(&self, message: &[F]) -> Vec<F> {
+        self.message_polynomial(message).evaluate_over_domain(&self.domain)
+    }
+
+    // Recovers the message from a codeword with erased positions set to None. The message polynomial is interpolated
+    // through message_len of the surviving positions, and every other surviving position has to agree with it, so a
+    // corrupted symbol is detected as long as more than message_len positions survive
+    pub fn decode_erasures(&self, received: &[Option<F>]) -> Result<Vec<F>, ReedSolomonError> {
+        if received.len() != self.codeword_len() {
+            return Err(ReedSolomonError::WrongLength { expected: self.codeword_len(), provided: received.len() });
+        }
+
+        let elements = self.domain.elements();
+        let (xs, ys): (Vec<F>, Vec<F>) = received
+            .iter()
+            .zip(&elements)
+            .filter_map(|(symbol, x)| symbol.map(|y| (*x, y)))
+            .unzip();
+        if xs.len() < self.message_len {
+            return Err(ReedSolomonError::TooManyErasures { needed: self.message_len, available: xs.len() });
+        }
+
+        let polynomial = UnivariatePoly::interpolate(&xs[..self.message_len], &ys[..self.message_len]);
+        let codeword = polynomial.evaluate_over_domain(&self.domain);
+        if received.iter().zip(&codeword).any(|(symbol, value)| symbol.is_some_and(|symbol| symbol != *value)) {
+            return Err(ReedSolomonError::Inconsistent);
+        }
+
+        Ok(codeword[..self.message_len].to_vec())
+    }
+}
+
+// Gao's decoder: the polynomial of degree < message_len through all but at most (n - message_len) / 2 of the n points,
+// or None if there is none. The points do not have to be a domain, so this also decodes Shamir shares.
+//
+// With g0 = prod (x - x_i) and g1 the interpolation of all the points, the extended Euclidean algorithm on g0, g1 is
+// stopped at the first remainder r of degree < (n + message_len) / 2, with r = u * g0 + v * g1. If at most
+// (n - message_len) / 2 points are wrong, v is their error locator up to a constant and r = f * v for the message
+// polynomial f, so f is r / v
+pub fn decode_with_errors<F: PrimeField>(xs: &[F], ys: &[F], message_len: usize) -> Option<UnivariatePoly<F>> {
+    assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+    let n = xs.len();
+    if message_len == 0 || n < message_len {
+        return None;
+    }
+
+    let (mut r0, mut t0) = (UnivariatePoly::from_roots(xs), UnivariatePoly::zero());
+    let (mut r1, mut t1) = (UnivariatePoly::interpolate(xs, ys), UnivariatePoly::new(vec![F::one()]));
+    while !r1.is_zero() && 2 * r1.degree() >= n + message_len {
+        let (quotient, remainder) = r0.div_rem(&r1)?;
+        let t2 = &t0 - &(&quotient * &t1);
+        (r0, t0) = (r1, t1);
+        (r1, t1) = (remainder, t2);
+    }
+
+    let (polynomial, remainder) = r1.div_rem(&t1)?;
+    if !remainder.is_zero() || polynomial.coefficients.len() > message_len {
+        return None;
+    }
+
+    // more errors than the decoder corrects can still leave an exact division, which is then a wrong answer
+    let errors = xs.iter().zip(ys).filter(|(x, y)| polynomial.evaluate(**x) != **y).count();
+    (2 * errors <= n - message_len).then_some(polynomial)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrary::field_element;
+    use ark_bn254::Fr;
+    use proptest::prelude::*;
+
+    fn message(values: &[u64]) -> Vec<Fr> {
+        values.iter().map(|value| Fr::from(*value)).collect()
+    }
+
+    #[test]
+    fn test_encoding_is_systematic() {
+        let code = ReedSolomon::<Fr>::new(3, 8).unwrap();
+        let codeword = code.encode(&message(&[4, 8, 15]));
+
+        assert_eq!(codeword.len(), 8);
+        assert_eq!(codeword[..3], message(&[4, 8, 15]));
+        // the codeword is the message polynomial over the domain
+        assert_eq!(codeword, code.message_polynomial(&message(&[4, 8, 15])).batch_evaluate(&code.domain.elements()));
+    }
+
+    #[test]
+    fn test_erasure_decoding() {
+        let code = ReedSolomon::<Fr>::new(3, 8).unwrap();
+        let codeword = code.encode(&message(&[4, 8, 15]));
+
+        // any 5 erasures are fine, the message positions included
+        let received: Vec<Option<Fr>> =
+            codeword.iter().enumerate().map(|(i, symbol)| [1, 4, 6].contains(&i).then_some(*symbol)).collect();
+        assert_eq!(code.decode_erasures(&received), Ok(message(&[4, 8, 15])));
+
+        let received: Vec<Option<Fr>> =
+            codeword.iter().enumerate().map(|(i, symbol)| [1, 6].contains(&i).then_some(*symbol)).collect();
+        assert_eq!(code.decode_erasures(&received), Err(ReedSolomonError::TooManyErasures { needed: 3, available: 2 }));
+    }
+
+    #[test]
+    fn test_corruption_is_detected() {
+        let code = ReedSolomon::<Fr>::new(3, 8).unwrap();
+        let mut received: Vec<Option<Fr>> = code.encode(&message(&[4, 8, 15])).into_iter().map(Some).collect();
+        received[5] = Some(Fr::from(16));
+        received[0] = None;
+
+        assert_eq!(code.decode_erasures(&received), Err(ReedSolomonError::Inconsistent));
+        assert_eq!(code.decode_erasures(&received[..4]), Err(ReedSolomonError::WrongLength { expected: 8, provided: 4 }));
+    }
+
+    #[test]
+    fn test_code_parameters() {
+        assert!(ReedSolomon::<Fr>::new(0, 8).is_none());
+        // rounded up to a power of two, and never shorter than the message
+        assert_eq!(ReedSolomon::<Fr>::new(3, 5).unwrap().codeword_len(), 8);
+        assert_eq!(ReedSolomon::<Fr>::new(6, 2).unwrap().codeword_len(), 8);
+    }
+
+    #[test]
+    fn test_decoding_corrects_errors() {
+        let xs: Vec<Fr> = (1..=7u64).map(Fr::from).collect();
+        let polynomial = UnivariatePoly::new(message(&[3, 1, 4]));
+        let mut ys = polynomial.batch_evaluate(&xs);
+        assert_eq!(decode_with_errors(&xs, &ys, 3), Some(polynomial.clone()));
+
+        // 7 points of a degree 2 polynomial correct (7 - 3) / 2 = 2 errors
+        ys[1] += Fr::from(1);
+        ys[5] = Fr::from(0);
+        assert_eq!(decode_with_errors(&xs, &ys, 3), Some(polynomial));
+
+        ys[6] += Fr::from(9);
+        assert_eq!(decode_with_errors(&xs, &ys, 3), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_any_message_len_positions_decode(
+            message in prop::collection::vec(field_element::<Fr>(), 1..8),
+            kept in prop::sample::subsequence((0..16).collect::<Vec<usize>>(), 8),
+        ) {
+            let code = ReedSolomon::new(message.len(), 16).unwrap();
+            let codeword = code.encode(&message);
+            let received: Vec<Option<Fr>> = codeword.iter().enumerate().map(|(i, symbol)| kept.contains(&i).then_some(*symbol)).collect();
+
+            prop_assert_eq!(code.decode_erasures(&received), Ok(message));
+        }
+
+        #[test]
+        fn prop_up_to_half_the_redundancy_is_corrected(
+            message in prop::collection::vec(field_element::<Fr>(), 1..6),
+            errors in prop::collection::vec((0..12usize, field_element::<Fr>()), 0..4),
+        ) {
+            let code = ReedSolomon::new(message.len(), 12).unwrap();
+            let xs: Vec<Fr> = code.domain.elements().into_iter().take(12).collect();
+            let polynomial = code.message_polynomial(&message);
+            let mut ys = polynomial.batch_evaluate(&xs);
+            for (position, error) in errors.iter().take((12 - message.len()) / 2) {
+                ys[*position] += error;
+            }
+
+            prop_assert_eq!(decode_with_errors(&xs, &ys, message.len()), Some(polynomial));
+        }
+    }
+}
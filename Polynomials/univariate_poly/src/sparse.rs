@@ -0,0 +1,227 @@
+use ark_ff::PrimeField;
+use std::ops::{Add, Mul, Sub};
+use crate::univariate::UnivariatePoly;
+
+// A univariate polynomial stored as a list of (exponent, coefficient) terms.
+// Polynomials such as x^1024 - 1 only need two terms here instead of 1025 dense coefficients.
+// Terms are kept sorted by exponent, with no zero coefficients and no repeated exponents
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparsePolynomial<F: PrimeField> {
+    pub terms: Vec<(usize, F)>,
+}
+
+impl <F: PrimeField>SparsePolynomial<F> {
+    // Sorts the terms, merges terms with the same exponent and drops zero coefficients
+    pub fn new(mut terms: Vec<(usize, F)>) -> Self {
+        terms.sort_by_key(|(exponent, _)| *exponent);
+
+        let mut merged: Vec<(usize, F)> = Vec::with_capacity(terms.len());
+        for (exponent, coefficient) in terms {
+            match merged.last_mut() {
+                Some((last_exponent, last_coefficient)) if *last_exponent == exponent => {
+                    *last_coefficient += coefficient;
+                }
+                _ => merged.push((exponent, coefficient)),
+            }
+        }
+        merged.retain(|(_, coefficient)| !coefficient.is_zero());
+
+        Self { terms: merged }
+    }
+
+    pub fn zero() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn degree(&self) -> usize {
+        self.terms.last().map(|(exponent, _)| *exponent).unwrap_or(0)
+    }
+
+    // Each term is evaluated with a square-and-multiply exponentiation, so the cost depends
+    // on the number of terms and not on the degree
+    pub fn evaluate(&self, x: F) -> F {
+        self.terms
+            .iter()
+            .map(|(exponent, coefficient)| x.pow([*exponent as u64]) * coefficient)
+            .sum()
+    }
+
+    pub fn scalar_mul(&self, scalar: F) -> Self {
+        SparsePolynomial::new(
+            self.terms
+                .iter()
+                .map(|(exponent, coefficient)| (*exponent, *coefficient * scalar))
+                .collect(),
+        )
+    }
+
+    pub fn to_dense(&self) -> UnivariatePoly<F> {
+        let mut coefficients = vec![F::zero(); self.degree() + 1];
+        for (exponent, coefficient) in &self.terms {
+            coefficients[*exponent] = *coefficient;
+        }
+
+        UnivariatePoly::new(coefficients)
+    }
+
+    pub fn from_dense(polynomial: &UnivariatePoly<F>) -> Self {
+        SparsePolynomial::new(polynomial.coefficients.iter().copied().enumerate().collect())
+    }
+}
+
+impl <F: PrimeField>From<SparsePolynomial<F>> for UnivariatePoly<F> {
+    fn from(polynomial: SparsePolynomial<F>) -> Self {
+        polynomial.to_dense()
+    }
+}
+
+impl <F: PrimeField>Add for &SparsePolynomial<F> {
+    type Output = SparsePolynomial<F>;
+
+    fn add(self, other: Self) -> SparsePolynomial<F> {
+        SparsePolynomial::new(self.terms.iter().chain(other.terms.iter()).copied().collect())
+    }
+}
+
+impl <F: PrimeField>Sub for &SparsePolynomial<F> {
+    type Output = SparsePolynomial<F>;
+
+    fn sub(self, other: Self) -> SparsePolynomial<F> {
+        self + &other.scalar_mul(-F::one())
+    }
+}
+
+impl <F: PrimeField>Mul for &SparsePolynomial<F> {
+    type Output = SparsePolynomial<F>;
+
+    fn mul(self, other: Self) -> SparsePolynomial<F> {
+        let mut terms = Vec::with_capacity(self.terms.len() * other.terms.len());
+        for (left_exponent, left_coefficient) in &self.terms {
+            for (right_exponent, right_coefficient) in &other.terms {
+                terms.push((left_exponent + right_exponent, *left_coefficient * right_coefficient));
+            }
+        }
+
+        SparsePolynomial::new(terms)
+    }
+}
+
+// Mixed arithmetic always produces a dense polynomial, since the dense operand already pays for every coefficient
+
+impl <F: PrimeField>Add<&UnivariatePoly<F>> for &SparsePolynomial<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn add(self, other: &UnivariatePoly<F>) -> UnivariatePoly<F> {
+        let mut coefficients = other.coefficients.clone();
+        if coefficients.len() <= self.degree() {
+            coefficients.resize(self.degree() + 1, F::zero());
+        }
+        for (exponent, coefficient) in &self.terms {
+            coefficients[*exponent] += coefficient;
+        }
+
+        UnivariatePoly::new(coefficients)
+    }
+}
+
+impl <F: PrimeField>Add<&SparsePolynomial<F>> for &UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn add(self, other: &SparsePolynomial<F>) -> UnivariatePoly<F> {
+        other + self
+    }
+}
+
+impl <F: PrimeField>Sub<&SparsePolynomial<F>> for &UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn sub(self, other: &SparsePolynomial<F>) -> UnivariatePoly<F> {
+        &other.scalar_mul(-F::one()) + self
+    }
+}
+
+// O(n * k) where n is the number of dense coefficients and k the number of sparse terms
+impl <F: PrimeField>Mul<&UnivariatePoly<F>> for &SparsePolynomial<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn mul(self, other: &UnivariatePoly<F>) -> UnivariatePoly<F> {
+        if self.is_zero() || other.is_zero() {
+            return UnivariatePoly::zero();
+        }
+
+        let mut coefficients = vec![F::zero(); self.degree() + other.coefficients.len()];
+        for (exponent, sparse_coefficient) in &self.terms {
+            for (i, dense_coefficient) in other.coefficients.iter().enumerate() {
+                coefficients[exponent + i] += *sparse_coefficient * dense_coefficient;
+            }
+        }
+
+        UnivariatePoly::new(coefficients)
+    }
+}
+
+impl <F: PrimeField>Mul<&SparsePolynomial<F>> for &UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn mul(self, other: &SparsePolynomial<F>) -> UnivariatePoly<F> {
+        other * self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    fn sparse(terms: &[(usize, i64)]) -> SparsePolynomial<Fr> {
+        SparsePolynomial::new(terms.iter().map(|(e, c)| (*e, Fr::from(*c))).collect())
+    }
+
+    #[test]
+    fn test_new_normalizes_terms() {
+        let polynomial = sparse(&[(5, 2), (0, -1), (5, 3), (2, 0)]);
+
+        assert_eq!(polynomial.terms, vec![(0, -Fr::from(1)), (5, Fr::from(5))]);
+        assert_eq!(polynomial.degree(), 5);
+        assert!(sparse(&[(3, 1), (3, -1)]).is_zero());
+    }
+
+    #[test]
+    fn test_evaluate_matches_dense() {
+        // x^1024 - 1
+        let polynomial = sparse(&[(1024, 1), (0, -1)]);
+        let dense = polynomial.to_dense();
+
+        assert_eq!(dense.coefficients.len(), 1025);
+        assert_eq!(polynomial.evaluate(Fr::from(3)), dense.evaluate(Fr::from(3)));
+        assert_eq!(polynomial.evaluate(Fr::from(1)), Fr::from(0));
+        assert_eq!(SparsePolynomial::from_dense(&dense), polynomial);
+    }
+
+    #[test]
+    fn test_sparse_arithmetic() {
+        let a = sparse(&[(10, 1), (0, 2)]);
+        let b = sparse(&[(3, 4), (0, -2)]);
+
+        assert_eq!(&a + &b, sparse(&[(10, 1), (3, 4)]));
+        assert_eq!((&a - &b).to_dense(), &a.to_dense() - &b.to_dense());
+        assert_eq!((&a * &b).to_dense(), &a.to_dense() * &b.to_dense());
+    }
+
+    #[test]
+    fn test_mixed_arithmetic() {
+        let s = sparse(&[(6, 5), (1, 3)]);
+        let d = UnivariatePoly::new(vec![Fr::from(1), Fr::from(2), Fr::from(3)]);
+
+        assert_eq!(&s + &d, &s.to_dense() + &d);
+        assert_eq!(&d + &s, &s.to_dense() + &d);
+        assert_eq!(&d - &s, &d - &s.to_dense());
+        assert_eq!(&s * &d, &s.to_dense() * &d);
+        assert_eq!(&d * &s, &s.to_dense() * &d);
+    }
+}
@@ -0,0 +1,596 @@
+use ark_ff::PrimeField;
+use ark_std::rand::RngCore;
+use std::ops::{Add, Mul, Sub};
+use crate::domain::EvaluationDomain;
+use crate::multiplication::{schoolbook, KARATSUBA_THRESHOLD, TOOM3_THRESHOLD};
+
+// Below this number of coefficients multiplying without a domain is faster than paying for three NTTs,
+// so multiplication only switches to the NTT above it
+const NTT_MULTIPLICATION_THRESHOLD: usize = 64;
+
+// A univariate polynomial over a prime field stored in coefficient form
+// coefficients[i] is the coefficient of x^i, so 2x + 5 is stored as [5, 2]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnivariatePoly<F: PrimeField> {
+    pub coefficients: Vec<F>,
+}
+
+impl <F: PrimeField>UnivariatePoly<F> {
+    // Trailing zero coefficients are removed so that two equal polynomials always have the same representation
+    pub fn new(coefficients: Vec<F>) -> Self {
+        let mut polynomial = Self { coefficients };
+        polynomial.trim();
+        polynomial
+    }
+
+    // degree + 1 uniform coefficients drawn from rng, so a seeded rng gives the same polynomial every run.
+    // The leading coefficient is zero with probability 1/p, in which case the degree is lower
+    pub fn random<R: RngCore>(degree: usize, rng: &mut R) -> Self {
+        Self::new((0..=degree).map(|_| F::rand(rng)).collect())
+    }
+
+    pub fn zero() -> Self {
+        Self { coefficients: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    // The zero polynomial is given degree 0, the same as a constant
+    pub fn degree(&self) -> usize {
+        if self.coefficients.is_empty() {
+            0
+        } else {
+            self.coefficients.len() - 1
+        }
+    }
+
+    // Evaluates the polynomial at x using Horner's method:
+    // a0 + x(a1 + x(a2 + ... x(an)))
+    pub fn evaluate(&self, x: F) -> F {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(F::zero(), |accumulator, coefficient| accumulator * x + coefficient)
+    }
+
+    // Evaluates the polynomial at many points with one shared Horner loop:
+    // the coefficients are walked once from the highest to the lowest and every point's
+    // accumulator is updated in the same pass, instead of re-reading the coefficients per point
+    pub fn batch_evaluate(&self, points: &[F]) -> Vec<F> {
+        let mut accumulators = vec![F::zero(); points.len()];
+
+        for coefficient in self.coefficients.iter().rev() {
+            for (accumulator, point) in accumulators.iter_mut().zip(points.iter()) {
+                *accumulator = *accumulator * point + coefficient;
+            }
+        }
+
+        accumulators
+    }
+
+    pub fn scalar_mul(&self, scalar: F) -> Self {
+        let scaled_coefficients: Vec<F> = self.coefficients
+            .iter()
+            .map(|coefficient| *coefficient * scalar)
+            .collect();
+
+        UnivariatePoly::new(scaled_coefficients)
+    }
+
+    // O(n^2) multiplication, every coefficient of self is multiplied with every coefficient of other
+    pub fn schoolbook_mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+
+        UnivariatePoly::new(schoolbook(&self.coefficients, &other.coefficients))
+    }
+
+    // O(n log n) multiplication: evaluate both polynomials over a domain big enough to hold the product,
+    // multiply the evaluations point-wise, then interpolate back with the inverse NTT.
+    // Returns None if the field does not have a large enough power-of-two subgroup
+    pub fn ntt_mul(&self, other: &Self) -> Option<Self> {
+        if self.is_zero() || other.is_zero() {
+            return Some(Self::zero());
+        }
+
+        let product_size = self.coefficients.len() + other.coefficients.len() - 1;
+        let domain = EvaluationDomain::<F>::new(product_size)?;
+
+        let left_evaluations = domain.fft(&self.coefficients);
+        let right_evaluations = domain.fft(&other.coefficients);
+
+        let product_evaluations: Vec<F> = left_evaluations
+            .iter()
+            .zip(right_evaluations.iter())
+            .map(|(left, right)| *left * right)
+            .collect();
+
+        Some(UnivariatePoly::new(domain.ifft(&product_evaluations)))
+    }
+
+    // Builds the monic polynomial (x - r0)(x - r1)...(x - rk) that is zero exactly at the given roots
+    pub fn from_roots(roots: &[F]) -> Self {
+        roots.iter().fold(UnivariatePoly::new(vec![F::one()]), |product, root| {
+            product.schoolbook_mul(&UnivariatePoly::new(vec![-*root, F::one()]))
+        })
+    }
+
+    // Formal derivative: the derivative of a_i * x^i is i * a_i * x^(i-1)
+    pub fn derivative(&self) -> Self {
+        let coefficients: Vec<F> = self.coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, coefficient)| F::from(i as u64) * coefficient)
+            .collect();
+
+        UnivariatePoly::new(coefficients)
+    }
+
+    // Composition self(other(x)), computed with Horner's method where x is replaced by the polynomial other:
+    // a0 + other(a1 + other(a2 + ... other(an)))
+    pub fn compose(&self, other: &Self) -> Self {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Self::zero(), |accumulator, coefficient| {
+                &(&accumulator * other) + &UnivariatePoly::new(vec![*coefficient])
+            })
+    }
+
+    // p(c * x): the i-th coefficient is scaled by c^i, e.g. to move a polynomial onto a coset c * H
+    pub fn scale_arg(&self, c: F) -> Self {
+        let mut power = F::one();
+        let coefficients: Vec<F> = self.coefficients
+            .iter()
+            .map(|coefficient| {
+                let scaled = power * coefficient;
+                power *= c;
+                scaled
+            })
+            .collect();
+
+        UnivariatePoly::new(coefficients)
+    }
+
+    // p(x + a), the Taylor shift: repeated synthetic division by (x - (-a)) peels off the coefficients of p in powers
+    // of (x + a) one at a time, in O(n^2) additions and multiplications and without any binomial coefficients.
+    // shift_arg(-a) undoes it
+    pub fn shift_arg(&self, a: F) -> Self {
+        let mut coefficients = self.coefficients.clone();
+        let n = coefficients.len();
+
+        for i in 0..n {
+            for j in (i..n.saturating_sub(1)).rev() {
+                let carried = a * coefficients[j + 1];
+                coefficients[j] += carried;
+            }
+        }
+
+        UnivariatePoly::new(coefficients)
+    }
+
+    // Long division: returns (quotient, remainder) with self = quotient * divisor + remainder
+    // and deg(remainder) < deg(divisor). Returns None when dividing by the zero polynomial
+    pub fn div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        let leading_inverse = divisor.coefficients.last()?.inverse()?;
+
+        if self.coefficients.len() < divisor.coefficients.len() {
+            return Some((Self::zero(), self.clone()));
+        }
+
+        let divisor_len = divisor.coefficients.len();
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![F::zero(); remainder.len() - divisor_len + 1];
+
+        // cancel the leading term of the remainder, from the highest degree down
+        for i in (0..quotient.len()).rev() {
+            let factor = remainder[i + divisor_len - 1] * leading_inverse;
+            quotient[i] = factor;
+            for (j, coefficient) in divisor.coefficients.iter().enumerate() {
+                remainder[i + j] -= factor * coefficient;
+            }
+        }
+
+        remainder.truncate(divisor_len - 1);
+        Some((UnivariatePoly::new(quotient), UnivariatePoly::new(remainder)))
+    }
+
+    // Synthetic division by (X - z): returns (quotient, self(z)), the remainder of dividing by a linear factor being the
+    // value at its root. One pass over the coefficients from the top, the same recurrence as Horner's method keeping
+    // the intermediate sums, which are the quotient's coefficients
+    pub fn divide_by_linear(&self, z: F) -> (Self, F) {
+        let mut quotient = vec![F::zero(); self.coefficients.len().saturating_sub(1)];
+        let mut carry = F::zero();
+
+        for (i, coefficient) in self.coefficients.iter().enumerate().rev() {
+            carry = carry * z + coefficient;
+            if i > 0 {
+                quotient[i - 1] = carry;
+            }
+        }
+
+        (UnivariatePoly::new(quotient), carry)
+    }
+
+    // Divides by the leading coefficient so it becomes 1. The zero polynomial stays zero
+    pub fn monic(&self) -> Self {
+        match self.coefficients.last().and_then(|leading| leading.inverse()) {
+            Some(leading_inverse) => self.scalar_mul(leading_inverse),
+            None => Self::zero(),
+        }
+    }
+
+    // Greatest common divisor, made monic so equal inputs always give the same answer. gcd(0, 0) = 0
+    pub fn gcd(&self, other: &Self) -> Self {
+        self.xgcd(other).0
+    }
+
+    // Extended Euclidean algorithm: returns (g, s, t) with s * self + t * other = g = gcd(self, other).
+    // Euclid's remainders r_{i+1} = r_{i-1} - q_i * r_i are carried along as combinations s_i * self + t_i * other,
+    // and the last nonzero one is the gcd
+    pub fn xgcd(&self, other: &Self) -> (Self, Self, Self) {
+        let one = UnivariatePoly::new(vec![F::one()]);
+        let (mut r0, mut s0, mut t0) = (self.clone(), one.clone(), Self::zero());
+        let (mut r1, mut s1, mut t1) = (other.clone(), Self::zero(), one);
+
+        while !r1.is_zero() {
+            let (quotient, remainder) = r0.div_rem(&r1).expect("the divisor is nonzero");
+            let s2 = &s0 - &(&quotient * &s1);
+            let t2 = &t0 - &(&quotient * &t1);
+            (r0, s0, t0) = (r1, s1, t1);
+            (r1, s1, t1) = (remainder, s2, t2);
+        }
+
+        match r0.coefficients.last().and_then(|leading| leading.inverse()) {
+            Some(leading_inverse) => (r0.scalar_mul(leading_inverse), s0.scalar_mul(leading_inverse), t0.scalar_mul(leading_inverse)),
+            None => (Self::zero(), Self::zero(), Self::zero()),
+        }
+    }
+
+    // Lagrange interpolation through the points (xs[i], ys[i]):
+    // p(x) = sum of ys[i] * L_i(x) where L_i(x) = product over j != i of (x - xs[j]) / (xs[i] - xs[j])
+    pub fn interpolate(xs: &[F], ys: &[F]) -> Self {
+        assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+
+        let mut result = Self::zero();
+
+        for i in 0..xs.len() {
+            let mut basis = UnivariatePoly::new(vec![F::one()]);
+            let mut denominator = F::one();
+
+            for j in 0..xs.len() {
+                if i == j {
+                    continue;
+                }
+                // multiply the basis by (x - xs[j])
+                basis = basis.schoolbook_mul(&UnivariatePoly::new(vec![-xs[j], F::one()]));
+                denominator *= xs[i] - xs[j];
+            }
+
+            let denominator_inverse = denominator.inverse().expect("interpolation points must be distinct");
+            result = &result + &basis.scalar_mul(ys[i] * denominator_inverse);
+        }
+
+        result
+    }
+
+    // Interpolates the polynomial whose evaluations over the domain elements are given, using the inverse NTT
+    pub fn interpolate_over_domain(domain: &EvaluationDomain<F>, evaluations: &[F]) -> Self {
+        UnivariatePoly::new(domain.ifft(evaluations))
+    }
+
+    // The evaluations at every element of the domain, in O(n log n) with the domain's cached twiddle factors. A
+    // polynomial with more coefficients than the domain has elements is first reduced modulo the vanishing polynomial
+    // x^n - offset^n, which takes the same values on the domain: x^(qn + i) becomes offset^(qn) x^i
+    pub fn evaluate_over_domain(&self, domain: &EvaluationDomain<F>) -> Vec<F> {
+        if self.coefficients.len() <= domain.size {
+            return domain.fft(&self.coefficients);
+        }
+
+        let wrap = domain.offset.pow([domain.size as u64]);
+        let mut reduced = vec![F::zero(); domain.size];
+        let mut factor = F::one();
+        for chunk in self.coefficients.chunks(domain.size) {
+            for (target, coefficient) in reduced.iter_mut().zip(chunk) {
+                *target += factor * coefficient;
+            }
+            factor *= wrap;
+        }
+        domain.fft(&reduced)
+    }
+
+    fn trim(&mut self) {
+        while let Some(true) = self.coefficients.last().map(|coefficient| coefficient.is_zero()) {
+            self.coefficients.pop();
+        }
+    }
+}
+
+impl <F: PrimeField>Add for &UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn add(self, other: Self) -> UnivariatePoly<F> {
+        let (longer, shorter) = if self.coefficients.len() >= other.coefficients.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut result = longer.coefficients.clone();
+        for (i, coefficient) in shorter.coefficients.iter().enumerate() {
+            result[i] += coefficient;
+        }
+
+        UnivariatePoly::new(result)
+    }
+}
+
+impl <F: PrimeField>Sub for &UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn sub(self, other: Self) -> UnivariatePoly<F> {
+        self + &other.scalar_mul(-F::one())
+    }
+}
+
+// Multiplication picks the algorithm by the size of the shorter operand: schoolbook for small ones, Karatsuba
+// above KARATSUBA_THRESHOLD, NTT for large ones when the field supports a big enough evaluation domain, and
+// otherwise Toom-3 above TOOM3_THRESHOLD, see multiplication.rs
+impl <F: PrimeField>Mul for &UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn mul(self, other: Self) -> UnivariatePoly<F> {
+        let shorter = self.coefficients.len().min(other.coefficients.len());
+        if shorter < KARATSUBA_THRESHOLD {
+            return self.schoolbook_mul(other);
+        }
+        if shorter >= NTT_MULTIPLICATION_THRESHOLD {
+            if let Some(product) = self.ntt_mul(other) {
+                return product;
+            }
+        }
+
+        if shorter >= TOOM3_THRESHOLD {
+            self.toom3_mul(other)
+        } else {
+            self.karatsuba_mul(other)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrary::distinct_field_elements;
+    use ark_bn254::Fr;
+    use proptest::prelude::*;
+
+    fn poly(coefficients: &[u64]) -> UnivariatePoly<Fr> {
+        UnivariatePoly::new(coefficients.iter().map(|c| Fr::from(*c)).collect())
+    }
+
+    #[test]
+    fn test_random_is_reproducible_from_a_seed() {
+        use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+        let a = UnivariatePoly::<Fr>::random(5, &mut StdRng::seed_from_u64(7));
+        let b = UnivariatePoly::<Fr>::random(5, &mut StdRng::seed_from_u64(7));
+        let c = UnivariatePoly::<Fr>::random(5, &mut StdRng::seed_from_u64(8));
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.degree(), 5);
+    }
+
+    #[test]
+    fn test_evaluate_and_degree() {
+        // 4x^3 + 8x^2 + 2
+        let polynomial = poly(&[2, 0, 8, 4]);
+
+        assert_eq!(polynomial.degree(), 3);
+        assert_eq!(polynomial.evaluate(Fr::from(2)), Fr::from(66));
+        assert_eq!(poly(&[5, 0, 0]).degree(), 0);
+    }
+
+    #[test]
+    fn test_batch_evaluate() {
+        let polynomial = poly(&[2, 0, 8, 4]);
+        let points: Vec<Fr> = (0..10u64).map(Fr::from).collect();
+        let expected: Vec<Fr> = points.iter().map(|x| polynomial.evaluate(*x)).collect();
+
+        assert_eq!(polynomial.batch_evaluate(&points), expected);
+        assert_eq!(UnivariatePoly::zero().batch_evaluate(&points), vec![Fr::from(0); 10]);
+    }
+
+    #[test]
+    fn test_add_sub_mul() {
+        let a = poly(&[5, 2]); // 2x + 5
+        let b = poly(&[1, 0, 3]); // 3x^2 + 1
+
+        assert_eq!(&a + &b, poly(&[6, 2, 3]));
+        assert_eq!(&(&a + &b) - &b, a);
+        // (2x + 5)(3x^2 + 1) = 6x^3 + 15x^2 + 2x + 5
+        assert_eq!(&a * &b, poly(&[5, 2, 15, 6]));
+    }
+
+    #[test]
+    fn test_ntt_mul_matches_schoolbook() {
+        let a: UnivariatePoly<Fr> = UnivariatePoly::new((0..100u64).map(|i| Fr::from(i * i + 1)).collect());
+        let b: UnivariatePoly<Fr> = UnivariatePoly::new((0..77u64).map(|i| Fr::from(3 * i + 7)).collect());
+
+        assert_eq!(a.ntt_mul(&b).unwrap(), a.schoolbook_mul(&b));
+        assert_eq!(&a * &b, a.schoolbook_mul(&b));
+    }
+
+    #[test]
+    fn test_from_roots() {
+        let roots = vec![Fr::from(1), Fr::from(2), Fr::from(3)];
+        let polynomial = UnivariatePoly::from_roots(&roots);
+
+        // (x - 1)(x - 2)(x - 3) = x^3 - 6x^2 + 11x - 6
+        assert_eq!(polynomial, UnivariatePoly::new(vec![-Fr::from(6), Fr::from(11), -Fr::from(6), Fr::from(1)]));
+        for root in roots {
+            assert_eq!(polynomial.evaluate(root), Fr::from(0));
+        }
+        assert_eq!(UnivariatePoly::<Fr>::from_roots(&[]), poly(&[1]));
+    }
+
+    #[test]
+    fn test_derivative() {
+        // d/dx (4x^3 + 8x^2 + 2) = 12x^2 + 16x
+        assert_eq!(poly(&[2, 0, 8, 4]).derivative(), poly(&[0, 16, 12]));
+        assert_eq!(poly(&[7]).derivative(), UnivariatePoly::zero());
+    }
+
+    #[test]
+    fn test_compose() {
+        let p = poly(&[1, 0, 1]); // x^2 + 1
+        let q = poly(&[3, 2]); // 2x + 3
+
+        // (2x + 3)^2 + 1 = 4x^2 + 12x + 10
+        assert_eq!(p.compose(&q), poly(&[10, 12, 4]));
+        assert_eq!(p.compose(&q).evaluate(Fr::from(5)), p.evaluate(q.evaluate(Fr::from(5))));
+    }
+
+    #[test]
+    fn test_scale_and_shift_arg() {
+        // x^2 + 2x + 3
+        let p = poly(&[3, 2, 1]);
+
+        // (5x)^2 + 2(5x) + 3 and (x + 2)^2 + 2(x + 2) + 3 = x^2 + 6x + 11
+        assert_eq!(p.scale_arg(Fr::from(5)), poly(&[3, 10, 25]));
+        assert_eq!(p.shift_arg(Fr::from(2)), poly(&[11, 6, 1]));
+        assert_eq!(p.shift_arg(Fr::from(2)).shift_arg(-Fr::from(2)), p);
+        assert_eq!(p.scale_arg(Fr::from(0)), poly(&[3]));
+        assert!(UnivariatePoly::<Fr>::zero().shift_arg(Fr::from(4)).is_zero());
+    }
+
+    #[test]
+    fn test_div_rem() {
+        // x^3 + 2x^2 + 3x + 4 = (x^2 + 1)(x + 2) + (2x + 2)
+        let (quotient, remainder) = poly(&[4, 3, 2, 1]).div_rem(&poly(&[1, 0, 1])).unwrap();
+        assert_eq!(quotient, poly(&[2, 1]));
+        assert_eq!(remainder, poly(&[2, 2]));
+
+        // (x - 3) divides any polynomial minus its value at 3
+        let polynomial = poly(&[5, 0, 7, 1]);
+        let numerator = &polynomial - &UnivariatePoly::new(vec![polynomial.evaluate(Fr::from(3))]);
+        let (_, remainder) = numerator.div_rem(&UnivariatePoly::new(vec![-Fr::from(3), Fr::from(1)])).unwrap();
+        assert!(remainder.is_zero());
+
+        assert_eq!(poly(&[1, 2]).div_rem(&poly(&[0, 0, 1])), Some((UnivariatePoly::zero(), poly(&[1, 2]))));
+        assert_eq!(poly(&[1, 2]).div_rem(&UnivariatePoly::zero()), None);
+    }
+
+    #[test]
+    fn test_divide_by_linear_matches_div_rem() {
+        let polynomial = poly(&[5, 0, 7, 1]);
+        for z in [0u64, 3, 11] {
+            let z = Fr::from(z);
+            let (quotient, value) = polynomial.divide_by_linear(z);
+            let (expected, remainder) = polynomial.div_rem(&UnivariatePoly::new(vec![-z, Fr::from(1)])).unwrap();
+
+            assert_eq!(quotient, expected);
+            assert_eq!(value, polynomial.evaluate(z));
+            assert_eq!(UnivariatePoly::new(vec![value]), remainder);
+        }
+
+        assert_eq!(UnivariatePoly::<Fr>::zero().divide_by_linear(Fr::from(2)), (UnivariatePoly::zero(), Fr::from(0)));
+        assert_eq!(poly(&[9]).divide_by_linear(Fr::from(2)), (UnivariatePoly::zero(), Fr::from(9)));
+    }
+
+    #[test]
+    fn test_gcd_and_xgcd() {
+        // (x - 1)(x - 2) and (x - 1)(x - 3) share x - 1
+        let a = UnivariatePoly::from_roots(&[Fr::from(1), Fr::from(2)]);
+        let b = UnivariatePoly::from_roots(&[Fr::from(1), Fr::from(3)]).scalar_mul(Fr::from(5));
+        assert_eq!(a.gcd(&b), UnivariatePoly::from_roots(&[Fr::from(1)]));
+
+        let (g, s, t) = a.xgcd(&b);
+        assert_eq!(&(&s * &a) + &(&t * &b), g);
+
+        // coprime polynomials have gcd 1, so s is the inverse of a modulo b
+        let c = poly(&[1, 0, 1]);
+        let (g, s, _) = a.xgcd(&c);
+        assert_eq!(g, poly(&[1]));
+        assert_eq!((&s * &a).div_rem(&c).unwrap().1, poly(&[1]));
+
+        // the gcd with zero is the other polynomial made monic
+        assert_eq!(b.gcd(&UnivariatePoly::zero()), b.monic());
+        assert_eq!(UnivariatePoly::<Fr>::zero().gcd(&UnivariatePoly::zero()), UnivariatePoly::zero());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let xs = vec![Fr::from(0), Fr::from(1), Fr::from(2)];
+        let ys = vec![Fr::from(5), Fr::from(7), Fr::from(9)];
+
+        assert_eq!(UnivariatePoly::interpolate(&xs, &ys), poly(&[5, 2]));
+    }
+
+    #[test]
+    fn test_evaluate_over_domain() {
+        let subgroup = EvaluationDomain::<Fr>::new(8).unwrap();
+        let coset = subgroup.get_coset(Fr::from(7)).unwrap();
+        // fewer coefficients than the domain, as many, and more, which wrap around the vanishing polynomial
+        for length in [3u64, 8, 21] {
+            let polynomial = UnivariatePoly::new((0..length).map(|i| Fr::from(i * i + 4)).collect());
+            for domain in [&subgroup, &coset] {
+                assert_eq!(polynomial.evaluate_over_domain(domain), polynomial.batch_evaluate(&domain.elements()));
+            }
+        }
+        assert_eq!(UnivariatePoly::zero().evaluate_over_domain(&subgroup), vec![Fr::from(0); 8]);
+    }
+
+    #[test]
+    fn test_interpolate_over_domain() {
+        let polynomial = poly(&[3, 1, 4, 1, 5]);
+        let domain = EvaluationDomain::<Fr>::new(8).unwrap();
+        let evaluations = domain.fft(&polynomial.coefficients);
+
+        assert_eq!(UnivariatePoly::interpolate_over_domain(&domain, &evaluations), polynomial);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_interpolation_recovers_the_polynomial(poly in any::<UnivariatePoly<Fr>>(), xs in distinct_field_elements::<Fr>(8)) {
+            let ys = poly.batch_evaluate(&xs);
+            prop_assert_eq!(UnivariatePoly::interpolate(&xs, &ys), poly);
+        }
+
+        #[test]
+        fn prop_scale_and_shift_arg_match_compose(poly in any::<UnivariatePoly<Fr>>(), c in any::<u64>(), a in any::<u64>()) {
+            let (c, a) = (Fr::from(c), Fr::from(a));
+            prop_assert_eq!(poly.scale_arg(c), poly.compose(&UnivariatePoly::new(vec![Fr::from(0), c])));
+            prop_assert_eq!(poly.shift_arg(a), poly.compose(&UnivariatePoly::new(vec![a, Fr::from(1)])));
+        }
+
+        #[test]
+        fn prop_div_rem_is_euclidean_division(a in any::<UnivariatePoly<Fr>>(), b in any::<UnivariatePoly<Fr>>()) {
+            prop_assume!(!b.is_zero());
+            let (quotient, remainder) = a.div_rem(&b).unwrap();
+
+            prop_assert_eq!(&(&quotient * &b) + &remainder, a);
+            prop_assert!(remainder.is_zero() || remainder.degree() < b.degree());
+        }
+
+        #[test]
+        fn prop_xgcd_is_a_common_divisor(a in any::<UnivariatePoly<Fr>>(), b in any::<UnivariatePoly<Fr>>(), c in any::<UnivariatePoly<Fr>>()) {
+            // multiplying by c makes a shared factor likely instead of almost never
+            let (a, b) = (&a * &c, &b * &c);
+            let (g, s, t) = a.xgcd(&b);
+
+            prop_assert_eq!(&(&s * &a) + &(&t * &b), g.clone());
+            if !g.is_zero() {
+                prop_assert!(a.div_rem(&g).unwrap().1.is_zero() && b.div_rem(&g).unwrap().1.is_zero());
+                // c divides both, so it divides their gcd
+                prop_assert!(g.div_rem(&c).unwrap().1.is_zero());
+            }
+        }
+    }
+}
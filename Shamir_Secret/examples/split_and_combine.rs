@@ -0,0 +1,15 @@
+use ark_bn254::Fq;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use shamir_secret::shamir::{generate_shares_with_rng, reconstruct_secret};
+
+fn main() {
+    // Example usage of generate_shares_with_rng, seeded so every run prints the same shares.
+    let mut rng = StdRng::seed_from_u64(2024);
+    let shares = generate_shares_with_rng::<Fq, _>(500, 25, 4, 10, &mut rng).expect("valid sharing parameters");
+    println!("First share: {:?}", shares[0]);
+
+    // Any 4 of the 10 shares together with the password recover the secret.
+    let secret = reconstruct_secret(&shares[3..7], 25, 4);
+    println!("Reconstructed secret: {:?}", secret);
+}
@@ -0,0 +1,187 @@
+use crate::error::SecretSharingError;
+use crate::shamir::{generate_field_shares, reconstruct_secret, Point};
+use ark_ff::PrimeField;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Monotone access structures built from threshold gates over participants, shared with Shamir at every gate
+// (Benaloh-Leichter). A gate k of m shares its value with a k of m sharing at x = 1..=m, password 0, and the child i
+// receives the share at x = i. Leaves hand their value to the participant, so every participant ends up with one value
+// per leaf it appears in.
+//
+// OR is a 1 of m gate and AND an m of m gate, and weights come from repeating a participant: a participant with weight
+// w appears w times under the threshold gate and holds w shares of it. E.g. 2 admins OR 1 admin + 3 operators is
+//   any_of([threshold(2, admins), all_of([threshold(1, admins), threshold(3, operators)])])
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessStructure {
+    Participant(usize),
+    Threshold(usize, Vec<AccessStructure>),
+}
+
+// The value of one leaf. path holds the child index (from 1) taken at every gate on the way from the root
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct LeafShare<F: PrimeField> {
+    pub participant: usize,
+    pub path: Vec<usize>,
+    pub value: F,
+}
+
+impl AccessStructure {
+    pub fn threshold(threshold: usize, children: Vec<AccessStructure>) -> Self {
+        AccessStructure::Threshold(threshold, children)
+    }
+
+    pub fn any_of(children: Vec<AccessStructure>) -> Self {
+        AccessStructure::Threshold(1, children)
+    }
+
+    pub fn all_of(children: Vec<AccessStructure>) -> Self {
+        AccessStructure::Threshold(children.len(), children)
+    }
+
+    // threshold of the participants, with participant i holding weights[i] shares
+    pub fn weighted(threshold: usize, weights: &[usize]) -> Self {
+        let children = weights
+            .iter()
+            .enumerate()
+            .flat_map(|(participant, weight)| (0..*weight).map(move |_| AccessStructure::Participant(participant)))
+            .collect();
+        AccessStructure::Threshold(threshold, children)
+    }
+
+    pub fn is_authorized(&self, participants: &[usize]) -> bool {
+        match self {
+            AccessStructure::Participant(participant) => participants.contains(participant),
+            AccessStructure::Threshold(threshold, children) => {
+                children.iter().filter(|child| child.is_authorized(participants)).count() >= *threshold
+            }
+        }
+    }
+
+    pub fn share<F: PrimeField>(&self, secret: F) -> Result<Vec<LeafShare<F>>, SecretSharingError> {
+        let mut shares = Vec::new();
+        self.share_at(secret, &mut Vec::new(), &mut shares)?;
+        Ok(shares)
+    }
+
+    // Recombines the gates bottom up from the given leaf shares, fails with UnauthorizedSet when their
+    // participants do not satisfy the structure
+    pub fn reconstruct<F: PrimeField>(&self, shares: &[LeafShare<F>]) -> Result<F, SecretSharingError> {
+        self.reconstruct_at(shares, &mut Vec::new())?.ok_or(SecretSharingError::UnauthorizedSet)
+    }
+
+    fn share_at<F: PrimeField>(
+        &self,
+        value: F,
+        path: &mut Vec<usize>,
+        shares: &mut Vec<LeafShare<F>>,
+    ) -> Result<(), SecretSharingError> {
+        match self {
+            AccessStructure::Participant(participant) => {
+                shares.push(LeafShare { participant: *participant, path: path.clone(), value });
+            }
+            AccessStructure::Threshold(threshold, children) => {
+                let points = generate_field_shares(value, 0, *threshold, children.len())?;
+                for (child, point) in children.iter().zip(points) {
+                    path.push(point.id);
+                    child.share_at(point.y, path, shares)?;
+                    path.pop();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // None when this subtree is not satisfied by the shares
+    fn reconstruct_at<F: PrimeField>(&self, shares: &[LeafShare<F>], path: &mut Vec<usize>) -> Result<Option<F>, SecretSharingError> {
+        match self {
+            AccessStructure::Participant(participant) => Ok(shares
+                .iter()
+                .find(|share| share.participant == *participant && share.path == *path)
+                .map(|share| share.value)),
+            AccessStructure::Threshold(threshold, children) => {
+                let mut points = Vec::new();
+                for (i, child) in children.iter().enumerate() {
+                    path.push(i + 1);
+                    let value = child.reconstruct_at(shares, path)?;
+                    path.pop();
+
+                    if let Some(y) = value {
+                        points.push(Point { id: i + 1, x: F::from((i + 1) as u64), y });
+                    }
+                    if points.len() == *threshold {
+                        return reconstruct_secret(&points, 0, *threshold).map(Some);
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fq;
+
+    fn shares_of(shares: &[LeafShare<Fq>], participants: &[usize]) -> Vec<LeafShare<Fq>> {
+        shares.iter().filter(|share| participants.contains(&share.participant)).cloned().collect()
+    }
+
+    #[test]
+    fn test_weighted_sharing() {
+        // weights 3, 2, 1, 1 and a threshold of 4
+        let structure = AccessStructure::weighted(4, &[3, 2, 1, 1]);
+        let shares = structure.share(Fq::from(8u64)).unwrap();
+
+        assert_eq!(shares.iter().filter(|share| share.participant == 0).count(), 3);
+        assert_eq!(structure.reconstruct(&shares_of(&shares, &[0, 3])), Ok(Fq::from(8u64)));
+        assert_eq!(structure.reconstruct(&shares_of(&shares, &[1, 2, 3])), Ok(Fq::from(8u64)));
+        assert_eq!(structure.reconstruct(&shares_of(&shares, &[1, 2])), Err(SecretSharingError::UnauthorizedSet));
+        assert!(!structure.is_authorized(&[0]));
+    }
+
+    #[test]
+    fn test_admins_or_admin_and_operators() {
+        // admins are 0, 1 and 2, operators 3 to 6
+        let admins = || (0..3).map(AccessStructure::Participant).collect::<Vec<_>>();
+        let operators = (3..7).map(AccessStructure::Participant).collect();
+        let structure = AccessStructure::any_of(vec![
+            AccessStructure::threshold(2, admins()),
+            AccessStructure::all_of(vec![AccessStructure::any_of(admins()), AccessStructure::threshold(3, operators)]),
+        ]);
+        let shares = structure.share(Fq::from(2024u64)).unwrap();
+
+        let authorized: [&[usize]; 3] = [&[0, 2], &[1, 3, 4, 6], &[0, 1, 2, 3, 4, 5, 6]];
+        for participants in authorized {
+            assert!(structure.is_authorized(participants));
+            assert_eq!(structure.reconstruct(&shares_of(&shares, participants)), Ok(Fq::from(2024u64)));
+        }
+
+        let unauthorized: [&[usize]; 3] = [&[0], &[3, 4, 5, 6], &[2, 3, 4]];
+        for participants in unauthorized {
+            assert!(!structure.is_authorized(participants));
+            assert_eq!(structure.reconstruct(&shares_of(&shares, participants)), Err(SecretSharingError::UnauthorizedSet));
+        }
+    }
+
+    #[test]
+    fn test_nested_and_of_thresholds() {
+        // (2 of {0, 1, 2}) AND (participant 3) AND (1 of {4, 5})
+        let structure = AccessStructure::all_of(vec![
+            AccessStructure::threshold(2, (0..3).map(AccessStructure::Participant).collect()),
+            AccessStructure::Participant(3),
+            AccessStructure::any_of(vec![AccessStructure::Participant(4), AccessStructure::Participant(5)]),
+        ]);
+        let shares = structure.share(Fq::from(31u64)).unwrap();
+
+        assert_eq!(structure.reconstruct(&shares_of(&shares, &[1, 2, 3, 5])), Ok(Fq::from(31u64)));
+        assert_eq!(structure.reconstruct(&shares_of(&shares, &[0, 1, 2, 4, 5])), Err(SecretSharingError::UnauthorizedSet));
+
+        let invalid = AccessStructure::threshold(3, vec![AccessStructure::Participant(0)]);
+        assert_eq!(
+            invalid.share(Fq::from(1u64)),
+            Err(SecretSharingError::ThresholdAboveTotal { threshold: 3, total_shares: 1 })
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use crate::shamir::{generate_field_shares_with_rng, Point};
+use ark_ff::PrimeField;
+use polynomial::arbitrary::field_element;
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// proptest generators, built for the tests of this crate and for other crates with the proptest feature.
+// A share set is a secret split with its password and threshold. The randomness of the sharing polynomial comes
+// from a generated seed, so a failing case shrinks and replays like any other value
+#[derive(Debug, Clone)]
+pub struct ShareSet<F: PrimeField> {
+    pub secret: F,
+    pub password: i32,
+    pub threshold: usize,
+    pub shares: Vec<Point<F>>,
+}
+
+// A password never equal to the id of one of up to 99 shares, as split requires
+pub fn password() -> impl Strategy<Value = i32> {
+    prop_oneof![i32::MIN..=0, 100..=i32::MAX]
+}
+
+// Share sets of at most max_shares shares with any threshold from 1 up to the number of shares
+pub fn share_set<F: PrimeField>(max_shares: usize) -> impl Strategy<Value = ShareSet<F>> {
+    (1..=max_shares)
+        .prop_flat_map(|total_shares| (1..=total_shares, Just(total_shares)))
+        .prop_flat_map(|(threshold, total_shares)| {
+            (field_element::<F>(), password(), any::<u64>()).prop_map(move |(secret, password, seed)| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let shares = generate_field_shares_with_rng(secret, password, threshold, total_shares, &mut rng)
+                    .expect("threshold and password are in range");
+                ShareSet { secret, password, threshold, shares }
+            })
+        })
+}
+
+// A share set together with a random selection of exactly threshold of its shares
+pub fn share_set_and_quorum<F: PrimeField>(max_shares: usize) -> impl Strategy<Value = (ShareSet<F>, Vec<Point<F>>)> {
+    share_set::<F>(max_shares).prop_flat_map(|set| {
+        let quorum = prop::sample::subsequence(set.shares.clone(), set.threshold);
+        (Just(set), quorum)
+    })
+}
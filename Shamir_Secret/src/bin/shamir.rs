@@ -0,0 +1,131 @@
+use ark_bn254::Fq;
+use clap::{Parser, Subcommand};
+use shamir_secret::error::SecretSharingError;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use shamir_secret::shamir::{generate_byte_shares, generate_byte_shares_with_rng, reconstruct_bytes, ByteShare};
+use shamir_secret::share::{decode_hex, encode_hex, SecretBytesShare};
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use zeroize::Zeroize;
+
+// Splits a file into share files and combines share files back into the secret.
+// Every share file holds one share in hex, and carries the threshold so combine needs nothing else
+#[derive(Parser)]
+#[command(name = "shamir", about = "Shamir secret sharing of files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Split a secret file into share files share-<id>.txt
+    Split {
+        #[arg(long)]
+        secret_file: PathBuf,
+        /// Number of shares needed to recover the secret
+        #[arg(long)]
+        threshold: usize,
+        /// Number of shares to write
+        #[arg(long)]
+        shares: usize,
+        #[arg(long, default_value = ".")]
+        out_dir: PathBuf,
+        /// The x coordinate of the secret, it must not be one of 1..=shares
+        #[arg(long, default_value_t = 0)]
+        password: i32,
+        /// Draw the randomness from a generator seeded with this value, for reproducible runs only:
+        /// anyone who knows the seed can rebuild every share
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Bind the shares to this context, e.g. an application id and key epoch, which combine then requires
+        #[arg(long)]
+        context: Option<String>,
+    },
+    /// Combine share files, writing the secret to --output or to stdout
+    Combine {
+        #[arg(long, num_args = 1.., required = true)]
+        share_files: Vec<PathBuf>,
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// The password used to split the secret
+        #[arg(long, default_value_t = 0)]
+        password: i32,
+        /// The context the shares were split under
+        #[arg(long)]
+        context: Option<String>,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Split { secret_file, threshold, shares, out_dir, password, seed, context } => {
+            let mut secret = fs::read(&secret_file).map_err(|e| format!("reading {}: {}", secret_file.display(), e))?;
+            let byte_shares = match seed {
+                Some(seed) => generate_byte_shares_with_rng::<Fq, _>(&secret, password, threshold, shares, &mut StdRng::seed_from_u64(seed)),
+                None => generate_byte_shares::<Fq>(&secret, password, threshold, shares),
+            };
+            secret.zeroize();
+            let byte_shares = byte_shares.map_err(|e| e.to_string())?;
+
+            fs::create_dir_all(&out_dir).map_err(|e| format!("creating {}: {}", out_dir.display(), e))?;
+            for share in byte_shares {
+                let path = out_dir.join(format!("share-{}.txt", share.id));
+                let mut share = SecretBytesShare::new(share, threshold);
+                if let Some(context) = &context {
+                    share = share.with_context(context.as_bytes());
+                }
+                let hex = encode_hex(&share.to_bytes().map_err(|e| e.to_string())?);
+                fs::write(&path, hex + "\n").map_err(|e| format!("writing {}: {}", path.display(), e))?;
+                println!("{}", path.display());
+            }
+            Ok(())
+        }
+        Command::Combine { share_files, output, password, context } => {
+            let shares = share_files
+                .iter()
+                .map(|path| {
+                    let text = fs::read_to_string(path).map_err(|e| format!("reading {}: {}", path.display(), e))?;
+                    read_share(&text).map_err(|e| format!("{}: {}", path.display(), e))
+                })
+                .collect::<Result<Vec<SecretBytesShare<Fq>>, String>>()?;
+
+            let threshold = shares[0].threshold;
+            if shares.iter().any(|share| share.threshold != threshold) {
+                return Err(SecretSharingError::MismatchedShares.to_string());
+            }
+            if !shares.iter().all(|share| share.matches_context(context.as_deref().map(str::as_bytes))) {
+                return Err(SecretSharingError::ContextMismatch.to_string());
+            }
+
+            let byte_shares: Vec<ByteShare<Fq>> = shares.iter().map(|share| share.share.clone()).collect();
+            let mut secret = reconstruct_bytes(&byte_shares, password, threshold).map_err(|e| e.to_string())?;
+
+            let written = match output {
+                Some(path) => fs::write(&path, &secret).map_err(|e| format!("writing {}: {}", path.display(), e)),
+                None => {
+                    use std::io::Write;
+                    std::io::stdout().write_all(&secret).map_err(|e| e.to_string())
+                }
+            };
+            secret.zeroize();
+            written
+        }
+    }
+}
+
+fn read_share(text: &str) -> Result<SecretBytesShare<Fq>, SecretSharingError> {
+    SecretBytesShare::from_bytes(&decode_hex(text)?)
+}
@@ -0,0 +1,389 @@
+use crate::error::SecretSharingError;
+use crate::shamir::{check_parameters, share_x, Point};
+use crate::vss::{evaluate_in_exponent, feldman_deal, verify_share, FeldmanDealing};
+use ark_ec::{CurveGroup, PrimeGroup};
+use ark_ff::{PrimeField, UniformRand, Zero};
+use rand::RngCore;
+use std::collections::{BTreeMap, BTreeSet};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Distributed key generation (joint Feldman): every one of the n participants deals a random secret with Feldman VSS,
+// so nobody ever learns the joint secret key. Participants are numbered 1..=n and participant i holds the shares
+// at x = i, so every dealing lands on the same evaluation points.
+//
+// 1. every participant broadcasts its commitments and sends the share f_j(i) privately to participant i
+// 2. every participant checks the shares it received against the broadcast commitments and complains about
+//    the dealers whose share is wrong or missing
+// 3. the dealers nobody complained about form the qualified set QUAL. The joint public key is the sum of C_0 over QUAL,
+//    and the secret key share of participant i is the sum of f_j(i) over QUAL, a share of the sum of the dealt secrets
+//
+// A complaint disqualifies the dealer right away, there is no round where the dealer can answer it by publishing the
+// share. That keeps the state machine short but lets a malicious participant knock an honest dealer out of QUAL
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitmentBroadcast<G: CurveGroup> {
+    pub dealer: usize,
+    pub commitments: Vec<G>,
+}
+
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct PrivateShare<F: PrimeField> {
+    pub dealer: usize,
+    pub recipient: usize,
+    pub value: F,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Complaint {
+    pub accuser: usize,
+    pub dealer: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DkgPhase {
+    Dealing,
+    SharesReceived,
+    Complaints,
+    Finished,
+}
+
+// What Participant::deal sends: the broadcast of its commitments and a private share for every other participant
+pub type DealtMessages<G> = (CommitmentBroadcast<G>, Vec<PrivateShare<<G as PrimeGroup>::ScalarField>>);
+
+#[derive(Debug, Clone)]
+pub struct Participant<G: CurveGroup> {
+    pub index: usize,
+    pub threshold: usize,
+    pub total_participants: usize,
+    pub phase: DkgPhase,
+    dealing: Option<FeldmanDealing<G>>,
+    broadcasts: BTreeMap<usize, Vec<G>>,
+    shares: BTreeMap<usize, G::ScalarField>,
+    complaints: BTreeSet<Complaint>,
+}
+
+// What a participant keeps once the protocol ends. The commitments are the sums of the qualified dealers' commitments,
+// so verify_share checks any participant's secret key share against them, and commitments[0] is the public key
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct DkgOutput<G: CurveGroup> {
+    // only the secret share is wiped when the output is dropped, the rest is public
+    #[zeroize(skip)]
+    pub qualified: Vec<usize>,
+    #[zeroize(skip)]
+    pub public_key: G,
+    #[zeroize(skip)]
+    pub commitments: Vec<G>,
+    pub secret_share: Point<G::ScalarField>,
+}
+
+impl <G: CurveGroup>Participant<G> {
+    // Participants are numbered 1..=total_participants, and every participant deals with the same threshold
+    pub fn new(index: usize, threshold: usize, total_participants: usize) -> Result<Self, SecretSharingError> {
+        if index == 0 || index > total_participants {
+            return Err(SecretSharingError::UnknownParticipant { participant: index });
+        }
+        check_parameters(0, threshold, total_participants)?;
+
+        Ok(Self {
+            index,
+            threshold,
+            total_participants,
+            phase: DkgPhase::Dealing,
+            dealing: None,
+            broadcasts: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            complaints: BTreeSet::new(),
+        })
+    }
+
+    // Samples this participant's contribution to the joint secret and returns the broadcast and one private share
+    // for every other participant. The participant's own share is kept without going through the network
+    pub fn deal<R: RngCore>(&mut self, rng: &mut R) -> Result<DealtMessages<G>, SecretSharingError> {
+        // deal runs once, at the start of the protocol
+        self.check_phase(DkgPhase::Dealing)?;
+
+        let dealing = feldman_deal::<G, R>(G::ScalarField::rand(rng), self.threshold, self.total_participants, rng)?;
+        let broadcast = CommitmentBroadcast { dealer: self.index, commitments: dealing.commitments.clone() };
+        let private_shares = dealing
+            .shares
+            .iter()
+            .enumerate()
+            .map(|(i, share)| PrivateShare { dealer: self.index, recipient: i + 1, value: share.y })
+            .filter(|share| share.recipient != self.index)
+            .collect();
+
+        self.broadcasts.insert(self.index, dealing.commitments.clone());
+        self.shares.insert(self.index, dealing.shares[self.index - 1].y);
+        self.dealing = Some(dealing);
+        self.phase = DkgPhase::SharesReceived;
+
+        Ok((broadcast, private_shares))
+    }
+
+    // The receive_ methods take messages from the other participants, so a message naming a participant that does
+    // not exist, or a share meant for someone else, is an error rather than a panic and leaves the state unchanged.
+    // So is calling any step in the wrong phase
+    pub fn receive_broadcast(&mut self, broadcast: CommitmentBroadcast<G>) -> Result<(), SecretSharingError> {
+        // broadcasts arrive after dealing
+        self.check_phase(DkgPhase::SharesReceived)?;
+        self.check_participant(broadcast.dealer)?;
+        self.broadcasts.entry(broadcast.dealer).or_insert(broadcast.commitments);
+        Ok(())
+    }
+
+    pub fn receive_share(&mut self, share: PrivateShare<G::ScalarField>) -> Result<(), SecretSharingError> {
+        // shares arrive after dealing
+        self.check_phase(DkgPhase::SharesReceived)?;
+        if share.recipient != self.index {
+            return Err(SecretSharingError::MisdirectedShare { recipient: share.recipient });
+        }
+        self.check_participant(share.dealer)?;
+        self.shares.entry(share.dealer).or_insert(share.value);
+        Ok(())
+    }
+
+    // Checks every dealer's share against its commitments. A dealer is accused when its broadcast or share never
+    // arrived, when it committed to a polynomial of the wrong degree, or when the share is off the committed polynomial
+    pub fn complain(&mut self) -> Result<Vec<Complaint>, SecretSharingError> {
+        // complaints follow the dealing round
+        self.check_phase(DkgPhase::SharesReceived)?;
+
+        let complaints: Vec<Complaint> = (1..=self.total_participants)
+            .filter(|dealer| !self.is_valid_dealing(*dealer))
+            .map(|dealer| Complaint { accuser: self.index, dealer })
+            .collect();
+
+        self.complaints.extend(complaints.iter().copied());
+        self.phase = DkgPhase::Complaints;
+
+        Ok(complaints)
+    }
+
+    pub fn receive_complaint(&mut self, complaint: Complaint) -> Result<(), SecretSharingError> {
+        // complaints arrive after this participant checked its shares
+        self.check_phase(DkgPhase::Complaints)?;
+        self.check_participant(complaint.accuser)?;
+        self.check_participant(complaint.dealer)?;
+        self.complaints.insert(complaint);
+        Ok(())
+    }
+
+    // Returns None when fewer than threshold dealers remain qualified. With at most threshold - 1 corrupt participants,
+    // a qualified set of threshold dealers holds at least one honest contribution that keeps the joint secret random
+    pub fn finalize(&mut self) -> Result<Option<DkgOutput<G>>, SecretSharingError> {
+        // finalize follows the complaint round
+        self.check_phase(DkgPhase::Complaints)?;
+        self.phase = DkgPhase::Finished;
+
+        let disqualified: BTreeSet<usize> = self.complaints.iter().map(|complaint| complaint.dealer).collect();
+        let qualified: Vec<usize> = (1..=self.total_participants).filter(|dealer| !disqualified.contains(dealer)).collect();
+
+        if qualified.len() < self.threshold {
+            return Ok(None);
+        }
+
+        let mut commitments = vec![G::zero(); self.threshold];
+        let mut secret_share = G::ScalarField::zero();
+        for dealer in &qualified {
+            for (sum, commitment) in commitments.iter_mut().zip(self.broadcasts[dealer].iter()) {
+                *sum += commitment;
+            }
+            secret_share += self.shares[dealer];
+        }
+
+        Ok(Some(DkgOutput {
+            qualified,
+            public_key: commitments[0],
+            commitments,
+            secret_share: Point { id: self.index, x: share_x(self.index), y: secret_share },
+        }))
+    }
+
+    fn check_phase(&self, expected: DkgPhase) -> Result<(), SecretSharingError> {
+        if self.phase != expected {
+            return Err(SecretSharingError::WrongPhase { expected, actual: self.phase });
+        }
+        Ok(())
+    }
+
+    fn check_participant(&self, participant: usize) -> Result<(), SecretSharingError> {
+        if participant == 0 || participant > self.total_participants {
+            return Err(SecretSharingError::UnknownParticipant { participant });
+        }
+        Ok(())
+    }
+
+    fn is_valid_dealing(&self, dealer: usize) -> bool {
+        match (self.broadcasts.get(&dealer), self.shares.get(&dealer)) {
+            (Some(commitments), Some(value)) => {
+                let share = Point { id: self.index, x: share_x(self.index), y: *value };
+                commitments.len() == self.threshold && verify_share(&share, commitments)
+            }
+            _ => false,
+        }
+    }
+}
+
+// The received shares add up to this participant's secret key share
+impl <G: CurveGroup>Drop for Participant<G> {
+    fn drop(&mut self) {
+        for value in self.shares.values_mut() {
+            value.zeroize();
+        }
+    }
+}
+
+impl <G: CurveGroup>DkgOutput<G> {
+    // The public verification key of participant index, secret_share * G for that participant
+    pub fn verification_key(&self, index: usize) -> G {
+        evaluate_in_exponent(&self.commitments, share_x(index))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shamir::reconstruct_secret;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_ec::PrimeGroup;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Runs every round over an in-memory network. tamper can rewrite the private shares before they are delivered
+    fn simulate(
+        threshold: usize,
+        total_participants: usize,
+        tamper: impl Fn(&mut PrivateShare<Fr>),
+    ) -> (Vec<Participant<G1Projective>>, Vec<Option<DkgOutput<G1Projective>>>) {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let mut participants: Vec<Participant<G1Projective>> =
+            (1..=total_participants).map(|index| Participant::new(index, threshold, total_participants).unwrap()).collect();
+
+        let mut broadcasts = Vec::new();
+        let mut private_shares = Vec::new();
+        for participant in participants.iter_mut() {
+            let (broadcast, shares) = participant.deal(rng).unwrap();
+            broadcasts.push(broadcast);
+            private_shares.extend(shares);
+        }
+
+        for mut share in private_shares {
+            tamper(&mut share);
+            participants[share.recipient - 1].receive_share(share).unwrap();
+        }
+        for broadcast in &broadcasts {
+            for participant in participants.iter_mut().filter(|participant| participant.index != broadcast.dealer) {
+                participant.receive_broadcast(broadcast.clone()).unwrap();
+            }
+        }
+
+        let complaints: Vec<Complaint> = participants.iter_mut().flat_map(|participant| participant.complain().unwrap()).collect();
+        for complaint in complaints {
+            for participant in participants.iter_mut() {
+                participant.receive_complaint(complaint).unwrap();
+            }
+        }
+
+        let outputs = participants.iter_mut().map(|participant| participant.finalize().unwrap()).collect();
+        (participants, outputs)
+    }
+
+    fn dealt_secret(participant: &Participant<G1Projective>) -> Fr {
+        let dealing = participant.dealing.as_ref().unwrap();
+        let shares = &dealing.shares[..participant.threshold];
+        reconstruct_secret(shares, 0, participant.threshold).unwrap()
+    }
+
+    #[test]
+    fn test_honest_dkg() {
+        let (participants, outputs) = simulate(3, 5, |_| {});
+        let outputs: Vec<DkgOutput<G1Projective>> = outputs.into_iter().map(Option::unwrap).collect();
+
+        assert!(outputs.iter().all(|output| output.qualified == vec![1, 2, 3, 4, 5]));
+        assert!(outputs.iter().all(|output| output.public_key == outputs[0].public_key));
+
+        let joint_secret: Fr = participants.iter().map(dealt_secret).sum();
+        assert_eq!(outputs[0].public_key, G1Projective::generator() * joint_secret);
+
+        let shares: Vec<Point<Fr>> = outputs[1..4].iter().map(|output| output.secret_share.clone()).collect();
+        assert_eq!(reconstruct_secret(&shares, 0, 3), Ok(joint_secret));
+
+        for output in &outputs {
+            assert!(verify_share(&output.secret_share, &outputs[0].commitments));
+        }
+    }
+
+    #[test]
+    fn test_dkg_disqualifies_a_misbehaving_dealer() {
+        // dealer 2 sends participant 4 a share off its committed polynomial
+        let (participants, outputs) = simulate(3, 5, |share| {
+            if share.dealer == 2 && share.recipient == 4 {
+                share.value += Fr::from(1);
+            }
+        });
+        let outputs: Vec<DkgOutput<G1Projective>> = outputs.into_iter().map(Option::unwrap).collect();
+
+        assert!(participants.iter().all(|participant| participant.complaints == BTreeSet::from([Complaint { accuser: 4, dealer: 2 }])));
+        assert!(outputs.iter().all(|output| output.qualified == vec![1, 3, 4, 5]));
+        assert!(outputs.iter().all(|output| output.commitments == outputs[0].commitments));
+
+        // dealer 2's contribution is left out of the joint key
+        let joint_secret: Fr = [0, 2, 3, 4].iter().map(|i| dealt_secret(&participants[*i])).sum();
+        assert_eq!(outputs[0].public_key, G1Projective::generator() * joint_secret);
+
+        let shares: Vec<Point<Fr>> = [0, 3, 4].iter().map(|i| outputs[*i].secret_share.clone()).collect();
+        assert_eq!(reconstruct_secret(&shares, 0, 3), Ok(joint_secret));
+        assert_eq!(outputs[2].verification_key(3), G1Projective::generator() * outputs[2].secret_share.y);
+    }
+
+    #[test]
+    fn test_dkg_fails_without_enough_qualified_dealers() {
+        // dealers 1, 2 and 3 cheat, leaving two qualified dealers for a threshold of 3
+        let (_, outputs) = simulate(3, 4, |share| {
+            if share.dealer <= 3 {
+                share.value += Fr::from(1);
+            }
+        });
+
+        assert!(outputs.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_malformed_messages_are_errors() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let mut participant = Participant::<G1Projective>::new(1, 2, 3).unwrap();
+        let (broadcast, shares) = participant.deal(rng).unwrap();
+
+        let misdirected = shares[0].clone();
+        assert_eq!(participant.receive_share(misdirected), Err(SecretSharingError::MisdirectedShare { recipient: 2 }));
+        let unknown = PrivateShare { dealer: 4, recipient: 1, value: Fr::from(1) };
+        assert_eq!(participant.receive_share(unknown), Err(SecretSharingError::UnknownParticipant { participant: 4 }));
+        let unknown = CommitmentBroadcast { dealer: 0, ..broadcast };
+        assert_eq!(participant.receive_broadcast(unknown), Err(SecretSharingError::UnknownParticipant { participant: 0 }));
+
+        // none of them was recorded, so dealers 2 and 3 are still missing
+        assert_eq!(participant.complain().unwrap().len(), 2);
+        let complaint = Complaint { accuser: 7, dealer: 2 };
+        assert_eq!(participant.receive_complaint(complaint), Err(SecretSharingError::UnknownParticipant { participant: 7 }));
+        assert_eq!(participant.complaints.len(), 2);
+    }
+
+    #[test]
+    fn test_bad_parameters_and_out_of_phase_steps_are_errors() {
+        assert_eq!(Participant::<G1Projective>::new(0, 2, 3).unwrap_err(), SecretSharingError::UnknownParticipant { participant: 0 });
+        assert_eq!(Participant::<G1Projective>::new(1, 0, 3).unwrap_err(), SecretSharingError::ZeroThreshold);
+        assert_eq!(
+            Participant::<G1Projective>::new(1, 4, 3).unwrap_err(),
+            SecretSharingError::ThresholdAboveTotal { threshold: 4, total_shares: 3 }
+        );
+
+        let rng = &mut StdRng::seed_from_u64(0);
+        let mut participant = Participant::<G1Projective>::new(1, 2, 3).unwrap();
+        let wrong_phase = SecretSharingError::WrongPhase { expected: DkgPhase::Complaints, actual: DkgPhase::Dealing };
+        assert_eq!(participant.finalize().unwrap_err(), wrong_phase);
+
+        participant.deal(rng).unwrap();
+        let wrong_phase = SecretSharingError::WrongPhase { expected: DkgPhase::Dealing, actual: DkgPhase::SharesReceived };
+        assert_eq!(participant.deal(rng).unwrap_err(), wrong_phase);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::dkg::DkgPhase;
+use std::fmt;
+
+// Everything that can go wrong while sharing or reconstructing a secret
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSharingError {
+    // the threshold is 0, a polynomial needs at least the secret as a coefficient
+    ZeroThreshold,
+    // a packed sharing of an empty list of secrets
+    NoSecrets,
+    ThresholdAboveTotal { threshold: usize, total_shares: usize },
+    // the password is the x coordinate of one of the shares, so that share would be the secret itself
+    PasswordOnShare { password: i32 },
+    InsufficientShares { threshold: usize, provided: usize },
+    // more shares are off the sharing polynomial than robust reconstruction can correct
+    TooManyFaultyShares { correctable: usize },
+    // the holders of the shares do not satisfy the access structure
+    UnauthorizedSet,
+    DuplicateX,
+    // the random points interpolated to a polynomial of lower degree than threshold - 1
+    InterpolationFailed,
+    // the byte shares hold different numbers of elements, they do not come from the same secret
+    MismatchedShares,
+    // the reconstructed elements are not the canonical encoding of a byte string
+    InvalidEncoding,
+    // the bytes are not a share in the format of Share::to_bytes
+    InvalidShareEncoding,
+    // the share does not fit the share format, e.g. an id or threshold of 2^32 or more
+    UnencodableShare,
+    // a signer signed or was expected in a signing session without committing to nonces for it
+    UnknownSigner { signer: usize },
+    // a partial signature does not check out against the signer's verification key
+    InvalidPartialSignature { signer: usize },
+    MissingPartialSignature { signer: usize },
+    // the shares were dealt under a different context than the one they are combined under
+    ContextMismatch,
+    UnsupportedVersion { version: u8 },
+    // a DKG message names a participant outside 1..=total_participants
+    UnknownParticipant { participant: usize },
+    // a DKG share addressed to another participant than the one receiving it
+    MisdirectedShare { recipient: usize },
+    // a DKG participant was asked for a step of another phase than the one it is in
+    WrongPhase { expected: DkgPhase, actual: DkgPhase },
+}
+
+impl fmt::Display for SecretSharingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SecretSharingError::ZeroThreshold => write!(f, "threshold must be greater than 0"),
+            SecretSharingError::NoSecrets => write!(f, "no secrets to share"),
+            SecretSharingError::ThresholdAboveTotal { threshold, total_shares } => {
+                write!(f, "threshold {} greater than total shares {}", threshold, total_shares)
+            }
+            SecretSharingError::PasswordOnShare { password } => {
+                write!(f, "password {} collides with a share x coordinate", password)
+            }
+            SecretSharingError::InsufficientShares { threshold, provided } => {
+                write!(f, "{} shares provided, {} needed", provided, threshold)
+            }
+            SecretSharingError::TooManyFaultyShares { correctable } => {
+                write!(f, "more than {} shares are faulty", correctable)
+            }
+            SecretSharingError::UnauthorizedSet => write!(f, "shares do not satisfy the access structure"),
+            SecretSharingError::DuplicateX => write!(f, "two shares have the same x coordinate"),
+            SecretSharingError::InterpolationFailed => write!(f, "failed to interpolate polynomial"),
+            SecretSharingError::MismatchedShares => write!(f, "shares belong to different secrets"),
+            SecretSharingError::InvalidEncoding => write!(f, "reconstructed elements are not a valid byte encoding"),
+            SecretSharingError::InvalidShareEncoding => write!(f, "bytes do not encode a share"),
+            SecretSharingError::UnencodableShare => write!(f, "share does not fit the share format"),
+            SecretSharingError::UnknownSigner { signer } => write!(f, "signer {} is not part of the signing session", signer),
+            SecretSharingError::InvalidPartialSignature { signer } => write!(f, "invalid partial signature from signer {}", signer),
+            SecretSharingError::MissingPartialSignature { signer } => write!(f, "no partial signature from signer {}", signer),
+            SecretSharingError::ContextMismatch => write!(f, "shares were dealt under a different context"),
+            SecretSharingError::UnsupportedVersion { version } => write!(f, "unsupported share format version {}", version),
+            SecretSharingError::UnknownParticipant { participant } => write!(f, "no participant {} in the protocol", participant),
+            SecretSharingError::MisdirectedShare { recipient } => write!(f, "share addressed to participant {}", recipient),
+            SecretSharingError::WrongPhase { expected, actual } => write!(f, "step of phase {:?} called in phase {:?}", expected, actual),
+        }
+    }
+}
+
+impl std::error::Error for SecretSharingError {}
@@ -0,0 +1,311 @@
+use crate::dkg::DkgOutput;
+use crate::error::SecretSharingError;
+use crate::resharing::lagrange_coefficient;
+use crate::shamir::share_x;
+use ark_ec::CurveGroup;
+use ark_ff::{UniformRand, Zero};
+use rand::RngCore;
+use sigma::schnorr::{point_to_bytes, signature_challenge, SchnorrSignature};
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Threshold Schnorr signatures on the keys of the DKG, a simplified FROST.
+//
+// After the DKG participant i holds a share s_i of the joint secret key s, with verification key Y_i = s_i*G, and
+// Y = s*G is the public key. Any t of them sign a message in two rounds:
+// 1. every signer samples two nonces (d_i, e_i) and publishes D_i = d_i*G and E_i = e_i*G. The commitments of the
+//    signers that take part form the signing package
+// 2. every signer derives the binding factor rho_i = H(i, message, package) of every signer, the group commitment
+//    R = sum D_i + rho_i*E_i and the challenge c = H(Y, R, message), and answers with
+//      z_i = d_i + rho_i*e_i + lambda_i*s_i*c
+//    where lambda_i is the Lagrange coefficient at 0 of signer i over the signers of the package
+// The aggregator checks every z_i against z_i*G == D_i + rho_i*E_i + c*lambda_i*Y_i, so a misbehaving signer is
+// named, and the signature is (R, sum z_i). Since sum lambda_i*s_i = s it satisfies z*G == R + c*Y, an ordinary
+// Schnorr signature: sigma::schnorr::verify_signature checks it against the joint public key.
+//
+// The binding factors tie every signer's nonce to the whole package, which stops the attacks on the one-nonce
+// scheme where a signer picks its commitment after seeing the others'. Left out from FROST: the preprocessing of
+// many nonces ahead of time, and the encoding of everything hashed, which follows sigma rather than the RFC.
+// Nonces are single use, sign takes them by value and they are wiped when dropped
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct SigningNonces<G: CurveGroup> {
+    pub signer: usize,
+    hiding: G::ScalarField,
+    binding: G::ScalarField,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NonceCommitment<G: CurveGroup> {
+    pub signer: usize,
+    pub hiding: G,
+    pub binding: G,
+}
+
+// The message and the nonce commitments of the signers taking part, ordered by signer
+#[derive(Debug, Clone, PartialEq)]
+pub struct SigningPackage<G: CurveGroup> {
+    pub message: Vec<u8>,
+    pub commitments: Vec<NonceCommitment<G>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartialSignature<G: CurveGroup> {
+    pub signer: usize,
+    pub response: G::ScalarField,
+}
+
+// Round 1: fresh nonces for one signing session, and their commitments for the package
+pub fn commit<G: CurveGroup, R: RngCore>(signer: usize, rng: &mut R) -> (SigningNonces<G>, NonceCommitment<G>) {
+    let nonces = SigningNonces { signer, hiding: G::ScalarField::rand(rng), binding: G::ScalarField::rand(rng) };
+    let commitment = NonceCommitment { signer, hiding: G::generator() * nonces.hiding, binding: G::generator() * nonces.binding };
+
+    (nonces, commitment)
+}
+
+impl <G: CurveGroup>SigningPackage<G> {
+    // Fails when a signer committed twice
+    pub fn new(message: &[u8], mut commitments: Vec<NonceCommitment<G>>) -> Result<Self, SecretSharingError> {
+        commitments.sort_by_key(|commitment| commitment.signer);
+        if commitments.windows(2).any(|pair| pair[0].signer == pair[1].signer) {
+            return Err(SecretSharingError::DuplicateX);
+        }
+
+        Ok(Self { message: message.to_vec(), commitments })
+    }
+
+    pub fn signers(&self) -> Vec<usize> {
+        self.commitments.iter().map(|commitment| commitment.signer).collect()
+    }
+
+    fn commitment(&self, signer: usize) -> Result<&NonceCommitment<G>, SecretSharingError> {
+        self.commitments
+            .iter()
+            .find(|commitment| commitment.signer == signer)
+            .ok_or(SecretSharingError::UnknownSigner { signer })
+    }
+
+    // rho_i for every signer in the order of the package, all drawn from one transcript of the message and the package
+    fn binding_factors(&self) -> Vec<G::ScalarField> {
+        let mut transcript = Transcript::new();
+        transcript.append(b"frost binding factors");
+        transcript.append(&(self.message.len() as u64).to_be_bytes());
+        transcript.append(&self.message);
+        for commitment in &self.commitments {
+            transcript.append(&(commitment.signer as u64).to_be_bytes());
+            transcript.append(&point_to_bytes(&commitment.hiding));
+            transcript.append(&point_to_bytes(&commitment.binding));
+        }
+
+        self.commitments.iter().map(|_| transcript.random_challenge_as_field_element()).collect()
+    }
+
+    fn group_commitment(&self, binding_factors: &[G::ScalarField]) -> G {
+        self.commitments
+            .iter()
+            .zip(binding_factors)
+            .fold(G::zero(), |sum, (commitment, rho)| sum + commitment.hiding + commitment.binding * rho)
+    }
+
+    // lambda_i for signer at x = 0 over the signers of the package
+    fn lagrange_coefficient(&self, position: usize) -> Result<G::ScalarField, SecretSharingError> {
+        let xs: Vec<G::ScalarField> = self.commitments.iter().map(|commitment| share_x(commitment.signer)).collect();
+        lagrange_coefficient(&xs, position, G::ScalarField::zero())
+    }
+
+    // The binding factors, R and c every round 2 computation starts from
+    fn session(&self, public_key: &G) -> (Vec<G::ScalarField>, G, G::ScalarField) {
+        let binding_factors = self.binding_factors();
+        let group_commitment = self.group_commitment(&binding_factors);
+        let challenge = signature_challenge(public_key, &group_commitment, &self.message);
+
+        (binding_factors, group_commitment, challenge)
+    }
+}
+
+// Round 2: this participant's share of the signature. Fails when the nonces are not this participant's, when it has
+// no commitment in the package, or when the package has fewer signers than the threshold
+pub fn sign<G: CurveGroup>(
+    key: &DkgOutput<G>,
+    nonces: SigningNonces<G>,
+    package: &SigningPackage<G>,
+) -> Result<PartialSignature<G>, SecretSharingError> {
+    let signer = key.secret_share.id;
+    let threshold = key.commitments.len();
+    if package.commitments.len() < threshold {
+        return Err(SecretSharingError::InsufficientShares { threshold, provided: package.commitments.len() });
+    }
+    let position = package.signers().iter().position(|id| *id == signer).ok_or(SecretSharingError::UnknownSigner { signer })?;
+    // the package must hold this signer's own commitments, or a coordinator could have it answer for other nonces
+    let commitment = &package.commitments[position];
+    if nonces.signer != signer
+        || commitment.hiding != G::generator() * nonces.hiding
+        || commitment.binding != G::generator() * nonces.binding
+    {
+        return Err(SecretSharingError::UnknownSigner { signer: nonces.signer });
+    }
+
+    let (binding_factors, _, challenge) = package.session(&key.public_key);
+    let lambda = package.lagrange_coefficient(position)?;
+    let response = nonces.hiding + binding_factors[position] * nonces.binding + lambda * key.secret_share.y * challenge;
+
+    Ok(PartialSignature { signer, response })
+}
+
+// The aggregator's check of one partial signature against the signer's verification key
+pub fn verify_partial<G: CurveGroup>(key: &DkgOutput<G>, package: &SigningPackage<G>, partial: &PartialSignature<G>) -> bool {
+    let Some(position) = package.signers().iter().position(|id| *id == partial.signer) else {
+        return false;
+    };
+    let (binding_factors, _, challenge) = package.session(&key.public_key);
+    let Ok(lambda) = package.lagrange_coefficient(position) else {
+        return false;
+    };
+    let commitment = &package.commitments[position];
+
+    G::generator() * partial.response
+        == commitment.hiding + commitment.binding * binding_factors[position] + key.verification_key(partial.signer) * (challenge * lambda)
+}
+
+// Checks every partial signature and sums them into a Schnorr signature under the joint public key. key is any
+// participant's DKG output, the aggregator only reads the public parts. Every signer of the package must have
+// answered, the Lagrange coefficients depend on the whole set, so after a dropout the session restarts with a
+// package of the remaining signers
+pub fn aggregate<G: CurveGroup>(
+    key: &DkgOutput<G>,
+    package: &SigningPackage<G>,
+    partials: &[PartialSignature<G>],
+) -> Result<SchnorrSignature<G>, SecretSharingError> {
+    for partial in partials {
+        package.commitment(partial.signer)?;
+        if !verify_partial(key, package, partial) {
+            return Err(SecretSharingError::InvalidPartialSignature { signer: partial.signer });
+        }
+    }
+
+    let mut response = G::ScalarField::zero();
+    for signer in package.signers() {
+        let partial = partials
+            .iter()
+            .find(|partial| partial.signer == signer)
+            .ok_or(SecretSharingError::MissingPartialSignature { signer })?;
+        response += partial.response;
+    }
+
+    let (_, commitment, _) = package.session(&key.public_key);
+    Ok(SchnorrSignature { commitment, response })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::Participant;
+    use ark_bn254::{Fr, G1Projective};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use sigma::schnorr::verify_signature;
+
+    // An honest t of n DKG run over an in-memory network
+    fn dkg(threshold: usize, total_participants: usize, rng: &mut StdRng) -> Vec<DkgOutput<G1Projective>> {
+        let mut participants: Vec<Participant<G1Projective>> =
+            (1..=total_participants).map(|index| Participant::new(index, threshold, total_participants).unwrap()).collect();
+
+        let dealings: Vec<_> = participants.iter_mut().map(|participant| participant.deal(rng).unwrap()).collect();
+        for (broadcast, shares) in dealings {
+            for participant in participants.iter_mut().filter(|participant| participant.index != broadcast.dealer) {
+                participant.receive_broadcast(broadcast.clone()).unwrap();
+            }
+            for share in shares {
+                participants[share.recipient - 1].receive_share(share).unwrap();
+            }
+        }
+        for participant in participants.iter_mut() {
+            assert!(participant.complain().unwrap().is_empty());
+        }
+
+        participants.iter_mut().map(|participant| participant.finalize().unwrap().unwrap()).collect()
+    }
+
+    // Both rounds for the given signers, returning the package and every partial signature
+    fn run_session(
+        keys: &[DkgOutput<G1Projective>],
+        signers: &[usize],
+        message: &[u8],
+        rng: &mut StdRng,
+    ) -> (SigningPackage<G1Projective>, Vec<PartialSignature<G1Projective>>) {
+        let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|signer| commit::<G1Projective, _>(*signer, rng)).unzip();
+        let package = SigningPackage::new(message, commitments).unwrap();
+
+        let partials = nonces
+            .into_iter()
+            .map(|nonces| {
+                let key = &keys[nonces.signer - 1];
+                sign(key, nonces, &package).unwrap()
+            })
+            .collect();
+        (package, partials)
+    }
+
+    #[test]
+    fn test_three_of_five_signing_with_a_dropout() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let keys = dkg(3, 5, rng);
+
+        // signers 1, 2, 4 and 5 commit, then 4 drops out before sending its partial signature
+        let (package, partials) = run_session(&keys, &[1, 2, 4, 5], b"transfer 10", rng);
+        let without_4: Vec<_> = partials.iter().filter(|partial| partial.signer != 4).copied().collect();
+        assert_eq!(aggregate(&keys[0], &package, &without_4), Err(SecretSharingError::MissingPartialSignature { signer: 4 }));
+
+        // the session restarts with the three that are left
+        let (package, partials) = run_session(&keys, &[1, 2, 5], b"transfer 10", rng);
+        let signature = aggregate(&keys[0], &package, &partials).unwrap();
+
+        assert!(verify_signature(&keys[0].public_key, b"transfer 10", &signature));
+        assert!(!verify_signature(&keys[0].public_key, b"transfer 11", &signature));
+        // every participant agrees on the joint key the signature verifies against
+        assert!(keys.iter().all(|key| key.public_key == keys[0].public_key));
+
+        // the signers that stayed could also have signed with 4 in place of 2
+        let (package, partials) = run_session(&keys, &[4, 1, 5], b"transfer 10", rng);
+        assert_eq!(package.signers(), vec![1, 4, 5]);
+        assert!(verify_signature(&keys[0].public_key, b"transfer 10", &aggregate(&keys[3], &package, &partials).unwrap()));
+    }
+
+    #[test]
+    fn test_aggregator_names_a_bad_partial_signature() {
+        let rng = &mut StdRng::seed_from_u64(1);
+        let keys = dkg(2, 3, rng);
+        let (package, mut partials) = run_session(&keys, &[1, 3], b"message", rng);
+
+        assert!(partials.iter().all(|partial| verify_partial(&keys[0], &package, partial)));
+        partials[1].response += Fr::from(1);
+        assert!(!verify_partial(&keys[0], &package, &partials[1]));
+        assert_eq!(aggregate(&keys[0], &package, &partials), Err(SecretSharingError::InvalidPartialSignature { signer: 3 }));
+
+        // a partial signature from outside the package is refused rather than summed
+        let stranger = PartialSignature { signer: 2, response: Fr::from(5) };
+        assert_eq!(aggregate(&keys[0], &package, &[stranger]), Err(SecretSharingError::UnknownSigner { signer: 2 }));
+    }
+
+    #[test]
+    fn test_signing_errors() {
+        let rng = &mut StdRng::seed_from_u64(2);
+        let keys = dkg(3, 4, rng);
+
+        // fewer signers than the threshold
+        let (nonces, commitment) = commit::<G1Projective, _>(1, rng);
+        let (_, other) = commit::<G1Projective, _>(2, rng);
+        let small = SigningPackage::new(b"m", vec![commitment, other]).unwrap();
+        assert_eq!(sign(&keys[0], nonces, &small), Err(SecretSharingError::InsufficientShares { threshold: 3, provided: 2 }));
+
+        // a signer outside the package, and a signer committing twice
+        let commitments: Vec<_> = [1, 2, 3].iter().map(|signer| commit::<G1Projective, _>(*signer, rng).1).collect();
+        let package = SigningPackage::new(b"m", commitments.clone()).unwrap();
+        let (nonces, _) = commit::<G1Projective, _>(4, rng);
+        assert_eq!(sign(&keys[3], nonces, &package), Err(SecretSharingError::UnknownSigner { signer: 4 }));
+        // nonces that are not the ones committed to in the package
+        let (nonces, _) = commit::<G1Projective, _>(1, rng);
+        assert_eq!(sign(&keys[0], nonces, &package), Err(SecretSharingError::UnknownSigner { signer: 1 }));
+        assert_eq!(SigningPackage::new(b"m", vec![commitments[0], commitments[0]]), Err(SecretSharingError::DuplicateX));
+    }
+}
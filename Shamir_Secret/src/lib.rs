@@ -0,0 +1,12 @@
+pub mod error;
+pub mod shamir;
+pub mod share;
+pub mod resharing;
+pub mod multi_secret;
+pub mod access_structure;
+pub mod vss;
+pub mod dkg;
+pub mod frost;
+
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
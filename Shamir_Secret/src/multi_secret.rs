@@ -0,0 +1,145 @@
+use crate::error::SecretSharingError;
+use crate::shamir::{share_x, Point};
+use ark_ff::PrimeField;
+use polynomial::univariate::UnivariatePoly;
+use rand::RngCore;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Packed secret sharing: k secrets ride on one polynomial, so every participant stores a single field element
+// instead of one per secret. Secret i sits at x = -i for i = 0..k, and threshold - 1 random points at
+// x = -k, -(k + 1), ... keep the polynomial random, giving degree threshold + k - 2. Shares are evaluated at
+// x = 1..=n like everywhere else, which never hits a secret or a random point.
+//
+// Any threshold - 1 shares reveal nothing about the secrets, but reconstruction takes threshold + k - 1 shares:
+// the k extra coefficients are what the packing costs in the gap between privacy and reconstruction
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct PackedShare<F: PrimeField> {
+    pub no_of_secrets: usize,
+    pub threshold: usize,
+    pub point: Point<F>,
+}
+
+// The number of shares that reconstruct a packing of no_of_secrets secrets with the given privacy threshold
+pub fn packed_shares_needed(no_of_secrets: usize, threshold: usize) -> usize {
+    threshold + no_of_secrets - 1
+}
+
+pub fn generate_packed_shares<F: PrimeField>(
+    secrets: &[F],
+    threshold: usize,
+    total_shares: usize,
+) -> Result<Vec<PackedShare<F>>, SecretSharingError> {
+    generate_packed_shares_with_rng(secrets, threshold, total_shares, &mut rand::thread_rng())
+}
+
+// Same as generate_packed_shares with the random points drawn from rng
+pub fn generate_packed_shares_with_rng<F: PrimeField, R: RngCore>(
+    secrets: &[F],
+    threshold: usize,
+    total_shares: usize,
+    rng: &mut R,
+) -> Result<Vec<PackedShare<F>>, SecretSharingError> {
+    if threshold == 0 {
+        return Err(SecretSharingError::ZeroThreshold);
+    }
+    if secrets.is_empty() {
+        return Err(SecretSharingError::NoSecrets);
+    }
+    let needed = packed_shares_needed(secrets.len(), threshold);
+    if needed > total_shares {
+        return Err(SecretSharingError::ThresholdAboveTotal { threshold: needed, total_shares });
+    }
+
+    let xs: Vec<F> = (0..needed).map(packing_x).collect();
+    let mut ys = secrets.to_vec();
+    ys.extend((1..threshold).map(|_| F::rand(rng)));
+
+    let mut poly = UnivariatePoly::interpolate(&xs, &ys);
+
+    let shares = (1..=total_shares)
+        .map(|id| {
+            let x = share_x(id);
+            PackedShare { no_of_secrets: secrets.len(), threshold, point: Point { id, x, y: poly.evaluate(x) } }
+        })
+        .collect();
+
+    ys.zeroize();
+    poly.coefficients.zeroize();
+    Ok(shares)
+}
+
+pub fn reconstruct_packed<F: PrimeField>(shares: &[PackedShare<F>]) -> Result<Vec<F>, SecretSharingError> {
+    let first = shares.first().ok_or(SecretSharingError::InsufficientShares { threshold: 1, provided: 0 })?;
+    if shares.iter().any(|share| share.no_of_secrets != first.no_of_secrets || share.threshold != first.threshold) {
+        return Err(SecretSharingError::MismatchedShares);
+    }
+
+    let needed = packed_shares_needed(first.no_of_secrets, first.threshold);
+    if shares.len() < needed {
+        return Err(SecretSharingError::InsufficientShares { threshold: needed, provided: shares.len() });
+    }
+
+    let xs: Vec<F> = shares[..needed].iter().map(|share| share.point.x).collect();
+    if xs.iter().enumerate().any(|(i, x)| xs[..i].contains(x)) {
+        return Err(SecretSharingError::DuplicateX);
+    }
+    let mut ys: Vec<F> = shares[..needed].iter().map(|share| share.point.y).collect();
+
+    let mut poly = UnivariatePoly::interpolate(&xs, &ys);
+    let secrets = (0..first.no_of_secrets).map(|i| poly.evaluate(packing_x(i))).collect();
+
+    ys.zeroize();
+    poly.coefficients.zeroize();
+    Ok(secrets)
+}
+
+// -i, the x coordinates of the secrets followed by the random points
+fn packing_x<F: PrimeField>(i: usize) -> F {
+    -F::from(i as u64)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fq;
+
+    #[test]
+    fn test_packed_round_trip() {
+        let secrets: Vec<Fq> = [11u64, 22, 33, 44].iter().map(|secret| Fq::from(*secret)).collect();
+        let shares = generate_packed_shares(&secrets, 3, 8).unwrap();
+
+        // one field element per participant for four secrets
+        assert_eq!(shares.len(), 8);
+        assert_eq!(packed_shares_needed(4, 3), 6);
+        assert_eq!(reconstruct_packed(&shares[..6]), Ok(secrets.clone()));
+        assert_eq!(reconstruct_packed(&shares[2..]), Ok(secrets));
+        assert_eq!(
+            reconstruct_packed(&shares[..5]),
+            Err(SecretSharingError::InsufficientShares { threshold: 6, provided: 5 })
+        );
+    }
+
+    #[test]
+    fn test_packing_a_single_secret_is_plain_shamir() {
+        let shares = generate_packed_shares(&[Fq::from(5u64)], 2, 3).unwrap();
+        let points: Vec<Point<Fq>> = shares.iter().map(|share| share.point.clone()).collect();
+
+        assert_eq!(crate::shamir::reconstruct_secret(&points[1..], 0, 2), Ok(Fq::from(5u64)));
+    }
+
+    #[test]
+    fn test_packed_parameter_errors() {
+        let secrets = vec![Fq::from(1u64), Fq::from(2u64)];
+        assert_eq!(
+            generate_packed_shares(&secrets, 4, 4),
+            Err(SecretSharingError::ThresholdAboveTotal { threshold: 5, total_shares: 4 })
+        );
+
+        assert_eq!(generate_packed_shares::<Fq>(&[], 2, 4), Err(SecretSharingError::NoSecrets));
+
+        let mut shares = generate_packed_shares(&secrets, 2, 4).unwrap();
+        shares[1].no_of_secrets = 3;
+        assert_eq!(reconstruct_packed(&shares), Err(SecretSharingError::MismatchedShares));
+    }
+}
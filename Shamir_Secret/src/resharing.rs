@@ -0,0 +1,156 @@
+use crate::error::SecretSharingError;
+use crate::shamir::{generate_field_shares, Point};
+use ark_ff::PrimeField;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Re-sharing turns a (t, n) sharing of a secret into a (t', n') sharing of the same secret, without anyone holding the
+// secret on the way. With f the old polynomial, secret = f(p) for the password p and any t old holders i:
+//   secret = sum over i of lambda_i * f(x_i)
+// where lambda_i are the Lagrange coefficients at p of the old x coordinates. Every old holder i shares its own share
+// f(x_i) with a fresh polynomial g_i of degree t' - 1 over the new participants, and new participant j adds up
+//   f'(x_j) = sum over i of lambda_i * g_i(x_j)
+// f' = sum of lambda_i * g_i has degree t' - 1 and f'(p) = secret, so the new shares are a (t', n') sharing.
+//
+// Every new participant must combine sub-shares from the same t old holders, the Lagrange coefficients depend on the set
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct SubShare<F: PrimeField> {
+    // the old holder that dealt it and the x of its old share, which fix its Lagrange coefficient
+    pub dealer: usize,
+    pub dealer_x: F,
+    // g_dealer evaluated at the recipient, point.id is the new participant
+    pub point: Point<F>,
+}
+
+// Old holder's step: sub-share the held share among new_total participants with threshold new_threshold
+pub fn sub_share<F: PrimeField>(
+    share: &Point<F>,
+    password: i32,
+    new_threshold: usize,
+    new_total: usize,
+) -> Result<Vec<SubShare<F>>, SecretSharingError> {
+    let points = generate_field_shares(share.y, password, new_threshold, new_total)?;
+
+    Ok(points
+        .into_iter()
+        .map(|point| SubShare { dealer: share.id, dealer_x: share.x, point })
+        .collect())
+}
+
+// New participant's step: the Lagrange combination of the sub-shares received from the first old_threshold dealers
+pub fn combine_sub_shares<F: PrimeField>(
+    sub_shares: &[SubShare<F>],
+    password: i32,
+    old_threshold: usize,
+) -> Result<Point<F>, SecretSharingError> {
+    if old_threshold == 0 {
+        return Err(SecretSharingError::ZeroThreshold);
+    }
+    if sub_shares.len() < old_threshold {
+        return Err(SecretSharingError::InsufficientShares { threshold: old_threshold, provided: sub_shares.len() });
+    }
+
+    let sub_shares = &sub_shares[..old_threshold];
+    let recipient = &sub_shares[0].point;
+    if sub_shares.iter().any(|sub_share| sub_share.point.id != recipient.id || sub_share.point.x != recipient.x) {
+        return Err(SecretSharingError::MismatchedShares);
+    }
+
+    let dealer_xs: Vec<F> = sub_shares.iter().map(|sub_share| sub_share.dealer_x).collect();
+    let y = sub_shares
+        .iter()
+        .enumerate()
+        .map(|(i, sub_share)| Ok(lagrange_coefficient(&dealer_xs, i, F::from(password))? * sub_share.point.y))
+        .sum::<Result<F, SecretSharingError>>()?;
+
+    Ok(Point { id: recipient.id, x: recipient.x, y })
+}
+
+// Runs the whole protocol in one place: the first old_threshold old shares are sub-shared and every new participant
+// combines what it receives
+pub fn reshare<F: PrimeField>(
+    old_shares: &[Point<F>],
+    password: i32,
+    old_threshold: usize,
+    new_threshold: usize,
+    new_total: usize,
+) -> Result<Vec<Point<F>>, SecretSharingError> {
+    if old_shares.len() < old_threshold {
+        return Err(SecretSharingError::InsufficientShares { threshold: old_threshold, provided: old_shares.len() });
+    }
+
+    let dealt = old_shares[..old_threshold]
+        .iter()
+        .map(|share| sub_share(share, password, new_threshold, new_total))
+        .collect::<Result<Vec<Vec<SubShare<F>>>, SecretSharingError>>()?;
+
+    (0..new_total)
+        .map(|j| {
+            let received: Vec<SubShare<F>> = dealt.iter().map(|sub_shares| sub_shares[j].clone()).collect();
+            combine_sub_shares(&received, password, old_threshold)
+        })
+        .collect()
+}
+
+// L_i(at) for the Lagrange basis over xs, fails on duplicate xs
+pub fn lagrange_coefficient<F: PrimeField>(xs: &[F], i: usize, at: F) -> Result<F, SecretSharingError> {
+    let mut numerator = F::one();
+    let mut denominator = F::one();
+
+    for (j, x) in xs.iter().enumerate() {
+        if j != i {
+            numerator *= at - x;
+            denominator *= xs[i] - x;
+        }
+    }
+
+    denominator.inverse().map(|inverse| numerator * inverse).ok_or(SecretSharingError::DuplicateX)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shamir::{generate_shares, reconstruct_secret};
+    use ark_bn254::Fq;
+
+    #[test]
+    fn test_reshare_three_of_five_to_four_of_seven() {
+        let old_shares = generate_shares::<Fq>(1234, 25, 3, 5).unwrap();
+
+        // holders 2, 4 and 5 take part, the others may be offline
+        let participating = vec![old_shares[1].clone(), old_shares[3].clone(), old_shares[4].clone()];
+        let new_shares = reshare(&participating, 25, 3, 4, 7).unwrap();
+
+        assert_eq!(new_shares.len(), 7);
+        assert_eq!(new_shares.iter().map(|share| share.id).collect::<Vec<_>>(), (1..=7).collect::<Vec<_>>());
+        assert_eq!(reconstruct_secret(&new_shares[..4], 25, 4), Ok(Fq::from(1234)));
+        assert_eq!(reconstruct_secret(&new_shares[3..], 25, 4), Ok(Fq::from(1234)));
+
+        // three new shares are no longer enough
+        let interpolated = reconstruct_secret(&new_shares[..3], 25, 3).unwrap();
+        assert_ne!(interpolated, Fq::from(1234));
+    }
+
+    #[test]
+    fn test_reshare_to_fewer_participants() {
+        let old_shares = generate_shares::<Fq>(99, 0, 4, 6).unwrap();
+        let new_shares = reshare(&old_shares[2..], 0, 4, 2, 3).unwrap();
+
+        assert_eq!(reconstruct_secret(&new_shares[1..], 0, 2), Ok(Fq::from(99)));
+    }
+
+    #[test]
+    fn test_sub_shares_must_target_one_participant() {
+        let old_shares = generate_shares::<Fq>(5, 25, 2, 3).unwrap();
+        let first = sub_share(&old_shares[0], 25, 2, 3).unwrap();
+        let second = sub_share(&old_shares[1], 25, 2, 3).unwrap();
+
+        assert!(combine_sub_shares(&[first[0].clone(), second[0].clone()], 25, 2).is_ok());
+        assert_eq!(combine_sub_shares(&[first[0].clone(), second[1].clone()], 25, 2), Err(SecretSharingError::MismatchedShares));
+        assert_eq!(combine_sub_shares(&[first[0].clone(), first[0].clone()], 25, 2), Err(SecretSharingError::DuplicateX));
+        assert_eq!(
+            combine_sub_shares(&first[..1], 25, 2),
+            Err(SecretSharingError::InsufficientShares { threshold: 2, provided: 1 })
+        );
+    }
+}
@@ -0,0 +1,686 @@
+// Import necessary crates and modules for random number generation, prime field operations, and polynomial operations.
+use crate::error::SecretSharingError;
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use polynomial::field_utils::batch_inverse;
+use polynomial::reed_solomon::decode_with_errors;
+use polynomial::univariate::UnivariatePoly;
+use rand::RngCore;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// struct that represent a point in a 2D space, where each coordinate is of type F.
+// The id names the shareholder, share id i is always evaluated at x = i.
+// Shares are wiped from memory when dropped, like every other type here that holds secret values.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize, Zeroize, ZeroizeOnDrop)]
+pub struct Point<F: PrimeField> {
+    pub id: usize,
+    pub x: F,
+    pub y: F,
+}
+
+// A share of a byte secret: the secret is encoded as several field elements, each one shared with its own polynomial,
+// and every polynomial is evaluated at the same x so one share holds one y per element.
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct ByteShare<F: PrimeField> {
+    pub id: usize,
+    pub x: F,
+    pub ys: Vec<F>,
+}
+
+// Function to generate shares for Shamir's Secret Sharing scheme.
+pub fn generate_shares<F: PrimeField>(
+    secret: i32,
+    password: i32,
+    threshold: usize,
+    total_shares: usize,
+) -> Result<Vec<Point<F>>, SecretSharingError> {
+    generate_field_shares(F::from(secret), password, threshold, total_shares)
+}
+
+// Same as generate_shares with the randomness drawn from rng, a seeded rng gives the same shares every run.
+pub fn generate_shares_with_rng<F: PrimeField, R: RngCore>(
+    secret: i32,
+    password: i32,
+    threshold: usize,
+    total_shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Point<F>>, SecretSharingError> {
+    generate_field_shares_with_rng(F::from(secret), password, threshold, total_shares, rng)
+}
+
+// Function to generate shares of a secret that is already a field element.
+pub fn generate_field_shares<F: PrimeField>(
+    secret: F,
+    password: i32,
+    threshold: usize,
+    total_shares: usize,
+) -> Result<Vec<Point<F>>, SecretSharingError> {
+    generate_field_shares_with_rng(secret, password, threshold, total_shares, &mut rand::thread_rng())
+}
+
+// Same as generate_field_shares with the randomness drawn from rng, for targets without a thread local generator.
+pub fn generate_field_shares_with_rng<F: PrimeField, R: RngCore>(
+    secret: F,
+    password: i32,
+    threshold: usize,
+    total_shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Point<F>>, SecretSharingError> {
+    // Check the threshold against the number of shares and the password against the share x coordinates.
+    check_parameters(password, threshold, total_shares)?;
+
+    // Initialize vectors to hold x and y coordinates of points.
+    let mut xs: Vec<F> = Vec::new();
+    let mut ys: Vec<F> = Vec::new();
+
+    // Push the password as the first x coordinate and the secret as the first y coordinate.
+    xs.push(F::from(password));
+    ys.push(secret);
+
+    // Generate additional points up to the threshold.
+    for _ in 1..threshold {
+        // Generate a random x coordinate.
+        xs.push(F::rand(rng));
+        // Generate a random y coordinate.
+        ys.push(F::rand(rng));
+    }
+
+    // Interpolate a polynomial through the generated points.
+    let mut poly = UnivariatePoly::interpolate(&xs, &ys);
+    // The points include the secret, clear them as soon as the polynomial is known.
+    ys.zeroize();
+
+    // Check if the degree of the interpolated polynomial matches the expected degree.
+    if poly.degree() != threshold - 1 {
+        poly.coefficients.zeroize();
+        return Err(SecretSharingError::InterpolationFailed);
+    }
+
+    // Initialize a vector to hold the shares.
+    let mut shares = Vec::new();
+    // Generate shares by evaluating the polynomial at x = 1..=total_shares, distinct by construction.
+    for id in 1..=total_shares {
+        let x = share_x(id);
+        let y = poly.evaluate(x);
+        shares.push(Point { id, x, y });
+    }
+
+    // The coefficients are the secret and its randomness, wipe them before returning.
+    poly.coefficients.zeroize();
+
+    // Return the generated shares.
+    Ok(shares)
+}
+
+// Function to reconstruct the secret from shares.
+pub fn reconstruct_secret<F: PrimeField>(
+    shares: &[Point<F>],
+    password: i32,
+    threshold: usize,
+) -> Result<F, SecretSharingError> {
+    // Check if the number of shares is less than the threshold.
+    check_share_count(shares.len(), threshold)?;
+
+    // Prepare x and y coordinates of the shares for interpolation.
+    let xs: Vec<F> = shares[0..threshold].iter().map(|p| p.x).collect();
+    let mut ys: Vec<F> = shares[0..threshold].iter().map(|p| p.y).collect();
+
+    // Two shares at the same x leave the polynomial underdetermined.
+    if has_duplicates(&xs) {
+        ys.zeroize();
+        return Err(SecretSharingError::DuplicateX);
+    }
+
+    // Interpolate a polynomial through the shares.
+    let mut poly = UnivariatePoly::interpolate(&xs, &ys);
+
+    // Evaluate the polynomial at the password to get the secret.
+    let secret = poly.evaluate(F::from(password));
+
+    // Only the secret leaves this function, the share values and the polynomial are wiped.
+    ys.zeroize();
+    poly.coefficients.zeroize();
+    Ok(secret)
+}
+
+// The secret found by reconstruct_secret_robust and the ids of the shares that were not on the sharing polynomial.
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct RobustReconstruction<F: PrimeField> {
+    pub secret: F,
+    pub faulty_ids: Vec<usize>,
+}
+
+// Function to reconstruct the secret when some shares may have been corrupted.
+// reconstruct_secret reads threshold shares and trusts them, one bad share and the result is garbage. Here every share
+// is used: n shares of a threshold t sharing are a Reed-Solomon codeword with t message symbols, so Gao's decoder
+// corrects up to (n - t) / 2 bad shares and the ones it corrected are reported by id. More bad shares than that are
+// an error rather than a wrong secret. With exactly t shares nothing can be checked and every share is taken as is.
+pub fn reconstruct_secret_robust<F: PrimeField>(
+    shares: &[Point<F>],
+    password: i32,
+    threshold: usize,
+) -> Result<RobustReconstruction<F>, SecretSharingError> {
+    check_share_count(shares.len(), threshold)?;
+
+    let xs: Vec<F> = shares.iter().map(|p| p.x).collect();
+    let mut ys: Vec<F> = shares.iter().map(|p| p.y).collect();
+    if has_duplicates(&xs) {
+        ys.zeroize();
+        return Err(SecretSharingError::DuplicateX);
+    }
+
+    let poly = decode_with_errors(&xs, &ys, threshold);
+    ys.zeroize();
+    let mut poly = poly.ok_or(SecretSharingError::TooManyFaultyShares { correctable: (shares.len() - threshold) / 2 })?;
+
+    let faulty_ids = shares.iter().filter(|p| poly.evaluate(p.x) != p.y).map(|p| p.id).collect();
+    let secret = poly.evaluate(F::from(password));
+
+    poly.coefficients.zeroize();
+    Ok(RobustReconstruction { secret, faulty_ids })
+}
+
+// Function to reconstruct the secret without branching on share values.
+// The secret is sum over i of L_i(password) * y_i for the Lagrange basis L_i over the share x coordinates. The x coordinates
+// and the password are public, so only they decide the control flow: every coefficient runs over all shares in the same
+// order with a single batch inversion of the denominators, and the y values only go through multiplications and additions.
+// Returns the same result and errors as reconstruct_secret. How constant time the field arithmetic itself is depends on
+// the field implementation, arkworks does not promise it.
+pub fn reconstruct_secret_constant_time<F: PrimeField>(
+    shares: &[Point<F>],
+    password: i32,
+    threshold: usize,
+) -> Result<F, SecretSharingError> {
+    check_share_count(shares.len(), threshold)?;
+
+    let xs: Vec<F> = shares[0..threshold].iter().map(|p| p.x).collect();
+    if has_duplicates(&xs) {
+        return Err(SecretSharingError::DuplicateX);
+    }
+
+    let password = F::from(password);
+    let mut numerators = vec![F::one(); threshold];
+    let mut denominators = vec![F::one(); threshold];
+    for i in 0..threshold {
+        for j in 0..threshold {
+            // the factor for j = i is replaced by one instead of being skipped
+            let is_other = F::from((i != j) as u64);
+            numerators[i] *= is_other * (password - xs[j]) + (F::one() - is_other);
+            denominators[i] *= is_other * (xs[i] - xs[j]) + (F::one() - is_other);
+        }
+    }
+
+    let secret = numerators
+        .iter()
+        .zip(batch_inverse(&denominators).iter())
+        .zip(shares[0..threshold].iter())
+        .fold(F::zero(), |secret, ((numerator, inverse), share)| secret + *numerator * inverse * share.y);
+
+    Ok(secret)
+}
+
+// Function to refresh shares without changing the secret.
+// Every share is moved by delta(x) = (x - password) * r(x) for a random r of degree threshold - 2, so the sharing
+// polynomial changes everywhere except at the password. Old shares are useless once refreshed, combining them with new ones
+// interpolates some other polynomial. The dealer here samples delta itself, in a deployment without a dealer each holder
+// would deal a sharing of zero and everyone adds up what they receive.
+pub fn refresh_shares<F: PrimeField>(
+    shares: &[Point<F>],
+    password: i32,
+    threshold: usize,
+) -> Result<Vec<Point<F>>, SecretSharingError> {
+    refresh_shares_with_rng(shares, password, threshold, &mut rand::thread_rng())
+}
+
+// Same as refresh_shares with delta drawn from rng.
+pub fn refresh_shares_with_rng<F: PrimeField, R: RngCore>(
+    shares: &[Point<F>],
+    password: i32,
+    threshold: usize,
+    rng: &mut R,
+) -> Result<Vec<Point<F>>, SecretSharingError> {
+    if threshold == 0 {
+        return Err(SecretSharingError::ZeroThreshold);
+    }
+    let xs: Vec<F> = shares.iter().map(|p| p.x).collect();
+    if has_duplicates(&xs) {
+        return Err(SecretSharingError::DuplicateX);
+    }
+
+    // A threshold of 1 shares the secret as a constant, the only polynomial through it that keeps the degree.
+    let mut random = UnivariatePoly::new((1..threshold).map(|_| F::rand(rng)).collect());
+    let mut delta = &UnivariatePoly::from_roots(&[F::from(password)]) * &random;
+
+    let refreshed = shares
+        .iter()
+        .map(|p| Point { id: p.id, x: p.x, y: p.y + delta.evaluate(p.x) })
+        .collect();
+
+    random.coefficients.zeroize();
+    delta.coefficients.zeroize();
+    Ok(refreshed)
+}
+
+
+// Function to generate shares of a secret of any byte length.
+// The secret is encoded with encode_bytes, every element is shared like generate_shares does at the same x coordinates.
+pub fn generate_byte_shares<F: PrimeField>(
+    secret: &[u8],
+    password: i32,
+    threshold: usize,
+    total_shares: usize,
+) -> Result<Vec<ByteShare<F>>, SecretSharingError> {
+    generate_byte_shares_with_rng(secret, password, threshold, total_shares, &mut rand::thread_rng())
+}
+
+// Same as generate_byte_shares with the randomness drawn from rng.
+pub fn generate_byte_shares_with_rng<F: PrimeField, R: RngCore>(
+    secret: &[u8],
+    password: i32,
+    threshold: usize,
+    total_shares: usize,
+    rng: &mut R,
+) -> Result<Vec<ByteShare<F>>, SecretSharingError> {
+    check_parameters(password, threshold, total_shares)?;
+
+    let password = F::from(password);
+    let mut shares: Vec<ByteShare<F>> = (1..=total_shares).map(|id| ByteShare { id, x: share_x(id), ys: Vec::new() }).collect();
+
+    let mut elements = encode_bytes::<F>(secret);
+    for element in &elements {
+        // p(password) = element, and threshold - 1 random points fix the rest of the polynomial
+        let mut points_x = vec![password];
+        let mut points_y = vec![*element];
+        for _ in 1..threshold {
+            points_x.push(F::rand(rng));
+            points_y.push(F::rand(rng));
+        }
+        let mut poly = UnivariatePoly::interpolate(&points_x, &points_y);
+
+        for share in shares.iter_mut() {
+            share.ys.push(poly.evaluate(share.x));
+        }
+
+        points_y.zeroize();
+        poly.coefficients.zeroize();
+    }
+
+    elements.zeroize();
+    Ok(shares)
+}
+
+// Function to reconstruct a byte secret from shares.
+// Fails with too few shares, shares of different secrets, or when the interpolated elements are not a valid encoding.
+pub fn reconstruct_bytes<F: PrimeField>(
+    shares: &[ByteShare<F>],
+    password: i32,
+    threshold: usize,
+) -> Result<Vec<u8>, SecretSharingError> {
+    check_share_count(shares.len(), threshold)?;
+
+    let shares = &shares[0..threshold];
+    let no_of_elements = shares[0].ys.len();
+    if shares.iter().any(|share| share.ys.len() != no_of_elements) {
+        return Err(SecretSharingError::MismatchedShares);
+    }
+
+    let xs: Vec<F> = shares.iter().map(|share| share.x).collect();
+    if has_duplicates(&xs) {
+        return Err(SecretSharingError::DuplicateX);
+    }
+
+    let mut elements: Vec<F> = (0..no_of_elements)
+        .map(|i| {
+            let mut ys: Vec<F> = shares.iter().map(|share| share.ys[i]).collect();
+            let mut poly = UnivariatePoly::interpolate(&xs, &ys);
+            let element = poly.evaluate(F::from(password));
+
+            ys.zeroize();
+            poly.coefficients.zeroize();
+            element
+        })
+        .collect();
+
+    let secret = decode_bytes(&elements);
+    elements.zeroize();
+    secret
+}
+
+// The x coordinate of share id, ids start at 1 since x = 0 is the usual place of the secret.
+pub fn share_x<F: PrimeField>(id: usize) -> F {
+    F::from(id as u64)
+}
+
+pub(crate) fn check_parameters(password: i32, threshold: usize, total_shares: usize) -> Result<(), SecretSharingError> {
+    if threshold == 0 {
+        return Err(SecretSharingError::ZeroThreshold);
+    }
+    if threshold > total_shares {
+        return Err(SecretSharingError::ThresholdAboveTotal { threshold, total_shares });
+    }
+    if password >= 1 && password as usize <= total_shares {
+        return Err(SecretSharingError::PasswordOnShare { password });
+    }
+    Ok(())
+}
+
+fn check_share_count(provided: usize, threshold: usize) -> Result<(), SecretSharingError> {
+    if threshold == 0 {
+        return Err(SecretSharingError::ZeroThreshold);
+    }
+    if provided < threshold {
+        return Err(SecretSharingError::InsufficientShares { threshold, provided });
+    }
+    Ok(())
+}
+
+fn has_duplicates<F: PrimeField>(xs: &[F]) -> bool {
+    xs.iter().enumerate().any(|(i, x)| xs[..i].contains(x))
+}
+
+// The number of bytes packed into one field element, small enough that every chunk is below the modulus.
+pub fn bytes_per_element<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize - 1) / 8
+}
+
+// Encodes the bytes as the length in the first element followed by little endian chunks of bytes_per_element bytes.
+// The length prefix keeps trailing zero bytes, which the padding of the last chunk would otherwise swallow.
+pub fn encode_bytes<F: PrimeField>(bytes: &[u8]) -> Vec<F> {
+    let mut elements = vec![F::from(bytes.len() as u64)];
+    elements.extend(bytes.chunks(bytes_per_element::<F>()).map(F::from_le_bytes_mod_order));
+    elements
+}
+
+// Inverse of encode_bytes. Only the canonical encoding decodes: the chunk count matches the length
+// and no element has bits set above its chunk.
+pub fn decode_bytes<F: PrimeField>(elements: &[F]) -> Result<Vec<u8>, SecretSharingError> {
+    let chunk_size = bytes_per_element::<F>();
+    let (length, chunks) = elements.split_first().ok_or(SecretSharingError::InvalidEncoding)?;
+
+    let mut length_bytes = [0u8; 8];
+    length_bytes.copy_from_slice(&element_to_bytes(length, 8)?);
+    let length = u64::from_le_bytes(length_bytes) as usize;
+    if chunks.len() != length.div_ceil(chunk_size) {
+        return Err(SecretSharingError::InvalidEncoding);
+    }
+
+    let mut bytes = Vec::with_capacity(length);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let size = chunk_size.min(length - i * chunk_size);
+        bytes.extend(element_to_bytes(chunk, size)?);
+    }
+
+    Ok(bytes)
+}
+
+// The first size little endian bytes of the element, an error when any higher byte is set. Little endian rather than
+// transcript::encoding's big endian because the secret's bytes went in with from_le_bytes_mod_order, so a short last
+// chunk is the low bytes of its element and this is its inverse.
+fn element_to_bytes<F: PrimeField>(element: &F, size: usize) -> Result<Vec<u8>, SecretSharingError> {
+    let bytes = element.into_bigint().to_bytes_le();
+    if bytes[size..].iter().any(|byte| *byte != 0) {
+        return Err(SecretSharingError::InvalidEncoding);
+    }
+    Ok(bytes[..size].to_vec())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrary::share_set_and_quorum;
+    use ark_bn254::Fq;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_basic_sharing_and_reconstruction() {
+        let secret = 42;
+        let threshold = 3;
+        let total_shares = 5;
+        let password = 25;
+
+        let shares = generate_shares::<Fq>(secret, password, threshold, total_shares).unwrap();
+        assert_eq!(shares.len(), total_shares);
+
+        let reconstructed = reconstruct_secret(&shares[..threshold], password, threshold);
+        assert_eq!(reconstructed, Ok(Fq::from(secret)));
+    }
+
+    #[test]
+    fn test_reconstruction_with_different_share_combinations() {
+        let secret = 123;
+        let threshold = 3;
+        let total_shares = 5;
+        let password = 25;
+
+        let shares = generate_shares::<Fq>(secret, password, threshold, total_shares).unwrap();
+
+        let reconstructed1 = reconstruct_secret(&shares[1..4], password, threshold);
+        let reconstructed2 = reconstruct_secret(&shares[2..5], password, threshold);
+
+        assert_eq!(reconstructed1, Ok(Fq::from(secret)));
+        assert_eq!(reconstructed2, Ok(Fq::from(secret)));
+    }
+
+    #[test]
+    fn test_insufficient_shares() {
+        let secret = 42;
+        let threshold = 3;
+        let total_shares = 5;
+        let password = 25;
+
+        let shares = generate_shares::<Fq>(secret, password, threshold, total_shares).unwrap();
+        let reconstructed = reconstruct_secret(&shares[..2], password, threshold);
+        assert_eq!(reconstructed, Err(SecretSharingError::InsufficientShares { threshold: 3, provided: 2 }));
+    }
+
+    #[test]
+    fn test_reconstruction_from_hand_built_polynomial() {
+        let secret = Fq::from(77);
+        let password = Fq::from(25);
+
+        // p(x) = secret + (x - password)(x - 3), the product vanishes at the password so p(password) = secret
+        let poly = &UnivariatePoly::from_roots(&[password, Fq::from(3)]) + &UnivariatePoly::new(vec![secret]);
+        let shares: Vec<Point<Fq>> = (1..=3u64)
+            .map(|x| Point { id: x as usize, x: Fq::from(x), y: poly.evaluate(Fq::from(x)) })
+            .collect();
+
+        assert_eq!(reconstruct_secret(&shares, 25, 3), Ok(secret));
+    }
+
+    #[test]
+    fn test_shares_are_numbered() {
+        let shares = generate_shares::<Fq>(42, 25, 3, 5).unwrap();
+        assert_eq!(shares.iter().map(|share| share.id).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert!(shares.iter().all(|share| share.x == share_x(share.id)));
+
+        // the same share twice is only two distinct points
+        let duplicated = vec![shares[0].clone(), shares[3].clone(), shares[0].clone()];
+        assert_eq!(reconstruct_secret(&duplicated, 25, 3), Err(SecretSharingError::DuplicateX));
+
+        let bytes = generate_byte_shares::<Fq>(b"seed", 25, 2, 3).unwrap();
+        assert_eq!(reconstruct_bytes(&[bytes[1].clone(), bytes[1].clone()], 25, 2), Err(SecretSharingError::DuplicateX));
+        assert_eq!(reconstruct_bytes(&bytes[1..], 25, 2), Ok(b"seed".to_vec()));
+    }
+
+    #[test]
+    fn test_password_on_a_share() {
+        let shares = generate_shares::<Fq>(42, 3, 2, 5);
+        assert_eq!(shares, Err(SecretSharingError::PasswordOnShare { password: 3 }));
+    }
+
+    #[test]
+    fn test_constant_time_reconstruction_matches() {
+        for (secret, password, threshold, total_shares) in [(42, 25, 1, 1), (7, 0, 2, 3), (123, -4, 3, 5), (500, 25, 6, 10)] {
+            let shares = generate_shares::<Fq>(secret, password, threshold, total_shares).unwrap();
+
+            for start in 0..=(total_shares - threshold) {
+                let subset = &shares[start..start + threshold];
+                assert_eq!(
+                    reconstruct_secret_constant_time(subset, password, threshold),
+                    reconstruct_secret(subset, password, threshold)
+                );
+                assert_eq!(reconstruct_secret_constant_time(subset, password, threshold), Ok(Fq::from(secret)));
+            }
+        }
+
+        // a wrong password gives the same wrong answer on both paths
+        let shares = generate_shares::<Fq>(9, 25, 3, 4).unwrap();
+        assert_eq!(reconstruct_secret_constant_time(&shares, 26, 3), reconstruct_secret(&shares, 26, 3));
+
+        let duplicated = vec![shares[0].clone(), shares[0].clone()];
+        assert_eq!(reconstruct_secret_constant_time(&duplicated, 25, 2), Err(SecretSharingError::DuplicateX));
+        assert_eq!(
+            reconstruct_secret_constant_time(&shares[..1], 25, 2),
+            Err(SecretSharingError::InsufficientShares { threshold: 2, provided: 1 })
+        );
+    }
+
+    #[test]
+    fn test_refresh_shares() {
+        let shares = generate_shares::<Fq>(42, 25, 3, 5).unwrap();
+        let refreshed = refresh_shares(&shares, 25, 3).unwrap();
+
+        assert!(shares.iter().zip(refreshed.iter()).all(|(old, new)| old.id == new.id && old.x == new.x && old.y != new.y));
+        assert_eq!(reconstruct_secret(&refreshed[..3], 25, 3), Ok(Fq::from(42)));
+        assert_eq!(reconstruct_secret(&refreshed[2..], 25, 3), Ok(Fq::from(42)));
+
+        // an old share no longer combines with refreshed ones
+        let mixed = vec![shares[0].clone(), refreshed[1].clone(), refreshed[2].clone()];
+        assert_ne!(reconstruct_secret(&mixed, 25, 3), Ok(Fq::from(42)));
+
+        // rotating again keeps working
+        let rotated = refresh_shares(&refreshed, 25, 3).unwrap();
+        assert_eq!(reconstruct_secret(&rotated[1..4], 25, 3), Ok(Fq::from(42)));
+    }
+
+    #[test]
+    fn test_robust_reconstruction_finds_faulty_shares() {
+        let mut shares = generate_shares::<Fq>(42, 25, 3, 7).unwrap();
+        let robust = reconstruct_secret_robust(&shares, 25, 3).unwrap();
+        assert_eq!((robust.secret, robust.faulty_ids.clone()), (Fq::from(42), vec![]));
+
+        // 7 shares of a threshold 3 sharing correct 2 bad ones, which reconstruct_secret would have used
+        shares[0].y += Fq::from(1);
+        shares[4].y = Fq::from(0);
+        assert_ne!(reconstruct_secret(&shares, 25, 3), Ok(Fq::from(42)));
+        let robust = reconstruct_secret_robust(&shares, 25, 3).unwrap();
+        assert_eq!((robust.secret, robust.faulty_ids.clone()), (Fq::from(42), vec![1, 5]));
+
+        shares[6].y += Fq::from(3);
+        assert_eq!(
+            reconstruct_secret_robust(&shares, 25, 3),
+            Err(SecretSharingError::TooManyFaultyShares { correctable: 2 })
+        );
+        assert_eq!(
+            reconstruct_secret_robust(&shares[..2], 25, 3),
+            Err(SecretSharingError::InsufficientShares { threshold: 3, provided: 2 })
+        );
+    }
+
+    #[test]
+    fn test_seeded_rng_is_reproducible() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let shares = generate_shares_with_rng::<Fq, _>(42, 25, 3, 5, &mut StdRng::seed_from_u64(1)).unwrap();
+        let again = generate_shares_with_rng::<Fq, _>(42, 25, 3, 5, &mut StdRng::seed_from_u64(1)).unwrap();
+        let other = generate_shares_with_rng::<Fq, _>(42, 25, 3, 5, &mut StdRng::seed_from_u64(2)).unwrap();
+        assert_eq!(shares, again);
+        assert_ne!(shares, other);
+
+        let refreshed = refresh_shares_with_rng(&shares, 25, 3, &mut StdRng::seed_from_u64(3)).unwrap();
+        assert_eq!(refreshed, refresh_shares_with_rng(&shares, 25, 3, &mut StdRng::seed_from_u64(3)).unwrap());
+        assert_eq!(reconstruct_secret(&refreshed[1..4], 25, 3), Ok(Fq::from(42)));
+    }
+
+    fn assert_zeroize_on_drop<T: Zeroize + ZeroizeOnDrop>() {}
+
+    #[test]
+    fn test_shares_are_zeroized() {
+        assert_zeroize_on_drop::<Point<Fq>>();
+        assert_zeroize_on_drop::<ByteShare<Fq>>();
+        assert_zeroize_on_drop::<crate::share::Share<Fq>>();
+        assert_zeroize_on_drop::<crate::vss::PedersenShare<Fq>>();
+        assert_zeroize_on_drop::<crate::dkg::PrivateShare<Fq>>();
+        assert_zeroize_on_drop::<crate::vss::FeldmanDealing<ark_bn254::G1Projective>>();
+        assert_zeroize_on_drop::<crate::vss::PedersenDealing<ark_bn254::G1Projective>>();
+        assert_zeroize_on_drop::<crate::dkg::DkgOutput<ark_bn254::G1Projective>>();
+
+        let mut share = generate_shares::<Fq>(42, 25, 2, 3).unwrap()[0].clone();
+        share.zeroize();
+        assert_eq!(share, Point { id: 0, x: Fq::from(0u64), y: Fq::from(0u64) });
+
+        let mut byte_share = generate_byte_shares::<Fq>(b"key", 25, 2, 3).unwrap()[0].clone();
+        byte_share.zeroize();
+        assert!(byte_share.ys.is_empty());
+    }
+
+    #[test]
+    fn test_byte_secret_round_trip() {
+        let key: Vec<u8> = (0..32u8).map(|i| i.wrapping_mul(37)).collect();
+        let shares = generate_byte_shares::<Fq>(&key, 25, 3, 5).unwrap();
+        assert_eq!(reconstruct_bytes(&shares[2..5], 25, 3), Ok(key));
+
+        // trailing zeros survive thanks to the length prefix
+        let blob: Vec<u8> = (0..1024u32).map(|i| if i < 1000 { (i * 7 % 256) as u8 } else { 0 }).collect();
+        let shares = generate_byte_shares::<Fq>(&blob, 25, 4, 6).unwrap();
+        assert_eq!(reconstruct_bytes(&shares[1..5], 25, 4), Ok(blob.clone()));
+        assert_eq!(reconstruct_bytes(&shares[..3], 25, 4), Err(SecretSharingError::InsufficientShares { threshold: 4, provided: 3 }));
+
+        // the wrong password interpolates to garbage, which is not a canonical encoding
+        assert_eq!(reconstruct_bytes(&shares[1..5], 26, 4), Err(SecretSharingError::InvalidEncoding));
+
+        let mut truncated = shares[1..5].to_vec();
+        truncated[2].ys.pop();
+        assert_eq!(reconstruct_bytes(&truncated, 25, 4), Err(SecretSharingError::MismatchedShares));
+
+        let empty = generate_byte_shares::<Fq>(&[], 25, 2, 3).unwrap();
+        assert_eq!(reconstruct_bytes(&empty, 25, 2), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_byte_encoding_is_canonical() {
+        let bytes: Vec<u8> = (0..70u8).collect();
+        let encoded = encode_bytes::<Fq>(&bytes);
+        assert_eq!(encoded.len(), 1 + 70usize.div_ceil(bytes_per_element::<Fq>()));
+        assert_eq!(decode_bytes(&encoded), Ok(bytes));
+
+        let mut wrong_length = encoded.clone();
+        wrong_length[0] = Fq::from(100u64);
+        assert_eq!(decode_bytes(&wrong_length), Err(SecretSharingError::InvalidEncoding));
+
+        let mut overflowing = encoded;
+        overflowing[1] = -Fq::from(1u64);
+        assert_eq!(decode_bytes(&overflowing), Err(SecretSharingError::InvalidEncoding));
+    }
+
+    #[test]
+    fn test_invalid_threshold() {
+        assert_eq!(generate_shares::<Fq>(42, 15, 0, 5), Err(SecretSharingError::ZeroThreshold));
+        assert_eq!(
+            generate_byte_shares::<Fq>(b"key", 15, 6, 5),
+            Err(SecretSharingError::ThresholdAboveTotal { threshold: 6, total_shares: 5 })
+        );
+        assert_eq!(reconstruct_secret::<Fq>(&[], 15, 0), Err(SecretSharingError::ZeroThreshold));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_any_quorum_reconstructs_the_secret((set, quorum) in share_set_and_quorum::<Fq>(8)) {
+            prop_assert_eq!(reconstruct_secret(&quorum, set.password, set.threshold), Ok(set.secret));
+            prop_assert_eq!(reconstruct_secret_constant_time(&quorum, set.password, set.threshold), Ok(set.secret));
+
+            // one share short of the threshold is refused
+            prop_assert_eq!(
+                reconstruct_secret(&quorum[1..], set.password, set.threshold),
+                Err(SecretSharingError::InsufficientShares { threshold: set.threshold, provided: set.threshold - 1 })
+            );
+
+            // a tampered share reconstructs something else
+            let mut tampered = quorum.clone();
+            tampered[0].y += Fq::from(1);
+            prop_assert_ne!(reconstruct_secret(&tampered, set.password, set.threshold), Ok(set.secret));
+        }
+    }
+}
@@ -0,0 +1,427 @@
+use crate::error::SecretSharingError;
+use crate::shamir::{generate_field_shares_with_rng, reconstruct_secret, ByteShare, Point};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::RngCore;
+use serde::de::Error as _;
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Sha3_256};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub const SHARE_FORMAT_VERSION: u8 = 1;
+// The format of shares bound to a context, with the context tag after the threshold
+pub const CONTEXT_SHARE_FORMAT_VERSION: u8 = 2;
+
+// A share as it leaves the process: the point together with the threshold needed to recombine it, and optionally the
+// tag binding it to the context it was dealt under, see context_tag.
+//
+// to_bytes writes version 1 of the format,
+//   version (1 byte) | id (u32 le) | threshold (u32 le) | x (compressed field element) | y (compressed field element)
+// which for a 254 bit field is 73 bytes, or for a share with a context version 2,
+//   version (1 byte) | id (u32 le) | threshold (u32 le) | context tag (32 bytes) | x | y
+// serde goes through the same bytes, so a share printed as JSON or stored in a file and one read back from to_bytes
+// agree
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize, Zeroize, ZeroizeOnDrop)]
+pub struct Share<F: PrimeField> {
+    pub threshold: usize,
+    pub point: Point<F>,
+    pub tag: Option<[u8; 32]>,
+}
+
+impl <F: PrimeField>Share<F> {
+    pub fn new(point: Point<F>, threshold: usize) -> Self {
+        Self { threshold, point, tag: None }
+    }
+
+    // Binds the share to a context, e.g. an application id and a key epoch, which reconstruct_shares checks
+    pub fn with_context(mut self, context: &[u8]) -> Self {
+        self.tag = Some(context_tag(context, self.point.id, self.threshold, self.point.x, &[self.point.y]));
+        self
+    }
+
+    // Whether the share was bound to context, or to none when context is None, and has not changed since
+    pub fn matches_context(&self, context: Option<&[u8]>) -> bool {
+        self.tag == context.map(|context| context_tag(context, self.point.id, self.threshold, self.point.x, &[self.point.y]))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SecretSharingError> {
+        let mut bytes = header(self.point.id, self.threshold, &self.tag)?;
+        write_element(&mut bytes, &self.point.x)?;
+        write_element(&mut bytes, &self.point.y)?;
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SecretSharingError> {
+        let Header { id, threshold, tag, mut rest } = read_header(bytes)?;
+        let x = F::deserialize_compressed(&mut rest).map_err(|_| SecretSharingError::InvalidShareEncoding)?;
+        let y = F::deserialize_compressed(&mut rest).map_err(|_| SecretSharingError::InvalidShareEncoding)?;
+
+        if !rest.is_empty() {
+            return Err(SecretSharingError::InvalidShareEncoding);
+        }
+
+        Ok(Self { threshold, point: Point { id, x, y }, tag })
+    }
+}
+
+// The 32 bytes a share stores for its context: a hash of the context together with everything else in the share, its
+// id, threshold, x and y values. The tag of one share does not fit another share of the same dealing, and a share
+// whose values were changed after it was bound no longer matches its context. Anyone holding the share can still
+// recompute the tag, so it binds the share to the context against mistakes and mixing, not against its holder
+pub fn context_tag<F: PrimeField>(context: &[u8], id: usize, threshold: usize, x: F, ys: &[F]) -> [u8; 32] {
+    let mut values = Vec::new();
+    x.serialize_compressed(&mut values).expect("serializing into a Vec cannot fail");
+    for y in ys {
+        y.serialize_compressed(&mut values).expect("serializing into a Vec cannot fail");
+    }
+
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"shamir share context");
+    hasher.update((context.len() as u64).to_le_bytes());
+    hasher.update(context);
+    hasher.update((id as u64).to_le_bytes());
+    hasher.update((threshold as u64).to_le_bytes());
+    hasher.update(&values);
+    values.zeroize();
+    hasher.finalize().into()
+}
+
+// Function to deal shares ready to leave the process, each carrying the threshold and, given a context, its tag
+pub fn deal_shares<F: PrimeField, R: RngCore>(
+    secret: F,
+    password: i32,
+    threshold: usize,
+    total_shares: usize,
+    context: Option<&[u8]>,
+    rng: &mut R,
+) -> Result<Vec<Share<F>>, SecretSharingError> {
+    let points = generate_field_shares_with_rng(secret, password, threshold, total_shares, rng)?;
+
+    Ok(points
+        .into_iter()
+        .map(|point| match context {
+            Some(context) => Share::new(point, threshold).with_context(context),
+            None => Share::new(point, threshold),
+        })
+        .collect())
+}
+
+// Function to reconstruct the secret from serialized shares.
+// Every share must carry the threshold of the first and match context, or have no tag when context is None, so
+// shares of different dealings fail with MismatchedShares or ContextMismatch instead of interpolating to garbage
+pub fn reconstruct_shares<F: PrimeField>(
+    shares: &[Share<F>],
+    password: i32,
+    context: Option<&[u8]>,
+) -> Result<F, SecretSharingError> {
+    let first = shares.first().ok_or(SecretSharingError::InsufficientShares { threshold: 1, provided: 0 })?;
+    if shares.iter().any(|share| share.threshold != first.threshold) {
+        return Err(SecretSharingError::MismatchedShares);
+    }
+    if !shares.iter().all(|share| share.matches_context(context)) {
+        return Err(SecretSharingError::ContextMismatch);
+    }
+
+    let points: Vec<Point<F>> = shares.iter().map(|share| share.point.clone()).collect();
+    reconstruct_secret(&points, password, first.threshold)
+}
+
+// version | id | threshold, then the context tag for version 2
+fn header(id: usize, threshold: usize, tag: &Option<[u8; 32]>) -> Result<Vec<u8>, SecretSharingError> {
+    let mut bytes = vec![if tag.is_some() { CONTEXT_SHARE_FORMAT_VERSION } else { SHARE_FORMAT_VERSION }];
+    write_u32(&mut bytes, id)?;
+    write_u32(&mut bytes, threshold)?;
+    if let Some(tag) = tag {
+        bytes.extend(tag);
+    }
+    Ok(bytes)
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: usize) -> Result<(), SecretSharingError> {
+    let value = u32::try_from(value).map_err(|_| SecretSharingError::UnencodableShare)?;
+    bytes.extend(value.to_le_bytes());
+    Ok(())
+}
+
+fn write_element<F: PrimeField>(bytes: &mut Vec<u8>, element: &F) -> Result<(), SecretSharingError> {
+    element.serialize_compressed(bytes).map_err(|_| SecretSharingError::UnencodableShare)
+}
+
+// The fields every share format starts with, and the bytes after them
+struct Header<'a> {
+    id: usize,
+    threshold: usize,
+    tag: Option<[u8; 32]>,
+    rest: &'a [u8],
+}
+
+fn read_header(bytes: &[u8]) -> Result<Header<'_>, SecretSharingError> {
+    let (version, mut rest) = bytes.split_first().ok_or(SecretSharingError::InvalidShareEncoding)?;
+    if *version != SHARE_FORMAT_VERSION && *version != CONTEXT_SHARE_FORMAT_VERSION {
+        return Err(SecretSharingError::UnsupportedVersion { version: *version });
+    }
+
+    let id = read_u32(&mut rest)? as usize;
+    let threshold = read_u32(&mut rest)? as usize;
+    let tag = if *version == CONTEXT_SHARE_FORMAT_VERSION {
+        if rest.len() < 32 {
+            return Err(SecretSharingError::InvalidShareEncoding);
+        }
+        let (tag, remaining) = rest.split_at(32);
+        rest = remaining;
+        Some(tag.try_into().expect("split at 32 bytes"))
+    } else {
+        None
+    };
+
+    Ok(Header { id, threshold, tag, rest })
+}
+
+fn read_u32(bytes: &mut &[u8]) -> Result<u32, SecretSharingError> {
+    if bytes.len() < 4 {
+        return Err(SecretSharingError::InvalidShareEncoding);
+    }
+    let (value, rest) = bytes.split_at(4);
+    *bytes = rest;
+    Ok(u32::from_le_bytes([value[0], value[1], value[2], value[3]]))
+}
+
+// A share of a byte secret with its threshold and context, in the same formats followed by the y values,
+//   version | id (u32 le) | threshold (u32 le) | context tag (version 2 only) | x | number of ys (u32 le) | ys
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct SecretBytesShare<F: PrimeField> {
+    pub threshold: usize,
+    pub share: ByteShare<F>,
+    pub tag: Option<[u8; 32]>,
+}
+
+impl <F: PrimeField>SecretBytesShare<F> {
+    pub fn new(share: ByteShare<F>, threshold: usize) -> Self {
+        Self { threshold, share, tag: None }
+    }
+
+    pub fn with_context(mut self, context: &[u8]) -> Self {
+        self.tag = Some(context_tag(context, self.share.id, self.threshold, self.share.x, &self.share.ys));
+        self
+    }
+
+    pub fn matches_context(&self, context: Option<&[u8]>) -> bool {
+        self.tag == context.map(|context| context_tag(context, self.share.id, self.threshold, self.share.x, &self.share.ys))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SecretSharingError> {
+        let mut bytes = header(self.share.id, self.threshold, &self.tag)?;
+        write_element(&mut bytes, &self.share.x)?;
+        write_u32(&mut bytes, self.share.ys.len())?;
+        for y in &self.share.ys {
+            write_element(&mut bytes, y)?;
+        }
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SecretSharingError> {
+        let Header { id, threshold, tag, mut rest } = read_header(bytes)?;
+        let x = F::deserialize_compressed(&mut rest).map_err(|_| SecretSharingError::InvalidShareEncoding)?;
+        let no_of_ys = read_u32(&mut rest)? as usize;
+
+        // every y takes at least one byte, a count beyond the input would only allocate
+        if no_of_ys > rest.len() {
+            return Err(SecretSharingError::InvalidShareEncoding);
+        }
+        let ys = (0..no_of_ys)
+            .map(|_| F::deserialize_compressed(&mut rest).map_err(|_| SecretSharingError::InvalidShareEncoding))
+            .collect::<Result<Vec<F>, SecretSharingError>>()?;
+
+        if !rest.is_empty() {
+            return Err(SecretSharingError::InvalidShareEncoding);
+        }
+
+        Ok(Self { threshold, share: ByteShare { id, x, ys }, tag })
+    }
+}
+
+// Lower case hex, the text form of the share bytes for printing or files
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>, SecretSharingError> {
+    let hex = hex.trim();
+    // only hex digits: from_str_radix alone would also take a sign, reading "+f" as 0f
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(SecretSharingError::InvalidShareEncoding);
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| SecretSharingError::InvalidShareEncoding))
+        .collect()
+}
+
+impl <F: PrimeField>Serialize for Share<F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes().map_err(S::Error::custom)?)
+    }
+}
+
+impl <'de, F: PrimeField>Deserialize<'de> for Share<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Share::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shamir::{generate_byte_shares, generate_shares, reconstruct_bytes, reconstruct_secret};
+    use ark_bn254::Fq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_share_bytes_round_trip() {
+        let shares: Vec<Share<Fq>> = generate_shares::<Fq>(42, 25, 3, 5)
+            .unwrap()
+            .into_iter()
+            .map(|point| Share::new(point, 3))
+            .collect();
+
+        let bytes = shares[4].to_bytes().unwrap();
+        assert_eq!(bytes.len(), 73);
+        assert_eq!(bytes[0], SHARE_FORMAT_VERSION);
+        assert_eq!(Share::from_bytes(&bytes), Ok(shares[4].clone()));
+
+        let decoded: Vec<Share<Fq>> = shares.iter().map(|share| Share::from_bytes(&share.to_bytes().unwrap()).unwrap()).collect();
+        let points: Vec<Point<Fq>> = decoded[1..4].iter().map(|share| share.point.clone()).collect();
+        assert_eq!(reconstruct_secret(&points, 25, decoded[0].threshold), Ok(Fq::from(42)));
+    }
+
+    #[test]
+    fn test_malformed_share_bytes() {
+        let share = Share::new(generate_shares::<Fq>(7, 0, 2, 3).unwrap()[0].clone(), 2);
+        let bytes = share.to_bytes().unwrap();
+
+        let mut future = bytes.clone();
+        future[0] = 3;
+        assert_eq!(Share::<Fq>::from_bytes(&future), Err(SecretSharingError::UnsupportedVersion { version: 3 }));
+
+        assert_eq!(Share::<Fq>::from_bytes(&bytes[..40]), Err(SecretSharingError::InvalidShareEncoding));
+        assert_eq!(Share::<Fq>::from_bytes(&[bytes.clone(), vec![0]].concat()), Err(SecretSharingError::InvalidShareEncoding));
+        assert_eq!(Share::<Fq>::from_bytes(&[]), Err(SecretSharingError::InvalidShareEncoding));
+
+        // a y with every bit set is above the modulus, not a canonical field element
+        let mut non_canonical = bytes;
+        non_canonical[41..].fill(0xff);
+        assert_eq!(Share::<Fq>::from_bytes(&non_canonical), Err(SecretSharingError::InvalidShareEncoding));
+    }
+
+    #[test]
+    fn test_shares_outside_the_format_are_errors() {
+        let mut share = Share::new(generate_shares::<Fq>(7, 0, 2, 3).unwrap()[0].clone(), 2);
+        share.threshold = 1 << 32;
+        assert_eq!(share.to_bytes(), Err(SecretSharingError::UnencodableShare));
+        assert!(serde_json::to_string(&share).is_err());
+    }
+
+    #[test]
+    fn test_byte_share_file_round_trip() {
+        let shares = generate_byte_shares::<Fq>(b"a secret longer than one field element", 0, 2, 3).unwrap();
+        let files: Vec<String> = shares
+            .iter()
+            .map(|share| encode_hex(&SecretBytesShare::new(share.clone(), 2).to_bytes().unwrap()))
+            .collect();
+
+        let decoded: Vec<ByteShare<Fq>> = files[1..]
+            .iter()
+            .map(|file| SecretBytesShare::from_bytes(&decode_hex(file).unwrap()).unwrap().share.clone())
+            .collect();
+        assert_eq!(decoded, shares[1..].to_vec());
+        assert_eq!(reconstruct_bytes(&decoded, 0, 2), Ok(b"a secret longer than one field element".to_vec()));
+
+        let bytes = decode_hex(&files[0]).unwrap();
+        assert_eq!(SecretBytesShare::<Fq>::from_bytes(&bytes[..bytes.len() - 1]), Err(SecretSharingError::InvalidShareEncoding));
+        assert_eq!(decode_hex("abc"), Err(SecretSharingError::InvalidShareEncoding));
+        assert_eq!(decode_hex("zz"), Err(SecretSharingError::InvalidShareEncoding));
+        assert_eq!(decode_hex("+d"), Err(SecretSharingError::InvalidShareEncoding));
+        assert_eq!(decode_hex(" 00ff\n"), Ok(vec![0, 255]));
+    }
+
+    #[test]
+    fn test_shares_bound_to_a_context() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let epoch_1 = deal_shares::<Fq, _>(Fq::from(42), 0, 2, 3, Some(b"app/epoch-1"), &mut rng).unwrap();
+        let epoch_2 = deal_shares::<Fq, _>(Fq::from(43), 0, 2, 3, Some(b"app/epoch-2"), &mut rng).unwrap();
+
+        let decoded: Vec<Share<Fq>> = epoch_1.iter().map(|share| Share::from_bytes(&share.to_bytes().unwrap()).unwrap()).collect();
+        assert_eq!(decoded, epoch_1);
+        assert_eq!(epoch_1[0].to_bytes().unwrap()[0], CONTEXT_SHARE_FORMAT_VERSION);
+        assert_eq!(epoch_1[0].to_bytes().unwrap().len(), 73 + 32);
+        assert_eq!(reconstruct_shares(&decoded[1..], 0, Some(b"app/epoch-1")), Ok(Fq::from(42)));
+
+        // shares of two dealings, or combined under another context or none, are refused
+        let mixed = vec![epoch_1[0].clone(), epoch_2[1].clone()];
+        assert_eq!(reconstruct_shares(&mixed, 0, Some(b"app/epoch-1")), Err(SecretSharingError::ContextMismatch));
+        assert_eq!(reconstruct_shares(&epoch_1, 0, Some(b"app/epoch-2")), Err(SecretSharingError::ContextMismatch));
+        assert_eq!(reconstruct_shares(&epoch_1, 0, None), Err(SecretSharingError::ContextMismatch));
+        let plain = Share::new(epoch_1[0].point.clone(), 2);
+        assert_eq!(reconstruct_shares(&[plain, epoch_1[1].clone()], 0, Some(b"app/epoch-1")), Err(SecretSharingError::ContextMismatch));
+
+        // the tag of one share does not fit another, nor a share whose value changed after it was bound
+        let mut moved = epoch_1[1].clone();
+        moved.tag = epoch_1[0].tag;
+        assert!(!moved.matches_context(Some(b"app/epoch-1")));
+        let mut edited = epoch_1[1].clone();
+        edited.point.y += Fq::from(1);
+        assert_eq!(reconstruct_shares(&[epoch_1[0].clone(), edited], 0, Some(b"app/epoch-1")), Err(SecretSharingError::ContextMismatch));
+
+        // a truncated context tag is not a share
+        assert_eq!(Share::<Fq>::from_bytes(&epoch_1[0].to_bytes().unwrap()[..20]), Err(SecretSharingError::InvalidShareEncoding));
+    }
+
+    #[test]
+    fn test_byte_shares_carry_their_context() {
+        let shares = generate_byte_shares::<Fq>(b"key material", 0, 2, 2).unwrap();
+        let share = SecretBytesShare::new(shares[0].clone(), 2).with_context(b"app/epoch-1");
+
+        let decoded = SecretBytesShare::<Fq>::from_bytes(&share.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, share);
+        assert!(decoded.matches_context(Some(b"app/epoch-1")));
+        assert!(!decoded.matches_context(Some(b"app/epoch-2")));
+        assert!(!decoded.matches_context(None));
+        assert_eq!(SecretBytesShare::new(shares[1].clone(), 2).to_bytes().unwrap()[0], SHARE_FORMAT_VERSION);
+
+        // every y is in the tag
+        let mut edited = share.clone();
+        edited.share.ys[0] += Fq::from(1);
+        assert!(!edited.matches_context(Some(b"app/epoch-1")));
+    }
+
+    #[test]
+    fn test_serde_with_a_context() {
+        let share = Share::new(generate_shares::<Fq>(9, 0, 2, 2).unwrap()[1].clone(), 2).with_context(b"app");
+
+        let json = serde_json::to_string(&share).unwrap();
+        let decoded = serde_json::from_str::<Share<Fq>>(&json).unwrap();
+        assert_eq!(decoded, share);
+        assert!(decoded.matches_context(Some(b"app")));
+
+        let mut bytes = Vec::new();
+        share.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(Share::<Fq>::deserialize_compressed(&bytes[..]).unwrap(), share);
+    }
+
+    #[test]
+    fn test_serde_and_canonical_serialize() {
+        let share = Share::new(generate_shares::<Fq>(9, 0, 2, 2).unwrap()[1].clone(), 2);
+
+        let json = serde_json::to_string(&share).unwrap();
+        assert_eq!(serde_json::from_str::<Share<Fq>>(&json).unwrap(), share);
+
+        let mut bytes = Vec::new();
+        share.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(Share::<Fq>::deserialize_compressed(&bytes[..]).unwrap(), share);
+    }
+}
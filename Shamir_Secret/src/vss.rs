@@ -0,0 +1,176 @@
+use crate::error::SecretSharingError;
+use crate::shamir::{check_parameters, share_x, Point};
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use polynomial::univariate::UnivariatePoly;
+use rand::RngCore;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Feldman VSS: the secret is f(0) of a random polynomial f of degree threshold - 1, and the dealer publishes
+// C_j = a_j*G for every coefficient a_j. A share (x, y) is consistent when y*G == sum over j of x^j * C_j,
+// i.e. f(x) computed "in the exponent". C_0 = secret*G is public, so the secret is only computationally hidden
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop)]
+pub struct FeldmanDealing<G: CurveGroup> {
+    pub shares: Vec<Point<G::ScalarField>>,
+    // public, nothing to wipe
+    #[zeroize(skip)]
+    pub commitments: Vec<G>,
+}
+
+// Pedersen VSS: a second random polynomial g blinds the commitments, C_j = a_j*G + b_j*H, and every share carries
+// g(x) as well. The commitments hide the secret perfectly, and binding relies on nobody knowing log_G(H)
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop)]
+pub struct PedersenDealing<G: CurveGroup> {
+    pub shares: Vec<PedersenShare<G::ScalarField>>,
+    #[zeroize(skip)]
+    pub commitments: Vec<G>,
+}
+
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct PedersenShare<F: PrimeField> {
+    pub id: usize,
+    pub x: F,
+    pub y: F,
+    pub blinding: F,
+}
+
+// Like generate_shares, share id i is evaluated at x = i so the x coordinates never collide
+pub fn feldman_deal<G: CurveGroup, R: RngCore>(
+    secret: G::ScalarField,
+    threshold: usize,
+    total_shares: usize,
+    rng: &mut R,
+) -> Result<FeldmanDealing<G>, SecretSharingError> {
+    let mut polynomial = random_polynomial(secret, threshold, total_shares, rng)?;
+
+    let shares = (1..=total_shares)
+        .map(|id| {
+            let x = share_x(id);
+            Point { id, x, y: polynomial.evaluate(x) }
+        })
+        .collect();
+    let commitments = polynomial.coefficients.iter().map(|coefficient| G::generator() * coefficient).collect();
+    polynomial.coefficients.zeroize();
+
+    Ok(FeldmanDealing { shares, commitments })
+}
+
+pub fn verify_share<G: CurveGroup>(share: &Point<G::ScalarField>, commitments: &[G]) -> bool {
+    G::generator() * share.y == evaluate_in_exponent(commitments, share.x)
+}
+
+pub fn pedersen_deal<G: CurveGroup, R: RngCore>(
+    secret: G::ScalarField,
+    h: G,
+    threshold: usize,
+    total_shares: usize,
+    rng: &mut R,
+) -> Result<PedersenDealing<G>, SecretSharingError> {
+    let mut polynomial = random_polynomial(secret, threshold, total_shares, rng)?;
+    let mut blinding_polynomial = random_polynomial(G::ScalarField::rand(rng), threshold, total_shares, rng)?;
+
+    let shares = (1..=total_shares)
+        .map(|id| {
+            let x = share_x(id);
+            PedersenShare { id, x, y: polynomial.evaluate(x), blinding: blinding_polynomial.evaluate(x) }
+        })
+        .collect();
+    let commitments = polynomial
+        .coefficients
+        .iter()
+        .zip(blinding_polynomial.coefficients.iter())
+        .map(|(a, b)| G::generator() * a + h * b)
+        .collect();
+    polynomial.coefficients.zeroize();
+    blinding_polynomial.coefficients.zeroize();
+
+    Ok(PedersenDealing { shares, commitments })
+}
+
+pub fn verify_pedersen_share<G: CurveGroup>(share: &PedersenShare<G::ScalarField>, h: G, commitments: &[G]) -> bool {
+    G::generator() * share.y + h * share.blinding == evaluate_in_exponent(commitments, share.x)
+}
+
+// sum over j of x^j * C_j, Horner's rule on the commitments
+pub fn evaluate_in_exponent<G: CurveGroup>(commitments: &[G], x: G::ScalarField) -> G {
+    commitments.iter().rev().fold(G::zero(), |accumulator, commitment| accumulator * x + commitment)
+}
+
+// f(0) = secret and threshold - 1 random coefficients. The coefficients are sampled directly, which gives the same
+// distribution as interpolating random points the way generate_shares does. The secret sits at x = 0, which is never
+// a share's x, so the threshold is checked the way generate_shares checks it with a password of 0
+fn random_polynomial<F: PrimeField, R: RngCore>(
+    secret: F,
+    threshold: usize,
+    total_shares: usize,
+    rng: &mut R,
+) -> Result<UnivariatePoly<F>, SecretSharingError> {
+    check_parameters(0, threshold, total_shares)?;
+
+    let mut coefficients = vec![secret];
+    coefficients.extend((1..threshold).map(|_| F::rand(rng)));
+
+    Ok(UnivariatePoly::new(coefficients))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shamir::reconstruct_secret;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_ec::PrimeGroup;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_feldman_shares_verify_and_reconstruct() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let dealing = feldman_deal::<G1Projective, _>(Fr::from(42), 3, 5, rng).unwrap();
+
+        assert!(dealing.shares.iter().all(|share| verify_share(share, &dealing.commitments)));
+        assert_eq!(dealing.commitments[0], G1Projective::generator() * Fr::from(42));
+
+        // the secret sits at x = 0, the "password" of reconstruct_secret
+        assert_eq!(reconstruct_secret(&dealing.shares[2..5], 0, 3), Ok(Fr::from(42)));
+    }
+
+    #[test]
+    fn test_feldman_detects_a_malicious_dealer() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let mut dealing = feldman_deal::<G1Projective, _>(Fr::from(42), 3, 5, rng).unwrap();
+
+        // the dealer hands one shareholder a point off the committed polynomial
+        dealing.shares[1].y += Fr::from(1);
+
+        let valid: Vec<bool> = dealing.shares.iter().map(|share| verify_share(share, &dealing.commitments)).collect();
+        assert_eq!(valid, vec![true, false, true, true, true]);
+    }
+
+    #[test]
+    fn test_pedersen_vss() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let h = G1Projective::rand(rng);
+        let mut dealing = pedersen_deal::<G1Projective, _>(Fr::from(7), h, 2, 4, rng).unwrap();
+
+        assert!(dealing.shares.iter().all(|share| verify_pedersen_share(share, h, &dealing.commitments)));
+
+        dealing.shares[0].blinding += Fr::from(1);
+        assert!(!verify_pedersen_share(&dealing.shares[0], h, &dealing.commitments));
+
+        let shares: Vec<Point<Fr>> = dealing.shares[1..3].iter().map(|share| Point { id: share.id, x: share.x, y: share.y }).collect();
+        assert_eq!(reconstruct_secret(&shares, 0, 2), Ok(Fr::from(7)));
+    }
+
+    #[test]
+    fn test_dealing_checks_the_threshold() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let h = G1Projective::rand(rng);
+
+        assert_eq!(feldman_deal::<G1Projective, _>(Fr::from(42), 0, 5, rng).unwrap_err(), SecretSharingError::ZeroThreshold);
+        assert_eq!(
+            pedersen_deal::<G1Projective, _>(Fr::from(7), h, 5, 4, rng).unwrap_err(),
+            SecretSharingError::ThresholdAboveTotal { threshold: 5, total_shares: 4 }
+        );
+    }
+}
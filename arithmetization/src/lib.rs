@@ -0,0 +1,3 @@
+pub mod r1cs;
+pub mod qap;
+pub mod plonkish;
@@ -0,0 +1,397 @@
+use ark_ff::PrimeField;
+
+// PLONK-style arithmetization.
+//
+// The trace has three wire columns a, b, c and one row per gate. Row i satisfies the gate equation
+// q_L[i]*a[i] + q_R[i]*b[i] + q_O[i]*c[i] + q_M[i]*a[i]*b[i] + q_C[i] = 0
+// so the selectors choose the gate type: an addition gate is q_L = q_R = 1, q_O = -1, a multiplication gate
+// is q_M = 1, q_O = -1, and so on.
+//
+// Gates only see their own row, so wiring is expressed with copy constraints: cells that must hold the same value.
+// The copy constraints are encoded as a permutation sigma of the 3n cells that maps every cell to the next one
+// in its cycle of equal cells, and the trace is wired correctly exactly when trace[sigma(i)] = trace[i] for every cell.
+//
+// Lookup cells must hold a value of the lookup table, e.g. 0..2^k for range checks. They are collected into a single
+// column that a lookup argument proves is contained in the table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Selectors<F: PrimeField> {
+    pub q_l: F,
+    pub q_r: F,
+    pub q_o: F,
+    pub q_m: F,
+    pub q_c: F,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Column {
+    A,
+    B,
+    C,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cell {
+    pub column: Column,
+    pub row: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlonkishCircuit<F: PrimeField> {
+    pub gates: Vec<Selectors<F>>,
+    pub copy_constraints: Vec<(Cell, Cell)>,
+    pub lookups: Vec<Cell>,
+    pub lookup_table: Vec<F>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Trace<F: PrimeField> {
+    pub a: Vec<F>,
+    pub b: Vec<F>,
+    pub c: Vec<F>,
+}
+
+impl <F: PrimeField>Selectors<F> {
+    // a + b = c
+    pub fn addition() -> Self {
+        Self { q_l: F::one(), q_r: F::one(), q_o: -F::one(), q_m: F::zero(), q_c: F::zero() }
+    }
+
+    // a * b = c
+    pub fn multiplication() -> Self {
+        Self { q_l: F::zero(), q_r: F::zero(), q_o: -F::one(), q_m: F::one(), q_c: F::zero() }
+    }
+
+    // a = value
+    pub fn constant(value: F) -> Self {
+        Self { q_l: F::one(), q_r: F::zero(), q_o: F::zero(), q_m: F::zero(), q_c: -value }
+    }
+
+    pub fn evaluate(&self, a: F, b: F, c: F) -> F {
+        self.q_l * a + self.q_r * b + self.q_o * c + self.q_m * a * b + self.q_c
+    }
+}
+
+impl Cell {
+    pub fn new(column: Column, row: usize) -> Self {
+        Self { column, row }
+    }
+
+    // Position of the cell when the columns are laid out one after the other: a, then b, then c
+    pub fn index(&self, no_of_rows: usize) -> usize {
+        let column = match self.column {
+            Column::A => 0,
+            Column::B => 1,
+            Column::C => 2,
+        };
+        column * no_of_rows + self.row
+    }
+}
+
+impl <F: PrimeField>PlonkishCircuit<F> {
+    pub fn new() -> Self {
+        Self { gates: Vec::new(), copy_constraints: Vec::new(), lookups: Vec::new(), lookup_table: Vec::new() }
+    }
+
+    pub fn no_of_rows(&self) -> usize {
+        self.gates.len()
+    }
+
+    // Adds a gate and returns its row
+    pub fn add_gate(&mut self, selectors: Selectors<F>) -> usize {
+        self.gates.push(selectors);
+        self.gates.len() - 1
+    }
+
+    pub fn copy(&mut self, left: Cell, right: Cell) {
+        self.copy_constraints.push((left, right));
+    }
+
+    pub fn add_lookup(&mut self, cell: Cell) {
+        self.lookups.push(cell);
+    }
+
+    // The values of the lookup cells, padded to a power of two length with the first table entry
+    // so the padding is always in the table
+    pub fn lookup_column(&self, trace: &Trace<F>) -> Vec<F> {
+        let cells = trace.cells();
+        let mut column: Vec<F> = self.lookups.iter().map(|cell| cells[cell.index(self.no_of_rows())]).collect();
+
+        let padding = self.lookup_table.first().copied().unwrap_or(F::zero());
+        column.resize(column.len().next_power_of_two(), padding);
+        column
+    }
+
+    // sigma over the 3n cell indices. Cells connected by copy constraints (directly or through other cells)
+    // form one cycle, every other cell is a fixed point
+    pub fn permutation(&self) -> Vec<usize> {
+        let no_of_rows = self.no_of_rows();
+        let no_of_cells = 3 * no_of_rows;
+
+        // union-find over the cells, each class is a set of cells that must be equal
+        let mut parent: Vec<usize> = (0..no_of_cells).collect();
+        fn find(parent: &mut [usize], cell: usize) -> usize {
+            let mut root = cell;
+            while parent[root] != root {
+                root = parent[root];
+            }
+            parent[cell] = root;
+            root
+        }
+
+        for (left, right) in &self.copy_constraints {
+            let left_root = find(&mut parent, left.index(no_of_rows));
+            let right_root = find(&mut parent, right.index(no_of_rows));
+            parent[left_root] = right_root;
+        }
+
+        let mut classes: Vec<Vec<usize>> = vec![Vec::new(); no_of_cells];
+        for cell in 0..no_of_cells {
+            let root = find(&mut parent, cell);
+            classes[root].push(cell);
+        }
+
+        let mut sigma: Vec<usize> = (0..no_of_cells).collect();
+        for class in classes.iter().filter(|class| class.len() > 1) {
+            for (i, cell) in class.iter().enumerate() {
+                sigma[*cell] = class[(i + 1) % class.len()];
+            }
+        }
+
+        sigma
+    }
+
+    // Index of the first row whose gate equation fails, None if all of them hold
+    pub fn first_unsatisfied_gate(&self, trace: &Trace<F>) -> Option<usize> {
+        (0..self.no_of_rows()).find(|row| !self.gates[*row].evaluate(trace.a[*row], trace.b[*row], trace.c[*row]).is_zero())
+    }
+
+    pub fn is_satisfied(&self, trace: &Trace<F>) -> bool {
+        let no_of_rows = self.no_of_rows();
+        if trace.a.len() != no_of_rows || trace.b.len() != no_of_rows || trace.c.len() != no_of_rows {
+            return false;
+        }
+
+        let cells = trace.cells();
+        let wiring_holds = self.permutation().iter().enumerate().all(|(cell, sigma)| cells[cell] == cells[*sigma]);
+
+        let lookups_hold = self
+            .lookups
+            .iter()
+            .all(|cell| self.lookup_table.contains(&cells[cell.index(no_of_rows)]));
+
+        wiring_holds && lookups_hold && self.first_unsatisfied_gate(trace).is_none()
+    }
+}
+
+impl <F: PrimeField>Trace<F> {
+    pub fn new() -> Self {
+        Self { a: Vec::new(), b: Vec::new(), c: Vec::new() }
+    }
+
+    pub fn push_row(&mut self, a: F, b: F, c: F) {
+        self.a.push(a);
+        self.b.push(b);
+        self.c.push(c);
+    }
+
+    // The columns laid out one after the other, indexed like Cell::index
+    pub fn cells(&self) -> Vec<F> {
+        self.a.iter().chain(self.b.iter()).chain(self.c.iter()).copied().collect()
+    }
+}
+
+
+// The circuit and its trace as multilinear columns, for HyperPlonk-style provers over the boolean hypercube.
+//
+// Every column is the evaluation table of a multilinear polynomial over log n variables, with row i at the point whose
+// bits are i: the selectors q_l, q_r, q_o, q_m, q_c and the witness a, b, c. The rows are padded to a power of two with
+// all-zero selectors and witness, which satisfy the gate equation, so the trace is valid exactly when
+//   gate_constraint(q_l(x), q_r(x), q_o(x), q_m(x), q_c(x), a(x), b(x), c(x)) = 0
+// at every x of the hypercube. That is the zerocheck: with a random r it becomes the sumcheck of
+// eq(r, x) * gate_constraint(...) against 0, one degree above GATE_CONSTRAINT_DEGREE.
+//
+// The wiring is the permutation of the witness polynomial a || b || c || 0, the columns one after the other with a
+// fourth all-zero one to keep the length a power of two; its extra cells are fixed points
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultilinearColumns<F: PrimeField> {
+    pub q_l: Vec<F>,
+    pub q_r: Vec<F>,
+    pub q_o: Vec<F>,
+    pub q_m: Vec<F>,
+    pub q_c: Vec<F>,
+    pub a: Vec<F>,
+    pub b: Vec<F>,
+    pub c: Vec<F>,
+    pub no_of_vars: usize,
+}
+
+// The degree of gate_constraint in the column values, from q_m * a * b
+pub const GATE_CONSTRAINT_DEGREE: usize = 3;
+
+// The gate equation over the values of the columns at one point, in the order of MultilinearColumns::columns
+pub fn gate_constraint<F: PrimeField>(values: &[F; 8]) -> F {
+    let [q_l, q_r, q_o, q_m, q_c, a, b, c] = values;
+    Selectors { q_l: *q_l, q_r: *q_r, q_o: *q_o, q_m: *q_m, q_c: *q_c }.evaluate(*a, *b, *c)
+}
+
+impl <F: PrimeField>MultilinearColumns<F> {
+    // The columns of circuit and trace, None when the trace does not have one row per gate
+    pub fn new(circuit: &PlonkishCircuit<F>, trace: &Trace<F>) -> Option<Self> {
+        let no_of_rows = circuit.no_of_rows();
+        if trace.a.len() != no_of_rows || trace.b.len() != no_of_rows || trace.c.len() != no_of_rows {
+            return None;
+        }
+
+        let size = no_of_rows.next_power_of_two();
+        let column = |values: Vec<F>| {
+            let mut values = values;
+            values.resize(size, F::zero());
+            values
+        };
+        let selector = |select: fn(&Selectors<F>) -> F| column(circuit.gates.iter().map(select).collect());
+
+        Some(Self {
+            q_l: selector(|gate| gate.q_l),
+            q_r: selector(|gate| gate.q_r),
+            q_o: selector(|gate| gate.q_o),
+            q_m: selector(|gate| gate.q_m),
+            q_c: selector(|gate| gate.q_c),
+            a: column(trace.a.clone()),
+            b: column(trace.b.clone()),
+            c: column(trace.c.clone()),
+            no_of_vars: size.ilog2() as usize,
+        })
+    }
+
+    // q_l, q_r, q_o, q_m, q_c, a, b, c, the order gate_constraint reads them in
+    pub fn columns(&self) -> [&[F]; 8] {
+        [&self.q_l, &self.q_r, &self.q_o, &self.q_m, &self.q_c, &self.a, &self.b, &self.c]
+    }
+
+    // The first row of the hypercube where the gate equation fails
+    pub fn first_unsatisfied_row(&self) -> Option<usize> {
+        let columns = self.columns();
+        (0..1 << self.no_of_vars).find(|row| !gate_constraint(&columns.map(|column| column[*row])).is_zero())
+    }
+
+    // a || b || c || 0, over no_of_vars + 2 variables
+    pub fn witness(&self) -> Vec<F> {
+        let size = 1 << self.no_of_vars;
+        self.a.iter().chain(&self.b).chain(&self.c).copied().chain(core::iter::repeat_n(F::zero(), size)).collect()
+    }
+
+    // The circuit's copy constraint permutation, moved to the cell indices of witness
+    pub fn wiring(&self, circuit: &PlonkishCircuit<F>) -> Vec<usize> {
+        let no_of_rows = circuit.no_of_rows();
+        let size = 1 << self.no_of_vars;
+        let padded = |cell: usize| (cell / no_of_rows) * size + cell % no_of_rows;
+
+        let mut sigma: Vec<usize> = (0..4 * size).collect();
+        for (cell, target) in circuit.permutation().into_iter().enumerate() {
+            sigma[padded(cell)] = padded(target);
+        }
+        sigma
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    // (x + y) * y = out, as an addition row followed by a multiplication row
+    fn circuit() -> PlonkishCircuit<Fr> {
+        let mut circuit = PlonkishCircuit::new();
+        let sum = circuit.add_gate(Selectors::addition());
+        let product = circuit.add_gate(Selectors::multiplication());
+
+        // the sum feeds the multiplication, and y is used twice
+        circuit.copy(Cell::new(Column::C, sum), Cell::new(Column::A, product));
+        circuit.copy(Cell::new(Column::B, sum), Cell::new(Column::B, product));
+        circuit
+    }
+
+    fn trace(x: u64, y: u64) -> Trace<Fr> {
+        let mut trace = Trace::new();
+        trace.push_row(Fr::from(x), Fr::from(y), Fr::from(x + y));
+        trace.push_row(Fr::from(x + y), Fr::from(y), Fr::from((x + y) * y));
+        trace
+    }
+
+    #[test]
+    fn test_satisfied_trace() {
+        let circuit = circuit();
+
+        assert!(circuit.is_satisfied(&trace(3, 4)));
+        assert_eq!(Selectors::constant(Fr::from(9)).evaluate(Fr::from(9), Fr::from(1), Fr::from(2)), Fr::from(0));
+    }
+
+    #[test]
+    fn test_permutation_cycles() {
+        let mut circuit = circuit();
+        // a third cell in the cycle of y
+        circuit.add_gate(Selectors::constant(Fr::from(4)));
+        circuit.copy(Cell::new(Column::B, 1), Cell::new(Column::A, 2));
+
+        let sigma = circuit.permutation();
+        let no_of_rows = circuit.no_of_rows();
+
+        // following sigma from b[0] visits b[1] and a[2] and comes back
+        let start = Cell::new(Column::B, 0).index(no_of_rows);
+        let mut cycle = vec![start];
+        while sigma[*cycle.last().unwrap()] != start {
+            cycle.push(sigma[*cycle.last().unwrap()]);
+        }
+        cycle.sort();
+        // a[2] = 2, b[0] = 3 and b[1] = 4 with three rows
+        assert_eq!(cycle, vec![2, 3, 4]);
+        assert_eq!(sigma[Cell::new(Column::A, 0).index(no_of_rows)], Cell::new(Column::A, 0).index(no_of_rows));
+    }
+
+    #[test]
+    fn test_unsatisfied_gate_or_wiring() {
+        let circuit = circuit();
+
+        let mut wrong_gate = trace(3, 4);
+        wrong_gate.c[1] += Fr::from(1);
+        assert_eq!(circuit.first_unsatisfied_gate(&wrong_gate), Some(1));
+        assert!(!circuit.is_satisfied(&wrong_gate));
+
+        // every gate holds on its own, but the multiplication uses a different y
+        let mut wrong_wiring = trace(3, 4);
+        wrong_wiring.b[1] = Fr::from(5);
+        wrong_wiring.c[1] = Fr::from(35);
+        assert_eq!(circuit.first_unsatisfied_gate(&wrong_wiring), None);
+        assert!(!circuit.is_satisfied(&wrong_wiring));
+    }
+
+    #[test]
+    fn test_multilinear_columns() {
+        let mut circuit = circuit();
+        circuit.add_gate(Selectors::constant(Fr::from(4)));
+        circuit.copy(Cell::new(Column::B, 1), Cell::new(Column::A, 2));
+        let mut trace = trace(3, 4);
+        trace.push_row(Fr::from(4), Fr::from(0), Fr::from(0));
+
+        // three rows padded to four, the padding row is all zeros and holds
+        let columns = MultilinearColumns::new(&circuit, &trace).unwrap();
+        assert_eq!(columns.no_of_vars, 2);
+        assert_eq!(columns.q_m, vec![Fr::from(0), Fr::from(1), Fr::from(0), Fr::from(0)]);
+        assert_eq!(columns.c[3], Fr::from(0));
+        assert_eq!(columns.first_unsatisfied_row(), None);
+
+        // the wiring holds on the padded witness just as the permutation does on the trace
+        let witness = columns.witness();
+        let sigma = columns.wiring(&circuit);
+        assert_eq!(witness.len(), 16);
+        assert!(sigma.iter().enumerate().all(|(cell, target)| witness[cell] == witness[*target]));
+        // b[0] is cell 4 of the padded layout and is copied into b[1], which is cell 5
+        assert_eq!(sigma[4], 5);
+
+        let mut wrong = trace.clone();
+        wrong.c[1] += Fr::from(1);
+        assert_eq!(MultilinearColumns::new(&circuit, &wrong).unwrap().first_unsatisfied_row(), Some(1));
+        wrong.c.pop();
+        assert_eq!(MultilinearColumns::new(&circuit, &wrong), None);
+    }
+}
@@ -0,0 +1,164 @@
+use ark_ff::PrimeField;
+use univariate_poly::domain::EvaluationDomain;
+use univariate_poly::univariate::UnivariatePoly;
+use crate::r1cs::{ConstraintSystem, SparseMatrix};
+
+// Quadratic arithmetic program from an R1CS.
+//
+// Constraint i is assigned the domain element w^i. For every column j (variable of z), A_j(x) is the polynomial
+// with A_j(w^i) = A[i][j], and the same for B and C. For an assignment z,
+// A(x) = sum of z_j * A_j(x), B(x) and C(x) likewise, so A(w^i) * B(w^i) - C(w^i) is the slack of constraint i.
+// z satisfies the R1CS exactly when A(x) * B(x) - C(x) vanishes on the domain, i.e. when it is divisible
+// by the target polynomial Z(x) = x^n - 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Qap<F: PrimeField> {
+    pub domain: EvaluationDomain<F>,
+    pub no_of_public_inputs: usize,
+    // one polynomial per column of the R1CS matrices
+    pub a: Vec<UnivariatePoly<F>>,
+    pub b: Vec<UnivariatePoly<F>>,
+    pub c: Vec<UnivariatePoly<F>>,
+    pub target: UnivariatePoly<F>,
+}
+
+impl <F: PrimeField>Qap<F> {
+    // Returns None if the field has no evaluation domain with room for every constraint
+    pub fn from_r1cs(cs: &ConstraintSystem<F>) -> Option<Self> {
+        let domain = EvaluationDomain::<F>::new(cs.constraints.len())?;
+        let (a, b, c) = cs.matrices();
+        let no_of_columns = cs.no_of_variables();
+
+        Some(Self {
+            no_of_public_inputs: cs.no_of_public_inputs,
+            a: interpolate_columns(&domain, &a, no_of_columns),
+            b: interpolate_columns(&domain, &b, no_of_columns),
+            c: interpolate_columns(&domain, &c, no_of_columns),
+            target: domain.vanishing_poly().into(),
+            domain,
+        })
+    }
+
+    // A(x), B(x) and C(x) for the assignment z
+    pub fn combine(&self, assignment: &[F]) -> (UnivariatePoly<F>, UnivariatePoly<F>, UnivariatePoly<F>) {
+        assert_eq!(assignment.len(), self.a.len(), "assignment must have one value per column");
+
+        let linear_combination = |polynomials: &[UnivariatePoly<F>]| {
+            polynomials
+                .iter()
+                .zip(assignment.iter())
+                .fold(UnivariatePoly::zero(), |sum, (polynomial, value)| &sum + &polynomial.scalar_mul(*value))
+        };
+
+        (linear_combination(&self.a), linear_combination(&self.b), linear_combination(&self.c))
+    }
+
+    // The quotient h(x) = (A(x) * B(x) - C(x)) / Z(x), or None if Z(x) does not divide it
+    pub fn quotient(&self, assignment: &[F]) -> Option<UnivariatePoly<F>> {
+        let (a, b, c) = self.combine(assignment);
+        let numerator = &(&a * &b) - &c;
+
+        let (quotient, remainder) = numerator.div_rem(&self.target)?;
+        if remainder.is_zero() {
+            Some(quotient)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_satisfied(&self, assignment: &[F]) -> bool {
+        assignment.len() == self.a.len() && self.quotient(assignment).is_some()
+    }
+}
+
+// The polynomial of every column: its evaluations are the column's entries, one per domain element
+fn interpolate_columns<F: PrimeField>(domain: &EvaluationDomain<F>, matrix: &SparseMatrix<F>, no_of_columns: usize) -> Vec<UnivariatePoly<F>> {
+    let mut columns = vec![vec![F::zero(); domain.size]; no_of_columns];
+
+    for (row, entries) in matrix.iter().enumerate() {
+        for (column, value) in entries {
+            columns[*column][row] = *value;
+        }
+    }
+
+    columns
+        .iter()
+        .map(|evaluations| UnivariatePoly::interpolate_over_domain(domain, evaluations))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r1cs::{LinearCombination, Variable};
+    use ark_bn254::Fr;
+
+    // out = a * b * c, with a, b, c private
+    fn multiplication_circuit() -> (ConstraintSystem<Fr>, Vec<Variable>) {
+        let mut cs = ConstraintSystem::new();
+        let out = cs.alloc_public_input();
+        let inputs: Vec<Variable> = (0..3).map(|_| cs.alloc_variable()).collect();
+        let ab = cs.alloc_variable();
+
+        cs.enforce(inputs[0].into(), inputs[1].into(), ab.into());
+        cs.enforce(ab.into(), inputs[2].into(), out.into());
+
+        (cs, inputs)
+    }
+
+    #[test]
+    fn test_columns_interpolate_the_matrices() {
+        let (cs, _) = multiplication_circuit();
+        let qap = Qap::from_r1cs(&cs).unwrap();
+        let (a, _, c) = cs.matrices();
+
+        assert_eq!(qap.domain.size, 2);
+        assert_eq!(qap.a.len(), cs.no_of_variables());
+
+        for (row, entries) in a.iter().enumerate() {
+            for (column, value) in entries {
+                assert_eq!(qap.a[*column].evaluate(qap.domain.element(row)), *value);
+            }
+        }
+        // out only appears in the C entry of the second constraint
+        assert_eq!(qap.c[1].evaluate(qap.domain.element(0)), Fr::from(0));
+        assert_eq!(qap.c[1].evaluate(qap.domain.element(1)), c[1][0].1);
+        assert_eq!(qap.target.evaluate(qap.domain.element(1)), Fr::from(0));
+    }
+
+    #[test]
+    fn test_valid_witness_is_divisible() {
+        let (cs, inputs) = multiplication_circuit();
+        let qap = Qap::from_r1cs(&cs).unwrap();
+
+        let known: Vec<(Variable, Fr)> = inputs.iter().copied().zip([Fr::from(2), Fr::from(3), Fr::from(7)]).collect();
+        let assignment = cs.solve(&known).unwrap();
+        assert_eq!(assignment[cs.index(Variable::Public(0))], Fr::from(42));
+
+        let quotient = qap.quotient(&assignment).unwrap();
+        let (a, b, c) = qap.combine(&assignment);
+        assert_eq!(&(&a * &b) - &c, &quotient * &qap.target);
+        assert!(qap.is_satisfied(&assignment));
+    }
+
+    #[test]
+    fn test_invalid_witness_is_not_divisible() {
+        let (cs, inputs) = multiplication_circuit();
+        let qap = Qap::from_r1cs(&cs).unwrap();
+
+        let known: Vec<(Variable, Fr)> = inputs.iter().copied().zip([Fr::from(2), Fr::from(3), Fr::from(7)]).collect();
+        let mut assignment = cs.solve(&known).unwrap();
+        assignment[cs.index(Variable::Public(0))] = Fr::from(43);
+
+        assert_eq!(qap.quotient(&assignment), None);
+        assert!(!qap.is_satisfied(&assignment));
+
+        // a constraint with a constant term: the constant column is scaled by z[0] = 1
+        let mut cs = ConstraintSystem::<Fr>::new();
+        let x = cs.alloc_variable();
+        cs.enforce(LinearCombination::from(x) + LinearCombination::constant(Fr::from(1)), Variable::One.into(), LinearCombination::constant(Fr::from(5)));
+        let qap = Qap::from_r1cs(&cs).unwrap();
+        assert!(qap.is_satisfied(&[Fr::from(1), Fr::from(4)]));
+        assert!(!qap.is_satisfied(&[Fr::from(1), Fr::from(5)]));
+    }
+}
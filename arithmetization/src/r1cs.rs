@@ -0,0 +1,349 @@
+use ark_ff::PrimeField;
+use std::ops::{Add, Mul, Sub};
+
+// Rank-1 constraint systems.
+//
+// Every constraint has the form <A_i, z> * <B_i, z> = <C_i, z> where z is the full assignment
+// z = (1, public inputs..., private variables...). A, B and C are sparse, so each row is
+// stored as a linear combination of the variables it uses.
+//
+// Variables are allocated while building the system and only mapped to their position in z
+// when the matrices are exported, so public inputs and private variables can be allocated in any order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Variable {
+    // the constant 1 at z[0]
+    One,
+    Public(usize),
+    Private(usize),
+}
+
+// sum of coefficient * variable, kept sorted by variable with zero coefficients dropped
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LinearCombination<F: PrimeField> {
+    pub terms: Vec<(Variable, F)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint<F: PrimeField> {
+    pub a: LinearCombination<F>,
+    pub b: LinearCombination<F>,
+    pub c: LinearCombination<F>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConstraintSystem<F: PrimeField> {
+    pub no_of_public_inputs: usize,
+    pub no_of_private_variables: usize,
+    pub constraints: Vec<Constraint<F>>,
+}
+
+// rows[i] holds the (column, value) pairs of the non-zero entries in row i
+pub type SparseMatrix<F> = Vec<Vec<(usize, F)>>;
+
+impl <F: PrimeField>LinearCombination<F> {
+    pub fn zero() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    pub fn constant(value: F) -> Self {
+        Self::zero().add_term(Variable::One, value)
+    }
+
+    pub fn add_term(mut self, variable: Variable, coefficient: F) -> Self {
+        match self.terms.binary_search_by(|(existing, _)| existing.cmp(&variable)) {
+            Ok(position) => {
+                self.terms[position].1 += coefficient;
+                if self.terms[position].1.is_zero() {
+                    self.terms.remove(position);
+                }
+            }
+            Err(position) => {
+                if !coefficient.is_zero() {
+                    self.terms.insert(position, (variable, coefficient));
+                }
+            }
+        }
+        self
+    }
+
+    pub fn scalar_mul(&self, scalar: F) -> Self {
+        if scalar.is_zero() {
+            return Self::zero();
+        }
+        Self { terms: self.terms.iter().map(|(variable, coefficient)| (*variable, *coefficient * scalar)).collect() }
+    }
+
+    pub fn coefficient(&self, variable: Variable) -> F {
+        self.terms
+            .iter()
+            .find(|(existing, _)| *existing == variable)
+            .map(|(_, coefficient)| *coefficient)
+            .unwrap_or(F::zero())
+    }
+}
+
+impl <F: PrimeField>From<Variable> for LinearCombination<F> {
+    fn from(variable: Variable) -> Self {
+        Self::zero().add_term(variable, F::one())
+    }
+}
+
+impl <F: PrimeField>Add for LinearCombination<F> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        other.terms.into_iter().fold(self, |sum, (variable, coefficient)| sum.add_term(variable, coefficient))
+    }
+}
+
+impl <F: PrimeField>Sub for LinearCombination<F> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + other.scalar_mul(-F::one())
+    }
+}
+
+impl <F: PrimeField>Mul<F> for LinearCombination<F> {
+    type Output = Self;
+
+    fn mul(self, scalar: F) -> Self {
+        self.scalar_mul(scalar)
+    }
+}
+
+impl <F: PrimeField>ConstraintSystem<F> {
+    pub fn new() -> Self {
+        Self {
+            no_of_public_inputs: 0,
+            no_of_private_variables: 0,
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn alloc_public_input(&mut self) -> Variable {
+        self.no_of_public_inputs += 1;
+        Variable::Public(self.no_of_public_inputs - 1)
+    }
+
+    pub fn alloc_variable(&mut self) -> Variable {
+        self.no_of_private_variables += 1;
+        Variable::Private(self.no_of_private_variables - 1)
+    }
+
+    // Adds the constraint a * b = c
+    pub fn enforce(&mut self, a: LinearCombination<F>, b: LinearCombination<F>, c: LinearCombination<F>) {
+        self.constraints.push(Constraint { a, b, c });
+    }
+
+    // Length of the full assignment z
+    pub fn no_of_variables(&self) -> usize {
+        1 + self.no_of_public_inputs + self.no_of_private_variables
+    }
+
+    // Position of a variable in z = (1, public inputs..., private variables...)
+    pub fn index(&self, variable: Variable) -> usize {
+        match variable {
+            Variable::One => 0,
+            Variable::Public(i) => 1 + i,
+            Variable::Private(i) => 1 + self.no_of_public_inputs + i,
+        }
+    }
+
+    // The A, B and C matrices, one row per constraint and one column per entry of z
+    pub fn matrices(&self) -> (SparseMatrix<F>, SparseMatrix<F>, SparseMatrix<F>) {
+        let to_row = |combination: &LinearCombination<F>| {
+            combination
+                .terms
+                .iter()
+                .map(|(variable, coefficient)| (self.index(*variable), *coefficient))
+                .collect::<Vec<(usize, F)>>()
+        };
+
+        let a = self.constraints.iter().map(|constraint| to_row(&constraint.a)).collect();
+        let b = self.constraints.iter().map(|constraint| to_row(&constraint.b)).collect();
+        let c = self.constraints.iter().map(|constraint| to_row(&constraint.c)).collect();
+
+        (a, b, c)
+    }
+
+    pub fn evaluate(&self, combination: &LinearCombination<F>, assignment: &[F]) -> F {
+        combination
+            .terms
+            .iter()
+            .map(|(variable, coefficient)| assignment[self.index(*variable)] * coefficient)
+            .sum()
+    }
+
+    // Index of the first constraint the assignment violates, None if it satisfies all of them
+    pub fn first_unsatisfied(&self, assignment: &[F]) -> Option<usize> {
+        self.constraints.iter().position(|constraint| {
+            self.evaluate(&constraint.a, assignment) * self.evaluate(&constraint.b, assignment)
+                != self.evaluate(&constraint.c, assignment)
+        })
+    }
+
+    pub fn is_satisfied(&self, assignment: &[F]) -> bool {
+        assignment.len() == self.no_of_variables()
+            && assignment[0] == F::one()
+            && self.first_unsatisfied(assignment).is_none()
+    }
+
+    // Witness solver: starting from the known values, repeatedly finds a constraint with exactly one
+    // unknown variable and solves for it. The unknown x can appear in C and in one of A or B:
+    // with B known, (a_known + a*x) * B = c_known + c*x gives x = (c_known - a_known*B) / (a*B - c),
+    // and symmetrically with A known. Returns the full assignment z, or None if some variable
+    // cannot be determined or the result does not satisfy the system
+    pub fn solve(&self, known: &[(Variable, F)]) -> Option<Vec<F>> {
+        let mut values: Vec<Option<F>> = vec![None; self.no_of_variables()];
+        values[0] = Some(F::one());
+        for (variable, value) in known {
+            values[self.index(*variable)] = Some(*value);
+        }
+
+        let mut progress = true;
+        while progress {
+            progress = false;
+
+            for constraint in &self.constraints {
+                let mut unknowns = constraint
+                    .a
+                    .terms
+                    .iter()
+                    .chain(constraint.b.terms.iter())
+                    .chain(constraint.c.terms.iter())
+                    .map(|(variable, _)| *variable)
+                    .filter(|variable| values[self.index(*variable)].is_none());
+
+                let unknown = match unknowns.next() {
+                    Some(unknown) => unknown,
+                    None => continue,
+                };
+                if unknowns.any(|other| other != unknown) {
+                    continue;
+                }
+
+                // the known part of a combination, the unknown contributes nothing
+                let known_part = |combination: &LinearCombination<F>| -> F {
+                    combination
+                        .terms
+                        .iter()
+                        .filter_map(|(variable, coefficient)| values[self.index(*variable)].map(|value| value * coefficient))
+                        .sum()
+                };
+
+                let (a, b, c) = (
+                    constraint.a.coefficient(unknown),
+                    constraint.b.coefficient(unknown),
+                    constraint.c.coefficient(unknown),
+                );
+                let (a_known, b_known, c_known) = (known_part(&constraint.a), known_part(&constraint.b), known_part(&constraint.c));
+
+                let solution = if b.is_zero() {
+                    ((a * b_known) - c).inverse().map(|inverse| (c_known - a_known * b_known) * inverse)
+                } else if a.is_zero() {
+                    ((b * a_known) - c).inverse().map(|inverse| (c_known - a_known * b_known) * inverse)
+                } else {
+                    // x appears in both A and B, the constraint is quadratic in x
+                    None
+                };
+
+                if let Some(solution) = solution {
+                    values[self.index(unknown)] = Some(solution);
+                    progress = true;
+                }
+            }
+        }
+
+        let assignment: Vec<F> = values.into_iter().collect::<Option<Vec<F>>>()?;
+
+        if self.is_satisfied(&assignment) {
+            Some(assignment)
+        } else {
+            None
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    // x^3 + x + 5 = out, with x private and out public
+    fn cubic() -> (ConstraintSystem<Fr>, Variable, Variable) {
+        let mut cs = ConstraintSystem::new();
+        let out = cs.alloc_public_input();
+        let x = cs.alloc_variable();
+        let x_squared = cs.alloc_variable();
+        let x_cubed = cs.alloc_variable();
+
+        cs.enforce(x.into(), x.into(), x_squared.into());
+        cs.enforce(x_squared.into(), x.into(), x_cubed.into());
+        cs.enforce(
+            LinearCombination::from(x_cubed) + x.into() + LinearCombination::constant(Fr::from(5)),
+            Variable::One.into(),
+            out.into(),
+        );
+
+        (cs, x, out)
+    }
+
+    #[test]
+    fn test_linear_combination() {
+        let x = Variable::Private(0);
+        let y = Variable::Public(0);
+
+        let combination = LinearCombination::<Fr>::from(x) + LinearCombination::from(y) * Fr::from(3) + x.into();
+        assert_eq!(combination.terms, vec![(y, Fr::from(3)), (x, Fr::from(2))]);
+
+        // terms cancelling out are dropped
+        assert_eq!((combination.clone() - combination).terms, vec![]);
+    }
+
+    #[test]
+    fn test_solve_and_satisfy() {
+        let (cs, x, out) = cubic();
+        let assignment = cs.solve(&[(x, Fr::from(3))]).unwrap();
+
+        // z = (1, out, x, x^2, x^3)
+        assert_eq!(assignment, vec![Fr::from(1), Fr::from(35), Fr::from(3), Fr::from(9), Fr::from(27)]);
+        assert_eq!(assignment[cs.index(out)], Fr::from(35));
+        assert!(cs.is_satisfied(&assignment));
+
+        let mut wrong = assignment.clone();
+        wrong[cs.index(out)] = Fr::from(36);
+        assert_eq!(cs.first_unsatisfied(&wrong), Some(2));
+        assert!(!cs.is_satisfied(&wrong));
+    }
+
+    #[test]
+    fn test_solve_backwards_and_failures() {
+        let (cs, x, out) = cubic();
+
+        // x cannot be recovered from out alone, the first constraint is quadratic in x
+        assert_eq!(cs.solve(&[(out, Fr::from(35))]), None);
+        // inconsistent known values are rejected
+        assert_eq!(cs.solve(&[(x, Fr::from(3)), (out, Fr::from(36))]), None);
+
+        // a variable in A is solved by dividing by B: y * 4 = 12
+        let mut cs = ConstraintSystem::<Fr>::new();
+        let y = cs.alloc_variable();
+        cs.enforce(y.into(), LinearCombination::constant(Fr::from(4)), LinearCombination::constant(Fr::from(12)));
+        assert_eq!(cs.solve(&[]), Some(vec![Fr::from(1), Fr::from(3)]));
+    }
+
+    #[test]
+    fn test_matrices() {
+        let (cs, _, _) = cubic();
+        let (a, b, c) = cs.matrices();
+
+        assert_eq!(cs.no_of_variables(), 5);
+        assert_eq!(a.len(), 3);
+        // x^3 + x + 5: the constant, x and x^3 columns
+        assert_eq!(a[2], vec![(0, Fr::from(5)), (2, Fr::from(1)), (4, Fr::from(1))]);
+        assert_eq!(b[2], vec![(0, Fr::from(1))]);
+        assert_eq!(c[2], vec![(1, Fr::from(1))]);
+    }
+}
@@ -0,0 +1,18 @@
+use ark_bn254::Fr;
+use circuits::error::Error;
+use circuits::{Circuit, Gate, Layer, Operator};
+
+// Evaluates (a + b) * (b * c) as a two layer circuit and prints every layer, output layer first
+fn main() -> Result<(), Error> {
+    let layer1 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Add), Gate::new(1, 2, 1, Operator::Mul)]);
+    let layer2 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Mul)]);
+    let mut circuit = Circuit::new(vec![layer1, layer2]);
+
+    let outputs = circuit.evaluate(vec![Fr::from(3), Fr::from(4), Fr::from(5)])?;
+    println!("output: {}", outputs[0]);
+
+    for layer in 0..=circuit.layers.len() {
+        println!("layer {}: {:?}", layer, circuit.get_round_poly(layer)?.iter().map(|value| value.to_string()).collect::<Vec<_>>());
+    }
+    Ok(())
+}
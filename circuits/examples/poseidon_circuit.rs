@@ -0,0 +1,19 @@
+use circuits::poseidon_gadget::{poseidon_circuit, poseidon_circuit_input};
+use std::error::Error;
+
+// Evaluates the Poseidon gadget and compares it with the native permutation
+fn main() -> Result<(), Box<dyn Error>> {
+    let params = poseidon::poseidon::bn254_params();
+    let state = [ark_bn254::Fr::from(0), ark_bn254::Fr::from(1), ark_bn254::Fr::from(2)];
+
+    let mut circuit = poseidon_circuit(params)?;
+    let in_circuit = circuit.evaluate(poseidon_circuit_input(params, &state)?)?;
+
+    let mut native = state;
+    params.permute(&mut native)?;
+
+    println!("poseidon in-circuit: {}", in_circuit[0]);
+    println!("poseidon native:     {}", native[0]);
+    Ok(())
+}
+
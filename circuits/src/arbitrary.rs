@@ -0,0 +1,72 @@
+use crate::{Circuit, Gate, Layer, Operator};
+use ark_ff::PrimeField;
+use proptest::prelude::*;
+use univariate_poly::arbitrary::field_element;
+
+// proptest generators, built for the tests of this crate and for other crates with the proptest feature.
+// Generated circuits are always well wired: gate i of a layer writes output i, and reads two wires of the layer
+// below, so evaluate succeeds on any input with input_arity elements, at most no_of_inputs as the first layer need
+// not read every one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitParams {
+    pub no_of_inputs: usize,
+    pub max_layers: usize,
+    pub max_gates: usize,
+}
+
+impl Default for CircuitParams {
+    fn default() -> Self {
+        Self { no_of_inputs: 4, max_layers: 4, max_gates: 4 }
+    }
+}
+
+impl <F: PrimeField>Arbitrary for Operator<F> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![Just(Operator::Add), Just(Operator::Mul)].boxed()
+    }
+}
+
+impl <F: PrimeField>Arbitrary for Circuit<F> {
+    type Parameters = CircuitParams;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(1..=params.max_gates, 1..=params.max_layers)
+            .prop_flat_map(move |layer_sizes| {
+                // layer i reads the outputs of the layer below, which for the first one are the inputs
+                let widths = std::iter::once(params.no_of_inputs).chain(layer_sizes.iter().copied());
+                layer_sizes
+                    .iter()
+                    .zip(widths)
+                    .map(|(size, width)| prop::collection::vec((any::<Operator<F>>(), 0..width, 0..width), *size))
+                    .collect::<Vec<_>>()
+            })
+            .prop_map(|layers| {
+                let layers = layers
+                    .into_iter()
+                    .map(|gates| {
+                        Layer::new(
+                            gates
+                                .into_iter()
+                                .enumerate()
+                                .map(|(output, (operator, left, right))| Gate::new(left, right, output, operator))
+                                .collect(),
+                        )
+                    })
+                    .collect();
+                Circuit::new(layers)
+            })
+            .boxed()
+    }
+}
+
+// A circuit generated with the default parameters and an input it can be evaluated on
+pub fn circuit_and_input<F: PrimeField>() -> impl Strategy<Value = (Circuit<F>, Vec<F>)> {
+    any_with::<Circuit<F>>(CircuitParams::default()).prop_flat_map(|circuit| {
+        let no_of_inputs = circuit.input_arity().expect("generated circuits have at least one layer");
+        (Just(circuit), prop::collection::vec(field_element::<F>(), no_of_inputs))
+    })
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+// Everything that can go wrong wiring or evaluating a layered circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    // evaluating a circuit without layers on no input leaves nothing to output
+    EmptyInput,
+    // a circuit without layers has no output layer for a claim or a gate for an export to be about
+    EmptyCircuit,
+    // a gate of `layer` reads a wire the layer below it does not have
+    WireOutOfRange { layer: usize, wire: usize, no_of_wires: usize },
+    LayerOutOfRange { layer: usize, no_of_layers: usize },
+    // the layer values are only known after evaluate
+    NotEvaluated,
+    // a wire index needs more bits than the add_i / mul_i labels of its layer give it
+    GateIndexTooWide { layer: usize, index: usize, bits: usize },
+    // an Affine gate whose constants are neither a subtraction nor a constant, see Selector::of
+    NoSelector { layer: usize, gate: usize },
+    // the first layer reads `expected` inputs, and inputs expected..provided would be ignored
+    TooManyInputs { expected: usize, provided: usize },
+    // a point with `provided` coordinates for a polynomial in `expected` variables
+    WrongPointLength { expected: usize, provided: usize },
+    // a point needs `needed` coordinates to index every value of the table it is evaluated against
+    PointTooShort { needed: usize, provided: usize },
+    // two gates of `layer` write wire `output`: evaluate would keep one and add_i / mul_i the other
+    RepeatedOutput { layer: usize, output: usize },
+}
+
+// Everything that can go wrong building the Poseidon and Feldman gadgets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GadgetError {
+    // the gadget only lays out the x^5 S-box
+    UnsupportedSbox { alpha: u64 },
+    WrongStateWidth { expected: usize, provided: usize },
+    // a Feldman check needs at least the commitment to the secret
+    ZeroThreshold,
+    WrongNumberOfCommitments { expected: usize, provided: usize },
+}
+
+// The errors of every module of the crate, so a caller going through several of them can use one Result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Circuit(CircuitError),
+    Gadget(GadgetError),
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CircuitError::EmptyInput => write!(f, "no input to evaluate"),
+            CircuitError::EmptyCircuit => write!(f, "the circuit has no layers"),
+            CircuitError::WireOutOfRange { layer, wire, no_of_wires } => {
+                write!(f, "layer {} reads wire {} of {} wires", layer, wire, no_of_wires)
+            }
+            CircuitError::LayerOutOfRange { layer, no_of_layers } => {
+                write!(f, "layer {} out of range for {} layers", layer, no_of_layers)
+            }
+            CircuitError::NotEvaluated => write!(f, "circuit has not been evaluated"),
+            CircuitError::GateIndexTooWide { layer, index, bits } => {
+                write!(f, "layer {} wire {} does not fit in {} bits", layer, index, bits)
+            }
+            CircuitError::NoSelector { layer, gate } => {
+                write!(f, "gate {} of layer {} is an affine gate with no selector, only sub and const ones have one", gate, layer)
+            }
+            CircuitError::TooManyInputs { expected, provided } => {
+                write!(f, "the circuit reads {} inputs, inputs {}..{} are unused", expected, expected, provided)
+            }
+            CircuitError::WrongPointLength { expected, provided } => {
+                write!(f, "the point has {} coordinates for {} variables", provided, expected)
+            }
+            CircuitError::PointTooShort { needed, provided } => {
+                write!(f, "the point has {} coordinates, {} are needed", provided, needed)
+            }
+            CircuitError::RepeatedOutput { layer, output } => write!(f, "layer {} writes wire {} more than once", layer, output),
+        }
+    }
+}
+
+impl fmt::Display for GadgetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GadgetError::UnsupportedSbox { alpha } => write!(f, "unsupported S-box x^{}, only x^5 is implemented", alpha),
+            GadgetError::WrongStateWidth { expected, provided } => {
+                write!(f, "state has {} elements, the permutation width is {}", provided, expected)
+            }
+            GadgetError::ZeroThreshold => write!(f, "threshold must be greater than 0"),
+            GadgetError::WrongNumberOfCommitments { expected, provided } => {
+                write!(f, "{} commitments provided, the threshold is {}", provided, expected)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Circuit(error) => write!(f, "circuit: {}", error),
+            Error::Gadget(error) => write!(f, "gadget: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+impl std::error::Error for GadgetError {}
+
+impl std::error::Error for Error {}
+
+impl From<CircuitError> for Error {
+    fn from(error: CircuitError) -> Self {
+        Error::Circuit(error)
+    }
+}
+
+impl From<GadgetError> for Error {
+    fn from(error: GadgetError) -> Self {
+        Error::Gadget(error)
+    }
+}
@@ -0,0 +1,131 @@
+use ark_ff::PrimeField;
+use shamir_secret::shamir::{share_x, Point};
+use crate::error::GadgetError;
+use crate::poseidon_gadget::GadgetBuilder;
+use crate::{Circuit, Operator};
+
+// Feldman share verification as a layered circuit, with the exponent-free trick.
+//
+// shamir_secret::vss::verify_share accepts a share (x, y) when y*G == sum over j of x^j * C_j for the dealer's
+// commitments C_j = a_j*G. Checking that over the real commitments takes curve arithmetic in the circuit, over a curve
+// whose base field is the circuit's field. For training the gadget works on the discrete logs a_j instead: the
+// equation is linear, so dropping G leaves y == sum x^j a_j, and the circuit outputs
+//   sum over j of x^j * a_j - y
+// which is zero exactly when the share is consistent. The logs are the dealer's secret coefficients, so the gadget
+// models the check a curve-based one would do rather than something a shareholder can run on a real dealing.
+//
+// x is the public point of the share, so its powers are Affine constants: the first layer scales pairs of inputs by
+// their weights, x^j for the coefficients and -1 for y, and the next layers sum pairs until one wire is left. The
+// input is the coefficients a_0..a_(t-1) followed by y
+pub fn feldman_circuit<F: PrimeField>(share_id: usize, threshold: usize) -> Result<Circuit<F>, GadgetError> {
+    if threshold == 0 {
+        return Err(GadgetError::ZeroThreshold);
+    }
+
+    let x: F = share_x(share_id);
+    let weights: Vec<F> = core::iter::successors(Some(F::one()), |power| Some(*power * x))
+        .take(threshold)
+        .chain([-F::one()])
+        .collect();
+    let mut builder = GadgetBuilder::new();
+
+    let products = weights
+        .chunks(2)
+        .enumerate()
+        .map(|(pair, weights)| {
+            let left = 2 * pair;
+            match weights {
+                [left_weight, right_weight] => (left, left + 1, Operator::affine(*left_weight, *right_weight, F::zero())),
+                _ => (left, left, Operator::affine(weights[0], F::zero(), F::zero())),
+            }
+        })
+        .collect();
+    builder.push_layer(products);
+
+    let mut terms = weights.len().div_ceil(2);
+    while terms > 1 {
+        let add = Operator::affine(F::one(), F::one(), F::zero());
+        let copy = Operator::copy_left();
+        let sums = (0..terms.div_ceil(2))
+            .map(|pair| if 2 * pair + 1 < terms { (2 * pair, 2 * pair + 1, add) } else { (2 * pair, 2 * pair, copy) })
+            .collect();
+        builder.push_layer(sums);
+        terms = terms.div_ceil(2);
+    }
+
+    Ok(Circuit::new(builder.layers))
+}
+
+// The input vector for feldman_circuit: the discrete logs of the commitments, then the share's value
+pub fn feldman_circuit_input<F: PrimeField>(
+    share: &Point<F>,
+    commitment_logs: &[F],
+    threshold: usize,
+) -> Result<Vec<F>, GadgetError> {
+    if commitment_logs.len() != threshold {
+        return Err(GadgetError::WrongNumberOfCommitments { expected: threshold, provided: commitment_logs.len() });
+    }
+
+    Ok(commitment_logs.iter().copied().chain([share.y]).collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_ec::PrimeGroup;
+    use shamir_secret::vss::verify_share;
+
+    // f(X) = 7 + 3X + 5X^2, the dealer's polynomial for a threshold of 3
+    fn coefficients() -> Vec<Fr> {
+        vec![Fr::from(7), Fr::from(3), Fr::from(5)]
+    }
+
+    fn share(id: usize) -> Point<Fr> {
+        let x = share_x::<Fr>(id);
+        Point { id, x, y: Fr::from(7) + Fr::from(3) * x + Fr::from(5) * x * x }
+    }
+
+    #[test]
+    fn test_circuit_agrees_with_verify_share() {
+        let commitments: Vec<G1Projective> = coefficients().iter().map(|a| G1Projective::generator() * a).collect();
+
+        for id in 1..=4 {
+            let share = share(id);
+            let mut circuit = feldman_circuit::<Fr>(id, 3).unwrap();
+            let input = feldman_circuit_input(&share, &coefficients(), 3).unwrap();
+
+            assert!(verify_share(&share, &commitments));
+            assert_eq!(circuit.evaluate(input), Ok(vec![Fr::from(0)]));
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_share_gives_a_nonzero_output() {
+        let mut tampered = share(2);
+        tampered.y += Fr::from(1);
+        let mut circuit = feldman_circuit::<Fr>(2, 3).unwrap();
+
+        // y is one more than f(2), so the output is -1
+        let output = circuit.evaluate(feldman_circuit_input(&tampered, &coefficients(), 3).unwrap()).unwrap();
+        assert_eq!(output, vec![-Fr::from(1)]);
+
+        // nor does a valid share check out at another share's point
+        let mut other_point = feldman_circuit::<Fr>(3, 3).unwrap();
+        assert_ne!(other_point.evaluate(feldman_circuit_input(&share(2), &coefficients(), 3).unwrap()), Ok(vec![Fr::from(0)]));
+    }
+
+    #[test]
+    fn test_layout_and_parameter_errors() {
+        // 4 inputs make 2 products and one sum; a threshold of 1 makes a single product
+        assert_eq!(feldman_circuit::<Fr>(1, 3).unwrap().layers.len(), 2);
+        assert_eq!(feldman_circuit::<Fr>(1, 1).unwrap().layers.len(), 1);
+
+        assert_eq!(feldman_circuit::<Fr>(1, 0).err(), Some(GadgetError::ZeroThreshold));
+        assert_eq!(
+            feldman_circuit_input(&share(1), &coefficients()[..2], 3),
+            Err(GadgetError::WrongNumberOfCommitments { expected: 3, provided: 2 })
+        );
+    }
+}
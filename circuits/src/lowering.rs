@@ -0,0 +1,153 @@
+use ark_ff::PrimeField;
+use arithmetization::r1cs::{ConstraintSystem, LinearCombination, Variable};
+use crate::error::CircuitError;
+use crate::{Circuit, Operator};
+
+impl <F: PrimeField>Circuit<F> {
+    // Lowers the circuit into an R1CS.
+    //
+    // Every wire is tracked as a linear combination of variables: the circuit inputs are private variables, the
+    // input constants are constant combinations,
+    // an Add or Affine gate is a linear combination of its input combinations and costs nothing, and a Mul gate allocates a new variable
+    // with the constraint left * right = product. The wires of the output layer become public inputs
+    // through output * 1 = public. Returns the system and the variables of the circuit inputs in order,
+    // so the witness is ConstraintSystem::solve with the input values
+    pub fn to_r1cs(&self) -> Result<(ConstraintSystem<F>, Vec<Variable>), CircuitError> {
+        let mut cs = ConstraintSystem::new();
+
+        let no_of_inputs = self
+            .layers
+            .first()
+            .and_then(|layer| layer.gates.iter().map(|gate| gate.left_index.max(gate.right_index) + 1).max())
+            .unwrap_or(0)
+            .saturating_sub(self.input_constants.len());
+        let inputs: Vec<Variable> = (0..no_of_inputs).map(|_| cs.alloc_variable()).collect();
+
+        let mut wires: Vec<LinearCombination<F>> = self
+            .input_constants
+            .iter()
+            .map(|constant| LinearCombination::constant(*constant))
+            .chain(inputs.iter().map(|input| (*input).into()))
+            .collect();
+
+        for (layer_index, layer) in self.layers.iter().enumerate() {
+            layer.check_inputs(layer_index, wires.len())?;
+
+            // same sizing as evaluate: outputs without a gate stay zero
+            let layer_size = layer.gates.iter().map(|gate| gate.output_index + 1).max().unwrap_or(1);
+            let mut next_wires = vec![LinearCombination::zero(); layer_size];
+
+            for gate in &layer.gates {
+                let left = wires[gate.left_index].clone();
+                let right = wires[gate.right_index].clone();
+
+                next_wires[gate.output_index] = match gate.gate_operator {
+                    Operator::Add => left + right,
+                    Operator::Mul => {
+                        let product = cs.alloc_variable();
+                        cs.enforce(left, right, product.into());
+                        product.into()
+                    }
+                    Operator::Affine(constants) => {
+                        left * constants.left + right * constants.right + LinearCombination::constant(constants.constant)
+                    }
+                };
+            }
+
+            wires = next_wires;
+        }
+
+        for wire in wires {
+            let output = cs.alloc_public_input();
+            cs.enforce(wire, Variable::One.into(), output.into());
+        }
+
+        Ok((cs, inputs))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circuit, Gate, Layer, Operator};
+    use crate::arbitrary::circuit_and_input;
+    use crate::poseidon_gadget::{poseidon_circuit, poseidon_circuit_input};
+    use arithmetization::r1cs::Variable;
+    use ark_bn254::Fr;
+    use poseidon::poseidon::PoseidonParams;
+
+    fn two_layer_circuit() -> Circuit<Fr> {
+        let layer1 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Add), Gate::new(1, 2, 1, Operator::Mul)]);
+        let layer2 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Mul)]);
+        Circuit::new(vec![layer1, layer2])
+    }
+
+    #[test]
+    fn test_lowered_circuit_is_satisfied_by_its_evaluation() {
+        let mut circuit = two_layer_circuit();
+        let input = vec![Fr::from(3), Fr::from(4), Fr::from(5)];
+        let (cs, inputs) = circuit.to_r1cs().unwrap();
+
+        let known: Vec<(Variable, Fr)> = inputs.iter().copied().zip(input.iter().copied()).collect();
+        let assignment = cs.solve(&known).unwrap();
+
+        // two Mul gates and one output binding
+        assert_eq!(cs.constraints.len(), 3);
+        assert_eq!(assignment[cs.index(Variable::Public(0))], circuit.evaluate(input).unwrap()[0]);
+
+        let mut wrong = assignment.clone();
+        wrong[cs.index(Variable::Public(0))] += Fr::from(1);
+        assert!(!cs.is_satisfied(&wrong));
+    }
+
+    #[test]
+    fn test_lowered_poseidon_gadget() {
+        let params = PoseidonParams::<Fr>::new(3, 4, 3, 5).unwrap();
+        let state = [Fr::from(1), Fr::from(2), Fr::from(3)];
+
+        let (cs, inputs) = poseidon_circuit(&params).unwrap().to_r1cs().unwrap();
+        let input = poseidon_circuit_input(&params, &state).unwrap();
+        let known: Vec<(Variable, Fr)> = inputs.iter().copied().zip(input).collect();
+        let assignment = cs.solve(&known).unwrap();
+
+        let mut expected = state;
+        params.permute(&mut expected).unwrap();
+
+        let outputs: Vec<Fr> = (0..3).map(|i| assignment[cs.index(Variable::Public(i))]).collect();
+        assert_eq!(outputs, expected.to_vec());
+    }
+
+    #[test]
+    fn test_input_constants_are_not_variables() {
+        // (10 + a) * (b * 2) with 10 and 2 declared on the circuit
+        let mut circuit = two_layer_circuit().with_input_constants(vec![Fr::from(10), Fr::from(2)]);
+        circuit.layers[0] = Layer::new(vec![Gate::new(0, 2, 0, Operator::Add), Gate::new(3, 1, 1, Operator::Mul)]);
+        let (cs, inputs) = circuit.to_r1cs().unwrap();
+
+        // only a and b are inputs to solve for
+        assert_eq!(inputs.len(), 2);
+        let assignment = cs.solve(&[(inputs[0], Fr::from(3)), (inputs[1], Fr::from(4))]).unwrap();
+        assert_eq!(assignment[cs.index(Variable::Public(0))], circuit.evaluate(vec![Fr::from(3), Fr::from(4)]).unwrap()[0]);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_lowered_circuit_agrees_with_evaluate((circuit, input) in circuit_and_input::<Fr>()) {
+            let mut circuit = circuit;
+            let (cs, inputs) = circuit.to_r1cs().unwrap();
+            let known: Vec<(Variable, Fr)> = inputs.iter().copied().zip(input.iter().copied()).collect();
+            let assignment = cs.solve(&known).unwrap();
+
+            circuit.evaluate(input).unwrap();
+            let outputs = circuit.get_round_poly(0).unwrap();
+            for (i, output) in outputs.iter().enumerate() {
+                proptest::prop_assert_eq!(assignment[cs.index(Variable::Public(i))], *output);
+            }
+
+            // a wrong output is not a witness
+            let mut wrong = assignment;
+            wrong[cs.index(Variable::Public(0))] += Fr::from(1);
+            proptest::prop_assert!(!cs.is_satisfied(&wrong));
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use ark_ff::PrimeField;
+use arithmetization::plonkish::{Cell, Column, MultilinearColumns, PlonkishCircuit, Selectors, Trace};
+use crate::error::CircuitError;
+use crate::{Circuit, Operator};
+
+impl <F: PrimeField>Circuit<F> {
+    // Exports the circuit into the PLONK-ish format.
+    //
+    // Every gate becomes one row, layer by layer: a and b hold its input wires and c its output, and an Affine gate's
+    // constants become its selectors, a * left + b * right + c - out = 0.
+    // The wiring becomes copy constraints: a wire produced by a gate is copied into every cell that reads it in the
+    // next layer, and all the cells reading the same circuit input are copied together. Outputs without a gate are zero
+    // in evaluate, so the first gate reading one is preceded by a row fixing a = 0, and the cells reading it are copies
+    // of that cell.
+    // Each input constant k gets a row of its own ahead of the gates, a - k = 0, and the cells reading it are copies
+    // of its a cell
+    pub fn to_plonkish(&self) -> PlonkishCircuit<F> {
+        let mut plonkish = PlonkishCircuit::new();
+
+        // the cell holding each wire of the previous layer, or for the inputs the first cell reading it
+        let mut wires: Vec<Option<Cell>> = self
+            .input_constants
+            .iter()
+            .map(|constant| {
+                let row = plonkish.add_gate(Selectors { q_l: F::one(), q_r: F::zero(), q_o: F::zero(), q_m: F::zero(), q_c: -*constant });
+                Some(Cell::new(Column::A, row))
+            })
+            .collect();
+
+        for (layer_index, layer) in self.layers.iter().enumerate() {
+            let layer_size = layer.gates.iter().map(|gate| gate.output_index + 1).max().unwrap_or(1);
+            let mut next_wires = vec![None; layer_size];
+
+            for gate in &layer.gates {
+                if layer_index > 0 {
+                    for wire in [gate.left_index, gate.right_index] {
+                        if wire >= wires.len() {
+                            wires.resize(wire + 1, None);
+                        }
+                        if wires[wire].is_none() {
+                            let row = plonkish.add_gate(Selectors::constant(F::zero()));
+                            wires[wire] = Some(Cell::new(Column::A, row));
+                        }
+                    }
+                }
+
+                let row = plonkish.add_gate(match gate.gate_operator {
+                    Operator::Add => Selectors::addition(),
+                    Operator::Mul => Selectors::multiplication(),
+                    Operator::Affine(constants) => Selectors {
+                        q_l: constants.left,
+                        q_r: constants.right,
+                        q_o: -F::one(),
+                        q_m: F::zero(),
+                        q_c: constants.constant,
+                    },
+                });
+
+                for (column, wire) in [(Column::A, gate.left_index), (Column::B, gate.right_index)] {
+                    let cell = Cell::new(column, row);
+                    if wire >= wires.len() {
+                        wires.resize(wire + 1, None);
+                    }
+
+                    // only the inputs can be read before anything holds them
+                    match wires[wire] {
+                        Some(source) => plonkish.copy(source, cell),
+                        None => wires[wire] = Some(cell),
+                    }
+                }
+
+                next_wires[gate.output_index] = Some(Cell::new(Column::C, row));
+            }
+
+            wires = next_wires;
+        }
+
+        plonkish
+    }
+
+    // The trace of the rows produced by to_plonkish for the given input
+    pub fn plonkish_trace(&self, input: &[F]) -> Result<Trace<F>, CircuitError> {
+        let mut trace = Trace::new();
+        for constant in &self.input_constants {
+            trace.push_row(*constant, F::zero(), F::zero());
+        }
+        let mut current_input = self.input_layer(input.to_vec());
+        let mut held = vec![true; current_input.len()];
+
+        for (layer_index, layer) in self.layers.iter().enumerate() {
+            layer.check_inputs(layer_index, current_input.len())?;
+
+            let layer_size = layer.gates.iter().map(|gate| gate.output_index + 1).max().unwrap_or(1);
+            let mut output_vec = vec![F::zero(); layer_size];
+            let mut next_held = vec![false; layer_size];
+
+            for gate in &layer.gates {
+                // the zero rows to_plonkish adds for wires no gate produced
+                if layer_index > 0 {
+                    for wire in [gate.left_index, gate.right_index] {
+                        if !held[wire] {
+                            trace.push_row(F::zero(), F::zero(), F::zero());
+                            held[wire] = true;
+                        }
+                    }
+                }
+
+                let output = gate.execute_gate(&current_input);
+                trace.push_row(current_input[gate.left_index], current_input[gate.right_index], output);
+                output_vec[gate.output_index] = output;
+                next_held[gate.output_index] = true;
+            }
+
+            current_input = output_vec;
+            held = next_held;
+        }
+
+        Ok(trace)
+    }
+
+    // The rows of to_plonkish and their trace for the given input as multilinear selector and witness columns, see
+    // MultilinearColumns; its wiring takes the to_plonkish circuit
+    pub fn plonkish_columns(&self, input: &[F]) -> Result<MultilinearColumns<F>, CircuitError> {
+        let trace = self.plonkish_trace(input)?;
+        Ok(MultilinearColumns::new(&self.to_plonkish(), &trace).expect("plonkish_trace has a row for every gate"))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circuit, Gate, Layer, Operator};
+    use crate::arbitrary::circuit_and_input;
+    use crate::poseidon_gadget::{poseidon_circuit, poseidon_circuit_input};
+    use ark_bn254::Fr;
+    use poseidon::poseidon::PoseidonParams;
+
+    // (a + b) * (b * c), so b is read twice and both layer 1 outputs feed layer 2
+    fn two_layer_circuit() -> Circuit<Fr> {
+        let layer1 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Add), Gate::new(1, 2, 1, Operator::Mul)]);
+        let layer2 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Mul)]);
+        Circuit::new(vec![layer1, layer2])
+    }
+
+    #[test]
+    fn test_exported_circuit_is_satisfied_by_its_trace() {
+        let mut circuit = two_layer_circuit();
+        let input = vec![Fr::from(3), Fr::from(4), Fr::from(5)];
+
+        let plonkish = circuit.to_plonkish();
+        let trace = circuit.plonkish_trace(&input).unwrap();
+
+        // one row per gate, and three copies: b into the Mul gate, both outputs into layer 2
+        assert_eq!(plonkish.no_of_rows(), 3);
+        assert_eq!(plonkish.copy_constraints.len(), 3);
+        assert!(plonkish.is_satisfied(&trace));
+        assert_eq!(trace.c[2], circuit.evaluate(input).unwrap()[0]);
+
+        // rewiring the second layer to a different value breaks a copy constraint, not a gate
+        let mut wrong = trace.clone();
+        wrong.a[2] = Fr::from(8);
+        wrong.c[2] = Fr::from(8 * 20);
+        assert_eq!(plonkish.first_unsatisfied_gate(&wrong), None);
+        assert!(!plonkish.is_satisfied(&wrong));
+    }
+
+    #[test]
+    fn test_exported_poseidon_gadget() {
+        let params = PoseidonParams::<Fr>::new(3, 4, 3, 5).unwrap();
+        let state = [Fr::from(1), Fr::from(2), Fr::from(3)];
+        let circuit = poseidon_circuit(&params).unwrap();
+
+        let plonkish = circuit.to_plonkish();
+        let trace = circuit.plonkish_trace(&poseidon_circuit_input(&params, &state).unwrap()).unwrap();
+        assert!(plonkish.is_satisfied(&trace));
+
+        let mut expected = state;
+        params.permute(&mut expected).unwrap();
+
+        let outputs = &trace.c[trace.c.len() - 3..];
+        assert_eq!(outputs, expected.as_slice());
+    }
+
+    #[test]
+    fn test_input_constants_get_fixed_rows() {
+        // (10 + a) * (b * 2) with 10 and 2 declared on the circuit
+        let layer1 = Layer::new(vec![Gate::new(0, 2, 0, Operator::Add), Gate::new(3, 1, 1, Operator::Mul)]);
+        let layer2 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Mul)]);
+        let mut circuit = Circuit::new(vec![layer1, layer2]).with_input_constants(vec![Fr::from(10), Fr::from(2)]);
+        let input = vec![Fr::from(3), Fr::from(4)];
+
+        let plonkish = circuit.to_plonkish();
+        let trace = circuit.plonkish_trace(&input).unwrap();
+
+        // two constant rows ahead of the three gates
+        assert_eq!(plonkish.no_of_rows(), 5);
+        assert!(plonkish.is_satisfied(&trace));
+        assert_eq!(trace.c[4], circuit.evaluate(input).unwrap()[0]);
+
+        // a trace with another constant breaks its row, even though the gates reading it are consistent
+        let mut wrong = trace.clone();
+        wrong.a[0] = Fr::from(11);
+        wrong.a[2] = Fr::from(11);
+        wrong.c[2] = Fr::from(14);
+        wrong.a[4] = Fr::from(14);
+        wrong.c[4] = Fr::from(14 * 8);
+        assert_eq!(plonkish.first_unsatisfied_gate(&wrong), Some(0));
+    }
+
+    #[test]
+    fn test_dangling_wires_are_copies_of_a_zero_row() {
+        // layer 1 has no gate for wire 1, which evaluate reads as zero in both layer 2 gates
+        let layer1 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Add), Gate::new(1, 2, 2, Operator::Mul)]);
+        let layer2 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Add), Gate::new(1, 2, 1, Operator::Add)]);
+        let mut circuit = Circuit::new(vec![layer1, layer2]);
+        let input = vec![Fr::from(3), Fr::from(4), Fr::from(5)];
+
+        let plonkish = circuit.to_plonkish();
+        let trace = circuit.plonkish_trace(&input).unwrap();
+
+        // a zero row between the layers, and the two cells reading wire 1 are copies of it
+        assert_eq!(plonkish.no_of_rows(), 5);
+        assert_eq!(trace.a[2], Fr::from(0));
+        assert!(plonkish.is_satisfied(&trace));
+        assert_eq!(trace.c[3..], circuit.evaluate(input).unwrap()[..]);
+
+        // a trace that gives the dangling wire another value satisfies every gate but not the wiring
+        let mut wrong = trace.clone();
+        wrong.b[3] = Fr::from(1);
+        wrong.c[3] += Fr::from(1);
+        assert_eq!(plonkish.first_unsatisfied_gate(&wrong), None);
+        assert!(!plonkish.is_satisfied(&wrong));
+    }
+
+    #[test]
+    fn test_exported_columns_pass_the_zerocheck_rows() {
+        let circuit = two_layer_circuit();
+        let input = vec![Fr::from(3), Fr::from(4), Fr::from(5)];
+
+        let columns = circuit.plonkish_columns(&input).unwrap();
+        assert_eq!(columns.no_of_vars, 2);
+        assert_eq!(columns.first_unsatisfied_row(), None);
+        assert_eq!(columns.c[2], Fr::from(7 * 20));
+
+        // the wiring of the to_plonkish copies holds on the padded witness
+        let witness = columns.witness();
+        let sigma = columns.wiring(&circuit.to_plonkish());
+        assert!(sigma.iter().enumerate().all(|(cell, target)| witness[cell] == witness[*target]));
+
+        assert!(circuit.plonkish_columns(&input[..1]).is_err());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_exported_circuit_is_satisfied_by_its_trace((circuit, input) in circuit_and_input::<Fr>()) {
+            let plonkish = circuit.to_plonkish();
+            let trace = circuit.plonkish_trace(&input).unwrap();
+            proptest::prop_assert!(plonkish.is_satisfied(&trace));
+
+            // the last row is the last gate of the output layer
+            let mut circuit = circuit;
+            circuit.evaluate(input).unwrap();
+            let outputs = circuit.get_round_poly(0).unwrap();
+            proptest::prop_assert_eq!(trace.c.last(), outputs.last());
+
+            // changing an output breaks its gate
+            let mut wrong = trace;
+            let last = wrong.c.len() - 1;
+            wrong.c[last] += Fr::from(1);
+            proptest::prop_assert!(!plonkish.is_satisfied(&wrong));
+        }
+    }
+}
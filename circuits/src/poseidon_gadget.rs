@@ -0,0 +1,185 @@
+use ark_ff::PrimeField;
+use poseidon::poseidon::PoseidonParams;
+use crate::error::GadgetError;
+use crate::{Circuit, Gate, Layer, Operator};
+
+// Poseidon permutation as a layered circuit.
+//
+// Gates only read wires of the previous layer, so a value a layer does not change is copied up by an Affine gate
+// 1 * x + 0 * x + 0. The round constants and the MDS matrix are Affine constants, so the input is just the state
+// and every layer is as wide as the state, or twice as wide in the S-box.
+//
+// Each round is
+// 1. three layers for the S-box x^5: (x, x^2), (x, x^4), x^4 * x
+// 2. the MDS mix as a tree of Affine layers, the first one scaling pairs of state elements by their MDS entries,
+//    M[row][c] * s_c + M[row][c + 1] * s_(c + 1), and the next ones summing pairs of terms, with the next round's
+//    constants added by the last one
+// and one Affine layer before the first round adds its constants, so the circuit outputs the permuted state and
+// evaluate returns its first element, which is the Poseidon hash of the remaining inputs
+pub fn poseidon_circuit<F: PrimeField>(params: &PoseidonParams<F>) -> Result<Circuit<F>, GadgetError> {
+    if params.alpha != 5 {
+        return Err(GadgetError::UnsupportedSbox { alpha: params.alpha });
+    }
+
+    let width = params.width;
+    let half_full_rounds = params.full_rounds / 2;
+    let mut builder = GadgetBuilder::new();
+
+    let first_constants = params.round_constants.first().map(Vec::as_slice).unwrap_or(&[]);
+    let add_constants = (0..width)
+        .map(|i| (i, i, Operator::affine(F::one(), F::zero(), first_constants.get(i).copied().unwrap_or(F::zero()))))
+        .collect();
+    builder.push_layer(add_constants);
+
+    for round in 0..params.round_constants.len() {
+        // S-box on every element in full rounds, on the first one in partial rounds
+        let no_of_sboxes = if round < half_full_rounds || round >= half_full_rounds + params.partial_rounds {
+            width
+        } else {
+            1
+        };
+
+        let copy = Operator::copy_left();
+        let square = (0..width)
+            .map(|i| (i, i, copy))
+            .chain((0..no_of_sboxes).map(|i| (i, i, Operator::Mul)))
+            .collect();
+        builder.push_layer(square);
+
+        let fourth_power = (0..width)
+            .map(|i| (i, i, copy))
+            .chain((0..no_of_sboxes).map(|i| (width + i, width + i, Operator::Mul)))
+            .collect();
+        builder.push_layer(fourth_power);
+
+        let fifth_power = (0..width)
+            .map(|i| if i < no_of_sboxes { (i, width + i, Operator::Mul) } else { (i, i, copy) })
+            .collect();
+        builder.push_layer(fifth_power);
+
+        // the constants the last layer of the mix adds, those of the next round
+        let next_constants: Vec<F> = match params.round_constants.get(round + 1) {
+            Some(constants) => constants.clone(),
+            None => vec![F::zero(); width],
+        };
+
+        // MDS: each row's terms M[row][c] * s_c in pairs, then sums of pairs until one term per row is left
+        let mut terms = width.div_ceil(2);
+        let last = terms == 1;
+        let mut products = Vec::with_capacity(width * terms);
+        for (mds_row, next_constant) in params.mds.iter().zip(&next_constants) {
+            let constant = if last { *next_constant } else { F::zero() };
+            for pair in 0..terms {
+                let column = 2 * pair;
+                let operator = match mds_row.get(column + 1) {
+                    Some(right) => Operator::affine(mds_row[column], *right, constant),
+                    None => Operator::affine(mds_row[column], F::zero(), constant),
+                };
+                products.push((column, (column + 1).min(width - 1), operator));
+            }
+        }
+        builder.push_layer(products);
+
+        while terms > 1 {
+            let next_terms = terms.div_ceil(2);
+            let last = next_terms == 1;
+            let mut sums = Vec::with_capacity(width * next_terms);
+
+            for (row, next_constant) in next_constants.iter().enumerate() {
+                let constant = if last { *next_constant } else { F::zero() };
+                let start = row * terms;
+                for pair in 0..terms / 2 {
+                    sums.push((start + 2 * pair, start + 2 * pair + 1, Operator::affine(F::one(), F::one(), constant)));
+                }
+                if terms % 2 == 1 {
+                    let wire = start + terms - 1;
+                    sums.push((wire, wire, Operator::affine(F::one(), F::zero(), constant)));
+                }
+            }
+
+            builder.push_layer(sums);
+            terms = next_terms;
+        }
+    }
+
+    Ok(Circuit::new(builder.layers))
+}
+
+// The input vector for poseidon_circuit, the state itself: the constants are in the circuit
+pub fn poseidon_circuit_input<F: PrimeField>(params: &PoseidonParams<F>, state: &[F]) -> Result<Vec<F>, GadgetError> {
+    if state.len() != params.width {
+        return Err(GadgetError::WrongStateWidth { expected: params.width, provided: state.len() });
+    }
+
+    Ok(state.to_vec())
+}
+
+// The layers of a gadget as it is laid out, shared by the gadgets of this crate
+pub(crate) struct GadgetBuilder<F: PrimeField> {
+    pub(crate) layers: Vec<Layer<F>>,
+}
+
+impl <F: PrimeField>GadgetBuilder<F> {
+    pub(crate) fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    // Adds a layer of the given (left, right, operator) gates, gate i writing output i
+    pub(crate) fn push_layer(&mut self, gates: Vec<(usize, usize, Operator<F>)>) {
+        let gates = gates
+            .into_iter()
+            .enumerate()
+            .map(|(output, (left, right, operator))| Gate::new(left, right, output, operator))
+            .collect();
+        self.layers.push(Layer::new(gates));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use poseidon::poseidon::bn254_params;
+
+    #[test]
+    fn test_gadget_matches_native_hash() {
+        let params = bn254_params();
+        let mut circuit = poseidon_circuit(params).unwrap();
+
+        let input = poseidon_circuit_input(params, &[Fr::from(0), Fr::from(1), Fr::from(2)]).unwrap();
+        let output = circuit.evaluate(input).unwrap();
+
+        assert_eq!(output[0], params.hash(&[Fr::from(1), Fr::from(2)]).unwrap());
+    }
+
+    #[test]
+    fn test_gadget_matches_native_permutation() {
+        // width 4 exercises an MDS row sum with an even number of terms
+        let params = PoseidonParams::<Fr>::new(4, 4, 3, 5).unwrap();
+        let state = [Fr::from(11), Fr::from(22), Fr::from(33), Fr::from(44)];
+
+        let mut circuit = poseidon_circuit(&params).unwrap();
+        circuit.evaluate(poseidon_circuit_input(&params, &state).unwrap()).unwrap();
+
+        let mut expected = state;
+        params.permute(&mut expected).unwrap();
+
+        // the first round's constants, then 7 rounds of 3 S-box layers and log2(4) mixing layers
+        assert_eq!(circuit.layers.len(), 1 + 7 * 5);
+        assert!(circuit.layers.iter().all(|layer| layer.gates.len() <= 2 * 4));
+        assert_eq!(circuit.get_round_poly(0), Ok(expected.to_vec()));
+    }
+
+    #[test]
+    fn test_gadget_parameter_errors() {
+        let params = bn254_params();
+        assert_eq!(
+            poseidon_circuit_input(params, &[Fr::from(1), Fr::from(2)]),
+            Err(GadgetError::WrongStateWidth { expected: 3, provided: 2 })
+        );
+
+        let cube = PoseidonParams::<Fr>::new(3, 4, 3, 3).unwrap();
+        assert_eq!(poseidon_circuit(&cube).err(), Some(GadgetError::UnsupportedSbox { alpha: 3 }));
+    }
+}
@@ -0,0 +1,226 @@
+use ark_ff::{BigInteger, PrimeField};
+use std::fmt;
+use crate::error::CircuitError;
+use crate::{Circuit, Operator};
+
+// A record of one evaluation, gate by gate, for finding the gate where a big circuit goes wrong.
+//
+// Every gate is listed in the order evaluate runs them with the wires it read, their values and the value it wrote,
+// so a wrong output can be bisected by comparing against a report of a circuit known to be right (first_divergence)
+// or by reading the pretty-printed report. Field arithmetic never overflows, but a circuit meant to compute over the
+// integers breaks silently when a value reaches p, so Add and Mul gates also say whether their result wrapped around
+// the modulus, and every value carries its width in bits
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionReport<F: PrimeField> {
+    // the number of wires of the input and of every layer after it
+    pub layer_widths: Vec<usize>,
+    pub gates: Vec<GateExecution<F>>,
+    pub output: Vec<F>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateExecution<F: PrimeField> {
+    pub layer: usize,
+    // the gate's position in its layer
+    pub gate: usize,
+    pub operator: Operator<F>,
+    pub left_wire: usize,
+    pub right_wire: usize,
+    pub output_wire: usize,
+    pub left: F,
+    pub right: F,
+    pub output: F,
+    // whether the integer sum or product of the inputs is p or more. None for Affine gates, whose constants are
+    // often negative and so close to p that wrapping is what they are meant to do
+    pub wrapped: Option<bool>,
+}
+
+impl <F: PrimeField>Circuit<F> {
+    // Evaluates the circuit like evaluate, without storing round_poly, and reports every gate on the way
+    pub fn evaluate_with_report(&self, input: Vec<F>) -> Result<ExecutionReport<F>, CircuitError> {
+        let widths = self.layer_widths(input.len())?;
+
+        let mut gates = Vec::with_capacity(self.layers.iter().map(|layer| layer.gates.len()).sum());
+        let mut current_input = self.input_layer(input);
+        let mut layer_widths = vec![current_input.len()];
+
+        for (layer_index, (layer, width)) in self.layers.iter().zip(&widths).enumerate() {
+            let mut output_vec = vec![F::zero(); *width];
+
+            for (gate_index, gate) in layer.gates.iter().enumerate() {
+                let (left, right) = (current_input[gate.left_index], current_input[gate.right_index]);
+                let output = gate.execute_gate(&current_input);
+                output_vec[gate.output_index] = output;
+
+                gates.push(GateExecution {
+                    layer: layer_index,
+                    gate: gate_index,
+                    operator: gate.gate_operator,
+                    left_wire: gate.left_index,
+                    right_wire: gate.right_index,
+                    output_wire: gate.output_index,
+                    left,
+                    right,
+                    output,
+                    wrapped: wrapped(gate.gate_operator, left, right),
+                });
+            }
+
+            layer_widths.push(*width);
+            current_input = output_vec;
+        }
+
+        Ok(ExecutionReport { layer_widths, gates, output: current_input })
+    }
+}
+
+impl <F: PrimeField>ExecutionReport<F> {
+    // The first gate whose output differs from the same gate in the other report, e.g. one of a reference circuit.
+    // Gates are compared in evaluation order, so the gate found is the first one to go wrong and every later
+    // difference may just follow from it
+    pub fn first_divergence(&self, other: &Self) -> Option<&GateExecution<F>> {
+        self.gates
+            .iter()
+            .zip(&other.gates)
+            .find(|(gate, other)| gate.output != other.output || gate.output_wire != other.output_wire)
+            .map(|(gate, _)| gate)
+    }
+
+    pub fn wrapped_gates(&self) -> impl Iterator<Item = &GateExecution<F>> {
+        self.gates.iter().filter(|gate| gate.wrapped == Some(true))
+    }
+
+    // The values the gates of one layer wrote, in gate order
+    pub fn layer(&self, layer: usize) -> impl Iterator<Item = &GateExecution<F>> {
+        self.gates.iter().filter(move |gate| gate.layer == layer)
+    }
+}
+
+// a + b and a * b of the canonical integers against the modulus
+fn wrapped<F: PrimeField>(operator: Operator<F>, left: F, right: F) -> Option<bool> {
+    let (left, right) = (left.into_bigint(), right.into_bigint());
+    match operator {
+        Operator::Add => {
+            let mut sum = left;
+            let carry = sum.add_with_carry(&right);
+            Some(carry || sum >= F::MODULUS)
+        }
+        Operator::Mul => {
+            let (low, high) = left.mul(&right);
+            Some(!high.is_zero() || low >= F::MODULUS)
+        }
+        Operator::Affine(_) => None,
+    }
+}
+
+fn bits<F: PrimeField>(value: F) -> u32 {
+    value.into_bigint().num_bits()
+}
+
+impl <F: PrimeField>fmt::Display for GateExecution<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operator = match self.operator {
+            Operator::Add => "add".to_string(),
+            Operator::Mul => "mul".to_string(),
+            Operator::Affine(constants) => format!("affine ({}, {}, {})", constants.left, constants.right, constants.constant),
+        };
+        write!(
+            f,
+            "layer {} gate {}: {} w{} = {}, w{} = {} -> w{} = {} ({} bits)",
+            self.layer,
+            self.gate,
+            operator,
+            self.left_wire,
+            self.left,
+            self.right_wire,
+            self.right,
+            self.output_wire,
+            self.output,
+            bits(self.output)
+        )?;
+        if self.wrapped == Some(true) {
+            write!(f, ", wrapped")?;
+        }
+        Ok(())
+    }
+}
+
+impl <F: PrimeField>fmt::Display for ExecutionReport<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "input: {} wires", self.layer_widths[0])?;
+        for (layer, width) in self.layer_widths.iter().enumerate().skip(1) {
+            writeln!(f, "layer {}: {} wires", layer - 1, width)?;
+            for gate in self.layer(layer - 1) {
+                writeln!(f, "  {}", gate)?;
+            }
+        }
+        let output: Vec<String> = self.output.iter().map(|value| value.to_string()).collect();
+        write!(f, "output: [{}]", output.join(", "))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circuit, Gate, Layer, Operator};
+    use ark_bn254::Fr;
+
+    // (a + b) * (b * c)
+    fn two_layer_circuit(second: Operator<Fr>) -> Circuit<Fr> {
+        let layer1 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Add), Gate::new(1, 2, 1, second)]);
+        let layer2 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Mul)]);
+        Circuit::new(vec![layer1, layer2])
+    }
+
+    #[test]
+    fn test_report_lists_every_gate() {
+        let mut circuit = two_layer_circuit(Operator::Mul);
+        let input = vec![Fr::from(3), Fr::from(4), Fr::from(5)];
+        let report = circuit.evaluate_with_report(input.clone()).unwrap();
+
+        assert_eq!(report.layer_widths, vec![3, 2, 1]);
+        assert_eq!(report.gates.len(), 3);
+        assert_eq!((report.gates[1].left, report.gates[1].right, report.gates[1].output), (Fr::from(4), Fr::from(5), Fr::from(20)));
+        assert_eq!(report.output, circuit.evaluate(input).unwrap());
+        assert_eq!(report.wrapped_gates().count(), 0);
+
+        assert_eq!(
+            report.to_string(),
+            "input: 3 wires\n\
+             layer 0: 2 wires\n  \
+             layer 0 gate 0: add w0 = 3, w1 = 4 -> w0 = 7 (3 bits)\n  \
+             layer 0 gate 1: mul w1 = 4, w2 = 5 -> w1 = 20 (5 bits)\n\
+             layer 1: 1 wires\n  \
+             layer 1 gate 0: mul w0 = 7, w1 = 20 -> w0 = 140 (8 bits)\n\
+             output: [140]"
+        );
+    }
+
+    #[test]
+    fn test_first_divergence_finds_the_wrong_gate() {
+        let input = vec![Fr::from(3), Fr::from(4), Fr::from(5)];
+        let reference = two_layer_circuit(Operator::Mul).evaluate_with_report(input.clone()).unwrap();
+        // the same circuit with the second gate miswired as an Add
+        let broken = two_layer_circuit(Operator::Add).evaluate_with_report(input).unwrap();
+
+        let divergence = broken.first_divergence(&reference).unwrap();
+        assert_eq!((divergence.layer, divergence.gate, divergence.output), (0, 1, Fr::from(9)));
+        assert_eq!(reference.first_divergence(&reference), None);
+    }
+
+    #[test]
+    fn test_wrapping_around_the_modulus() {
+        let minus_one = -Fr::from(1);
+        let circuit = Circuit::new(vec![Layer::new(vec![
+            Gate::new(0, 1, 0, Operator::Add),
+            Gate::new(0, 0, 1, Operator::Mul),
+            Gate::new(1, 1, 2, Operator::Mul),
+        ])]);
+        let report = circuit.evaluate_with_report(vec![minus_one, Fr::from(2)]).unwrap();
+
+        // p - 1 + 2 and (p - 1)^2 wrap, 2 * 2 does not
+        let wrapped: Vec<usize> = report.wrapped_gates().map(|gate| gate.gate).collect();
+        assert_eq!(wrapped, vec![0, 1]);
+        assert!(report.gates[0].to_string().ends_with("(1 bits), wrapped"));
+    }
+}
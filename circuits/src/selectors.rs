@@ -0,0 +1,135 @@
+use ark_ff::PrimeField;
+use std::collections::BTreeMap;
+use crate::error::CircuitError;
+use crate::{check_label_widths, label_bits, Circuit, GateConstants, Operator};
+
+// The gate identities a layer's wiring is split by, one selector polynomial each.
+//
+// add_i_and_mul_i_mle returns a layer's wiring as a map keyed by the identity a gate enforces, so a new kind of gate
+// is a new key rather than another entry in a fixed vector. Sub and Const are not operators of their own: they are
+// the Affine gates with the constants (1, -1, 0) and (0, 0, c), and the other Affine gates have no selector. Const is
+// the one selector that is not 0 or 1, its entry at the gate's label is c, and its term does not read the wires below
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Selector {
+    Add,
+    Mul,
+    Sub,
+    Const,
+}
+
+impl Selector {
+    // The selector of a gate with this operator and the value of its entry, None for an Affine gate with no selector
+    pub fn of<F: PrimeField>(operator: Operator<F>) -> Option<(Self, F)> {
+        match operator {
+            Operator::Add => Some((Selector::Add, F::one())),
+            Operator::Mul => Some((Selector::Mul, F::one())),
+            Operator::Affine(GateConstants { left, right, constant }) if left.is_one() && right == -F::one() && constant.is_zero() => {
+                Some((Selector::Sub, F::one()))
+            }
+            Operator::Affine(GateConstants { left, right, constant }) if left.is_zero() && right.is_zero() => Some((Selector::Const, constant)),
+            Operator::Affine(_) => None,
+        }
+    }
+
+    // The term of the identity where the selector is selector and the wires below hold left and right
+    pub fn identity<F: PrimeField>(self, selector: F, left: F, right: F) -> F {
+        match self {
+            Selector::Add => selector * (left + right),
+            Selector::Mul => selector * left * right,
+            Selector::Sub => selector * (left - right),
+            Selector::Const => selector,
+        }
+    }
+}
+
+// The nonzero entries of each selector's table, (label, value) sorted by label
+type SelectorEntries<F> = BTreeMap<Selector, Vec<(usize, F)>>;
+
+impl <F: PrimeField>Circuit<F> {
+    // The bits of a layer's gate labels and, for each selector it uses, the label and value of each of its gates
+    pub(crate) fn selector_entries(&self, layer_id: usize) -> Result<(usize, SelectorEntries<F>), CircuitError> {
+        let layer = self.layers.get(layer_id).ok_or(CircuitError::LayerOutOfRange {
+            layer: layer_id,
+            no_of_layers: self.layers.len(),
+        })?;
+
+        let (input_bits, output_bits) = label_bits(layer.gates.len());
+        check_label_widths(layer_id, layer, input_bits, output_bits)?;
+        layer.check_outputs(layer_id)?;
+
+        let mut entries = SelectorEntries::new();
+        for (gate_index, gate) in layer.gates.iter().enumerate() {
+            let (selector, value) =
+                Selector::of(gate.gate_operator).ok_or(CircuitError::NoSelector { layer: layer_id, gate: gate_index })?;
+            entries.entry(selector).or_default().push((gate.label(input_bits), value));
+        }
+
+        for entries in entries.values_mut() {
+            entries.sort_unstable_by_key(|(label, _)| *label);
+        }
+
+        Ok((2 * input_bits + output_bits, entries))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Gate, Layer};
+    use ark_bn254::Fr;
+    use multilinear::multilinear::MultilinearPolynomial;
+
+    // (a - b) + 7 and a * c, from four inputs
+    fn circuit() -> Circuit<Fr> {
+        let sub = Operator::affine(Fr::from(1), -Fr::from(1), Fr::from(0));
+        let seven = Operator::affine(Fr::from(0), Fr::from(0), Fr::from(7));
+        Circuit::new(vec![
+            Layer::new(vec![Gate::new(0, 1, 0, sub), Gate::new(0, 2, 1, Operator::Mul), Gate::new(3, 3, 2, seven)]),
+            Layer::new(vec![Gate::new(0, 2, 0, Operator::Add), Gate::new(1, 1, 1, Operator::Mul)]),
+        ])
+    }
+
+    #[test]
+    fn test_selectors_are_keyed_by_identity() {
+        let circuit = circuit();
+        let tables = circuit.add_i_and_mul_i_mle(0).unwrap();
+        assert_eq!(tables.keys().copied().collect::<Vec<_>>(), [Selector::Mul, Selector::Sub, Selector::Const]);
+
+        // one entry per gate, at its label, 1 for Sub and Mul and the constant for Const
+        let (input_bits, _) = label_bits(3);
+        assert_eq!(tables[&Selector::Sub].iter().filter(|value| **value != Fr::from(0)).count(), 1);
+        assert_eq!(tables[&Selector::Const][Gate::new(3, 3, 2, Operator::<Fr>::Add).label(input_bits)], Fr::from(7));
+
+        // the sparse evaluation agrees with the tables
+        let point: Vec<Fr> = (0..tables[&Selector::Mul].len().trailing_zeros() as u64).map(|i| Fr::from(i * 5 + 2)).collect();
+        let values = circuit.evaluate_add_i_and_mul_i(0, &point).unwrap();
+        for (selector, table) in &tables {
+            assert_eq!(values[selector], MultilinearPolynomial::new(table).evaluate(&point));
+        }
+
+        // a layer of Add and Mul gates has add_i and mul_i only
+        assert_eq!(circuit.add_i_and_mul_i_mle(1).unwrap().keys().copied().collect::<Vec<_>>(), [Selector::Add, Selector::Mul]);
+    }
+
+    #[test]
+    fn test_selector_of_and_identity() {
+        assert_eq!(Selector::of(Operator::affine(Fr::from(0), Fr::from(0), Fr::from(7))), Some((Selector::Const, Fr::from(7))));
+        assert_eq!(Selector::of(Operator::affine(Fr::from(2), Fr::from(0), Fr::from(7))), None);
+        assert_eq!(Selector::of::<Fr>(Operator::Mul), Some((Selector::Mul, Fr::from(1))));
+
+        assert_eq!(Selector::Sub.identity(Fr::from(2), Fr::from(9), Fr::from(4)), Fr::from(10));
+        assert_eq!(Selector::Const.identity(Fr::from(7), Fr::from(9), Fr::from(4)), Fr::from(7));
+    }
+
+    #[test]
+    fn test_affine_gates_without_a_selector_are_refused() {
+        let circuit = Circuit::new(vec![Layer::new(vec![
+            Gate::new(0, 1, 0, Operator::affine(Fr::from(1), -Fr::from(1), Fr::from(0))),
+            Gate::new(0, 1, 1, Operator::affine(Fr::from(2), Fr::from(3), Fr::from(1))),
+        ])]);
+        assert_eq!(circuit.add_i_and_mul_i_mle(0), Err(CircuitError::NoSelector { layer: 0, gate: 1 }));
+        assert_eq!(circuit.evaluate_add_i_and_mul_i(0, &[Fr::from(0); 5]), Err(CircuitError::NoSelector { layer: 0, gate: 1 }));
+        assert_eq!(circuit.add_i_and_mul_i_mle(3), Err(CircuitError::LayerOutOfRange { layer: 3, no_of_layers: 1 }));
+    }
+}
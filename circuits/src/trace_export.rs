@@ -0,0 +1,102 @@
+use ark_ff::{BigInteger, PrimeField};
+use std::io::{self, Write};
+use crate::report::ExecutionReport;
+use crate::Operator;
+
+// The columns of the CSV trace, one row per gate in evaluation order
+pub const TRACE_CSV_HEADER: &str = "layer,gate,op,constants,left_wire,right_wire,output_wire,left,right,output,output_bits,wrapped";
+
+// Writes an execution report as a table for notebooks, e.g. pandas.read_csv, one row per gate with its layer and
+// position, its operator, the wires it read and wrote and their values.
+//
+// Values are the canonical integers in decimal, which Python reads as exact ints however wide they are. constants
+// holds an Affine gate's a, b and c separated by spaces and is empty otherwise, and wrapped is true or false for Add
+// and Mul and empty for Affine, as in GateExecution. No value holds a comma or a quote, so nothing needs escaping.
+// Columnar formats like Parquet are a conversion of this table away in any dataframe library, and are left to it
+// rather than pulling an Arrow stack into the crate
+impl <F: PrimeField>ExecutionReport<F> {
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", TRACE_CSV_HEADER)?;
+        for gate in &self.gates {
+            let (op, constants) = match gate.operator {
+                Operator::Add => ("add", String::new()),
+                Operator::Mul => ("mul", String::new()),
+                Operator::Affine(constants) => ("affine", format!("{} {} {}", constants.left, constants.right, constants.constant)),
+            };
+            let wrapped = gate.wrapped.map(|wrapped| wrapped.to_string()).unwrap_or_default();
+
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{}",
+                gate.layer,
+                gate.gate,
+                op,
+                constants,
+                gate.left_wire,
+                gate.right_wire,
+                gate.output_wire,
+                gate.left,
+                gate.right,
+                gate.output,
+                gate.output.into_bigint().num_bits(),
+                wrapped
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut bytes = Vec::new();
+        self.write_csv(&mut bytes).expect("writing into a Vec cannot fail");
+        String::from_utf8(bytes).expect("the table is ASCII")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Gate, Layer};
+    use ark_bn254::Fr;
+
+    // (a + b) * (b * c)
+    fn circuit() -> Circuit<Fr> {
+        let layer1 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Add), Gate::new(1, 2, 1, Operator::Mul)]);
+        let layer2 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Mul)]);
+        Circuit::new(vec![layer1, layer2])
+    }
+
+    #[test]
+    fn test_trace_csv_has_a_row_per_gate() {
+        let report = circuit().evaluate_with_report(vec![Fr::from(3), Fr::from(4), Fr::from(5)]).unwrap();
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], TRACE_CSV_HEADER);
+        assert_eq!(
+            &lines[1..],
+            ["0,0,add,,0,1,0,3,4,7,3,false", "0,1,mul,,1,2,1,4,5,20,5,false", "1,0,mul,,0,1,0,7,20,140,8,false"]
+        );
+    }
+
+    #[test]
+    fn test_every_row_has_every_column() {
+        let report = circuit().evaluate_with_report(vec![-Fr::from(1), Fr::from(2), Fr::from(5)]).unwrap();
+        let columns = TRACE_CSV_HEADER.split(',').count();
+
+        for line in report.to_csv().lines() {
+            assert_eq!(line.split(',').count(), columns);
+        }
+        // -1 + 2 wraps around the modulus
+        assert!(report.to_csv().lines().nth(1).unwrap().ends_with(",1,true"));
+    }
+
+    #[test]
+    fn test_affine_rows_name_their_constants() {
+        let mut circuit = Circuit::new(vec![Layer::new(vec![Gate::new(0, 1, 0, Operator::affine(Fr::from(2), Fr::from(3), Fr::from(1)))])]);
+        let report = circuit.evaluate_with_report(vec![Fr::from(4), Fr::from(5)]).unwrap();
+        assert_eq!(report.to_csv().lines().nth(1), Some("0,0,affine,2 3 1,0,1,0,4,5,24,5,"));
+        assert!(report.gates[0].to_string().starts_with("layer 0 gate 0: affine (2, 3, 1) w0 = 4"));
+        assert_eq!(circuit.evaluate(vec![Fr::from(4), Fr::from(5)]), Ok(vec![Fr::from(24)]));
+    }
+}
@@ -0,0 +1,365 @@
+use ark_ff::PrimeField;
+use std::fmt;
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use transcript::encoding::field_to_bytes;
+use univariate_poly::domain::EvaluationDomain;
+use univariate_poly::univariate::UnivariatePoly;
+use crate::merkle::{Keccak256Hasher, MerkleProof, MerkleTree};
+
+// FRI (Fast Reed-Solomon Interactive Oracle Proof of Proximity) low-degree test.
+//
+// The prover evaluates a polynomial of degree < degree_bound over a domain blowup_factor times larger
+// (a Reed-Solomon codeword) and commits to it with a Merkle tree. Each round it receives a challenge beta and folds
+// the codeword in half using
+// f_next(x^2) = (f(x) + f(-x)) / 2 + beta * (f(x) - f(-x)) / (2x)
+// which halves the degree. After log2(degree_bound) rounds the polynomial is a constant that is sent in the clear.
+// The verifier then spot-checks random positions: the opened values of each layer must fold into the next layer
+#[derive(Debug, Clone, PartialEq)]
+pub struct FriParams {
+    pub blowup_factor: usize,
+    pub num_queries: usize,
+}
+
+// The two values f(x) and f(-x) used by a fold, with their Merkle paths in the layer's tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct FriLayerOpening<F: PrimeField> {
+    pub value: F,
+    pub value_proof: MerkleProof,
+    pub sibling_value: F,
+    pub sibling_proof: MerkleProof,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FriProof<F: PrimeField> {
+    pub layer_roots: Vec<[u8; 32]>,
+    pub final_value: F,
+    // queries[q][layer] opens the q-th query position in each layer
+    pub queries: Vec<Vec<FriLayerOpening<F>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FriError {
+    // the blowup factor must be a power of two and at least 2
+    BlowupFactor { blowup_factor: usize },
+    // with no queries the verifier would check nothing beyond the final value
+    NoQueries,
+    // the degree bound must be a power of two and at least 2, so there is at least one folding round
+    DegreeBound { degree_bound: usize },
+    // the polynomial has more than degree_bound coefficients
+    DegreeTooHigh { degree_bound: usize, no_of_coefficients: usize },
+    // the field has no evaluation domain of degree_bound * blowup_factor elements
+    DomainTooLarge { size: usize },
+}
+
+impl fmt::Display for FriError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FriError::BlowupFactor { blowup_factor } => write!(f, "blowup factor {} is not a power of two >= 2", blowup_factor),
+            FriError::NoQueries => write!(f, "FRI needs at least one query"),
+            FriError::DegreeBound { degree_bound } => write!(f, "degree bound {} is not a power of two >= 2", degree_bound),
+            FriError::DegreeTooHigh { degree_bound, no_of_coefficients } => {
+                write!(f, "polynomial of {} coefficients exceeds the degree bound {}", no_of_coefficients, degree_bound)
+            }
+            FriError::DomainTooLarge { size } => write!(f, "the field has no evaluation domain of {} elements", size),
+        }
+    }
+}
+
+impl std::error::Error for FriError {}
+
+impl FriParams {
+    pub fn new(blowup_factor: usize, num_queries: usize) -> Result<Self, FriError> {
+        if !blowup_factor.is_power_of_two() || blowup_factor < 2 {
+            return Err(FriError::BlowupFactor { blowup_factor });
+        }
+        if num_queries == 0 {
+            return Err(FriError::NoQueries);
+        }
+        Ok(Self { blowup_factor, num_queries })
+    }
+
+    // Proves that `polynomial` has degree below degree_bound, at most degree_bound coefficients.
+    // degree_bound must be a power of two and at least 2, so there is at least one folding round
+    pub fn prove<F: PrimeField>(
+        &self,
+        polynomial: &UnivariatePoly<F>,
+        degree_bound: usize,
+        transcript: &mut Transcript,
+    ) -> Result<FriProof<F>, FriError> {
+        if !degree_bound.is_power_of_two() || degree_bound < 2 {
+            return Err(FriError::DegreeBound { degree_bound });
+        }
+        if polynomial.coefficients.len() > degree_bound {
+            return Err(FriError::DegreeTooHigh { degree_bound, no_of_coefficients: polynomial.coefficients.len() });
+        }
+
+        let size = degree_bound * self.blowup_factor;
+        let domain = EvaluationDomain::<F>::new(size).ok_or(FriError::DomainTooLarge { size })?;
+
+        Ok(self.prove_codeword(domain.fft(&polynomial.coefficients), &domain, degree_bound, transcript))
+    }
+
+    // Runs the commit and query phases on a codeword over `domain`
+    fn prove_codeword<F: PrimeField>(
+        &self,
+        mut codeword: Vec<F>,
+        domain: &EvaluationDomain<F>,
+        degree_bound: usize,
+        transcript: &mut Transcript,
+    ) -> FriProof<F> {
+        let no_of_rounds = degree_bound.trailing_zeros() as usize;
+
+        let mut generator = domain.generator;
+        let mut layers: Vec<(Vec<F>, MerkleTree<Keccak256Hasher>)> = Vec::with_capacity(no_of_rounds);
+
+        // Commit phase
+        for _ in 0..no_of_rounds {
+            let tree = MerkleTree::<Keccak256Hasher>::new(&codeword_leaves(&codeword));
+            transcript.append(&tree.root());
+            let beta: F = transcript.random_challenge_as_field_element();
+
+            let next_codeword = fold_codeword(&codeword, generator, beta);
+            layers.push((codeword, tree));
+
+            codeword = next_codeword;
+            generator.square_in_place();
+        }
+
+        // after the last fold the codeword is constant
+        let final_value = codeword[0];
+        transcript.append(&field_to_bytes(final_value));
+
+        // Query phase
+        let queries = query_indices(transcript, self.num_queries, domain.size)
+            .into_iter()
+            .map(|index| {
+                layers
+                    .iter()
+                    .map(|(layer_codeword, tree)| {
+                        let position = index % layer_codeword.len();
+                        let sibling = (position + layer_codeword.len() / 2) % layer_codeword.len();
+
+                        FriLayerOpening {
+                            value: layer_codeword[position],
+                            value_proof: tree.open(position),
+                            sibling_value: layer_codeword[sibling],
+                            sibling_proof: tree.open(sibling),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        FriProof {
+            layer_roots: layers.iter().map(|(_, tree)| tree.root()).collect(),
+            final_value,
+            queries,
+        }
+    }
+
+    pub fn verify<F: PrimeField>(
+        &self,
+        degree_bound: usize,
+        proof: &FriProof<F>,
+        transcript: &mut Transcript,
+    ) -> bool {
+        if !degree_bound.is_power_of_two() || degree_bound < 2 || self.num_queries == 0 {
+            return false;
+        }
+        let domain = match EvaluationDomain::<F>::new(degree_bound * self.blowup_factor) {
+            Some(domain) => domain,
+            None => return false,
+        };
+        let no_of_rounds = degree_bound.trailing_zeros() as usize;
+
+        if proof.layer_roots.len() != no_of_rounds || proof.queries.len() != self.num_queries {
+            return false;
+        }
+
+        // re-derive the folding challenges in the same order as the prover
+        let betas: Vec<F> = proof
+            .layer_roots
+            .iter()
+            .map(|root| {
+                transcript.append(root);
+                transcript.random_challenge_as_field_element()
+            })
+            .collect();
+        transcript.append(&field_to_bytes(proof.final_value));
+
+        let two_inverse = F::from(2u64).inverse().unwrap();
+
+        for (index, query) in query_indices(transcript, self.num_queries, domain.size).into_iter().zip(proof.queries.iter()) {
+            if query.len() != no_of_rounds {
+                return false;
+            }
+
+            let mut layer_size = domain.size;
+            let mut generator = domain.generator;
+            let mut expected_value: Option<F> = None;
+
+            for ((opening, root), beta) in query.iter().zip(proof.layer_roots.iter()).zip(betas.iter()) {
+                let half = layer_size / 2;
+                let position = index % layer_size;
+                let sibling = (position + half) % layer_size;
+
+                // the opened values must be in the committed layer
+                if opening.value_proof.index != position
+                    || opening.sibling_proof.index != sibling
+                    || !MerkleTree::<Keccak256Hasher>::verify(root, &field_to_bytes(opening.value), &opening.value_proof)
+                    || !MerkleTree::<Keccak256Hasher>::verify(root, &field_to_bytes(opening.sibling_value), &opening.sibling_proof)
+                {
+                    return false;
+                }
+
+                // the value must match the fold computed from the previous layer
+                if let Some(expected) = expected_value {
+                    if expected != opening.value {
+                        return false;
+                    }
+                }
+
+                // f(x) is the value at the lower half position, f(-x) the one in the upper half
+                let (positive, negative) = if position < half {
+                    (opening.value, opening.sibling_value)
+                } else {
+                    (opening.sibling_value, opening.value)
+                };
+                let x_inverse = generator.pow([(position % half) as u64]).inverse().unwrap();
+                expected_value = Some(
+                    (positive + negative) * two_inverse + *beta * (positive - negative) * two_inverse * x_inverse,
+                );
+
+                layer_size = half;
+                generator.square_in_place();
+            }
+
+            if expected_value != Some(proof.final_value) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+// f_next[i] = (f[i] + f[i + n/2]) / 2 + beta * (f[i] - f[i + n/2]) / (2 * g^i)
+// since the domain element at i + n/2 is -g^i
+fn fold_codeword<F: PrimeField>(codeword: &[F], generator: F, beta: F) -> Vec<F> {
+    let half = codeword.len() / 2;
+    let two_inverse = F::from(2u64).inverse().unwrap();
+    let generator_inverse = generator.inverse().unwrap();
+
+    let mut x_inverse = F::one();
+    let mut folded = Vec::with_capacity(half);
+
+    for i in 0..half {
+        let positive = codeword[i];
+        let negative = codeword[i + half];
+        folded.push((positive + negative) * two_inverse + beta * (positive - negative) * two_inverse * x_inverse);
+        x_inverse *= generator_inverse;
+    }
+
+    folded
+}
+
+fn query_indices(transcript: &mut Transcript, num_queries: usize, domain_size: usize) -> Vec<usize> {
+    (0..num_queries)
+        .map(|_| {
+            let challenge = transcript.sample_random_challenge();
+            let mut index_bytes = [0u8; 8];
+            index_bytes.copy_from_slice(&challenge[..8]);
+            (u64::from_le_bytes(index_bytes) as usize) % domain_size
+        })
+        .collect()
+}
+
+pub(crate) fn codeword_leaves<F: PrimeField>(codeword: &[F]) -> Vec<Vec<u8>> {
+    codeword.iter().map(|value| field_to_bytes(*value)).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    fn polynomial(no_of_coefficients: u64) -> UnivariatePoly<Fr> {
+        UnivariatePoly::new((0..no_of_coefficients).map(|i| Fr::from(i * i + 3)).collect())
+    }
+
+    #[test]
+    fn test_fri_prove_and_verify() {
+        let params = FriParams::new(4, 8).unwrap();
+        let proof = params.prove(&polynomial(16), 16, &mut Transcript::new()).unwrap();
+
+        assert_eq!(proof.layer_roots.len(), 4);
+        assert!(params.verify(16, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_fri_accepts_lower_degree() {
+        let params = FriParams::new(2, 4).unwrap();
+        let proof = params.prove(&polynomial(5), 8, &mut Transcript::new()).unwrap();
+
+        assert!(params.verify(8, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_fri_rejects_tampered_proof() {
+        let params = FriParams::new(4, 8).unwrap();
+        let proof = params.prove(&polynomial(16), 16, &mut Transcript::new()).unwrap();
+
+        let mut wrong_final = proof.clone();
+        wrong_final.final_value += Fr::from(1);
+        assert!(!params.verify(16, &wrong_final, &mut Transcript::new()));
+
+        let mut wrong_value = proof.clone();
+        wrong_value.queries[0][1].value += Fr::from(1);
+        assert!(!params.verify(16, &wrong_value, &mut Transcript::new()));
+
+        // a claim of a smaller degree bound has the wrong number of layers
+        assert!(!params.verify(8, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_fri_rejects_high_degree_codeword() {
+        // a cheating prover commits to values that are not the evaluations of a degree < 16 polynomial
+        let params = FriParams::new(2, 16).unwrap();
+        let domain = EvaluationDomain::<Fr>::new(32).unwrap();
+        let codeword: Vec<Fr> = (0..32u64).map(|i| Fr::from(i * i * i + 11)).collect();
+
+        let proof = params.prove_codeword(codeword, &domain, 16, &mut Transcript::new());
+
+        assert!(!params.verify(16, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_fri_params_need_a_query() {
+        assert_eq!(FriParams::new(2, 0), Err(FriError::NoQueries));
+        assert_eq!(FriParams::new(3, 4), Err(FriError::BlowupFactor { blowup_factor: 3 }));
+    }
+
+    #[test]
+    fn test_fri_refuses_a_wrong_degree_bound() {
+        let params = FriParams::new(2, 4).unwrap();
+
+        assert_eq!(params.prove(&polynomial(5), 6, &mut Transcript::new()), Err(FriError::DegreeBound { degree_bound: 6 }));
+        assert_eq!(
+            params.prove(&polynomial(9), 8, &mut Transcript::new()),
+            Err(FriError::DegreeTooHigh { degree_bound: 8, no_of_coefficients: 9 })
+        );
+    }
+
+    #[test]
+    fn test_fri_without_queries_is_rejected() {
+        // built around new, the proof opens nothing, so any codeword would pass if it were accepted
+        let params = FriParams { blowup_factor: 2, num_queries: 0 };
+        let domain = EvaluationDomain::<Fr>::new(32).unwrap();
+        let codeword: Vec<Fr> = (0..32u64).map(|i| Fr::from(i * i * i + 11)).collect();
+        let proof = params.prove_codeword(codeword, &domain, 16, &mut Transcript::new());
+
+        assert!(proof.queries.is_empty());
+        assert!(!params.verify(16, &proof, &mut Transcript::new()));
+    }
+}
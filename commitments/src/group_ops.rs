@@ -0,0 +1,226 @@
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use std::ops::AddAssign;
+
+// Multi-scalar multiplication sum_i scalars[i] * bases[i], shared by every commitment scheme of the crate.
+//
+// The bases can be affine points, as in KZG and PST setups, or projective ones, as the folded generators of IPA:
+// the only thing asked of a base is that it can be added to the group. Bases and scalars must have the same length,
+// every function here returns None when they do not.
+//
+// Pippenger's bucket method cuts the scalars into windows of c bits. In one window every base goes into the bucket
+// of its c bit digit, and the window's sum is sum_k k * bucket_k, computed with a running sum from the top bucket
+// in 2^c additions. The windows are then combined from the top with c doublings in between. The cost is about
+// (bits / c) * (n + 2^c) additions against bits * n doublings and additions for the naive sum
+pub fn msm<G, B>(bases: &[B], scalars: &[G::ScalarField]) -> Option<G>
+where
+    G: CurveGroup + AddAssign<B>,
+    B: Copy + Send + Sync,
+{
+    if bases.len() != scalars.len() {
+        return None;
+    }
+    // below a few points the buckets cost more than they save
+    if bases.len() < 4 {
+        return naive_msm(bases, scalars);
+    }
+
+    #[cfg(feature = "parallel")]
+    return parallel_pippenger_msm(bases, scalars, window_size(bases.len()));
+
+    #[cfg(not(feature = "parallel"))]
+    pippenger_msm(bases, scalars, window_size(bases.len()))
+}
+
+// One scalar multiplication per base, the reference the bucket method is tested against
+pub fn naive_msm<G, B>(bases: &[B], scalars: &[G::ScalarField]) -> Option<G>
+where
+    G: CurveGroup + AddAssign<B>,
+    B: Copy,
+{
+    if bases.len() != scalars.len() {
+        return None;
+    }
+
+    Some(
+        bases
+            .iter()
+            .zip(scalars)
+            .map(|(base, scalar)| {
+                let mut point = G::zero();
+                point += *base;
+                point * scalar
+            })
+            .sum(),
+    )
+}
+
+pub fn pippenger_msm<G, B>(bases: &[B], scalars: &[G::ScalarField], window_size: usize) -> Option<G>
+where
+    G: CurveGroup + AddAssign<B>,
+    B: Copy,
+{
+    if bases.len() != scalars.len() {
+        return None;
+    }
+    assert!(window_size > 0 && window_size < 32, "window size must be between 1 and 31 bits");
+
+    let scalars: Vec<_> = scalars.iter().map(|scalar| scalar.into_bigint()).collect();
+    let window_sums: Vec<G> = window_starts::<G::ScalarField>(window_size)
+        .map(|start| window_sum(bases, &scalars, start, window_size))
+        .collect();
+
+    Some(combine_windows(&window_sums, window_size))
+}
+
+// The windows are independent, so each one is summed on its own thread and only the combination is serial
+#[cfg(feature = "parallel")]
+pub fn parallel_pippenger_msm<G, B>(bases: &[B], scalars: &[G::ScalarField], window_size: usize) -> Option<G>
+where
+    G: CurveGroup + AddAssign<B>,
+    B: Copy + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    if bases.len() != scalars.len() {
+        return None;
+    }
+    assert!(window_size > 0 && window_size < 32, "window size must be between 1 and 31 bits");
+
+    let scalars: Vec<_> = scalars.par_iter().map(|scalar| scalar.into_bigint()).collect();
+    let starts: Vec<usize> = window_starts::<G::ScalarField>(window_size).collect();
+    let window_sums: Vec<G> = starts
+        .into_par_iter()
+        .map(|start| window_sum(bases, &scalars, start, window_size))
+        .collect();
+
+    Some(combine_windows(&window_sums, window_size))
+}
+
+// About ln(n) + 2 bits, which balances the n bucket additions of a window against its 2^c bucket sums.
+// ln(n) is taken as 0.69 * log2(n)
+pub fn window_size(no_of_bases: usize) -> usize {
+    if no_of_bases < 32 {
+        3
+    } else {
+        (no_of_bases.ilog2() as usize * 69) / 100 + 2
+    }
+}
+
+fn window_starts<F: PrimeField>(window_size: usize) -> impl Iterator<Item = usize> {
+    (0..F::MODULUS_BIT_SIZE as usize).step_by(window_size)
+}
+
+fn window_sum<G, B>(bases: &[B], scalars: &[<G::ScalarField as PrimeField>::BigInt], start: usize, window_size: usize) -> G
+where
+    G: CurveGroup + AddAssign<B>,
+    B: Copy,
+{
+    // bucket k - 1 collects the bases whose digit is k, digit 0 adds nothing
+    let mut buckets = vec![G::zero(); (1 << window_size) - 1];
+    for (base, scalar) in bases.iter().zip(scalars) {
+        let digit = digit(scalar.as_ref(), start, window_size);
+        if digit != 0 {
+            buckets[digit - 1] += *base;
+        }
+    }
+
+    // running = bucket_top + ... + bucket_k, so adding running once per k counts bucket_k k times
+    let mut running = G::zero();
+    let mut sum = G::zero();
+    for bucket in buckets.into_iter().rev() {
+        running += bucket;
+        sum += running;
+    }
+    sum
+}
+
+fn combine_windows<G: CurveGroup>(window_sums: &[G], window_size: usize) -> G {
+    window_sums.iter().rev().fold(G::zero(), |total, window| {
+        let mut total = total;
+        for _ in 0..window_size {
+            total.double_in_place();
+        }
+        total + window
+    })
+}
+
+// The window_size bits of the little endian limbs starting at bit `start`, which may straddle two limbs
+fn digit(limbs: &[u64], start: usize, window_size: usize) -> usize {
+    let (limb, shift) = (start / 64, start % 64);
+    let mut bits = limbs[limb] >> shift;
+    if shift + window_size > 64 && limb + 1 < limbs.len() {
+        bits |= limbs[limb + 1] << (64 - shift);
+    }
+    (bits & ((1 << window_size) - 1)) as usize
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Affine, G1Projective};
+    use ark_ec::{AffineRepr, VariableBaseMSM};
+    use ark_ff::{One, UniformRand, Zero};
+    use ark_std::test_rng;
+
+    fn instance(n: usize) -> (Vec<G1Affine>, Vec<Fr>) {
+        let mut rng = test_rng();
+        let bases = (0..n).map(|_| G1Projective::rand(&mut rng).into_affine()).collect();
+        let scalars = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+        (bases, scalars)
+    }
+
+    #[test]
+    fn test_pippenger_matches_naive_and_arkworks() {
+        for n in [0, 1, 2, 5, 31, 32, 100] {
+            let (bases, scalars) = instance(n);
+            let expected = G1Projective::msm(&bases, &scalars).unwrap();
+
+            assert_eq!(naive_msm::<G1Projective, _>(&bases, &scalars), Some(expected));
+            assert_eq!(msm::<G1Projective, _>(&bases, &scalars), Some(expected));
+            for window_size in [1, 4, 7, 13] {
+                assert_eq!(pippenger_msm::<G1Projective, _>(&bases, &scalars, window_size), Some(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_projective_bases_and_edge_scalars() {
+        let (bases, _) = instance(40);
+        let projective: Vec<G1Projective> = bases.iter().map(|base| base.into_group()).collect();
+
+        // zero, one and -1, whose top window is full
+        let scalars: Vec<Fr> = (0..40).map(|i| [Fr::zero(), Fr::one(), -Fr::one()][i % 3]).collect();
+        let expected = G1Projective::msm(&bases, &scalars).unwrap();
+
+        assert_eq!(msm::<G1Projective, _>(&projective, &scalars), Some(expected));
+        assert_eq!(pippenger_msm::<G1Projective, _>(&projective, &scalars, 5), Some(expected));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_pippenger() {
+        let (bases, scalars) = instance(100);
+        assert_eq!(
+            parallel_pippenger_msm::<G1Projective, _>(&bases, &scalars, window_size(100)),
+            G1Projective::msm(&bases, &scalars).ok()
+        );
+    }
+
+    #[test]
+    fn test_length_mismatch_is_none() {
+        let (bases, scalars) = instance(10);
+        assert_eq!(msm::<G1Projective, _>(&bases[..9], &scalars), None);
+        assert_eq!(msm::<G1Projective, _>(&bases[..2], &scalars[..3]), None);
+        assert_eq!(naive_msm::<G1Projective, _>(&bases, &scalars[..9]), None);
+        assert_eq!(pippenger_msm::<G1Projective, _>(&bases, &scalars[..9], 4), None);
+    }
+
+    #[test]
+    fn test_window_size_grows_with_the_input() {
+        assert_eq!(window_size(1), 3);
+        assert_eq!(window_size(1 << 10), 8);
+        assert_eq!(window_size(1 << 20), 15);
+    }
+}
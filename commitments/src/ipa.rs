@@ -0,0 +1,234 @@
+use ark_ec::CurveGroup;
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use crate::pedersen::PedersenCommitment;
+use crate::group_ops::msm;
+
+// Bulletproofs-style inner product argument.
+//
+// The statement is a vector Pedersen commitment C = <a, G> + <b, H> and a public value c,
+// and the prover shows that c = <a, b> with a proof of 2 log2(n) group elements and 2 scalars.
+//
+// The verifier folds c into the commitment with a transcript-derived generator U, so P = C + c*U.
+// Every round halves the vectors: with challenge x,
+// a' = x*a_lo + x^-1*a_hi, b' = x^-1*b_lo + x*b_hi, G' = x^-1*G_lo + x*G_hi, H' = x*H_lo + x^-1*H_hi
+// and the commitment becomes P' = x^2*L + P + x^-2*R, where L and R hold the cross terms of the two halves.
+// At the end the vectors have length 1 and the verifier checks P' = a*G' + b*H' + a*b*U directly
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IpaParams<G: CurveGroup> {
+    pub g: Vec<G::Affine>,
+    pub h: Vec<G::Affine>,
+    pub u: G::Affine,
+}
+
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IpaProof<G: CurveGroup> {
+    pub l_vec: Vec<G>,
+    pub r_vec: Vec<G>,
+    pub a: G::ScalarField,
+    pub b: G::ScalarField,
+}
+
+impl <G: CurveGroup>IpaParams<G> {
+    // Samples the 2n + 1 generators, n must be a power of two
+    pub fn setup<R: RngCore>(n: usize, rng: &mut R) -> Self {
+        assert!(n.is_power_of_two(), "vector length must be a power of two");
+
+        let g: Vec<G> = (0..n).map(|_| G::rand(rng)).collect();
+        let h: Vec<G> = (0..n).map(|_| G::rand(rng)).collect();
+
+        Self {
+            g: G::normalize_batch(&g),
+            h: G::normalize_batch(&h),
+            u: G::rand(rng).into_affine(),
+        }
+    }
+
+    pub fn commit(&self, a: &[G::ScalarField], b: &[G::ScalarField]) -> PedersenCommitment<G> {
+        assert!(a.len() == self.g.len() && b.len() == self.h.len(), "vectors must match the setup size");
+
+        PedersenCommitment {
+            commitment: msm::<G, _>(&self.g, a).expect("bases and scalars have the same length") + msm::<G, _>(&self.h, b).expect("bases and scalars have the same length"),
+        }
+    }
+
+    // Proves that `commitment` = commit(a, b) holds vectors whose inner product is <a, b>.
+    // Returns the inner product and the proof
+    pub fn prove(
+        &self,
+        commitment: &PedersenCommitment<G>,
+        a: &[G::ScalarField],
+        b: &[G::ScalarField],
+        transcript: &mut Transcript,
+    ) -> (G::ScalarField, IpaProof<G>) {
+        assert!(a.len() == self.g.len() && b.len() == self.h.len(), "vectors must match the setup size");
+
+        let claimed_inner_product = inner_product(a, b);
+        let u = self.bind_statement(commitment, claimed_inner_product, transcript);
+
+        let mut a = a.to_vec();
+        let mut b = b.to_vec();
+        let mut g: Vec<G> = self.g.iter().map(|point| (*point).into()).collect();
+        let mut h: Vec<G> = self.h.iter().map(|point| (*point).into()).collect();
+
+        let mut l_vec = Vec::new();
+        let mut r_vec = Vec::new();
+
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+            let (h_lo, h_hi) = h.split_at(half);
+
+            let l = msm::<G, _>(g_hi, a_lo).expect("bases and scalars have the same length") + msm::<G, _>(h_lo, b_hi).expect("bases and scalars have the same length") + u * inner_product(a_lo, b_hi);
+            let r = msm::<G, _>(g_lo, a_hi).expect("bases and scalars have the same length") + msm::<G, _>(h_hi, b_lo).expect("bases and scalars have the same length") + u * inner_product(a_hi, b_lo);
+
+            let (x, x_inverse) = round_challenge(&l, &r, transcript);
+
+            a = fold_scalars(a_lo, a_hi, x, x_inverse);
+            b = fold_scalars(b_lo, b_hi, x_inverse, x);
+            g = fold_points(g_lo, g_hi, x_inverse, x);
+            h = fold_points(h_lo, h_hi, x, x_inverse);
+
+            l_vec.push(l);
+            r_vec.push(r);
+        }
+
+        (claimed_inner_product, IpaProof { l_vec, r_vec, a: a[0], b: b[0] })
+    }
+
+    pub fn verify(
+        &self,
+        commitment: &PedersenCommitment<G>,
+        inner_product: G::ScalarField,
+        proof: &IpaProof<G>,
+        transcript: &mut Transcript,
+    ) -> bool {
+        let no_of_rounds = self.g.len().trailing_zeros() as usize;
+        if proof.l_vec.len() != no_of_rounds || proof.r_vec.len() != no_of_rounds {
+            return false;
+        }
+
+        let u = self.bind_statement(commitment, inner_product, transcript);
+
+        let mut p = commitment.commitment + u * inner_product;
+        let mut g: Vec<G> = self.g.iter().map(|point| (*point).into()).collect();
+        let mut h: Vec<G> = self.h.iter().map(|point| (*point).into()).collect();
+
+        for (l, r) in proof.l_vec.iter().zip(proof.r_vec.iter()) {
+            let (x, x_inverse) = round_challenge(l, r, transcript);
+            let half = g.len() / 2;
+
+            let (g_lo, g_hi) = g.split_at(half);
+            let (h_lo, h_hi) = h.split_at(half);
+            let next_g = fold_points(g_lo, g_hi, x_inverse, x);
+            let next_h = fold_points(h_lo, h_hi, x, x_inverse);
+            g = next_g;
+            h = next_h;
+
+            p += *l * x.square() + *r * x_inverse.square();
+        }
+
+        p == g[0] * proof.a + h[0] * proof.b + u * (proof.a * proof.b)
+    }
+
+    // Absorbs the commitment and the claimed inner product, then derives U' = x*U so the prover
+    // cannot pick the inner product after seeing the generator it is bound to
+    fn bind_statement(&self, commitment: &PedersenCommitment<G>, inner_product: G::ScalarField, transcript: &mut Transcript) -> G {
+        transcript.append(&to_bytes(&commitment.commitment));
+        transcript.append(&to_bytes(&inner_product));
+        let x: G::ScalarField = transcript.random_challenge_as_field_element();
+
+        self.u * x
+    }
+}
+
+fn round_challenge<G: CurveGroup>(l: &G, r: &G, transcript: &mut Transcript) -> (G::ScalarField, G::ScalarField) {
+    transcript.append(&to_bytes(l));
+    transcript.append(&to_bytes(r));
+    let x: G::ScalarField = transcript.random_challenge_as_field_element();
+
+    (x, x.inverse().expect("challenge is non-zero with overwhelming probability"))
+}
+
+pub fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b.iter()).map(|(x, y)| *x * y).sum()
+}
+
+// lo[i] * lo_factor + hi[i] * hi_factor
+fn fold_scalars<F: Field>(lo: &[F], hi: &[F], lo_factor: F, hi_factor: F) -> Vec<F> {
+    lo.iter().zip(hi.iter()).map(|(l, h)| *l * lo_factor + *h * hi_factor).collect()
+}
+
+fn fold_points<G: CurveGroup>(lo: &[G], hi: &[G], lo_factor: G::ScalarField, hi_factor: G::ScalarField) -> Vec<G> {
+    lo.iter().zip(hi.iter()).map(|(l, h)| *l * lo_factor + *h * hi_factor).collect()
+}
+
+fn to_bytes<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec cannot fail");
+    bytes
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+
+    fn vectors() -> (Vec<Fr>, Vec<Fr>) {
+        let a = (1..=8u64).map(Fr::from).collect();
+        let b = (1..=8u64).map(|i| Fr::from(i * 3 + 1)).collect();
+        (a, b)
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let params = IpaParams::<G1Projective>::setup(8, &mut ark_std::test_rng());
+        let (a, b) = vectors();
+        let commitment = params.commit(&a, &b);
+
+        let (inner_product, proof) = params.prove(&commitment, &a, &b, &mut Transcript::new());
+
+        assert_eq!(inner_product, super::inner_product(&a, &b));
+        // log2(8) = 3 rounds
+        assert_eq!(proof.l_vec.len(), 3);
+        assert!(params.verify(&commitment, inner_product, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_inner_product() {
+        let params = IpaParams::<G1Projective>::setup(8, &mut ark_std::test_rng());
+        let (a, b) = vectors();
+        let commitment = params.commit(&a, &b);
+
+        let (inner_product, proof) = params.prove(&commitment, &a, &b, &mut Transcript::new());
+
+        assert!(!params.verify(&commitment, inner_product + Fr::from(1), &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_proof_or_commitment() {
+        let params = IpaParams::<G1Projective>::setup(8, &mut ark_std::test_rng());
+        let (a, b) = vectors();
+        let commitment = params.commit(&a, &b);
+
+        let (inner_product, proof) = params.prove(&commitment, &a, &b, &mut Transcript::new());
+
+        let mut tampered = proof.clone();
+        tampered.a += Fr::from(1);
+        assert!(!params.verify(&commitment, inner_product, &tampered, &mut Transcript::new()));
+
+        let mut short = proof.clone();
+        short.l_vec.pop();
+        assert!(!params.verify(&commitment, inner_product, &short, &mut Transcript::new()));
+
+        let other_commitment = params.commit(&b, &a);
+        assert!(!params.verify(&other_commitment, inner_product, &proof, &mut Transcript::new()));
+    }
+}
@@ -0,0 +1,414 @@
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+use std::fmt;
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use univariate_poly::univariate::UnivariatePoly;
+use transcript::encoding::field_to_bytes;
+use crate::group_ops;
+use crate::srs::Srs;
+
+// Univariate KZG commitments (Kate-Zaverucha-Goldberg).
+//
+// A trusted setup publishes [1]_1, [tau]_1, ..., [tau^d]_1 and [tau]_2 for a secret tau.
+// A polynomial f of degree <= d is committed as [f(tau)]_1.
+//
+// To open at z, the prover divides f(X) - f(z) by (X - z), which is exact because z is a root,
+// and sends [q(tau)]_1. The verifier checks f(tau) - f(z) = q(tau) * (tau - z) with one pairing equation:
+// e([f(tau)] - [f(z)], [1]) == e([q(tau)], [tau] - [z])
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgParams<E: Pairing> {
+    // [tau^i]_1 for i = 0..=max_degree
+    pub powers_of_tau: Vec<E::G1Affine>,
+    pub g2: E::G2Affine,
+    pub g2_tau: E::G2Affine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgCommitment<E: Pairing> {
+    pub commitment: E::G1Affine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgOpeningProof<E: Pairing> {
+    pub quotient: E::G1Affine,
+}
+
+// Opening one polynomial at t points takes [Z(tau)]_2 for the vanishing polynomial Z of the points, so the verifier
+// holds [tau^i]_2 up to max_points besides the usual parameters
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgBatchParams<E: Pairing> {
+    pub kzg: KzgParams<E>,
+    // [tau^i]_2 for i = 0..=max_points
+    pub powers_of_tau_g2: Vec<E::G2Affine>,
+}
+
+// Openings the prover cannot make. A polynomial beyond the setup's degree is still a panic in commit, which the
+// PolynomialCommitmentScheme trait shares with the other schemes and has no error to return
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KzgError {
+    // open_batch needs one commitment per polynomial
+    WrongNumberOfCommitments { expected: usize, provided: usize },
+    // [Z(tau)]_2 for more points than the setup's G2 powers reach
+    TooManyPoints { max_points: usize, provided: usize },
+    // a point appears twice, Z would have a double root and the interpolation is not defined
+    RepeatedPoint,
+}
+
+impl fmt::Display for KzgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KzgError::WrongNumberOfCommitments { expected, provided } => {
+                write!(f, "{} commitments for {} polynomials", provided, expected)
+            }
+            KzgError::TooManyPoints { max_points, provided } => {
+                write!(f, "{} points, the setup supports at most {}", provided, max_points)
+            }
+            KzgError::RepeatedPoint => write!(f, "the points are not distinct"),
+        }
+    }
+}
+
+impl std::error::Error for KzgError {}
+
+impl <E: Pairing>KzgParams<E> {
+    // Trusted setup for polynomials of degree up to max_degree.
+    // The secret tau is sampled from rng and wiped before this returns (toxic waste). To share one setup between
+    // runs, generate an Srs once, save it and take the KZG parameters from its prefix
+    pub fn setup<R: RngCore>(max_degree: usize, rng: &mut R) -> Self {
+        Srs::generate(max_degree, 1, rng)
+            .kzg_params(max_degree)
+            .expect("the setup was generated for max_degree")
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.powers_of_tau.len() - 1
+    }
+
+    pub fn commit(&self, polynomial: &UnivariatePoly<E::ScalarField>) -> KzgCommitment<E> {
+        KzgCommitment { commitment: self.commit_coefficients(&polynomial.coefficients) }
+    }
+
+    // The evaluation argument: returns y = f(z) and the proof [q(tau)]_1 for q = (f(X) - y) / (X - z). Synthetic
+    // division by X - z gives the quotient and y in one pass, its remainder being exactly f(z)
+    pub fn prove_eval(
+        &self,
+        polynomial: &UnivariatePoly<E::ScalarField>,
+        z: E::ScalarField,
+    ) -> (E::ScalarField, KzgOpeningProof<E>) {
+        let (quotient, y) = polynomial.divide_by_linear(z);
+
+        (y, KzgOpeningProof { quotient: self.commit_coefficients(&quotient.coefficients) })
+    }
+
+    // Accepts when the committed polynomial takes the value y at z:
+    // e(C - [y]_1, [1]_2) == e(proof, [tau]_2 - [z]_2)
+    pub fn verify_eval(
+        &self,
+        commitment: &KzgCommitment<E>,
+        z: E::ScalarField,
+        y: E::ScalarField,
+        proof: &KzgOpeningProof<E>,
+    ) -> bool {
+        let g1 = self.powers_of_tau[0];
+
+        let left = E::pairing(commitment.commitment.into_group() - g1 * y, self.g2);
+        let right = E::pairing(proof.quotient, self.g2_tau.into_group() - self.g2 * z);
+
+        left == right
+    }
+
+    // Returns f(point) and the commitment to the quotient (f(X) - f(point)) / (X - point), the same as prove_eval
+    pub fn open(
+        &self,
+        polynomial: &UnivariatePoly<E::ScalarField>,
+        point: E::ScalarField,
+    ) -> (E::ScalarField, KzgOpeningProof<E>) {
+        self.prove_eval(polynomial, point)
+    }
+
+    pub fn verify(
+        &self,
+        commitment: &KzgCommitment<E>,
+        point: E::ScalarField,
+        value: E::ScalarField,
+        proof: &KzgOpeningProof<E>,
+    ) -> bool {
+        self.verify_eval(commitment, point, value, proof)
+    }
+
+    // One proof for several polynomials at the same point. With gamma drawn from the transcript after the commitments,
+    // the point and the values, the prover opens g = sum gamma^i f_i, and the verifier checks it against
+    // sum gamma^i C_i and sum gamma^i v_i. A wrong value survives only if gamma is a root of a nonzero polynomial of
+    // degree < k, so the check costs the two pairings of a single opening whatever the number of polynomials
+    pub fn open_batch(
+        &self,
+        polynomials: &[UnivariatePoly<E::ScalarField>],
+        commitments: &[KzgCommitment<E>],
+        point: E::ScalarField,
+        transcript: &mut Transcript,
+    ) -> Result<(Vec<E::ScalarField>, KzgOpeningProof<E>), KzgError> {
+        if polynomials.len() != commitments.len() {
+            return Err(KzgError::WrongNumberOfCommitments { expected: polynomials.len(), provided: commitments.len() });
+        }
+
+        let values: Vec<E::ScalarField> = polynomials.iter().map(|polynomial| polynomial.evaluate(point)).collect();
+        let gamma = batch_challenge(transcript, commitments, point, &values);
+
+        let combined = polynomials
+            .iter()
+            .zip(powers_of(gamma, polynomials.len()))
+            .fold(UnivariatePoly::zero(), |sum, (polynomial, power)| &sum + &polynomial.scalar_mul(power));
+        let (_, proof) = self.prove_eval(&combined, point);
+
+        Ok((values, proof))
+    }
+
+    pub fn verify_batch(
+        &self,
+        commitments: &[KzgCommitment<E>],
+        point: E::ScalarField,
+        values: &[E::ScalarField],
+        proof: &KzgOpeningProof<E>,
+        transcript: &mut Transcript,
+    ) -> bool {
+        if commitments.len() != values.len() {
+            return false;
+        }
+
+        let gamma = batch_challenge(transcript, commitments, point, values);
+        let powers = powers_of(gamma, values.len());
+        let bases: Vec<E::G1Affine> = commitments.iter().map(|commitment| commitment.commitment).collect();
+        let commitment = KzgCommitment { commitment: group_ops::msm::<E::G1, _>(&bases, &powers).expect("bases and scalars have the same length").into_affine() };
+        let value = values.iter().zip(&powers).map(|(value, power)| *value * power).sum();
+
+        self.verify_eval(&commitment, point, value, proof)
+    }
+
+    fn commit_coefficients(&self, coefficients: &[E::ScalarField]) -> E::G1Affine {
+        assert!(coefficients.len() <= self.powers_of_tau.len(), "polynomial degree exceeds the setup");
+
+        group_ops::msm::<E::G1, _>(&self.powers_of_tau[..coefficients.len()], coefficients).expect("bases and scalars have the same length").into_affine()
+    }
+}
+
+impl <E: Pairing>KzgBatchParams<E> {
+    // Trusted setup for polynomials of degree up to max_degree opened at up to max_points points at once
+    pub fn setup<R: RngCore>(max_degree: usize, max_points: usize, rng: &mut R) -> Self {
+        Srs::generate(max_degree, max_points.max(1), rng)
+            .kzg_batch_params(max_degree, max_points)
+            .expect("the setup was generated for these degrees")
+    }
+
+    pub fn max_points(&self) -> usize {
+        self.powers_of_tau_g2.len() - 1
+    }
+
+    // One proof for one polynomial at several distinct points z_i. With Z(X) = prod (X - z_i) and I the
+    // interpolation of the values, f - I vanishes at every z_i, so Z divides it, and the proof is [q(tau)]_1 for
+    // q = (f - I) / Z. The verifier checks f(tau) - I(tau) = q(tau) * Z(tau) with
+    // e([f(tau)] - [I(tau)], [1]) == e([q(tau)], [Z(tau)]_2)
+    // which is two pairings for any number of points, [Z(tau)]_2 taking the G2 powers up to the number of points
+    pub fn open_multi_point(
+        &self,
+        polynomial: &UnivariatePoly<E::ScalarField>,
+        points: &[E::ScalarField],
+    ) -> Result<(Vec<E::ScalarField>, KzgOpeningProof<E>), KzgError> {
+        if points.len() > self.max_points() {
+            return Err(KzgError::TooManyPoints { max_points: self.max_points(), provided: points.len() });
+        }
+        if !distinct(points) {
+            return Err(KzgError::RepeatedPoint);
+        }
+
+        let values = polynomial.batch_evaluate(points);
+        let numerator = polynomial - &UnivariatePoly::interpolate(points, &values);
+        let (quotient, _) = numerator
+            .div_rem(&UnivariatePoly::from_roots(points))
+            .expect("the vanishing polynomial is not the zero polynomial");
+
+        Ok((values, KzgOpeningProof { quotient: self.kzg.commit_coefficients(&quotient.coefficients) }))
+    }
+
+    pub fn verify_multi_point(
+        &self,
+        commitment: &KzgCommitment<E>,
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+        proof: &KzgOpeningProof<E>,
+    ) -> bool {
+        if points.len() != values.len() || points.len() > self.max_points() || !distinct(points) {
+            return false;
+        }
+
+        let interpolation = UnivariatePoly::interpolate(points, values);
+        if interpolation.coefficients.len() > self.kzg.powers_of_tau.len() {
+            return false;
+        }
+        let vanishing = UnivariatePoly::from_roots(points);
+        let vanishing_g2 =
+            group_ops::msm::<E::G2, _>(&self.powers_of_tau_g2[..vanishing.coefficients.len()], &vanishing.coefficients).expect("bases and scalars have the same length");
+
+        let left = E::pairing(commitment.commitment.into_group() - self.kzg.commit_coefficients(&interpolation.coefficients), self.kzg.g2);
+        let right = E::pairing(proof.quotient, vanishing_g2);
+
+        left == right
+    }
+}
+
+// gamma for a batch opening, drawn once everything the verifier is told is in the transcript
+fn batch_challenge<E: Pairing>(
+    transcript: &mut Transcript,
+    commitments: &[KzgCommitment<E>],
+    point: E::ScalarField,
+    values: &[E::ScalarField],
+) -> E::ScalarField {
+    transcript.append(&(commitments.len() as u64).to_be_bytes());
+    for commitment in commitments {
+        let mut bytes = Vec::new();
+        commitment.serialize_compressed(&mut bytes).expect("serializing into a Vec cannot fail");
+        transcript.append(&bytes);
+    }
+    transcript.append(&field_to_bytes(point));
+    for value in values {
+        transcript.append(&field_to_bytes(*value));
+    }
+    transcript.random_challenge_as_field_element()
+}
+
+// (1, x, x^2, ..., x^(count - 1))
+fn powers_of<F: ark_ff::Field>(x: F, count: usize) -> Vec<F> {
+    core::iter::successors(Some(F::one()), |power| Some(*power * x)).take(count).collect()
+}
+
+fn distinct<F: PartialEq>(points: &[F]) -> bool {
+    points.iter().enumerate().all(|(i, point)| !points[..i].contains(point))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+
+    fn polynomial() -> UnivariatePoly<Fr> {
+        UnivariatePoly::new(vec![Fr::from(3), Fr::from(0), Fr::from(2), Fr::from(7)])
+    }
+
+    #[test]
+    fn test_commit_open_verify() {
+        let params = KzgParams::<Bn254>::setup(8, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+
+        let commitment = params.commit(&polynomial);
+        let (value, proof) = params.open(&polynomial, Fr::from(5));
+
+        // 3 + 2 * 25 + 7 * 125
+        assert_eq!(value, Fr::from(928));
+        assert!(params.verify(&commitment, Fr::from(5), value, &proof));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_value_or_point() {
+        let params = KzgParams::<Bn254>::setup(8, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+
+        let commitment = params.commit(&polynomial);
+        let (value, proof) = params.open(&polynomial, Fr::from(5));
+
+        assert!(!params.verify(&commitment, Fr::from(5), value + Fr::from(1), &proof));
+        assert!(!params.verify(&commitment, Fr::from(6), value, &proof));
+
+        let other = params.commit(&UnivariatePoly::new(vec![Fr::from(1), Fr::from(1)]));
+        assert!(!params.verify(&other, Fr::from(5), value, &proof));
+    }
+
+    #[test]
+    fn test_prove_eval_verify_eval() {
+        let params = KzgParams::<Bn254>::setup(8, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+
+        let commitment = params.commit(&polynomial);
+        let (value, proof) = params.prove_eval(&polynomial, Fr::from(5));
+
+        // 3 + 2 * 25 + 7 * 125
+        assert_eq!(value, Fr::from(928));
+        assert!(params.verify_eval(&commitment, Fr::from(5), value, &proof));
+        // the proof is the commitment to the quotient by X - 5
+        let (quotient, _) = (&polynomial - &UnivariatePoly::new(vec![value]))
+            .div_rem(&UnivariatePoly::new(vec![-Fr::from(5), Fr::from(1)]))
+            .unwrap();
+        assert_eq!(proof.quotient, params.commit(&quotient).commitment);
+    }
+
+    #[test]
+    fn test_verify_eval_rejects_wrong_value_or_point() {
+        let params = KzgParams::<Bn254>::setup(8, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+
+        let commitment = params.commit(&polynomial);
+        let (value, proof) = params.prove_eval(&polynomial, Fr::from(5));
+
+        assert!(!params.verify_eval(&commitment, Fr::from(5), value + Fr::from(1), &proof));
+        assert!(!params.verify_eval(&commitment, Fr::from(6), value, &proof));
+        // nor does the proof carry over to another point, even with the true value there
+        assert!(!params.verify_eval(&commitment, Fr::from(6), polynomial.evaluate(Fr::from(6)), &proof));
+
+        let other = params.commit(&UnivariatePoly::new(vec![Fr::from(1), Fr::from(1)]));
+        assert!(!params.verify_eval(&other, Fr::from(5), value, &proof));
+    }
+
+    #[test]
+    fn test_batch_opening_at_one_point() {
+        let params = KzgParams::<Bn254>::setup(8, &mut ark_std::test_rng());
+        let polynomials = vec![polynomial(), UnivariatePoly::new(vec![Fr::from(1), Fr::from(1)]), UnivariatePoly::zero()];
+        let commitments: Vec<_> = polynomials.iter().map(|polynomial| params.commit(polynomial)).collect();
+
+        let (values, proof) = params.open_batch(&polynomials, &commitments, Fr::from(5), &mut Transcript::new()).unwrap();
+        assert_eq!(values, vec![Fr::from(928), Fr::from(6), Fr::from(0)]);
+        assert!(params.verify_batch(&commitments, Fr::from(5), &values, &proof, &mut Transcript::new()));
+
+        // every value is bound, as are the point, the commitments and their number
+        let mut wrong = values.clone();
+        wrong[1] += Fr::from(1);
+        assert!(!params.verify_batch(&commitments, Fr::from(5), &wrong, &proof, &mut Transcript::new()));
+        assert!(!params.verify_batch(&commitments, Fr::from(6), &values, &proof, &mut Transcript::new()));
+        let swapped = vec![commitments[1], commitments[0], commitments[2]];
+        assert!(!params.verify_batch(&swapped, Fr::from(5), &values, &proof, &mut Transcript::new()));
+        assert!(!params.verify_batch(&commitments[..2], Fr::from(5), &values[..2], &proof, &mut Transcript::new()));
+
+        assert_eq!(
+            params.open_batch(&polynomials, &commitments[..2], Fr::from(5), &mut Transcript::new()),
+            Err(KzgError::WrongNumberOfCommitments { expected: 3, provided: 2 })
+        );
+    }
+
+    #[test]
+    fn test_opening_at_many_points() {
+        let params = KzgBatchParams::<Bn254>::setup(8, 4, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+        let commitment = params.kzg.commit(&polynomial);
+        let points = vec![Fr::from(0), Fr::from(1), Fr::from(5), -Fr::from(2)];
+
+        let (values, proof) = params.open_multi_point(&polynomial, &points).unwrap();
+        assert_eq!(values[..3], [Fr::from(3), Fr::from(12), Fr::from(928)]);
+        assert!(params.verify_multi_point(&commitment, &points, &values, &proof));
+        // with fewer points than the degree the quotient is not constant, and the proof is still one point
+        let (values_at_two, proof_at_two) = params.open_multi_point(&polynomial, &points[..2]).unwrap();
+        assert!(params.verify_multi_point(&commitment, &points[..2], &values_at_two, &proof_at_two));
+
+        let mut wrong = values.clone();
+        wrong[3] += Fr::from(1);
+        assert!(!params.verify_multi_point(&commitment, &points, &wrong, &proof));
+        assert!(!params.verify_multi_point(&commitment, &points[..3], &values[..3], &proof));
+        // repeated points or more points than the setup supports are refused rather than checked
+        assert!(!params.verify_multi_point(&commitment, &[Fr::from(1), Fr::from(1)], &[Fr::from(12), Fr::from(12)], &proof));
+        let five: Vec<Fr> = (0..5u64).map(Fr::from).collect();
+        assert!(!params.verify_multi_point(&commitment, &five, &polynomial.batch_evaluate(&five), &proof));
+
+        // and the prover refuses to open at them
+        assert_eq!(params.open_multi_point(&polynomial, &[Fr::from(1), Fr::from(1)]), Err(KzgError::RepeatedPoint));
+        assert_eq!(params.open_multi_point(&polynomial, &five), Err(KzgError::TooManyPoints { max_points: 4, provided: 5 }));
+    }
+}
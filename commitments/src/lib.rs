@@ -0,0 +1,9 @@
+pub mod pst;
+pub mod kzg;
+pub mod merkle;
+pub mod fri;
+pub mod pedersen;
+pub mod ipa;
+pub mod pcs;
+pub mod group_ops;
+pub mod srs;
@@ -0,0 +1,341 @@
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use poseidon::poseidon::bn254_params;
+use poseidon::sponge::{bytes_to_field_elements, field_element_to_bytes, PoseidonSponge};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use std::marker::PhantomData;
+
+// The hash function used by a MerkleTree. Leaves and internal nodes are hashed with different
+// functions so a leaf can never be passed off as an internal node (second preimage attacks)
+pub trait MerkleHasher {
+    fn hash_leaf(leaf: &[u8]) -> [u8; 32];
+    fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32];
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keccak256Hasher;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sha256Hasher;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blake3Hasher;
+
+// Poseidon over the bn254 scalar field. Node hashes are field elements, so a tree built with it
+// can be opened inside a circuit far more cheaply than one built with a bit-oriented hash
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseidonHasher;
+
+// Domain separation prefixes for leaves and internal nodes
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+// Any RustCrypto hash with a 32-byte output can be used with the same prefix scheme
+fn digest_leaf<D: Digest>(leaf: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    output.copy_from_slice(&D::new().chain_update([LEAF_PREFIX]).chain_update(leaf).finalize());
+    output
+}
+
+fn digest_nodes<D: Digest>(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    output.copy_from_slice(&D::new().chain_update([NODE_PREFIX]).chain_update(left).chain_update(right).finalize());
+    output
+}
+
+impl MerkleHasher for Keccak256Hasher {
+    fn hash_leaf(leaf: &[u8]) -> [u8; 32] {
+        digest_leaf::<Keccak256>(leaf)
+    }
+
+    fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        digest_nodes::<Keccak256>(left, right)
+    }
+}
+
+impl MerkleHasher for Sha256Hasher {
+    fn hash_leaf(leaf: &[u8]) -> [u8; 32] {
+        digest_leaf::<Sha256>(leaf)
+    }
+
+    fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        digest_nodes::<Sha256>(left, right)
+    }
+}
+
+impl MerkleHasher for Blake3Hasher {
+    fn hash_leaf(leaf: &[u8]) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&[LEAF_PREFIX]);
+        hasher.update(leaf);
+        *hasher.finalize().as_bytes()
+    }
+
+    fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&[NODE_PREFIX]);
+        hasher.update(left);
+        hasher.update(right);
+        *hasher.finalize().as_bytes()
+    }
+}
+
+impl MerkleHasher for PoseidonHasher {
+    fn hash_leaf(leaf: &[u8]) -> [u8; 32] {
+        let mut sponge = PoseidonSponge::new(bn254_params());
+        sponge.absorb(&[Fr::from(LEAF_PREFIX)]);
+        sponge.absorb(&bytes_to_field_elements(leaf));
+        field_element_to_bytes(sponge.squeeze())
+    }
+
+    fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut sponge = PoseidonSponge::new(bn254_params());
+        sponge.absorb(&[Fr::from(NODE_PREFIX), Fr::from_le_bytes_mod_order(left), Fr::from_le_bytes_mod_order(right)]);
+        field_element_to_bytes(sponge.squeeze())
+    }
+}
+
+// A binary Merkle tree over byte-string leaves with a pluggable hash function.
+// layers[0] holds the leaf hashes and the last layer holds the single root hash
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleTree<H: MerkleHasher = Keccak256Hasher> {
+    pub layers: Vec<Vec<[u8; 32]>>,
+    _hasher: PhantomData<H>,
+}
+
+// The sibling hashes on the path from a leaf to the root, lowest layer first
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+// An opening of several leaves at once. Nodes that the verifier can compute itself from the opened
+// leaves are left out, so opening k neighbouring leaves costs much less than k single proofs
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleMultiProof {
+    pub indices: Vec<usize>,
+    // the missing sibling hashes, in the order the verifier walks up the tree
+    pub auxiliary_nodes: Vec<[u8; 32]>,
+}
+
+impl <H: MerkleHasher>MerkleTree<H> {
+    // The number of leaves must be a power of two so every node has two children
+    pub fn new(leaves: &[Vec<u8>]) -> Self {
+        assert!(leaves.len().is_power_of_two(), "number of leaves must be a power of two");
+
+        let mut layers = vec![leaves.iter().map(|leaf| H::hash_leaf(leaf)).collect::<Vec<_>>()];
+
+        while layers.last().unwrap().len() > 1 {
+            let next_layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| H::hash_nodes(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next_layer);
+        }
+
+        Self { layers, _hasher: PhantomData }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn no_of_leaves(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    pub fn open(&self, index: usize) -> MerkleProof {
+        let mut siblings = Vec::with_capacity(self.layers.len() - 1);
+        let mut position = index;
+
+        for layer in &self.layers[..self.layers.len() - 1] {
+            // flipping the lowest bit gives the other child of the same parent
+            siblings.push(layer[position ^ 1]);
+            position /= 2;
+        }
+
+        MerkleProof { index, siblings }
+    }
+
+    // Walks up the tree layer by layer from the opened positions. A sibling is only included
+    // when it is not itself opened (or computed from opened leaves)
+    pub fn open_many(&self, indices: &[usize]) -> MerkleMultiProof {
+        let mut positions = sorted_unique(indices);
+        let mut auxiliary_nodes = Vec::new();
+
+        for layer in &self.layers[..self.layers.len() - 1] {
+            for (i, position) in positions.iter().enumerate() {
+                let sibling = position ^ 1;
+                let sibling_is_known = (i > 0 && positions[i - 1] == sibling) || positions.get(i + 1) == Some(&sibling);
+                if !sibling_is_known {
+                    auxiliary_nodes.push(layer[sibling]);
+                }
+            }
+            positions = sorted_unique(&positions.iter().map(|position| position / 2).collect::<Vec<_>>());
+        }
+
+        MerkleMultiProof { indices: sorted_unique(indices), auxiliary_nodes }
+    }
+
+    // Rebuilds the path from the leaf to the root and compares it against the expected root
+    pub fn verify(root: &[u8; 32], leaf: &[u8], proof: &MerkleProof) -> bool {
+        let mut current = H::hash_leaf(leaf);
+        let mut position = proof.index;
+
+        for sibling in &proof.siblings {
+            current = if position.is_multiple_of(2) {
+                H::hash_nodes(&current, sibling)
+            } else {
+                H::hash_nodes(sibling, &current)
+            };
+            position /= 2;
+        }
+
+        position == 0 && current == *root
+    }
+
+    // `leaves` must be in the same order as proof.indices (sorted by index)
+    pub fn verify_many(root: &[u8; 32], leaves: &[Vec<u8>], proof: &MerkleMultiProof) -> bool {
+        if leaves.len() != proof.indices.len() || proof.indices.is_empty() || proof.indices != sorted_unique(&proof.indices) {
+            return false;
+        }
+
+        let mut nodes: Vec<(usize, [u8; 32])> = proof
+            .indices
+            .iter()
+            .zip(leaves.iter())
+            .map(|(index, leaf)| (*index, H::hash_leaf(leaf)))
+            .collect();
+        let mut auxiliary_nodes = proof.auxiliary_nodes.iter();
+
+        // every layer halves the positions until only the root is left. Once a single node at position 0
+        // remains, the leftover auxiliary nodes are the siblings on its path to the root
+        while nodes.len() > 1 || nodes[0].0 > 0 || auxiliary_nodes.len() > 0 {
+            let mut parents: Vec<(usize, [u8; 32])> = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+
+            while i < nodes.len() {
+                let (position, hash) = nodes[i];
+                let parent = if position.is_multiple_of(2) {
+                    if i + 1 < nodes.len() && nodes[i + 1].0 == position + 1 {
+                        i += 1;
+                        H::hash_nodes(&hash, &nodes[i].1)
+                    } else {
+                        match auxiliary_nodes.next() {
+                            Some(sibling) => H::hash_nodes(&hash, sibling),
+                            None => return false,
+                        }
+                    }
+                } else {
+                    match auxiliary_nodes.next() {
+                        Some(sibling) => H::hash_nodes(sibling, &hash),
+                        None => return false,
+                    }
+                };
+                parents.push((position / 2, parent));
+                i += 1;
+            }
+            nodes = parents;
+        }
+
+        nodes[0].1 == *root
+    }
+
+    // Checks several single-leaf proofs against the same root, stopping at the first invalid one
+    pub fn verify_batch(root: &[u8; 32], openings: &[(Vec<u8>, MerkleProof)]) -> bool {
+        openings.iter().all(|(leaf, proof)| Self::verify(root, leaf, proof))
+    }
+}
+
+fn sorted_unique(indices: &[usize]) -> Vec<usize> {
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    sorted
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves() -> Vec<Vec<u8>> {
+        (0..8u8).map(|i| vec![i; 4]).collect()
+    }
+
+    fn check_open_and_verify<H: MerkleHasher>() {
+        let tree = MerkleTree::<H>::new(&leaves());
+        let root = tree.root();
+
+        for (i, leaf) in leaves().iter().enumerate() {
+            assert!(MerkleTree::<H>::verify(&root, leaf, &tree.open(i)));
+        }
+    }
+
+    #[test]
+    fn test_open_and_verify_with_every_hasher() {
+        check_open_and_verify::<Keccak256Hasher>();
+        check_open_and_verify::<Sha256Hasher>();
+        check_open_and_verify::<Blake3Hasher>();
+        check_open_and_verify::<PoseidonHasher>();
+
+        // the hashers produce different roots for the same leaves
+        assert_ne!(MerkleTree::<Sha256Hasher>::new(&leaves()).root(), MerkleTree::<Blake3Hasher>::new(&leaves()).root());
+        assert_ne!(MerkleTree::<Keccak256Hasher>::new(&leaves()).root(), MerkleTree::<PoseidonHasher>::new(&leaves()).root());
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_leaf_or_index() {
+        let tree: MerkleTree = MerkleTree::new(&leaves());
+        let root = tree.root();
+        let mut proof = tree.open(3);
+
+        assert!(!MerkleTree::<Keccak256Hasher>::verify(&root, &[9, 9, 9, 9], &proof));
+        proof.index = 2;
+        assert!(!MerkleTree::<Keccak256Hasher>::verify(&root, &leaves()[3], &proof));
+    }
+
+    #[test]
+    fn test_multi_proof() {
+        let tree = MerkleTree::<Sha256Hasher>::new(&leaves());
+        let root = tree.root();
+        let indices = vec![6, 1, 0, 7];
+        let proof = tree.open_many(&indices);
+        let opened: Vec<Vec<u8>> = proof.indices.iter().map(|i| leaves()[*i].clone()).collect();
+
+        // leaves 0 and 1 share a parent, as do 6 and 7, so only the two middle subtree hashes are needed
+        assert_eq!(proof.auxiliary_nodes.len(), 2);
+        assert!(MerkleTree::<Sha256Hasher>::verify_many(&root, &opened, &proof));
+
+        let mut tampered = opened.clone();
+        tampered[2] = vec![42; 4];
+        assert!(!MerkleTree::<Sha256Hasher>::verify_many(&root, &tampered, &proof));
+        assert!(!MerkleTree::<Sha256Hasher>::verify_many(&root, &opened[..3], &proof));
+    }
+
+    #[test]
+    fn test_multi_proof_single_leaf_matches_single_proof() {
+        let tree = MerkleTree::<Blake3Hasher>::new(&leaves());
+        for index in [0, 5] {
+            let proof = tree.open_many(&[index]);
+
+            assert_eq!(proof.auxiliary_nodes, tree.open(index).siblings);
+            assert!(MerkleTree::<Blake3Hasher>::verify_many(&tree.root(), &[leaves()[index].clone()], &proof));
+        }
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let tree = MerkleTree::<Keccak256Hasher>::new(&leaves());
+        let root = tree.root();
+        let mut openings: Vec<(Vec<u8>, MerkleProof)> = (0..8).map(|i| (leaves()[i].clone(), tree.open(i))).collect();
+
+        assert!(MerkleTree::<Keccak256Hasher>::verify_batch(&root, &openings));
+        openings[4].0 = vec![0; 4];
+        assert!(!MerkleTree::<Keccak256Hasher>::verify_batch(&root, &openings));
+    }
+}
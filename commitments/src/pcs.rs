@@ -0,0 +1,646 @@
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::{Field, PrimeField};
+use ark_std::rand::RngCore;
+use multilinear::multilinear::MultilinearPolynomial;
+use std::marker::PhantomData;
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use univariate_poly::domain::EvaluationDomain;
+use univariate_poly::univariate::UnivariatePoly;
+use crate::fri::{codeword_leaves, FriLayerOpening, FriParams, FriProof};
+use transcript::encoding::field_to_bytes;
+use crate::group_ops::msm;
+use crate::ipa::{IpaParams, IpaProof};
+use crate::kzg::{KzgCommitment, KzgOpeningProof, KzgParams};
+use crate::merkle::{Keccak256Hasher, MerkleTree};
+use crate::pedersen::PedersenCommitment;
+use crate::pst::{PstCommitment, PstOpeningProof, PstParams};
+
+// A polynomial commitment scheme, so protocols like sumcheck can be written once against this trait
+// and instantiated with any backend.
+//
+// open and verify take the protocol transcript: interactive backends (IPA, FRI) draw their challenges
+// from it, pairing-based ones (KZG, PST) ignore it
+pub trait PolynomialCommitmentScheme<F: PrimeField> {
+    type Params;
+    type Polynomial;
+    type Point: ?Sized;
+    type Commitment: Clone;
+    type OpeningProof: Clone;
+
+    // max_size is the maximum degree for univariate schemes and the maximum number of variables
+    // for multilinear ones
+    fn setup<R: RngCore>(max_size: usize, rng: &mut R) -> Self::Params;
+
+    fn commit(params: &Self::Params, polynomial: &Self::Polynomial) -> Self::Commitment;
+
+    // Returns the evaluation of the polynomial at `point` and the proof for it
+    fn open(
+        params: &Self::Params,
+        polynomial: &Self::Polynomial,
+        point: &Self::Point,
+        transcript: &mut Transcript,
+    ) -> (F, Self::OpeningProof);
+
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &Self::Point,
+        value: F,
+        proof: &Self::OpeningProof,
+        transcript: &mut Transcript,
+    ) -> bool;
+}
+
+// Schemes for univariate polynomials opened at a single field element
+pub trait UnivariatePcs<F: PrimeField>: PolynomialCommitmentScheme<F, Polynomial = UnivariatePoly<F>, Point = F> {}
+
+impl <F: PrimeField, S>UnivariatePcs<F> for S
+where
+    S: PolynomialCommitmentScheme<F, Polynomial = UnivariatePoly<F>, Point = F>,
+{
+}
+
+// Schemes for multilinear polynomials opened at a point with one coordinate per variable
+pub trait MultilinearPcs<F: PrimeField>: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>, Point = [F]> {}
+
+impl <F: PrimeField, S>MultilinearPcs<F> for S
+where
+    S: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>, Point = [F]>,
+{
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KzgPcs<E: Pairing>(PhantomData<E>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PstPcs<E: Pairing>(PhantomData<E>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IpaPcs<G: CurveGroup>(PhantomData<G>);
+
+// IpaPcs over multilinear polynomials, opened through the eq table of the point instead of the powers of z
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MultilinearIpaPcs<G: CurveGroup>(PhantomData<G>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FriPcs<F: PrimeField>(PhantomData<F>);
+
+impl <E: Pairing>PolynomialCommitmentScheme<E::ScalarField> for KzgPcs<E> {
+    type Params = KzgParams<E>;
+    type Polynomial = UnivariatePoly<E::ScalarField>;
+    type Point = E::ScalarField;
+    type Commitment = KzgCommitment<E>;
+    type OpeningProof = KzgOpeningProof<E>;
+
+    fn setup<R: RngCore>(max_size: usize, rng: &mut R) -> Self::Params {
+        KzgParams::setup(max_size, rng)
+    }
+
+    fn commit(params: &Self::Params, polynomial: &Self::Polynomial) -> Self::Commitment {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_commit", scheme = "kzg").entered();
+
+        params.commit(polynomial)
+    }
+
+    fn open(
+        params: &Self::Params,
+        polynomial: &Self::Polynomial,
+        point: &Self::Point,
+        _transcript: &mut Transcript,
+    ) -> (E::ScalarField, Self::OpeningProof) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_open", scheme = "kzg").entered();
+
+        params.prove_eval(polynomial, *point)
+    }
+
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &Self::Point,
+        value: E::ScalarField,
+        proof: &Self::OpeningProof,
+        _transcript: &mut Transcript,
+    ) -> bool {
+        params.verify_eval(commitment, *point, value, proof)
+    }
+}
+
+impl <E: Pairing>PolynomialCommitmentScheme<E::ScalarField> for PstPcs<E> {
+    type Params = PstParams<E>;
+    type Polynomial = MultilinearPolynomial<E::ScalarField>;
+    type Point = [E::ScalarField];
+    type Commitment = PstCommitment<E>;
+    type OpeningProof = PstOpeningProof<E>;
+
+    fn setup<R: RngCore>(max_size: usize, rng: &mut R) -> Self::Params {
+        PstParams::setup(max_size, rng)
+    }
+
+    fn commit(params: &Self::Params, polynomial: &Self::Polynomial) -> Self::Commitment {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_commit", scheme = "pst").entered();
+
+        params.commit(polynomial)
+    }
+
+    fn open(
+        params: &Self::Params,
+        polynomial: &Self::Polynomial,
+        point: &Self::Point,
+        _transcript: &mut Transcript,
+    ) -> (E::ScalarField, Self::OpeningProof) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_open", scheme = "pst").entered();
+
+        params.open(polynomial, point)
+    }
+
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &Self::Point,
+        value: E::ScalarField,
+        proof: &Self::OpeningProof,
+        _transcript: &mut Transcript,
+    ) -> bool {
+        params.verify(commitment, point, value, proof)
+    }
+}
+
+// The coefficient vector a is committed as <a, G>. To open at z the verifier adds <b, H> for the public
+// vector b = (1, z, z^2, ...) itself, and the inner product argument shows f(z) = <a, b>
+impl <G: CurveGroup>PolynomialCommitmentScheme<G::ScalarField> for IpaPcs<G> {
+    type Params = IpaParams<G>;
+    type Polynomial = UnivariatePoly<G::ScalarField>;
+    type Point = G::ScalarField;
+    type Commitment = PedersenCommitment<G>;
+    type OpeningProof = IpaProof<G>;
+
+    fn setup<R: RngCore>(max_size: usize, rng: &mut R) -> Self::Params {
+        IpaParams::setup((max_size + 1).next_power_of_two(), rng)
+    }
+
+    fn commit(params: &Self::Params, polynomial: &Self::Polynomial) -> Self::Commitment {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_commit", scheme = "ipa").entered();
+
+        ipa_commit(params, &polynomial.coefficients)
+    }
+
+    fn open(
+        params: &Self::Params,
+        polynomial: &Self::Polynomial,
+        point: &Self::Point,
+        transcript: &mut Transcript,
+    ) -> (G::ScalarField, Self::OpeningProof) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_open", scheme = "ipa").entered();
+
+        ipa_open(params, &polynomial.coefficients, &powers_of(*point, params.h.len()), transcript)
+    }
+
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &Self::Point,
+        value: G::ScalarField,
+        proof: &Self::OpeningProof,
+        transcript: &mut Transcript,
+    ) -> bool {
+        ipa_verify(params, commitment, &powers_of(*point, params.h.len()), value, proof, transcript)
+    }
+}
+
+// The multilinear variant of IpaPcs: the evaluation table is committed as <f, G>, and since
+// f(r) = sum over the hypercube of f(x) * eq(x, r), the public vector of an opening at r is the eq table of r.
+// A polynomial in fewer variables than the setup is padded with zeros, and so is the eq table of its point: the
+// commitment is to the polynomial in all the setup's variables that is f when the leading ones are 0 and zero
+// elsewhere. Both sides only take points in all of them, the opening at r being one at (0, ..., 0, r), since
+// the commitment does not say how many variables f has and a shorter point would let a proof about the first
+// part of a larger table pass for one about the whole. open refuses a shorter point rather than return a proof
+// verify would never accept
+impl <G: CurveGroup>PolynomialCommitmentScheme<G::ScalarField> for MultilinearIpaPcs<G> {
+    type Params = IpaParams<G>;
+    type Polynomial = MultilinearPolynomial<G::ScalarField>;
+    type Point = [G::ScalarField];
+    type Commitment = PedersenCommitment<G>;
+    type OpeningProof = IpaProof<G>;
+
+    fn setup<R: RngCore>(max_size: usize, rng: &mut R) -> Self::Params {
+        IpaParams::setup(1 << max_size, rng)
+    }
+
+    fn commit(params: &Self::Params, polynomial: &Self::Polynomial) -> Self::Commitment {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_commit", scheme = "multilinear_ipa").entered();
+
+        ipa_commit(params, &polynomial.evaluated_values)
+    }
+
+    fn open(
+        params: &Self::Params,
+        polynomial: &Self::Polynomial,
+        point: &Self::Point,
+        transcript: &mut Transcript,
+    ) -> (G::ScalarField, Self::OpeningProof) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_open", scheme = "multilinear_ipa").entered();
+
+        assert_eq!(point.len(), params.h.len().ilog2() as usize, "point must have one value per setup variable");
+        assert!(polynomial.no_of_vars <= point.len(), "polynomial has more variables than the setup");
+
+        ipa_open(params, &polynomial.evaluated_values, &padded_eq_evaluations(point, params.h.len()), transcript)
+    }
+
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &Self::Point,
+        value: G::ScalarField,
+        proof: &Self::OpeningProof,
+        transcript: &mut Transcript,
+    ) -> bool {
+        if point.len() != params.h.len().ilog2() as usize {
+            return false;
+        }
+
+        ipa_verify(params, commitment, &padded_eq_evaluations(point, params.h.len()), value, proof, transcript)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FriPcsParams {
+    pub fri: FriParams,
+    // committed polynomials have degree below degree_bound, at most degree_bound coefficients
+    pub degree_bound: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FriPcsProof<F: PrimeField> {
+    // FRI proof that X·q(X) has degree below degree_bound, for the quotient q(X) = (f(X) - f(z)) / (X - z)
+    pub quotient_proof: FriProof<F>,
+    // f at the positions of the first FRI layer's openings, opened against the commitment
+    pub evaluations: Vec<FriLayerOpening<F>>,
+}
+
+// FRI_BLOWUP_FACTOR and FRI_QUERIES give roughly num_queries * log2(blowup_factor) = 64 bits of security
+const FRI_BLOWUP_FACTOR: usize = 4;
+const FRI_QUERIES: usize = 32;
+
+// The commitment is the Merkle root of f over the FRI domain. An opening at z runs FRI on X·q(X) for the quotient
+// q(X) = (f(X) - f(z)) / (X - z), which is a polynomial exactly when f(z) is the right value, and the verifier
+// checks x·q(x) * (x - z) = x * (f(x) - f(z)) at every queried x using openings of f. The factor X makes the bound
+// exclusive: FRI on q alone would only show deg q < degree_bound, letting through an f of degree degree_bound, while
+// deg X·q < degree_bound is deg f < degree_bound
+impl <F: PrimeField>PolynomialCommitmentScheme<F> for FriPcs<F> {
+    type Params = FriPcsParams;
+    type Polynomial = UnivariatePoly<F>;
+    type Point = F;
+    type Commitment = [u8; 32];
+    type OpeningProof = FriPcsProof<F>;
+
+    fn setup<R: RngCore>(max_size: usize, _rng: &mut R) -> Self::Params {
+        FriPcsParams {
+            fri: FriParams::new(FRI_BLOWUP_FACTOR, FRI_QUERIES).expect("the default blowup factor and query count are valid"),
+            degree_bound: (max_size + 1).next_power_of_two().max(2),
+        }
+    }
+
+    fn commit(params: &Self::Params, polynomial: &Self::Polynomial) -> Self::Commitment {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_commit", scheme = "fri").entered();
+
+        fri_codeword_tree(params, polynomial).1.root()
+    }
+
+    fn open(
+        params: &Self::Params,
+        polynomial: &Self::Polynomial,
+        point: &Self::Point,
+        transcript: &mut Transcript,
+    ) -> (F, Self::OpeningProof) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("pcs_open", scheme = "fri").entered();
+
+        let (codeword, tree) = fri_codeword_tree(params, polynomial);
+        let value = polynomial.evaluate(*point);
+
+        bind_opening(transcript, &tree.root(), *point, value);
+
+        let numerator = polynomial - &UnivariatePoly::new(vec![value]);
+        let (quotient, _) = numerator
+            .div_rem(&UnivariatePoly::new(vec![-*point, F::one()]))
+            .expect("X - point is not the zero polynomial");
+        let shifted = UnivariatePoly::new([vec![F::zero()], quotient.coefficients].concat());
+
+        (value, prove_opening(params, &codeword, &tree, &shifted, transcript))
+    }
+
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &Self::Point,
+        value: F,
+        proof: &Self::OpeningProof,
+        transcript: &mut Transcript,
+    ) -> bool {
+        bind_opening(transcript, commitment, *point, value);
+
+        if !params.fri.verify(params.degree_bound, &proof.quotient_proof, transcript)
+            || proof.evaluations.len() != proof.quotient_proof.queries.len()
+        {
+            return false;
+        }
+
+        let domain = match EvaluationDomain::<F>::new(params.degree_bound * params.fri.blowup_factor) {
+            Some(domain) => domain,
+            None => return false,
+        };
+
+        // x·q(x) * (x - z) must equal x * (f(x) - f(z))
+        let consistent = |shifted_value: F, evaluation: F, position: usize| {
+            let x = domain.element(position);
+            shifted_value * (x - point) == x * (evaluation - value)
+        };
+
+        proof.quotient_proof.queries.iter().zip(proof.evaluations.iter()).all(|(query, evaluation)| {
+            let quotient = &query[0];
+
+            evaluation.value_proof.index == quotient.value_proof.index
+                && evaluation.sibling_proof.index == quotient.sibling_proof.index
+                && MerkleTree::<Keccak256Hasher>::verify(commitment, &field_to_bytes(evaluation.value), &evaluation.value_proof)
+                && MerkleTree::<Keccak256Hasher>::verify(commitment, &field_to_bytes(evaluation.sibling_value), &evaluation.sibling_proof)
+                && consistent(quotient.value, evaluation.value, quotient.value_proof.index)
+                && consistent(quotient.sibling_value, evaluation.sibling_value, quotient.sibling_proof.index)
+        })
+    }
+}
+
+// FRI on proven, with f opened at the first layer's positions of each query
+fn prove_opening<F: PrimeField>(
+    params: &FriPcsParams,
+    codeword: &[F],
+    tree: &MerkleTree<Keccak256Hasher>,
+    proven: &UnivariatePoly<F>,
+    transcript: &mut Transcript,
+) -> FriPcsProof<F> {
+    // setup makes degree_bound a power of two >= 2, and fri_codeword_tree already refused a polynomial beyond it
+    let quotient_proof = params
+        .fri
+        .prove(proven, params.degree_bound, transcript)
+        .expect("the degree bound and the polynomial's degree were checked by setup and commit");
+
+    let evaluations = quotient_proof
+        .queries
+        .iter()
+        .map(|query| {
+            let position = query[0].value_proof.index;
+            let sibling = query[0].sibling_proof.index;
+
+            FriLayerOpening {
+                value: codeword[position],
+                value_proof: tree.open(position),
+                sibling_value: codeword[sibling],
+                sibling_proof: tree.open(sibling),
+            }
+        })
+        .collect();
+
+    FriPcsProof { quotient_proof, evaluations }
+}
+
+// The codeword of f over the FRI domain and its Merkle tree
+fn fri_codeword_tree<F: PrimeField>(params: &FriPcsParams, polynomial: &UnivariatePoly<F>) -> (Vec<F>, MerkleTree<Keccak256Hasher>) {
+    assert!(polynomial.coefficients.len() <= params.degree_bound, "polynomial exceeds the degree bound");
+
+    let domain = EvaluationDomain::<F>::new(params.degree_bound * params.fri.blowup_factor)
+        .expect("field does not support a domain of this size");
+    let codeword = domain.fft(&polynomial.coefficients);
+    let tree = MerkleTree::<Keccak256Hasher>::new(&codeword_leaves(&codeword));
+
+    (codeword, tree)
+}
+
+fn bind_opening<F: PrimeField>(transcript: &mut Transcript, commitment: &[u8; 32], point: F, value: F) {
+    transcript.append(commitment);
+    transcript.append(&field_to_bytes(point));
+    transcript.append(&field_to_bytes(value));
+}
+
+// The IPA core both IpaPcs and MultilinearIpaPcs open through: the committed vector a is <a, G>, an opening
+// is the inner product of a with a public vector b the verifier rebuilds from the point and commits to itself
+fn ipa_commit<G: CurveGroup>(params: &IpaParams<G>, values: &[G::ScalarField]) -> PedersenCommitment<G> {
+    PedersenCommitment { commitment: msm::<G, _>(&params.g, &padded(values, params.g.len())).expect("bases and scalars have the same length") }
+}
+
+fn ipa_open<G: CurveGroup>(
+    params: &IpaParams<G>,
+    values: &[G::ScalarField],
+    public: &[G::ScalarField],
+    transcript: &mut Transcript,
+) -> (G::ScalarField, IpaProof<G>) {
+    let values = padded(values, params.g.len());
+
+    params.prove(&params.commit(&values, public), &values, public, transcript)
+}
+
+fn ipa_verify<G: CurveGroup>(
+    params: &IpaParams<G>,
+    commitment: &PedersenCommitment<G>,
+    public: &[G::ScalarField],
+    value: G::ScalarField,
+    proof: &IpaProof<G>,
+    transcript: &mut Transcript,
+) -> bool {
+    let full_commitment = PedersenCommitment {
+        commitment: commitment.commitment + msm::<G, _>(&params.h, public).expect("bases and scalars have the same length"),
+    };
+
+    params.verify(&full_commitment, value, proof, transcript)
+}
+
+fn padded<F: PrimeField>(values: &[F], size: usize) -> Vec<F> {
+    assert!(values.len() <= size, "polynomial exceeds the setup");
+
+    let mut values = values.to_vec();
+    values.resize(size, F::zero());
+    values
+}
+
+// The eq table of the point, zero past its 2^len(point) entries
+fn padded_eq_evaluations<F: PrimeField>(point: &[F], size: usize) -> Vec<F> {
+    padded(&MultilinearPolynomial::eq_evaluations(point), size)
+}
+
+// (1, x, x^2, ..., x^(count - 1))
+fn powers_of<F: Field>(x: F, count: usize) -> Vec<F> {
+    let mut power = F::one();
+    (0..count)
+        .map(|_| {
+            let current = power;
+            power *= x;
+            current
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr, G1Projective};
+
+    fn polynomial() -> UnivariatePoly<Fr> {
+        UnivariatePoly::new((1..=6u64).map(|i| Fr::from(i * i + 1)).collect())
+    }
+
+    // Written once against the trait, run with every univariate backend
+    fn check_univariate_scheme<S: UnivariatePcs<Fr>>() {
+        let params = S::setup(7, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+        let point = Fr::from(17);
+
+        let commitment = S::commit(&params, &polynomial);
+        let (value, proof) = S::open(&params, &polynomial, &point, &mut Transcript::new());
+
+        assert_eq!(value, polynomial.evaluate(point));
+        assert!(S::verify(&params, &commitment, &point, value, &proof, &mut Transcript::new()));
+        assert!(!S::verify(&params, &commitment, &point, value + Fr::from(1), &proof, &mut Transcript::new()));
+        assert!(!S::verify(&params, &commitment, &Fr::from(18), value, &proof, &mut Transcript::new()));
+
+        let other_commitment = S::commit(&params, &UnivariatePoly::new(vec![Fr::from(4), Fr::from(2)]));
+        assert!(!S::verify(&params, &other_commitment, &point, value, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_univariate_backends() {
+        check_univariate_scheme::<KzgPcs<Bn254>>();
+        check_univariate_scheme::<IpaPcs<G1Projective>>();
+        check_univariate_scheme::<FriPcs<Fr>>();
+    }
+
+    #[test]
+    fn test_multilinear_backend() {
+        fn check<S: MultilinearPcs<Fr>>() {
+            let params = S::setup(3, &mut ark_std::test_rng());
+            let polynomial = MultilinearPolynomial::new(&(0..8u64).map(Fr::from).collect::<Vec<Fr>>());
+            let point = [Fr::from(2), Fr::from(3), Fr::from(4)];
+
+            let commitment = S::commit(&params, &polynomial);
+            let (value, proof) = S::open(&params, &polynomial, &point, &mut Transcript::new());
+
+            assert_eq!(value, polynomial.evaluate(&point));
+            assert!(S::verify(&params, &commitment, &point, value, &proof, &mut Transcript::new()));
+            assert!(!S::verify(&params, &commitment, &point, value + Fr::from(1), &proof, &mut Transcript::new()));
+        }
+
+        check::<PstPcs<Bn254>>();
+        check::<MultilinearIpaPcs<G1Projective>>();
+    }
+
+    #[test]
+    fn test_multilinear_ipa_pads_smaller_polynomials() {
+        let params = MultilinearIpaPcs::<G1Projective>::setup(3, &mut ark_std::test_rng());
+        let polynomial = MultilinearPolynomial::new(&[Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(8)]);
+        let point = [Fr::from(6), Fr::from(2)];
+
+        let commitment = MultilinearIpaPcs::commit(&params, &polynomial);
+        // the point is in all the setup's variables, the padding ones first
+        let full_point = [Fr::from(0), Fr::from(6), Fr::from(2)];
+        let (value, proof) = MultilinearIpaPcs::open(&params, &polynomial, &full_point, &mut Transcript::new());
+
+        assert_eq!(value, polynomial.evaluate(&point));
+        assert_eq!(value, Fr::from(78));
+        // log2 of the setup's 8 generators, not of the polynomial's 4 evaluations
+        assert_eq!(proof.l_vec.len(), 3);
+        assert!(MultilinearIpaPcs::verify(&params, &commitment, &full_point, value, &proof, &mut Transcript::new()));
+        assert!(!MultilinearIpaPcs::verify(&params, &commitment, &point, value, &proof, &mut Transcript::new()));
+        assert!(!MultilinearIpaPcs::verify(&params, &commitment, &[Fr::from(0), Fr::from(6), Fr::from(3)], value, &proof, &mut Transcript::new()));
+        assert!(!MultilinearIpaPcs::verify(&params, &commitment, &[Fr::from(6); 4], value, &proof, &mut Transcript::new()));
+
+        // away from the padding the opening is of the padded table, zero outside f
+        let (value, proof) = MultilinearIpaPcs::open(&params, &polynomial, &[Fr::from(1), Fr::from(6), Fr::from(2)], &mut Transcript::new());
+        assert_eq!(value, Fr::from(0));
+        assert!(MultilinearIpaPcs::verify(&params, &commitment, &[Fr::from(1), Fr::from(6), Fr::from(2)], value, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "point must have one value per setup variable")]
+    fn test_multilinear_ipa_open_refuses_a_point_shorter_than_the_setup() {
+        // the opening would be checked at (0, 6, 2) and never at (6, 2), so open does not make it
+        let params = MultilinearIpaPcs::<G1Projective>::setup(3, &mut ark_std::test_rng());
+        let polynomial = MultilinearPolynomial::new(&[Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(8)]);
+        MultilinearIpaPcs::open(&params, &polynomial, &[Fr::from(6), Fr::from(2)], &mut Transcript::new());
+    }
+
+    #[test]
+    fn test_multilinear_ipa_rejects_an_opening_of_a_prefix() {
+        // a 3 variable table, and an opening of its first half at a 2 coordinate point
+        let params = MultilinearIpaPcs::<G1Projective>::setup(3, &mut ark_std::test_rng());
+        let table: Vec<Fr> = [1u64, 2, 3, 4, 100, 100, 100, 100].iter().map(|value| Fr::from(*value)).collect();
+        let commitment = MultilinearIpaPcs::commit(&params, &MultilinearPolynomial::new(&table));
+
+        let point = [Fr::from(6), Fr::from(2)];
+        let (value, proof) = ipa_open(&params, &table, &padded_eq_evaluations(&point, 8), &mut Transcript::new());
+        assert_eq!(value, MultilinearPolynomial::new(&table[..4]).evaluate(&point));
+
+        assert!(!MultilinearIpaPcs::verify(&params, &commitment, &point, value, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_fri_opening_rejects_tampered_evaluations() {
+        let params = FriPcs::<Fr>::setup(7, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+        let point = Fr::from(17);
+
+        let commitment = FriPcs::commit(&params, &polynomial);
+        let (value, proof) = FriPcs::open(&params, &polynomial, &point, &mut Transcript::new());
+
+        let mut tampered = proof.clone();
+        tampered.evaluations[0].value += Fr::from(1);
+        assert!(!FriPcs::verify(&params, &commitment, &point, value, &tampered, &mut Transcript::new()));
+
+        let mut missing = proof;
+        missing.evaluations.pop();
+        assert!(!FriPcs::verify(&params, &commitment, &point, value, &missing, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_fri_degree_bound_is_exclusive() {
+        let params = FriPcs::<Fr>::setup(7, &mut ark_std::test_rng());
+        assert_eq!(params.degree_bound, 8);
+        let point = Fr::from(17);
+
+        // degree 7, the largest below the bound, opens
+        let polynomial = UnivariatePoly::new((1..=8u64).map(Fr::from).collect());
+        let commitment = FriPcs::commit(&params, &polynomial);
+        let (value, proof) = FriPcs::open(&params, &polynomial, &point, &mut Transcript::new());
+        assert!(FriPcs::verify(&params, &commitment, &point, value, &proof, &mut Transcript::new()));
+
+        // degree 8 has a quotient of degree 7, which FRI alone accepts under a bound of 8, so the opening has to prove
+        // the bound on X·q instead
+        let polynomial = UnivariatePoly::new((1..=9u64).map(Fr::from).collect());
+        let domain = EvaluationDomain::<Fr>::new(params.degree_bound * params.fri.blowup_factor).unwrap();
+        let codeword = domain.fft(&polynomial.coefficients);
+        let tree = MerkleTree::<Keccak256Hasher>::new(&codeword_leaves(&codeword));
+        let value = polynomial.evaluate(point);
+        let (quotient, _) = (&polynomial - &UnivariatePoly::new(vec![value]))
+            .div_rem(&UnivariatePoly::new(vec![-point, Fr::from(1)]))
+            .unwrap();
+
+        let mut transcript = Transcript::new();
+        bind_opening(&mut transcript, &tree.root(), point, value);
+        let proof = prove_opening(&params, &codeword, &tree, &quotient, &mut transcript);
+        assert!(!FriPcs::verify(&params, &tree.root(), &point, value, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the degree bound")]
+    fn test_fri_refuses_to_commit_past_the_degree_bound() {
+        let params = FriPcs::<Fr>::setup(7, &mut ark_std::test_rng());
+        FriPcs::commit(&params, &UnivariatePoly::new((1..=9u64).map(Fr::from).collect()));
+    }
+}
@@ -0,0 +1,163 @@
+use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+use std::ops::{Add, Mul, Sub};
+use crate::group_ops::msm;
+
+// Pedersen commitments over an elliptic curve group.
+//
+// A scalar v with blinding factor r is committed as C = v*G + r*H, and a vector (v_1, ..., v_n) as
+// C = v_1*G_1 + ... + v_n*G_n + r*H. The commitment hides v perfectly because r is uniformly random,
+// and binds v as long as nobody knows a discrete log relation between the generators.
+//
+// Commitments are additively homomorphic: Com(a, r) + Com(b, s) = Com(a + b, r + s)
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenParams<G: CurveGroup> {
+    pub generators: Vec<G::Affine>,
+    pub h: G::Affine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenCommitment<G: CurveGroup> {
+    pub commitment: G,
+}
+
+impl <G: CurveGroup>PedersenParams<G> {
+    // Samples vector_size + 1 independent random generators.
+    // Nobody learns the discrete logs between them as long as the rng output is not kept
+    pub fn setup<R: RngCore>(vector_size: usize, rng: &mut R) -> Self {
+        let generators: Vec<G> = (0..vector_size.max(1)).map(|_| G::rand(rng)).collect();
+
+        Self {
+            generators: G::normalize_batch(&generators),
+            h: G::rand(rng).into_affine(),
+        }
+    }
+
+    // C = value * G_1 + blinding * H
+    pub fn commit(&self, value: G::ScalarField, blinding: G::ScalarField) -> PedersenCommitment<G> {
+        PedersenCommitment {
+            commitment: self.generators[0] * value + self.h * blinding,
+        }
+    }
+
+    // C = values_1 * G_1 + ... + values_n * G_n + blinding * H
+    pub fn commit_vector(&self, values: &[G::ScalarField], blinding: G::ScalarField) -> PedersenCommitment<G> {
+        assert!(values.len() <= self.generators.len(), "vector is longer than the number of generators");
+
+        PedersenCommitment {
+            commitment: msm::<G, _>(&self.generators[..values.len()], values).expect("bases and scalars have the same length") + self.h * blinding,
+        }
+    }
+
+    // Samples a fresh blinding factor and returns it together with the commitment.
+    // The caller has to keep the blinding factor to open the commitment later
+    pub fn commit_hiding<R: RngCore>(&self, value: G::ScalarField, rng: &mut R) -> (PedersenCommitment<G>, G::ScalarField) {
+        let blinding = G::ScalarField::rand(rng);
+        (self.commit(value, blinding), blinding)
+    }
+
+    pub fn commit_vector_hiding<R: RngCore>(&self, values: &[G::ScalarField], rng: &mut R) -> (PedersenCommitment<G>, G::ScalarField) {
+        let blinding = G::ScalarField::rand(rng);
+        (self.commit_vector(values, blinding), blinding)
+    }
+
+    // Opening a commitment means revealing the value and the blinding factor
+    pub fn verify(&self, commitment: &PedersenCommitment<G>, value: G::ScalarField, blinding: G::ScalarField) -> bool {
+        self.commit(value, blinding) == *commitment
+    }
+
+    pub fn verify_vector(&self, commitment: &PedersenCommitment<G>, values: &[G::ScalarField], blinding: G::ScalarField) -> bool {
+        values.len() <= self.generators.len() && self.commit_vector(values, blinding) == *commitment
+    }
+}
+
+impl <G: CurveGroup>Add for PedersenCommitment<G> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        PedersenCommitment { commitment: self.commitment + other.commitment }
+    }
+}
+
+impl <G: CurveGroup>Sub for PedersenCommitment<G> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        PedersenCommitment { commitment: self.commitment - other.commitment }
+    }
+}
+
+// k * Com(v, r) = Com(k * v, k * r)
+impl <G: CurveGroup>Mul<G::ScalarField> for PedersenCommitment<G> {
+    type Output = Self;
+
+    fn mul(self, scalar: G::ScalarField) -> Self {
+        PedersenCommitment { commitment: self.commitment * scalar }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+
+    #[test]
+    fn test_commit_and_verify() {
+        let rng = &mut ark_std::test_rng();
+        let params = PedersenParams::<G1Projective>::setup(1, rng);
+
+        let (commitment, blinding) = params.commit_hiding(Fr::from(42), rng);
+
+        assert!(params.verify(&commitment, Fr::from(42), blinding));
+        assert!(!params.verify(&commitment, Fr::from(43), blinding));
+        assert!(!params.verify(&commitment, Fr::from(42), blinding + Fr::from(1)));
+    }
+
+    #[test]
+    fn test_hiding() {
+        let rng = &mut ark_std::test_rng();
+        let params = PedersenParams::<G1Projective>::setup(1, rng);
+
+        // the same value committed twice gives unrelated commitments
+        let (first, _) = params.commit_hiding(Fr::from(7), rng);
+        let (second, _) = params.commit_hiding(Fr::from(7), rng);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_homomorphic_addition() {
+        let rng = &mut ark_std::test_rng();
+        let params = PedersenParams::<G1Projective>::setup(1, rng);
+
+        let (a, r) = params.commit_hiding(Fr::from(10), rng);
+        let (b, s) = params.commit_hiding(Fr::from(32), rng);
+
+        assert!(params.verify(&(a + b), Fr::from(42), r + s));
+        assert!(params.verify(&(a - b), Fr::from(10) - Fr::from(32), r - s));
+        assert!(params.verify(&(a * Fr::from(3)), Fr::from(30), r * Fr::from(3)));
+    }
+
+    #[test]
+    fn test_vector_commitment() {
+        let rng = &mut ark_std::test_rng();
+        let params = PedersenParams::<G1Projective>::setup(4, rng);
+        let values = vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)];
+        let other_values = vec![Fr::from(5), Fr::from(6), Fr::from(7), Fr::from(8)];
+
+        let (commitment, blinding) = params.commit_vector_hiding(&values, rng);
+        let (other, other_blinding) = params.commit_vector_hiding(&other_values, rng);
+
+        assert!(params.verify_vector(&commitment, &values, blinding));
+        assert!(!params.verify_vector(&commitment, &other_values, blinding));
+
+        // the sum of vector commitments commits to the element-wise sum
+        let sum: Vec<Fr> = values.iter().zip(other_values.iter()).map(|(a, b)| *a + b).collect();
+        assert!(params.verify_vector(&(commitment + other), &sum, blinding + other_blinding));
+
+        // a scalar commitment is a vector commitment of length 1
+        assert_eq!(params.commit(Fr::from(9), blinding), params.commit_vector(&[Fr::from(9)], blinding));
+    }
+}
@@ -0,0 +1,199 @@
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+use ark_ff::UniformRand;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+use zeroize::Zeroize;
+use multilinear::multilinear::MultilinearPolynomial;
+use crate::group_ops;
+
+// Multilinear KZG commitments (Papamanthou-Shi-Tamassia, PST13).
+//
+// A trusted setup samples a secret point tau = (tau_1, ..., tau_n). A multilinear polynomial f is committed as
+// [f(tau)]_1 = sum over the hypercube of f(b) * [eq(b, tau)]_1, so the prover only needs the group elements [eq(b, tau)]_1.
+//
+// To open at r, the prover uses the decomposition
+// f(X) - f(r) = sum over i of (X_i - r_i) * q_i(X_(i+1), ..., X_n)
+// and sends [q_i(tau)]_1 for every variable. The verifier checks it "in the exponent" with pairings:
+// e([f(tau)] - [f(r)], [1]) == product over i of e([q_i(tau)], [tau_i - r_i])
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PstParams<E: Pairing> {
+    pub no_of_vars: usize,
+    // lagrange_bases[k] holds [eq(b, (tau_(k+1), ..., tau_n))]_1 for every b in {0, 1}^(n - k)
+    pub lagrange_bases: Vec<Vec<E::G1Affine>>,
+    pub g1: E::G1Affine,
+    pub g2: E::G2Affine,
+    // [tau_i]_2 for every variable
+    pub g2_tau: Vec<E::G2Affine>,
+}
+
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PstCommitment<E: Pairing> {
+    pub no_of_vars: usize,
+    pub commitment: E::G1Affine,
+}
+
+// One quotient commitment per variable of the opened polynomial
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PstOpeningProof<E: Pairing> {
+    pub quotients: Vec<E::G1Affine>,
+}
+
+impl <E: Pairing>PstParams<E> {
+    // Trusted setup for polynomials of up to no_of_vars variables.
+    // The secret tau is sampled from rng and wiped at the end of this function (toxic waste)
+    pub fn setup<R: RngCore>(no_of_vars: usize, rng: &mut R) -> Self {
+        let mut tau: Vec<E::ScalarField> = (0..no_of_vars).map(|_| E::ScalarField::rand(rng)).collect();
+
+        let g1 = E::G1::generator();
+        let g2 = E::G2::generator();
+
+        let lagrange_bases = (0..=no_of_vars)
+            .map(|k| {
+                let mut eq_table = MultilinearPolynomial::eq_evaluations(&tau[k..]);
+                let bases: Vec<E::G1> = eq_table.iter().map(|eq| g1 * eq).collect();
+                eq_table.zeroize();
+                E::G1::normalize_batch(&bases)
+            })
+            .collect();
+
+        let g2_tau: Vec<E::G2> = tau.iter().map(|t| g2 * t).collect();
+
+        tau.zeroize();
+
+        Self {
+            no_of_vars,
+            lagrange_bases,
+            g1: g1.into_affine(),
+            g2: g2.into_affine(),
+            g2_tau: E::G2::normalize_batch(&g2_tau),
+        }
+    }
+
+    // A polynomial with fewer variables than the setup uses the last tau values,
+    // so one setup can commit to polynomials of every size up to no_of_vars
+    fn offset(&self, no_of_vars: usize) -> usize {
+        assert!(no_of_vars <= self.no_of_vars, "polynomial has more variables than the setup supports");
+        self.no_of_vars - no_of_vars
+    }
+
+    pub fn commit(&self, polynomial: &MultilinearPolynomial<E::ScalarField>) -> PstCommitment<E> {
+        let offset = self.offset(polynomial.no_of_vars);
+
+        PstCommitment {
+            no_of_vars: polynomial.no_of_vars,
+            commitment: msm::<E>(&self.lagrange_bases[offset], &polynomial.evaluated_values),
+        }
+    }
+
+    // Returns f(point) together with the proof that the committed polynomial evaluates to it.
+    // For each variable, in the same order as MultilinearPolynomial::evaluate binds them:
+    // q_i = f(1, X') - f(0, X') (right half minus left half of the table), then f is partially evaluated at r_i
+    pub fn open(
+        &self,
+        polynomial: &MultilinearPolynomial<E::ScalarField>,
+        point: &[E::ScalarField],
+    ) -> (E::ScalarField, PstOpeningProof<E>) {
+        assert_eq!(point.len(), polynomial.no_of_vars, "point must have one value per variable");
+        let offset = self.offset(polynomial.no_of_vars);
+
+        let mut current = polynomial.evaluated_values.clone();
+        let mut quotients = Vec::with_capacity(point.len());
+
+        for (i, r) in point.iter().enumerate() {
+            let (left, right) = current.split_at(current.len() / 2);
+            let quotient: Vec<E::ScalarField> = left
+                .iter()
+                .zip(right.iter())
+                .map(|(low, high)| *high - low)
+                .collect();
+
+            quotients.push(msm::<E>(&self.lagrange_bases[offset + i + 1], &quotient));
+            current = MultilinearPolynomial::partial_evaluate(&current, 0, *r).evaluated_values;
+        }
+
+        (current[0], PstOpeningProof { quotients })
+    }
+
+    pub fn verify(
+        &self,
+        commitment: &PstCommitment<E>,
+        point: &[E::ScalarField],
+        value: E::ScalarField,
+        proof: &PstOpeningProof<E>,
+    ) -> bool {
+        if point.len() != commitment.no_of_vars
+            || proof.quotients.len() != commitment.no_of_vars
+            || commitment.no_of_vars > self.no_of_vars
+        {
+            return false;
+        }
+        let offset = self.no_of_vars - commitment.no_of_vars;
+
+        let left = E::pairing(commitment.commitment.into_group() - self.g1 * value, self.g2);
+
+        let g2_differences: Vec<E::G2Affine> = point
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (self.g2_tau[offset + i].into_group() - self.g2 * r).into_affine())
+            .collect();
+        let right = E::multi_pairing(proof.quotients.iter().copied(), g2_differences);
+
+        left == right
+    }
+}
+
+fn msm<E: Pairing>(bases: &[E::G1Affine], scalars: &[E::ScalarField]) -> E::G1Affine {
+    group_ops::msm::<E::G1, _>(bases, scalars).expect("bases and scalars have the same length").into_affine()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+
+    fn polynomial() -> MultilinearPolynomial<Fr> {
+        MultilinearPolynomial::new(&[Fr::from(0), Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(0), Fr::from(0), Fr::from(2), Fr::from(5)])
+    }
+
+    #[test]
+    fn test_commit_open_verify() {
+        let params = PstParams::<Bn254>::setup(3, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+        let point = vec![Fr::from(5), Fr::from(9), Fr::from(2)];
+
+        let commitment = params.commit(&polynomial);
+        let (value, proof) = params.open(&polynomial, &point);
+
+        assert_eq!(value, polynomial.evaluate(&point));
+        assert!(params.verify(&commitment, &point, value, &proof));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_value_and_point() {
+        let params = PstParams::<Bn254>::setup(3, &mut ark_std::test_rng());
+        let polynomial = polynomial();
+        let point = vec![Fr::from(5), Fr::from(9), Fr::from(2)];
+
+        let commitment = params.commit(&polynomial);
+        let (value, proof) = params.open(&polynomial, &point);
+
+        assert!(!params.verify(&commitment, &point, value + Fr::from(1), &proof));
+        assert!(!params.verify(&commitment, &[Fr::from(5), Fr::from(9), Fr::from(3)], value, &proof));
+        assert!(!params.verify(&commitment, &point[..2], value, &proof));
+    }
+
+    #[test]
+    fn test_smaller_polynomial_than_setup() {
+        let params = PstParams::<Bn254>::setup(4, &mut ark_std::test_rng());
+        let polynomial = MultilinearPolynomial::new(&[Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(8)]);
+        let point = vec![Fr::from(6), Fr::from(2)];
+
+        let commitment = params.commit(&polynomial);
+        let (value, proof) = params.open(&polynomial, &point);
+
+        assert_eq!(value, Fr::from(78));
+        assert!(params.verify(&commitment, &point, value, &proof));
+    }
+}
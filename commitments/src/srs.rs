@@ -0,0 +1,295 @@
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+use ark_ff::UniformRand;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::{RngCore, SeedableRng};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use zeroize::Zeroize;
+use crate::group_ops;
+use crate::kzg::{KzgBatchParams, KzgParams};
+
+pub const SRS_FORMAT_VERSION: u8 = 1;
+
+// A powers of tau structured reference string: [tau^i]_1 for i = 0..=max_degree and [tau^i]_2 for a few powers,
+// the part of a trusted setup every pairing based exercise shares. KZG needs [1]_2 and [tau]_2, a KZG batch opening
+// or a Groth16 style circuit setup more.
+//
+// The file format is
+//   version (1 byte) | no of G1 powers (u64 le) | no of G2 powers (u64 le) | G2 powers | G1 powers
+// with every point compressed, so every point of one group takes the same number of bytes. The short G2 part comes
+// first and the G1 powers last, so loading a prefix for a lower degree reads only the bytes it keeps.
+// This is a setup for testing: the process that generates it knows tau. A real one comes out of a ceremony
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Srs<E: Pairing> {
+    pub powers_of_tau_g1: Vec<E::G1Affine>,
+    pub powers_of_tau_g2: Vec<E::G2Affine>,
+}
+
+impl <E: Pairing>Srs<E> {
+    // max_g2_degree is at least 1, [tau]_2 is what every pairing check against the G1 powers needs.
+    // The secret tau is sampled from rng and wiped at the end of this function (toxic waste)
+    pub fn generate<R: RngCore>(max_degree: usize, max_g2_degree: usize, rng: &mut R) -> Self {
+        assert!(max_g2_degree >= 1, "the G2 powers include at least [tau]_2");
+
+        let mut tau = E::ScalarField::rand(rng);
+        let srs = Self {
+            powers_of_tau_g1: E::G1::normalize_batch(&powers(E::G1::generator(), tau, max_degree)),
+            powers_of_tau_g2: E::G2::normalize_batch(&powers(E::G2::generator(), tau, max_g2_degree)),
+        };
+
+        tau.zeroize();
+        srs
+    }
+
+    // The same parameters for every call with the same degrees, so tests and examples can share one setup
+    pub fn insecure_for_testing(max_degree: usize, max_g2_degree: usize) -> Self {
+        Self::generate(max_degree, max_g2_degree, &mut StdRng::seed_from_u64(0))
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.powers_of_tau_g1.len() - 1
+    }
+
+    pub fn max_g2_degree(&self) -> usize {
+        self.powers_of_tau_g2.len() - 1
+    }
+
+    // The prefix of the parameters for lower degrees, None if they go beyond this setup
+    pub fn trim(&self, max_degree: usize, max_g2_degree: usize) -> Option<Self> {
+        if max_degree > self.max_degree() || max_g2_degree > self.max_g2_degree() || max_g2_degree == 0 {
+            return None;
+        }
+        Some(Self {
+            powers_of_tau_g1: self.powers_of_tau_g1[..=max_degree].to_vec(),
+            powers_of_tau_g2: self.powers_of_tau_g2[..=max_g2_degree].to_vec(),
+        })
+    }
+
+    // KZG parameters for polynomials of degree up to max_degree, None if the setup is smaller
+    pub fn kzg_params(&self, max_degree: usize) -> Option<KzgParams<E>> {
+        let srs = self.trim(max_degree, 1)?;
+        Some(KzgParams {
+            powers_of_tau: srs.powers_of_tau_g1,
+            g2: srs.powers_of_tau_g2[0],
+            g2_tau: srs.powers_of_tau_g2[1],
+        })
+    }
+
+    // KZG parameters that also open at up to max_points points at once, None if the setup is smaller
+    pub fn kzg_batch_params(&self, max_degree: usize, max_points: usize) -> Option<KzgBatchParams<E>> {
+        Some(KzgBatchParams {
+            kzg: self.kzg_params(max_degree)?,
+            powers_of_tau_g2: self.trim(max_degree, max_points.max(1))?.powers_of_tau_g2,
+        })
+    }
+
+    // Checks that both lists are powers of one tau, starting at the generators: with a random combination r,
+    //   e(sum r^i [tau^i]_1, [tau]_2) == e(sum r^i [tau^(i+1)]_1, [1]_2)
+    // covers every consecutive pair of G1 powers in two pairings, and the same with the groups swapped for G2.
+    // A file from elsewhere is worth checking once before use
+    pub fn is_consistent<R: RngCore>(&self, rng: &mut R) -> bool {
+        let (g1, g2) = (&self.powers_of_tau_g1, &self.powers_of_tau_g2);
+        if g1.is_empty() || g2.len() < 2 || g1[0] != E::G1Affine::generator() || g2[0] != E::G2Affine::generator() {
+            return false;
+        }
+
+        let r = E::ScalarField::rand(rng);
+        let g1_powers = powers_of(r, g1.len() - 1);
+        let g2_powers = powers_of(r, g2.len() - 1);
+
+        let g1_low = group_ops::msm::<E::G1, _>(&g1[..g1.len() - 1], &g1_powers).expect("bases and scalars have the same length");
+        let g1_high = group_ops::msm::<E::G1, _>(&g1[1..], &g1_powers).expect("bases and scalars have the same length");
+        let g2_low = group_ops::msm::<E::G2, _>(&g2[..g2.len() - 1], &g2_powers).expect("bases and scalars have the same length");
+        let g2_high = group_ops::msm::<E::G2, _>(&g2[1..], &g2_powers).expect("bases and scalars have the same length");
+
+        E::pairing(g1_low, g2[1]) == E::pairing(g1_high, g2[0]) && E::pairing(g1[1], g2_low) == E::pairing(g1[0], g2_high)
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        writer.write_all(&[SRS_FORMAT_VERSION])?;
+        writer.write_all(&(self.powers_of_tau_g1.len() as u64).to_le_bytes())?;
+        writer.write_all(&(self.powers_of_tau_g2.len() as u64).to_le_bytes())?;
+        for point in &self.powers_of_tau_g2 {
+            point.serialize_compressed(&mut writer)?;
+        }
+        for point in &self.powers_of_tau_g1 {
+            point.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    // Reads the powers up to the given degrees and stops, the rest of the input is never read.
+    // Asking for more powers than the input holds is an error
+    pub fn read_prefix<R: Read>(mut reader: R, max_degree: usize, max_g2_degree: usize) -> Result<Self, SerializationError> {
+        let mut version = [0u8; 1];
+        reader.read_exact(&mut version)?;
+        if version[0] != SRS_FORMAT_VERSION || max_g2_degree == 0 {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let no_of_g1 = read_u64(&mut reader)?;
+        let no_of_g2 = read_u64(&mut reader)?;
+        if max_degree as u64 >= no_of_g1 || max_g2_degree as u64 >= no_of_g2 {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let powers_of_tau_g2 = (0..=max_g2_degree)
+            .map(|_| E::G2Affine::deserialize_compressed(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // skip the G2 powers beyond the prefix
+        let g2_size = E::G2Affine::generator().compressed_size() as u64;
+        let skip = (no_of_g2 - 1 - max_g2_degree as u64) * g2_size;
+        if std::io::copy(&mut (&mut reader).take(skip), &mut std::io::sink())? != skip {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let powers_of_tau_g1 = (0..=max_degree)
+            .map(|_| E::G1Affine::deserialize_compressed(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { powers_of_tau_g1, powers_of_tau_g2 })
+    }
+
+    // Reads a whole SRS written by write
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let mut header = [0u8; 17];
+        reader.read_exact(&mut header)?;
+        let no_of_g1 = u64::from_le_bytes(header[1..9].try_into().expect("8 bytes"));
+        let no_of_g2 = u64::from_le_bytes(header[9..17].try_into().expect("8 bytes"));
+        if no_of_g1 == 0 || no_of_g2 < 2 {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let max_degree = usize::try_from(no_of_g1 - 1).map_err(|_| SerializationError::InvalidData)?;
+        let max_g2_degree = usize::try_from(no_of_g2 - 1).map_err(|_| SerializationError::InvalidData)?;
+        let srs = Self::read_prefix(header.as_slice().chain(&mut reader), max_degree, max_g2_degree)?;
+
+        // nothing may follow the last G1 power
+        match reader.read(&mut [0u8; 1])? {
+            0 => Ok(srs),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SerializationError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SerializationError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    // Loads the powers up to the given degrees from a file that may hold many more
+    pub fn load_prefix(path: &Path, max_degree: usize, max_g2_degree: usize) -> Result<Self, SerializationError> {
+        Self::read_prefix(BufReader::new(File::open(path)?), max_degree, max_g2_degree)
+    }
+}
+
+// generator * tau^i for i = 0..=degree
+fn powers<G: CurveGroup>(generator: G, tau: G::ScalarField, degree: usize) -> Vec<G> {
+    let mut power = G::ScalarField::from(1u64);
+    let points = (0..=degree)
+        .map(|_| {
+            let point = generator * power;
+            power *= tau;
+            point
+        })
+        .collect();
+    power.zeroize();
+    points
+}
+
+// 1, r, ..., r^(len - 1)
+fn powers_of<F: ark_ff::Field>(r: F, len: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(len);
+    let mut power = F::one();
+    for _ in 0..len {
+        powers.push(power);
+        power *= r;
+    }
+    powers
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, SerializationError> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+    use univariate_poly::univariate::UnivariatePoly;
+
+    fn srs() -> Srs<Bn254> {
+        Srs::insecure_for_testing(16, 3)
+    }
+
+    #[test]
+    fn test_generated_srs_is_consistent() {
+        let srs = srs();
+        assert_eq!((srs.max_degree(), srs.max_g2_degree()), (16, 3));
+        assert!(srs.is_consistent(&mut ark_std::test_rng()));
+        assert_eq!(Srs::<Bn254>::insecure_for_testing(16, 3), srs);
+
+        // a single wrong power breaks the chain
+        let mut broken = srs.clone();
+        broken.powers_of_tau_g1[7] = broken.powers_of_tau_g1[6];
+        assert!(!broken.is_consistent(&mut ark_std::test_rng()));
+    }
+
+    #[test]
+    fn test_write_and_read_prefix() {
+        let srs = srs();
+        let mut bytes = Vec::new();
+        srs.write(&mut bytes).unwrap();
+
+        assert_eq!(Srs::<Bn254>::read(bytes.as_slice()).unwrap(), srs);
+        assert_eq!(Srs::<Bn254>::read_prefix(bytes.as_slice(), 4, 1).unwrap(), srs.trim(4, 1).unwrap());
+
+        // the prefix of degree 4 ends well before the file does, the rest can be missing
+        let prefix_length = 17 + 64 * 4 + 32 * 5;
+        assert!(Srs::<Bn254>::read_prefix(&bytes[..prefix_length], 4, 1).is_ok());
+        assert!(Srs::<Bn254>::read_prefix(&bytes[..prefix_length - 1], 4, 1).is_err());
+
+        assert!(Srs::<Bn254>::read_prefix(bytes.as_slice(), 17, 1).is_err());
+        assert!(Srs::<Bn254>::read(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Srs::<Bn254>::read([bytes.as_slice(), &[0]].concat().as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_from_disk() {
+        let path = std::env::temp_dir().join(format!("srs-test-{}.bin", std::process::id()));
+        let srs = srs();
+        srs.save(&path).unwrap();
+
+        assert_eq!(Srs::<Bn254>::load(&path).unwrap(), srs);
+        assert_eq!(Srs::<Bn254>::load_prefix(&path, 8, 1).unwrap(), srs.trim(8, 1).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_kzg_from_a_loaded_prefix() {
+        let srs = srs();
+        let params = srs.kzg_params(4).unwrap();
+        assert_eq!(params.max_degree(), 4);
+        assert!(srs.kzg_params(17).is_none());
+
+        let polynomial = UnivariatePoly::new(vec![Fr::from(3), Fr::from(1), Fr::from(4)]);
+        let commitment = params.commit(&polynomial);
+        let (value, proof) = params.open(&polynomial, Fr::from(2));
+        assert!(params.verify(&commitment, Fr::from(2), value, &proof));
+
+        // a commitment under the bigger setup is the same point
+        assert_eq!(srs.kzg_params(16).unwrap().commit(&polynomial), commitment);
+    }
+}
@@ -0,0 +1,43 @@
+#![no_main]
+
+// Circuits with arbitrary wiring, built from the fuzzer's bytes: every layer is a list of
+// (is_mul, left, right, output) gates. Evaluating, lowering and exporting a badly wired circuit returns an error
+// instead of panicking, and a circuit that evaluates has a PLONK-ish trace satisfying its export
+use ark_bn254::Fr;
+use circuits::{Circuit, Gate, Layer, Operator};
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut unstructured = Unstructured::new(data);
+    let Ok((input, layers)) = <(Vec<u64>, Vec<Vec<(bool, u8, u8, u8)>>)>::arbitrary(&mut unstructured) else {
+        return;
+    };
+
+    let input: Vec<Fr> = input.into_iter().map(Fr::from).collect();
+    let layers = layers
+        .into_iter()
+        .map(|gates| {
+            Layer::new(
+                gates
+                    .into_iter()
+                    .map(|(is_mul, left, right, output)| {
+                        let operator = if is_mul { Operator::Mul } else { Operator::Add };
+                        Gate::new(left as usize, right as usize, output as usize, operator)
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+    let mut circuit = Circuit::new(layers);
+
+    let _ = circuit.to_r1cs();
+    for layer in 0..=circuit.layers.len() {
+        let _ = circuit.add_i_and_mul_i_mle(layer);
+    }
+
+    let trace = circuit.plonkish_trace(&input);
+    if circuit.evaluate(input).is_ok() {
+        assert!(circuit.to_plonkish().is_satisfied(&trace.unwrap()));
+    }
+});
@@ -0,0 +1,33 @@
+#![no_main]
+
+// Arbitrary bytes through both proof encodings. Decoding never panics, whatever decodes is canonical (encoding it
+// again gives the same bytes), and verifying a decoded proof never panics either
+use ark_bn254::Fr;
+use libfuzzer_sys::fuzz_target;
+use sumcheck::composed::ComposedSumcheckProof;
+use sumcheck::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use sumcheck::grand_product::GrandProductProof;
+use sumcheck::proof_format::ProofFormat;
+
+fuzz_target!(|data: &[u8]| {
+    if let Some(proof) = ComposedSumcheckProof::<Fr>::from_bytes(data) {
+        assert_eq!(proof.to_bytes(), data);
+        let no_of_vars = proof.round_polys.len();
+        let degree = proof.round_polys.first().map_or(0, |round_poly| round_poly.len().saturating_sub(1));
+        let _ = proof.verify(Fr::from(0), no_of_vars, degree, &mut Transcript::new());
+    }
+
+    if let Some(proof) = GrandProductProof::<Fr>::from_bytes(data) {
+        assert_eq!(proof.to_bytes(), data);
+        let _ = proof.verify(proof.layers.len(), &mut Transcript::new());
+    }
+
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Some(proof) = ComposedSumcheckProof::<Fr>::from_json(text) {
+            assert_eq!(ComposedSumcheckProof::<Fr>::from_json(&proof.to_json()), Some(proof));
+        }
+        if let Some(proof) = GrandProductProof::<Fr>::from_json(text) {
+            assert_eq!(GrandProductProof::<Fr>::from_json(&proof.to_json()), Some(proof));
+        }
+    }
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+// Arbitrary bytes and text through the share decoders. Decoding never panics and whatever decodes is canonical
+use ark_bn254::Fq;
+use libfuzzer_sys::fuzz_target;
+use shamir_secret::share::{decode_hex, encode_hex, SecretBytesShare, Share};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(share) = Share::<Fq>::from_bytes(data) {
+        assert_eq!(share.to_bytes(), data);
+    }
+
+    if let Ok(share) = SecretBytesShare::<Fq>::from_bytes(data) {
+        assert_eq!(share.to_bytes(), data);
+    }
+
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(bytes) = decode_hex(text) {
+            assert_eq!(encode_hex(&bytes), text.trim().to_lowercase());
+        }
+    }
+});
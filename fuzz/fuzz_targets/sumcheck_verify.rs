@@ -0,0 +1,82 @@
+#![no_main]
+
+// Mutated valid proofs. The fuzzer picks a polynomial and a change to its sumcheck proof: the verifier accepts the
+// honest proof and refuses the changed one. Composed proofs are also changed at the byte level, where anything that
+// still decodes to a different proof must fail verification
+use ark_bn254::Fr;
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use multilinear::multilinear::MultilinearPolynomial;
+use sumcheck::composed::ComposedSumcheckProof;
+use sumcheck::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use sumcheck::proof_format::ProofFormat;
+use sumcheck::sumcheck::{Prover, Verifier};
+
+#[derive(Debug, Arbitrary)]
+enum Mutation {
+    ClaimedSum(u64),
+    InitialPoly(usize, u64),
+    RoundPoly(usize, usize, u64),
+    ProofBytes(Vec<(usize, u8)>),
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut unstructured = Unstructured::new(data);
+    let Ok((evaluations, mutation)) = <([u64; 8], Mutation)>::arbitrary(&mut unstructured) else {
+        return;
+    };
+
+    let evaluations: Vec<Fr> = evaluations.iter().copied().map(Fr::from).collect();
+    let prove = || Prover::new(&evaluations).prove();
+    assert!(Verifier::new().verify(prove()));
+
+    let mut proof = prove();
+    match mutation {
+        Mutation::ClaimedSum(delta) => {
+            if delta == 0 {
+                return;
+            }
+            proof.initial_claimed_sum += Fr::from(delta);
+        }
+        Mutation::InitialPoly(index, delta) => {
+            if delta == 0 {
+                return;
+            }
+            let values = &mut proof.initial_poly.evaluated_values;
+            let len = values.len();
+            values[index % len] += Fr::from(delta);
+        }
+        Mutation::RoundPoly(round, index, delta) => {
+            if delta == 0 {
+                return;
+            }
+            let rounds = proof.uni_poly_for_each_round.len();
+            let values = &mut proof.uni_poly_for_each_round[round % rounds].evaluated_values;
+            values[index % 2] += Fr::from(delta);
+        }
+        Mutation::ProofBytes(flips) => {
+            let polynomial = MultilinearPolynomial::new(&evaluations);
+            let claimed_sum: Fr = evaluations.iter().sum();
+            let (honest, _, _) =
+                ComposedSumcheckProof::prove(std::slice::from_ref(&polynomial), 1, |values: &[Fr]| values[0], &mut Transcript::new())
+                    .unwrap();
+
+            let mut bytes = honest.to_bytes();
+            let len = bytes.len();
+            for (index, flip) in flips {
+                bytes[index % len] ^= flip;
+            }
+
+            if let Some(decoded) = ComposedSumcheckProof::<Fr>::from_bytes(&bytes) {
+                if decoded != honest {
+                    let accepted = decoded
+                        .verify(claimed_sum, polynomial.no_of_vars, 1, &mut Transcript::new())
+                        .is_some_and(|(point, final_claim)| polynomial.evaluate(&point) == final_claim);
+                    assert!(!accepted);
+                }
+            }
+            return;
+        }
+    }
+    assert!(!Verifier::new().verify(proof));
+});
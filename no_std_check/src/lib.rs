@@ -0,0 +1,38 @@
+#![no_std]
+
+// A crate that cannot use std, calling the verifier side of the sumcheck crate the way an embedded verifier would.
+// If anything these paths reach starts to need std, this crate stops compiling.
+// Build it on its own: in a workspace build, features unify across the members, std comes back on for sumcheck and
+// the check passes whatever the verifiers use. For a target without std at all run
+//   cargo build -p no_std_check --target thumbv7em-none-eabihf
+use ark_bn254::Fr;
+use sha3::Sha3_256;
+use sumcheck::composed::ComposedSumcheckProof;
+use sumcheck::grand_product::GrandProductProof;
+use sumcheck::sumcheck::{SumcheckProof, Verifier};
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, HashTranscript, Transcript};
+
+// The plain sumcheck, whose proof carries the polynomial for the oracle check
+pub fn verify_plain_sumcheck(proof: SumcheckProof<Fr>) -> bool {
+    Verifier::new().verify(proof)
+}
+
+// A composed sumcheck over the default Keccak transcript, with the final claim checked against the polynomial itself
+pub fn verify_sumcheck(proof: &ComposedSumcheckProof<Fr>, claimed_sum: Fr, evaluations: &[Fr]) -> bool {
+    let polynomial = multilinear::multilinear::MultilinearPolynomial::new(evaluations);
+
+    match proof.verify(claimed_sum, polynomial.no_of_vars, 1, &mut Transcript::new()) {
+        Some((point, final_claim)) => polynomial.evaluate(&point) == final_claim,
+        None => false,
+    }
+}
+
+// A grand product verified over a SHA3-256 transcript instead of Keccak
+pub fn verify_grand_product(proof: &GrandProductProof<Fr>, no_of_vars: usize, evaluations: &[Fr]) -> bool {
+    let polynomial = multilinear::multilinear::MultilinearPolynomial::new(evaluations);
+
+    match proof.verify(no_of_vars, &mut HashTranscript::<Sha3_256>::new()) {
+        Some((point, claim)) => polynomial.evaluate(&point) == claim,
+        None => false,
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt;
+
+// Everything that can go wrong choosing Poseidon parameters or calling the permutation on them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoseidonError {
+    // the state needs a capacity and a rate element
+    WidthTooSmall { width: usize },
+    // full rounds are split evenly around the partial rounds
+    OddFullRounds { full_rounds: usize },
+    WrongStateWidth { expected: usize, provided: usize },
+    // hash takes width - 1 inputs
+    WrongNumberOfInputs { expected: usize, provided: usize },
+}
+
+impl fmt::Display for PoseidonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PoseidonError::WidthTooSmall { width } => write!(f, "width {} leaves no room for a capacity and a rate element", width),
+            PoseidonError::OddFullRounds { full_rounds } => write!(f, "{} full rounds cannot be split evenly", full_rounds),
+            PoseidonError::WrongStateWidth { expected, provided } => {
+                write!(f, "state has {} elements, the permutation width is {}", provided, expected)
+            }
+            PoseidonError::WrongNumberOfInputs { expected, provided } => {
+                write!(f, "{} inputs provided, the hash takes {}", provided, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PoseidonError {}
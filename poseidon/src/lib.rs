@@ -0,0 +1,4 @@
+pub mod error;
+pub mod poseidon;
+pub mod sponge;
+pub mod poseidon_transcript;
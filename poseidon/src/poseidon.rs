@@ -0,0 +1,276 @@
+use crate::error::PoseidonError;
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use std::sync::OnceLock;
+
+// Poseidon permutation (Grassi, Khovratovich, Rechberger, Roy, Schofnegger).
+//
+// The state is `width` field elements. Each round adds the round constants, applies the S-box x^alpha
+// and multiplies the state by an MDS matrix. The first and last full_rounds / 2 rounds apply the S-box
+// to every element, the partial rounds in between only to the first one, which keeps the number of
+// multiplications (and constraints, when the permutation is arithmetized) low.
+//
+// The round constants and the MDS matrix are generated with the Grain LFSR from the reference
+// implementation, so for bn254 with width 3 they are exactly the constants used by circomlib
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseidonParams<F: PrimeField> {
+    pub width: usize,
+    pub full_rounds: usize,
+    pub partial_rounds: usize,
+    pub alpha: u64,
+    // round_constants[r] holds the `width` constants added at the start of round r
+    pub round_constants: Vec<Vec<F>>,
+    pub mds: Vec<Vec<F>>,
+}
+
+// circomlib's parameters for a 2-to-1 hash over a 254-bit field
+pub const WIDTH: usize = 3;
+pub const FULL_ROUNDS: usize = 8;
+pub const PARTIAL_ROUNDS: usize = 57;
+pub const ALPHA: u64 = 5;
+
+impl <F: PrimeField>PoseidonParams<F> {
+    // alpha must satisfy gcd(alpha, p - 1) = 1 so that x^alpha is a permutation of the field
+    pub fn new(width: usize, full_rounds: usize, partial_rounds: usize, alpha: u64) -> Result<Self, PoseidonError> {
+        if width < 2 {
+            return Err(PoseidonError::WidthTooSmall { width });
+        }
+        if !full_rounds.is_multiple_of(2) {
+            return Err(PoseidonError::OddFullRounds { full_rounds });
+        }
+
+        let mut grain = GrainLfsr::new(F::MODULUS_BIT_SIZE as u64, width, full_rounds, partial_rounds);
+
+        // constants are rejection-sampled so they are uniform in the field
+        let round_constants = (0..full_rounds + partial_rounds)
+            .map(|_| {
+                (0..width)
+                    .map(|_| loop {
+                        if let Some(constant) = F::from_bigint(grain.next_bigint::<F>()) {
+                            break constant;
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            width,
+            full_rounds,
+            partial_rounds,
+            alpha,
+            round_constants,
+            mds: cauchy_mds(&mut grain, width),
+        })
+    }
+
+    // Applies the permutation to `state` in place
+    pub fn permute(&self, state: &mut [F]) -> Result<(), PoseidonError> {
+        if state.len() != self.width {
+            return Err(PoseidonError::WrongStateWidth { expected: self.width, provided: state.len() });
+        }
+
+        self.permute_state(state);
+        Ok(())
+    }
+
+    // The permutation on a state the caller knows to be `width` elements long, the sponge's own
+    pub(crate) fn permute_state(&self, state: &mut [F]) {
+        let half_full_rounds = self.full_rounds / 2;
+
+        for (round, constants) in self.round_constants.iter().enumerate() {
+            for (element, constant) in state.iter_mut().zip(constants.iter()) {
+                *element += constant;
+            }
+
+            if round < half_full_rounds || round >= half_full_rounds + self.partial_rounds {
+                for element in state.iter_mut() {
+                    *element = element.pow([self.alpha]);
+                }
+            } else {
+                state[0] = state[0].pow([self.alpha]);
+            }
+
+            let mixed: Vec<F> = self
+                .mds
+                .iter()
+                .map(|row| row.iter().zip(state.iter()).map(|(m, s)| *m * s).sum())
+                .collect();
+            state.copy_from_slice(&mixed);
+        }
+    }
+
+    // Fixed-length hash of width - 1 elements: the state starts as [0, inputs...] and the
+    // first element of the permuted state is the digest. This is circomlib's Poseidon(inputs)
+    pub fn hash(&self, inputs: &[F]) -> Result<F, PoseidonError> {
+        if inputs.len() != self.width - 1 {
+            return Err(PoseidonError::WrongNumberOfInputs { expected: self.width - 1, provided: inputs.len() });
+        }
+
+        let mut state = vec![F::zero()];
+        state.extend_from_slice(inputs);
+        self.permute_state(&mut state);
+
+        Ok(state[0])
+    }
+}
+
+// The circomlib parameters, generated for the field of `F`
+impl <F: PrimeField>Default for PoseidonParams<F> {
+    fn default() -> Self {
+        Self::new(WIDTH, FULL_ROUNDS, PARTIAL_ROUNDS, ALPHA).expect("the circomlib parameters have a width of 3 and 8 full rounds")
+    }
+}
+
+// Generating the constants takes a few hundred thousand LFSR steps, so the bn254 parameters
+// are generated once and shared
+pub fn bn254_params() -> &'static PoseidonParams<Fr> {
+    static PARAMS: OnceLock<PoseidonParams<Fr>> = OnceLock::new();
+    PARAMS.get_or_init(PoseidonParams::default)
+}
+
+// The 80-bit Grain LFSR from the Poseidon reference implementation.
+// It is seeded with the field type, S-box type, field size, width and round numbers, so different
+// parameter sets get unrelated constants
+struct GrainLfsr {
+    state: [bool; 80],
+    // state[head] is the oldest bit
+    head: usize,
+}
+
+impl GrainLfsr {
+    fn new(field_size: u64, width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let mut bits = Vec::with_capacity(80);
+        let mut push_bits = |value: u64, no_of_bits: usize| {
+            for i in (0..no_of_bits).rev() {
+                bits.push((value >> i) & 1 == 1);
+            }
+        };
+
+        // field = 1 (prime field), s-box = 0 (x^alpha)
+        push_bits(1, 2);
+        push_bits(0, 4);
+        push_bits(field_size, 12);
+        push_bits(width as u64, 12);
+        push_bits(full_rounds as u64, 10);
+        push_bits(partial_rounds as u64, 10);
+        push_bits((1 << 30) - 1, 30);
+
+        let mut lfsr = Self { state: [false; 80], head: 0 };
+        lfsr.state.copy_from_slice(&bits);
+
+        // the first 160 bits are discarded
+        for _ in 0..160 {
+            lfsr.step();
+        }
+
+        lfsr
+    }
+
+    // b_(i+80) = b_(i+62) + b_(i+51) + b_(i+38) + b_(i+23) + b_(i+13) + b_i
+    fn step(&mut self) -> bool {
+        let bit = |offset: usize| self.state[(self.head + offset) % 80];
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+
+        self.state[self.head] = new_bit;
+        self.head = (self.head + 1) % 80;
+
+        new_bit
+    }
+
+    // Output bits come in pairs: if the first bit is 1 the second is output, otherwise both are discarded
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.step();
+            let bit = self.step();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    // A MODULUS_BIT_SIZE-bit integer read most significant bit first, which may exceed the modulus
+    fn next_bigint<F: PrimeField>(&mut self) -> F::BigInt {
+        let bits: Vec<bool> = (0..F::MODULUS_BIT_SIZE).map(|_| self.next_bit()).collect();
+        F::BigInt::from_bits_be(&bits)
+    }
+}
+
+// Cauchy matrix M[i][j] = 1 / (x_i + y_j) for distinct x_i, y_j drawn from the LFSR.
+// Every square submatrix of a Cauchy matrix is invertible, which is what makes it MDS
+fn cauchy_mds<F: PrimeField>(grain: &mut GrainLfsr, width: usize) -> Vec<Vec<F>> {
+    loop {
+        let values: Vec<F> = (0..2 * width)
+            .map(|_| F::from_be_bytes_mod_order(&grain.next_bigint::<F>().to_bytes_be()))
+            .collect();
+
+        let all_distinct = values.iter().enumerate().all(|(i, value)| !values[..i].contains(value));
+        if !all_distinct {
+            continue;
+        }
+
+        let (xs, ys) = values.split_at(width);
+        let entries: Option<Vec<Vec<F>>> = xs
+            .iter()
+            .map(|x| ys.iter().map(|y| (*x + y).inverse()).collect())
+            .collect();
+
+        if let Some(mds) = entries {
+            return mds;
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::MontFp;
+
+    #[test]
+    fn test_constants_match_circomlib() {
+        let params = bn254_params();
+
+        assert_eq!(params.round_constants.len(), FULL_ROUNDS + PARTIAL_ROUNDS);
+        assert_eq!(
+            params.round_constants[0][0],
+            MontFp!("6745197990210204598374042828761989596302876299545964402857411729872131034734")
+        );
+        assert_eq!(
+            params.mds[0][0],
+            MontFp!("7511745149465107256748700652201246547602992235352608707588321460060273774987")
+        );
+    }
+
+    #[test]
+    fn test_permutation_matches_circomlib() {
+        // circomlib's Poseidon([1, 2]) permutes the state [0, 1, 2]
+        let params = bn254_params();
+        let expected: Fr = MontFp!("7853200120776062878684798364095072458815029376092732009249414926327459813530");
+
+        let mut state = [Fr::from(0), Fr::from(1), Fr::from(2)];
+        params.permute(&mut state).unwrap();
+
+        assert_eq!(state[0], expected);
+        assert_eq!(params.hash(&[Fr::from(1), Fr::from(2)]), Ok(expected));
+    }
+
+    #[test]
+    fn test_wrong_shapes_are_errors() {
+        let params = bn254_params();
+
+        assert_eq!(params.permute(&mut [Fr::from(0); 2]), Err(PoseidonError::WrongStateWidth { expected: 3, provided: 2 }));
+        assert_eq!(params.hash(&[Fr::from(1)]), Err(PoseidonError::WrongNumberOfInputs { expected: 2, provided: 1 }));
+        assert_eq!(PoseidonParams::<Fr>::new(1, 8, 57, 5), Err(PoseidonError::WidthTooSmall { width: 1 }));
+        assert_eq!(PoseidonParams::<Fr>::new(3, 7, 57, 5), Err(PoseidonError::OddFullRounds { full_rounds: 7 }));
+    }
+
+    #[test]
+    fn test_different_parameters_give_different_constants() {
+        let t3 = PoseidonParams::<Fr>::new(3, 8, 57, 5).unwrap();
+        let t5 = PoseidonParams::<Fr>::new(5, 8, 60, 5).unwrap();
+
+        assert_eq!(t5.mds.len(), 5);
+        assert_ne!(t3.round_constants[0][0], t5.round_constants[0][0]);
+    }
+}
@@ -0,0 +1,86 @@
+use ark_ff::PrimeField;
+use transcript::fiat_shamir::FiatShamirTranscriptInterface;
+use crate::poseidon::PoseidonParams;
+use crate::sponge::{bytes_to_field_elements, field_element_to_bytes, PoseidonSponge};
+
+// A Fiat-Shamir transcript over a Poseidon sponge instead of Keccak256.
+//
+// Field elements can be absorbed natively with append_field_elements, which is what a verifier
+// circuit does, so a proof generated with this transcript can be checked in-circuit with the
+// Poseidon gadget and both sides derive the same challenges
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseidonTranscript<F: PrimeField> {
+    sponge: PoseidonSponge<F>,
+}
+
+impl <F: PrimeField>PoseidonTranscript<F> {
+    pub fn with_params(params: &PoseidonParams<F>) -> Self {
+        Self { sponge: PoseidonSponge::new(params) }
+    }
+
+    pub fn append_field_elements(&mut self, elements: &[F]) {
+        self.sponge.absorb(elements);
+    }
+
+    pub fn challenge(&mut self) -> F {
+        self.sponge.squeeze()
+    }
+}
+
+impl <F: PrimeField>FiatShamirTranscriptInterface for PoseidonTranscript<F> {
+    // Uses the circomlib parameters for F
+    fn new() -> Self {
+        Self::with_params(&PoseidonParams::default())
+    }
+
+    fn append(&mut self, incoming_data: &[u8]) {
+        self.sponge.absorb(&bytes_to_field_elements(incoming_data));
+    }
+
+    fn sample_random_challenge(&mut self) -> [u8; 32] {
+        field_element_to_bytes(self.sponge.squeeze())
+    }
+
+    fn random_challenge_as_field_element<G: PrimeField>(&mut self) -> G {
+        G::from_le_bytes_mod_order(&self.sample_random_challenge())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poseidon::bn254_params;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_transcript_is_deterministic() {
+        let mut first = PoseidonTranscript::<Fr>::with_params(bn254_params());
+        let mut second = PoseidonTranscript::<Fr>::with_params(bn254_params());
+
+        first.append(b"commitment");
+        second.append(b"commitment");
+        assert_eq!(first.sample_random_challenge(), second.sample_random_challenge());
+
+        first.append(b"round 1");
+        second.append(b"round 2");
+        let a: Fr = first.random_challenge_as_field_element();
+        let b: Fr = second.random_challenge_as_field_element();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_native_challenges_match_sponge() {
+        let params = bn254_params();
+        let mut transcript = PoseidonTranscript::<Fr>::with_params(params);
+        transcript.append_field_elements(&[Fr::from(3), Fr::from(4)]);
+
+        let mut sponge = PoseidonSponge::new(params);
+        sponge.absorb(&[Fr::from(3), Fr::from(4)]);
+
+        let challenge = transcript.challenge();
+        assert_eq!(challenge, sponge.squeeze());
+        // a challenge in the native field is not reduced, so it converts back unchanged
+        assert_eq!(Fr::from_le_bytes_mod_order(&field_element_to_bytes(challenge)), challenge);
+    }
+}
@@ -0,0 +1,181 @@
+use ark_ff::{BigInteger, PrimeField};
+use crate::poseidon::PoseidonParams;
+
+// Duplex sponge over the Poseidon permutation.
+//
+// The first state element is the capacity and is never absorbed into or squeezed from,
+// the other width - 1 elements are the rate. Absorbing adds inputs into the rate and permutes
+// whenever it is full; squeezing permutes once and then reads the rate elements one by one.
+//
+// The first squeeze after absorbing pads what was absorbed with 10*: a 1 goes into the rate element
+// after the last input, in a block of its own when the last one is full, so inputs that differ only
+// by trailing zeros, like [x] and [x, 0], do not leave the sponge in the same state
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseidonSponge<F: PrimeField> {
+    params: PoseidonParams<F>,
+    state: Vec<F>,
+    mode: SpongeMode,
+}
+
+// The position in the rate the next element is absorbed into or squeezed from
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpongeMode {
+    Absorbing(usize),
+    Squeezing(usize),
+}
+
+impl <F: PrimeField>PoseidonSponge<F> {
+    pub fn new(params: &PoseidonParams<F>) -> Self {
+        Self {
+            params: params.clone(),
+            state: vec![F::zero(); params.width],
+            mode: SpongeMode::Absorbing(0),
+        }
+    }
+
+    fn rate(&self) -> usize {
+        self.params.width - 1
+    }
+
+    pub fn absorb(&mut self, elements: &[F]) {
+        let mut position = match self.mode {
+            SpongeMode::Absorbing(position) => position,
+            SpongeMode::Squeezing(_) => 0,
+        };
+
+        for element in elements {
+            if position == self.rate() {
+                self.params.permute_state(&mut self.state);
+                position = 0;
+            }
+            self.state[1 + position] += element;
+            position += 1;
+        }
+
+        self.mode = SpongeMode::Absorbing(position);
+    }
+
+    pub fn squeeze(&mut self) -> F {
+        let position = match self.mode {
+            SpongeMode::Squeezing(position) if position < self.rate() => position,
+            SpongeMode::Squeezing(_) => {
+                self.params.permute_state(&mut self.state);
+                0
+            }
+            SpongeMode::Absorbing(mut position) => {
+                if position == self.rate() {
+                    self.params.permute_state(&mut self.state);
+                    position = 0;
+                }
+                self.state[1 + position] += F::one();
+                self.params.permute_state(&mut self.state);
+                0
+            }
+        };
+
+        self.mode = SpongeMode::Squeezing(position + 1);
+        self.state[1 + position]
+    }
+
+    pub fn squeeze_many(&mut self, count: usize) -> Vec<F> {
+        (0..count).map(|_| self.squeeze()).collect()
+    }
+}
+
+// Variable-length hash: absorbs the inputs and squeezes one element, the padding keeping inputs of
+// different lengths apart
+pub fn hash_many<F: PrimeField>(params: &PoseidonParams<F>, inputs: &[F]) -> F {
+    let mut sponge = PoseidonSponge::new(params);
+    sponge.absorb(inputs);
+    sponge.squeeze()
+}
+
+// Packs bytes into field elements of (MODULUS_BIT_SIZE - 1) / 8 bytes each, little endian,
+// so every chunk fits below the modulus. The byte length comes first to keep the packing injective
+pub fn bytes_to_field_elements<F: PrimeField>(bytes: &[u8]) -> Vec<F> {
+    let chunk_size = ((F::MODULUS_BIT_SIZE - 1) / 8) as usize;
+
+    let mut elements = vec![F::from(bytes.len() as u64)];
+    elements.extend(bytes.chunks(chunk_size).map(F::from_le_bytes_mod_order));
+    elements
+}
+
+// Little-endian bytes of the canonical representative, padded or cut to 32 bytes. Little-endian, unlike
+// transcript::encoding, to match bytes_to_field_elements, so the Poseidon transcript and Merkle tree read back the
+// bytes they squeeze as the same element
+pub fn field_element_to_bytes<F: PrimeField>(element: F) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    let le_bytes = element.into_bigint().to_bytes_le();
+    let length = le_bytes.len().min(32);
+    bytes[..length].copy_from_slice(&le_bytes[..length]);
+    bytes
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poseidon::bn254_params;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_sponge_matches_permutation() {
+        let params = bn254_params();
+        let mut sponge = PoseidonSponge::new(params);
+        sponge.absorb(&[Fr::from(1), Fr::from(2)]);
+
+        // the two inputs fill the rate, so the padding 1 goes into a block of its own
+        let mut state = [Fr::from(0), Fr::from(1), Fr::from(2)];
+        params.permute(&mut state).unwrap();
+        state[1] += Fr::from(1);
+        params.permute(&mut state).unwrap();
+
+        // the first squeeze reads the rate of the permuted state
+        assert_eq!(sponge.squeeze(), state[1]);
+        assert_eq!(sponge.squeeze(), state[2]);
+
+        // the rate is used up, so the next squeeze permutes again
+        params.permute(&mut state).unwrap();
+        assert_eq!(sponge.squeeze(), state[1]);
+    }
+
+    #[test]
+    fn test_absorbing_in_pieces() {
+        let params = bn254_params();
+        let inputs: Vec<Fr> = (1..=7u64).map(Fr::from).collect();
+
+        let mut sponge = PoseidonSponge::new(params);
+        sponge.absorb(&inputs[..3]);
+        sponge.absorb(&inputs[3..]);
+
+        assert_eq!(sponge.squeeze(), hash_many(params, &inputs));
+        assert_ne!(hash_many(params, &inputs), hash_many(params, &inputs[..6]));
+    }
+
+    #[test]
+    fn test_trailing_zeros_change_the_hash() {
+        let params = bn254_params();
+        let x = Fr::from(5);
+
+        // [x] and [x, 0] add the same values into the rate, as do [] and [0]
+        assert_ne!(hash_many(params, &[x]), hash_many(params, &[x, Fr::from(0)]));
+        assert_ne!(hash_many(params, &[x, Fr::from(0)]), hash_many(params, &[x, Fr::from(0), Fr::from(0)]));
+        assert_ne!(hash_many::<Fr>(params, &[]), hash_many(params, &[Fr::from(0)]));
+
+        // nor does the padding 1 collide with an input 1
+        assert_ne!(hash_many(params, &[x]), hash_many(params, &[x, Fr::from(1)]));
+    }
+
+    #[test]
+    fn test_bytes_to_field_elements() {
+        let elements: Vec<Fr> = bytes_to_field_elements(&[7u8; 40]);
+
+        // length, then a 31-byte chunk and a 9-byte chunk
+        assert_eq!(elements.len(), 3);
+        assert_eq!(elements[0], Fr::from(40));
+        assert_ne!(bytes_to_field_elements::<Fr>(&[0u8]), bytes_to_field_elements::<Fr>(&[0u8, 0u8]));
+
+        let element = Fr::from(123456789u64);
+        assert_eq!(Fr::from_le_bytes_mod_order(&field_element_to_bytes(element)), element);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::protocol::SigmaProtocol;
+use ark_ff::PrimeField;
+use ark_std::rand::RngCore;
+use transcript::fiat_shamir::Transcript;
+
+// AND composition: knowledge of witnesses for both statements.
+// Both protocols run side by side and answer the same challenge
+#[derive(Debug, Clone, PartialEq)]
+pub struct And<A, B> {
+    pub left: A,
+    pub right: B,
+}
+
+// OR composition (Cramer-Damgard-Schoenmakers): knowledge of a witness for at least one of the statements,
+// without revealing which. The prover simulates the branch it has no witness for with a challenge of its own choice
+// c_fake, and answers the real branch with c - c_fake, so the verifier only checks that the two branch challenges
+// add up to c. Exactly one of them is free for the prover, the other is fixed by c after the commitment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Or<A, B> {
+    pub left: A,
+    pub right: B,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrWitness<L, R> {
+    Left(L),
+    Right(R),
+}
+
+// The prover keeps the state of the real branch and the simulated response and challenge of the other one
+pub enum OrState<F, A: SigmaProtocol<F>, B: SigmaProtocol<F>>
+where
+    F: PrimeField,
+{
+    Left(A::State, F, B::Response),
+    Right(F, A::Response, B::State),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrResponse<F, L, R> {
+    pub left_challenge: F,
+    pub left: L,
+    pub right: R,
+}
+
+impl <F: PrimeField, A: SigmaProtocol<F>, B: SigmaProtocol<F>>SigmaProtocol<F> for And<A, B> {
+    type Witness = (A::Witness, B::Witness);
+    type Commitment = (A::Commitment, B::Commitment);
+    type State = (A::State, B::State);
+    type Response = (A::Response, B::Response);
+
+    fn commit<R: RngCore>(&self, witness: &Self::Witness, rng: &mut R) -> (Self::Commitment, Self::State) {
+        let (left_commitment, left_state) = self.left.commit(&witness.0, rng);
+        let (right_commitment, right_state) = self.right.commit(&witness.1, rng);
+        ((left_commitment, right_commitment), (left_state, right_state))
+    }
+
+    fn respond(&self, witness: &Self::Witness, state: Self::State, challenge: F) -> Self::Response {
+        (self.left.respond(&witness.0, state.0, challenge), self.right.respond(&witness.1, state.1, challenge))
+    }
+
+    fn verify(&self, commitment: &Self::Commitment, challenge: F, response: &Self::Response) -> bool {
+        self.left.verify(&commitment.0, challenge, &response.0) && self.right.verify(&commitment.1, challenge, &response.1)
+    }
+
+    fn simulate<R: RngCore>(&self, challenge: F, rng: &mut R) -> (Self::Commitment, Self::Response) {
+        let (left_commitment, left_response) = self.left.simulate(challenge, rng);
+        let (right_commitment, right_response) = self.right.simulate(challenge, rng);
+        ((left_commitment, right_commitment), (left_response, right_response))
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript) {
+        self.left.append_statement(transcript);
+        self.right.append_statement(transcript);
+    }
+
+    fn append_commitment(&self, commitment: &Self::Commitment, transcript: &mut Transcript) {
+        self.left.append_commitment(&commitment.0, transcript);
+        self.right.append_commitment(&commitment.1, transcript);
+    }
+}
+
+impl <F: PrimeField, A: SigmaProtocol<F>, B: SigmaProtocol<F>>SigmaProtocol<F> for Or<A, B> {
+    type Witness = OrWitness<A::Witness, B::Witness>;
+    type Commitment = (A::Commitment, B::Commitment);
+    type State = OrState<F, A, B>;
+    type Response = OrResponse<F, A::Response, B::Response>;
+
+    fn commit<R: RngCore>(&self, witness: &Self::Witness, rng: &mut R) -> (Self::Commitment, Self::State) {
+        let fake_challenge = F::rand(rng);
+
+        match witness {
+            OrWitness::Left(witness) => {
+                let (left_commitment, left_state) = self.left.commit(witness, rng);
+                let (right_commitment, right_response) = self.right.simulate(fake_challenge, rng);
+                ((left_commitment, right_commitment), OrState::Left(left_state, fake_challenge, right_response))
+            }
+            OrWitness::Right(witness) => {
+                let (left_commitment, left_response) = self.left.simulate(fake_challenge, rng);
+                let (right_commitment, right_state) = self.right.commit(witness, rng);
+                ((left_commitment, right_commitment), OrState::Right(fake_challenge, left_response, right_state))
+            }
+        }
+    }
+
+    fn respond(&self, witness: &Self::Witness, state: Self::State, challenge: F) -> Self::Response {
+        match (witness, state) {
+            (OrWitness::Left(witness), OrState::Left(left_state, right_challenge, right)) => {
+                let left_challenge = challenge - right_challenge;
+                OrResponse { left_challenge, left: self.left.respond(witness, left_state, left_challenge), right }
+            }
+            (OrWitness::Right(witness), OrState::Right(left_challenge, left, right_state)) => {
+                let right_challenge = challenge - left_challenge;
+                OrResponse { left_challenge, left, right: self.right.respond(witness, right_state, right_challenge) }
+            }
+            _ => panic!("the state comes from a commitment with the other witness"),
+        }
+    }
+
+    fn verify(&self, commitment: &Self::Commitment, challenge: F, response: &Self::Response) -> bool {
+        let right_challenge = challenge - response.left_challenge;
+
+        self.left.verify(&commitment.0, response.left_challenge, &response.left)
+            && self.right.verify(&commitment.1, right_challenge, &response.right)
+    }
+
+    fn simulate<R: RngCore>(&self, challenge: F, rng: &mut R) -> (Self::Commitment, Self::Response) {
+        let left_challenge = F::rand(rng);
+        let (left_commitment, left) = self.left.simulate(left_challenge, rng);
+        let (right_commitment, right) = self.right.simulate(challenge - left_challenge, rng);
+        ((left_commitment, right_commitment), OrResponse { left_challenge, left, right })
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript) {
+        self.left.append_statement(transcript);
+        self.right.append_statement(transcript);
+    }
+
+    fn append_commitment(&self, commitment: &Self::Commitment, transcript: &mut Transcript) {
+        self.left.append_commitment(&commitment.0, transcript);
+        self.right.append_commitment(&commitment.1, transcript);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog::{DiscreteLog, DlogEquality};
+    use crate::protocol::{prove_non_interactive, verify_non_interactive};
+    use ark_bn254::{Fr, G1Projective};
+    use ark_ec::PrimeGroup;
+    use ark_ff::UniformRand;
+    use transcript::fiat_shamir::FiatShamirTranscriptInterface;
+
+    fn key(rng: &mut impl RngCore) -> (Fr, DiscreteLog<G1Projective>) {
+        let secret = Fr::rand(rng);
+        (secret, DiscreteLog { public_key: G1Projective::generator() * secret })
+    }
+
+    #[test]
+    fn test_and_composition() {
+        let rng = &mut ark_std::test_rng();
+        let (x, left) = key(rng);
+        let (y, right) = key(rng);
+        let statement = And { left, right };
+
+        let proof = prove_non_interactive(&statement, &(x, y), &mut Transcript::new(), rng);
+        assert!(verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+
+        // knowing one of the two secrets is not enough
+        let proof = prove_non_interactive(&statement, &(x, x), &mut Transcript::new(), rng);
+        assert!(!verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_or_composition() {
+        let rng = &mut ark_std::test_rng();
+        let (x, left) = key(rng);
+        let (y, right) = key(rng);
+        let statement = Or { left, right };
+
+        for witness in [OrWitness::Left(x), OrWitness::Right(y)] {
+            let proof = prove_non_interactive(&statement, &witness, &mut Transcript::new(), rng);
+            assert!(verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+        }
+
+        // without either secret the branch challenges cannot add up
+        let proof = prove_non_interactive(&statement, &OrWitness::Left(y), &mut Transcript::new(), rng);
+        assert!(!verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_nested_composition() {
+        let rng = &mut ark_std::test_rng();
+        let (x, key_statement) = key(rng);
+        let (g, h) = (G1Projective::rand(rng), G1Projective::rand(rng));
+        let z = Fr::rand(rng);
+        let equality = DlogEquality { g, h, u: g * z, v: h * z };
+
+        // (knows x AND knows z) OR knows y, proven with the left branch
+        let (_, other) = key(rng);
+        let statement = Or { left: And { left: key_statement, right: equality }, right: other };
+
+        let proof = prove_non_interactive(&statement, &OrWitness::Left((x, z)), &mut Transcript::new(), rng);
+        assert!(verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+
+        let challenge = Fr::rand(rng);
+        let (commitment, response) = statement.simulate(challenge, rng);
+        assert!(statement.verify(&commitment, challenge, &response));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::protocol::SigmaProtocol;
+use crate::schnorr::point_to_bytes;
+use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
+use ark_std::rand::RngCore;
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use zeroize::Zeroize;
+
+// Discrete-log statements as sigma protocols. All of them are Schnorr's protocol applied to a group homomorphism:
+// for the map phi from witnesses to points and the public image Y = phi(w), the prover sends phi(k) for a random k,
+// and answers the challenge c with s = k + c*w. The verifier checks phi(s) == phi(k) + c*Y, and the simulator picks s
+// first and sets the commitment to phi(s) - c*Y. The nonces are wiped once the response is computed.
+
+// Knowledge of x with P = x*G for the group generator G, plain Schnorr identification
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscreteLog<G: CurveGroup> {
+    pub public_key: G,
+}
+
+// Knowledge of a representation (x_1, ..., x_n) of P = x_1*G_1 + ... + x_n*G_n, e.g. the opening of a Pedersen commitment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Representation<G: CurveGroup> {
+    pub generators: Vec<G>,
+    pub point: G,
+}
+
+// Chaum-Pedersen: knowledge of x with U = x*G and V = x*H, proving both points share the same discrete log
+#[derive(Debug, Clone, PartialEq)]
+pub struct DlogEquality<G: CurveGroup> {
+    pub g: G,
+    pub h: G,
+    pub u: G,
+    pub v: G,
+}
+
+impl <G: CurveGroup>SigmaProtocol<G::ScalarField> for DiscreteLog<G> {
+    type Witness = G::ScalarField;
+    type Commitment = G;
+    type State = G::ScalarField;
+    type Response = G::ScalarField;
+
+    fn commit<R: RngCore>(&self, _witness: &Self::Witness, rng: &mut R) -> (Self::Commitment, Self::State) {
+        let nonce = G::ScalarField::rand(rng);
+        (G::generator() * nonce, nonce)
+    }
+
+    fn respond(&self, witness: &Self::Witness, mut state: Self::State, challenge: G::ScalarField) -> Self::Response {
+        let response = state + challenge * witness;
+        state.zeroize();
+        response
+    }
+
+    fn verify(&self, commitment: &Self::Commitment, challenge: G::ScalarField, response: &Self::Response) -> bool {
+        G::generator() * response == *commitment + self.public_key * challenge
+    }
+
+    fn simulate<R: RngCore>(&self, challenge: G::ScalarField, rng: &mut R) -> (Self::Commitment, Self::Response) {
+        let response = G::ScalarField::rand(rng);
+        (G::generator() * response - self.public_key * challenge, response)
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript) {
+        transcript.append(&point_to_bytes(&self.public_key));
+    }
+
+    fn append_commitment(&self, commitment: &Self::Commitment, transcript: &mut Transcript) {
+        transcript.append(&point_to_bytes(commitment));
+    }
+}
+
+impl <G: CurveGroup>SigmaProtocol<G::ScalarField> for Representation<G> {
+    type Witness = Vec<G::ScalarField>;
+    type Commitment = G;
+    type State = Vec<G::ScalarField>;
+    type Response = Vec<G::ScalarField>;
+
+    fn commit<R: RngCore>(&self, _witness: &Self::Witness, rng: &mut R) -> (Self::Commitment, Self::State) {
+        let nonces: Vec<G::ScalarField> = self.generators.iter().map(|_| G::ScalarField::rand(rng)).collect();
+        (self.combine(&nonces), nonces)
+    }
+
+    fn respond(&self, witness: &Self::Witness, mut state: Self::State, challenge: G::ScalarField) -> Self::Response {
+        assert_eq!(witness.len(), self.generators.len(), "one witness scalar per generator");
+        let response = state.iter().zip(witness.iter()).map(|(nonce, x)| *nonce + challenge * x).collect();
+        state.zeroize();
+        response
+    }
+
+    fn verify(&self, commitment: &Self::Commitment, challenge: G::ScalarField, response: &Self::Response) -> bool {
+        response.len() == self.generators.len() && self.combine(response) == *commitment + self.point * challenge
+    }
+
+    fn simulate<R: RngCore>(&self, challenge: G::ScalarField, rng: &mut R) -> (Self::Commitment, Self::Response) {
+        let response: Vec<G::ScalarField> = self.generators.iter().map(|_| G::ScalarField::rand(rng)).collect();
+        (self.combine(&response) - self.point * challenge, response)
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript) {
+        for generator in &self.generators {
+            transcript.append(&point_to_bytes(generator));
+        }
+        transcript.append(&point_to_bytes(&self.point));
+    }
+
+    fn append_commitment(&self, commitment: &Self::Commitment, transcript: &mut Transcript) {
+        transcript.append(&point_to_bytes(commitment));
+    }
+}
+
+impl <G: CurveGroup>Representation<G> {
+    // x_1*G_1 + ... + x_n*G_n
+    fn combine(&self, scalars: &[G::ScalarField]) -> G {
+        self.generators.iter().zip(scalars.iter()).map(|(generator, scalar)| *generator * scalar).sum()
+    }
+}
+
+impl <G: CurveGroup>SigmaProtocol<G::ScalarField> for DlogEquality<G> {
+    type Witness = G::ScalarField;
+    type Commitment = (G, G);
+    type State = G::ScalarField;
+    type Response = G::ScalarField;
+
+    fn commit<R: RngCore>(&self, _witness: &Self::Witness, rng: &mut R) -> (Self::Commitment, Self::State) {
+        let nonce = G::ScalarField::rand(rng);
+        ((self.g * nonce, self.h * nonce), nonce)
+    }
+
+    fn respond(&self, witness: &Self::Witness, mut state: Self::State, challenge: G::ScalarField) -> Self::Response {
+        let response = state + challenge * witness;
+        state.zeroize();
+        response
+    }
+
+    fn verify(&self, commitment: &Self::Commitment, challenge: G::ScalarField, response: &Self::Response) -> bool {
+        self.g * response == commitment.0 + self.u * challenge && self.h * response == commitment.1 + self.v * challenge
+    }
+
+    fn simulate<R: RngCore>(&self, challenge: G::ScalarField, rng: &mut R) -> (Self::Commitment, Self::Response) {
+        let response = G::ScalarField::rand(rng);
+        ((self.g * response - self.u * challenge, self.h * response - self.v * challenge), response)
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript) {
+        for point in [&self.g, &self.h, &self.u, &self.v] {
+            transcript.append(&point_to_bytes(point));
+        }
+    }
+
+    fn append_commitment(&self, commitment: &Self::Commitment, transcript: &mut Transcript) {
+        transcript.append(&point_to_bytes(&commitment.0));
+        transcript.append(&point_to_bytes(&commitment.1));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{prove_non_interactive, verify_non_interactive};
+    use ark_bn254::{Fr, G1Projective};
+
+    #[test]
+    fn test_representation() {
+        let rng = &mut ark_std::test_rng();
+        let generators: Vec<G1Projective> = (0..3).map(|_| G1Projective::rand(rng)).collect();
+        let witness: Vec<Fr> = (0..3).map(|_| Fr::rand(rng)).collect();
+        let statement = Representation {
+            point: generators.iter().zip(witness.iter()).map(|(g, x)| *g * x).sum(),
+            generators,
+        };
+
+        let proof = prove_non_interactive(&statement, &witness, &mut Transcript::new(), rng);
+        assert!(verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+
+        let mut wrong_witness = witness.clone();
+        wrong_witness[1] += Fr::from(1);
+        let proof = prove_non_interactive(&statement, &wrong_witness, &mut Transcript::new(), rng);
+        assert!(!verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_dlog_equality() {
+        let rng = &mut ark_std::test_rng();
+        let (g, h) = (G1Projective::rand(rng), G1Projective::rand(rng));
+        let x = Fr::rand(rng);
+
+        let statement = DlogEquality { g, h, u: g * x, v: h * x };
+        let proof = prove_non_interactive(&statement, &x, &mut Transcript::new(), rng);
+        assert!(verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+
+        // V with a different discrete log cannot be proven equal
+        let unequal = DlogEquality { g, h, u: g * x, v: h * (x + Fr::from(1)) };
+        let proof = prove_non_interactive(&unequal, &x, &mut Transcript::new(), rng);
+        assert!(!verify_non_interactive(&unequal, &proof, &mut Transcript::new()));
+    }
+}
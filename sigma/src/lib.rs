@@ -0,0 +1,4 @@
+pub mod schnorr;
+pub mod protocol;
+pub mod dlog;
+pub mod composition;
@@ -0,0 +1,136 @@
+use ark_ff::PrimeField;
+use ark_std::rand::RngCore;
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+
+// A three move public coin protocol (commitment, challenge, response) proving knowledge of a witness for a statement.
+// The implementing type is the statement, challenges are scalars of F.
+//
+// Besides completeness and special soundness, every protocol here is special honest verifier zero knowledge:
+// for any challenge, simulate produces an accepting (commitment, response) pair without the witness, distributed like
+// a real one. The OR composition depends on it to answer the branch it has no witness for
+pub trait SigmaProtocol<F: PrimeField> {
+    type Witness;
+    type Commitment: Clone;
+    // prover randomness kept between the commitment and the response
+    type State;
+    type Response: Clone;
+
+    fn commit<R: RngCore>(&self, witness: &Self::Witness, rng: &mut R) -> (Self::Commitment, Self::State);
+
+    fn respond(&self, witness: &Self::Witness, state: Self::State, challenge: F) -> Self::Response;
+
+    fn verify(&self, commitment: &Self::Commitment, challenge: F, response: &Self::Response) -> bool;
+
+    fn simulate<R: RngCore>(&self, challenge: F, rng: &mut R) -> (Self::Commitment, Self::Response);
+
+    fn append_statement(&self, transcript: &mut Transcript);
+
+    fn append_commitment(&self, commitment: &Self::Commitment, transcript: &mut Transcript);
+}
+
+// Fiat-Shamir transformation of a sigma protocol: the challenge is derived from the statement and the commitment
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonInteractiveProof<C, S> {
+    pub commitment: C,
+    pub response: S,
+}
+
+pub fn prove_non_interactive<F, P, R>(
+    protocol: &P,
+    witness: &P::Witness,
+    transcript: &mut Transcript,
+    rng: &mut R,
+) -> NonInteractiveProof<P::Commitment, P::Response>
+where
+    F: PrimeField,
+    P: SigmaProtocol<F>,
+    R: RngCore,
+{
+    // the nonces come from the transcript's RNG once the statement is in it, so they are bound to the statement
+    // as well as to rng
+    protocol.append_statement(transcript);
+    let (commitment, state) = protocol.commit(witness, &mut transcript.rng(rng));
+    protocol.append_commitment(&commitment, transcript);
+    let challenge = transcript.random_challenge_as_field_element();
+
+    NonInteractiveProof {
+        response: protocol.respond(witness, state, challenge),
+        commitment,
+    }
+}
+
+pub fn verify_non_interactive<F, P>(
+    protocol: &P,
+    proof: &NonInteractiveProof<P::Commitment, P::Response>,
+    transcript: &mut Transcript,
+) -> bool
+where
+    F: PrimeField,
+    P: SigmaProtocol<F>,
+{
+    let challenge = fiat_shamir_challenge(protocol, &proof.commitment, transcript);
+    protocol.verify(&proof.commitment, challenge, &proof.response)
+}
+
+fn fiat_shamir_challenge<F: PrimeField, P: SigmaProtocol<F>>(protocol: &P, commitment: &P::Commitment, transcript: &mut Transcript) -> F {
+    protocol.append_statement(transcript);
+    protocol.append_commitment(commitment, transcript);
+    transcript.random_challenge_as_field_element()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog::DiscreteLog;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_ec::PrimeGroup;
+    use ark_ff::UniformRand;
+
+    #[test]
+    fn test_fiat_shamir() {
+        let rng = &mut ark_std::test_rng();
+        let secret = Fr::rand(rng);
+        let statement = DiscreteLog { public_key: G1Projective::generator() * secret };
+
+        let proof = prove_non_interactive(&statement, &secret, &mut Transcript::new(), rng);
+        assert!(verify_non_interactive(&statement, &proof, &mut Transcript::new()));
+
+        // the challenge depends on the statement, so the proof does not carry over to another one
+        let other = DiscreteLog { public_key: statement.public_key + statement.public_key };
+        assert!(!verify_non_interactive(&other, &proof, &mut Transcript::new()));
+
+        // a transcript that already holds context derives another challenge
+        let mut bound = Transcript::new();
+        bound.append(b"context");
+        assert!(!verify_non_interactive(&statement, &proof, &mut bound));
+    }
+
+    #[test]
+    fn test_nonces_are_bound_to_the_statement() {
+        use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+        let secret = Fr::from(7);
+        let statement = DiscreteLog { public_key: G1Projective::generator() * secret };
+        let prove = |transcript: &mut Transcript| prove_non_interactive(&statement, &secret, transcript, &mut StdRng::seed_from_u64(1));
+
+        // the same local randomness under another transcript gives another nonce, so a repeating RNG does not
+        // repeat the commitment across statements
+        let mut bound = Transcript::new();
+        bound.append(b"context");
+        assert_eq!(prove(&mut Transcript::new()).commitment, prove(&mut Transcript::new()).commitment);
+        assert_ne!(prove(&mut Transcript::new()).commitment, prove(&mut bound).commitment);
+    }
+
+    #[test]
+    fn test_simulated_transcripts_verify() {
+        let rng = &mut ark_std::test_rng();
+        let statement = DiscreteLog { public_key: G1Projective::rand(rng) };
+
+        let challenge = Fr::rand(rng);
+        let (commitment, response) = statement.simulate(challenge, rng);
+
+        assert!(statement.verify(&commitment, challenge, &response));
+        assert!(!statement.verify(&commitment, challenge + Fr::from(1), &response));
+    }
+}
@@ -0,0 +1,141 @@
+use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+use transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Schnorr identification and signatures over a prime order group with generator G.
+//
+// The secret key is a scalar x and the public key is P = x*G. To prove knowledge of x the prover
+// 1. commits to a random nonce k with R = k*G
+// 2. receives a random challenge c from the verifier
+// 3. responds with s = k + c*x
+// and the verifier accepts when s*G == R + c*P. The nonce hides x, so the transcript reveals nothing about it,
+// and two accepting responses to different challenges for the same R would give x = (s - s') / (c - c').
+//
+// The signature replaces the verifier with Fiat-Shamir: c is derived from the transcript of the public key,
+// R and the message. A nonce must never be reused, it leaks the secret key through the same extraction,
+// so sign wipes it after use, and the key pair wipes the secret key when dropped
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct SchnorrKeyPair<G: CurveGroup> {
+    pub secret_key: G::ScalarField,
+    pub public_key: G,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SchnorrSignature<G: CurveGroup> {
+    pub commitment: G,
+    pub response: G::ScalarField,
+}
+
+impl <G: CurveGroup>SchnorrKeyPair<G> {
+    pub fn generate<R: RngCore>(rng: &mut R) -> Self {
+        let secret_key = G::ScalarField::rand(rng);
+
+        Self {
+            secret_key,
+            public_key: G::generator() * secret_key,
+        }
+    }
+
+    // First move of the identification: returns the commitment R = k*G and the nonce k,
+    // which the prover keeps for its response
+    pub fn commit<R: RngCore>(&self, rng: &mut R) -> (G, G::ScalarField) {
+        let nonce = G::ScalarField::rand(rng);
+        (G::generator() * nonce, nonce)
+    }
+
+    // Last move of the identification: s = k + c*x
+    pub fn respond(&self, nonce: G::ScalarField, challenge: G::ScalarField) -> G::ScalarField {
+        nonce + challenge * self.secret_key
+    }
+
+    pub fn sign<R: RngCore>(&self, message: &[u8], rng: &mut R) -> SchnorrSignature<G> {
+        let (commitment, mut nonce) = self.commit(rng);
+        let challenge = signature_challenge(&self.public_key, &commitment, message);
+        let response = self.respond(nonce, challenge);
+        nonce.zeroize();
+
+        SchnorrSignature { commitment, response }
+    }
+}
+
+// The verifier's check of one identification: s*G == R + c*P
+pub fn verify_identification<G: CurveGroup>(
+    public_key: &G,
+    commitment: &G,
+    challenge: G::ScalarField,
+    response: G::ScalarField,
+) -> bool {
+    G::generator() * response == *commitment + *public_key * challenge
+}
+
+pub fn verify_signature<G: CurveGroup>(public_key: &G, message: &[u8], signature: &SchnorrSignature<G>) -> bool {
+    let challenge = signature_challenge(public_key, &signature.commitment, message);
+    verify_identification(public_key, &signature.commitment, challenge, signature.response)
+}
+
+// c = H(P, R, message), binding the public key so a signature cannot be moved to another key
+pub fn signature_challenge<G: CurveGroup>(public_key: &G, commitment: &G, message: &[u8]) -> G::ScalarField {
+    let mut transcript = Transcript::new();
+    transcript.append(&point_to_bytes(public_key));
+    transcript.append(&point_to_bytes(commitment));
+    transcript.append(message);
+    transcript.random_challenge_as_field_element()
+}
+
+pub fn point_to_bytes<G: CurveGroup>(point: &G) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    point
+        .into_affine()
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec cannot fail");
+    bytes
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+
+    #[test]
+    fn test_identification() {
+        let rng = &mut ark_std::test_rng();
+        let key_pair = SchnorrKeyPair::<G1Projective>::generate(rng);
+
+        let (commitment, nonce) = key_pair.commit(rng);
+        let challenge = Fr::rand(rng);
+        let response = key_pair.respond(nonce, challenge);
+
+        assert!(verify_identification(&key_pair.public_key, &commitment, challenge, response));
+        assert!(!verify_identification(&key_pair.public_key, &commitment, challenge + Fr::from(1), response));
+
+        // answering two challenges with the same nonce reveals the secret key
+        let other_challenge = challenge + Fr::from(1);
+        let other_response = key_pair.respond(nonce, other_challenge);
+        let extracted = (response - other_response) / (challenge - other_challenge);
+        assert_eq!(extracted, key_pair.secret_key);
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let rng = &mut ark_std::test_rng();
+        let key_pair = SchnorrKeyPair::<G1Projective>::generate(rng);
+        let signature = key_pair.sign(b"zk training", rng);
+
+        assert!(verify_signature(&key_pair.public_key, b"zk training", &signature));
+        assert!(!verify_signature(&key_pair.public_key, b"zk trainings", &signature));
+
+        let mut other = SchnorrKeyPair::<G1Projective>::generate(rng);
+        assert!(!verify_signature(&other.public_key, b"zk training", &signature));
+
+        other.zeroize();
+        assert_eq!(other.secret_key, Fr::from(0));
+
+        let mut tampered = signature;
+        tampered.response += Fr::from(1);
+        assert!(!verify_signature(&key_pair.public_key, b"zk training", &tampered));
+    }
+}
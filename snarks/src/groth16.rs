@@ -0,0 +1,247 @@
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ec::{CurveGroup, PrimeGroup, VariableBaseMSM};
+use ark_ff::{Field, UniformRand};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+use zeroize::Zeroize;
+use arithmetization::qap::Qap;
+
+// Groth16 zk-SNARK over a QAP (training edition: the setup is run by one party and nothing is hardened).
+//
+// With u_j, v_j, w_j the A, B and C column polynomials of the QAP and the toxic waste tau, alpha, beta, gamma, delta,
+// the proof for an assignment z with quotient h is
+// A = alpha + sum z_j u_j(tau) + r*delta
+// B = beta + sum z_j v_j(tau) + s*delta
+// C = (sum over private j of z_j (beta u_j(tau) + alpha v_j(tau) + w_j(tau)) + h(tau) Z(tau)) / delta + s*A + r*B - r*s*delta
+// all in the exponent, and the verifier checks
+// e(A, B) = e(alpha, beta) * e(sum over public j of z_j (beta u_j(tau) + alpha v_j(tau) + w_j(tau)) / gamma, gamma) * e(C, delta)
+// e(alpha, beta) is part of the verifying key, so verification costs three pairings
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ProvingKey<E: Pairing> {
+    pub alpha_g1: E::G1Affine,
+    pub beta_g1: E::G1Affine,
+    pub beta_g2: E::G2Affine,
+    pub delta_g1: E::G1Affine,
+    pub delta_g2: E::G2Affine,
+    // [u_j(tau)]_1, [v_j(tau)]_1 and [v_j(tau)]_2 for every column
+    pub a_query: Vec<E::G1Affine>,
+    pub b_g1_query: Vec<E::G1Affine>,
+    pub b_g2_query: Vec<E::G2Affine>,
+    // [tau^i Z(tau) / delta]_1
+    pub h_query: Vec<E::G1Affine>,
+    // [(beta u_j(tau) + alpha v_j(tau) + w_j(tau)) / delta]_1 for the private columns
+    pub l_query: Vec<E::G1Affine>,
+    pub verifying_key: VerifyingKey<E>,
+}
+
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifyingKey<E: Pairing> {
+    pub alpha_beta: PairingOutput<E>,
+    pub gamma_g2: E::G2Affine,
+    pub delta_g2: E::G2Affine,
+    // [(beta u_j(tau) + alpha v_j(tau) + w_j(tau)) / gamma]_1 for the constant column and the public inputs
+    pub ic: Vec<E::G1Affine>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Proof<E: Pairing> {
+    pub a: E::G1Affine,
+    pub b: E::G2Affine,
+    pub c: E::G1Affine,
+}
+
+// Circuit-specific trusted setup. The toxic waste is sampled from rng and wiped at the end of this function,
+// together with everything derived from it
+pub fn setup<E: Pairing, R: RngCore>(qap: &Qap<E::ScalarField>, rng: &mut R) -> ProvingKey<E> {
+    let mut tau = E::ScalarField::rand(rng);
+    let mut alpha = E::ScalarField::rand(rng);
+    let mut beta = E::ScalarField::rand(rng);
+    let mut gamma = E::ScalarField::rand(rng);
+    let mut delta = E::ScalarField::rand(rng);
+
+    let mut gamma_inverse = gamma.inverse().expect("gamma is non-zero with overwhelming probability");
+    let mut delta_inverse = delta.inverse().expect("delta is non-zero with overwhelming probability");
+
+    let g1 = E::G1::generator();
+    let g2 = E::G2::generator();
+
+    let mut u: Vec<E::ScalarField> = qap.a.iter().map(|polynomial| polynomial.evaluate(tau)).collect();
+    let mut v: Vec<E::ScalarField> = qap.b.iter().map(|polynomial| polynomial.evaluate(tau)).collect();
+    let mut w: Vec<E::ScalarField> = qap.c.iter().map(|polynomial| polynomial.evaluate(tau)).collect();
+
+    // beta u_j(tau) + alpha v_j(tau) + w_j(tau)
+    let mut combined: Vec<E::ScalarField> = (0..u.len()).map(|j| beta * u[j] + alpha * v[j] + w[j]).collect();
+    let no_of_public_columns = 1 + qap.no_of_public_inputs;
+
+    let ic: Vec<E::G1> = combined[..no_of_public_columns].iter().map(|value| g1 * (*value * gamma_inverse)).collect();
+    let l_query: Vec<E::G1> = combined[no_of_public_columns..].iter().map(|value| g1 * (*value * delta_inverse)).collect();
+
+    // the quotient has degree at most n - 2
+    let mut target_at_tau = qap.target.evaluate(tau);
+    let mut power = E::ScalarField::from(1u64);
+    let h_query: Vec<E::G1> = (0..qap.domain.size.saturating_sub(1))
+        .map(|_| {
+            let point = g1 * (power * target_at_tau * delta_inverse);
+            power *= tau;
+            point
+        })
+        .collect();
+
+    let a_query: Vec<E::G1> = u.iter().map(|value| g1 * value).collect();
+    let b_g1_query: Vec<E::G1> = v.iter().map(|value| g1 * value).collect();
+    let b_g2_query: Vec<E::G2> = v.iter().map(|value| g2 * value).collect();
+
+    let verifying_key = VerifyingKey {
+        alpha_beta: E::pairing(g1 * alpha, g2 * beta),
+        gamma_g2: (g2 * gamma).into_affine(),
+        delta_g2: (g2 * delta).into_affine(),
+        ic: E::G1::normalize_batch(&ic),
+    };
+
+    let proving_key = ProvingKey {
+        alpha_g1: (g1 * alpha).into_affine(),
+        beta_g1: (g1 * beta).into_affine(),
+        beta_g2: (g2 * beta).into_affine(),
+        delta_g1: (g1 * delta).into_affine(),
+        delta_g2: verifying_key.delta_g2,
+        a_query: E::G1::normalize_batch(&a_query),
+        b_g1_query: E::G1::normalize_batch(&b_g1_query),
+        b_g2_query: E::G2::normalize_batch(&b_g2_query),
+        h_query: E::G1::normalize_batch(&h_query),
+        l_query: E::G1::normalize_batch(&l_query),
+        verifying_key,
+    };
+
+    for secret in [&mut tau, &mut alpha, &mut beta, &mut gamma, &mut delta, &mut gamma_inverse, &mut delta_inverse] {
+        secret.zeroize();
+    }
+    target_at_tau.zeroize();
+    power.zeroize();
+    for values in [&mut u, &mut v, &mut w, &mut combined] {
+        values.zeroize();
+    }
+
+    proving_key
+}
+
+impl <E: Pairing>ProvingKey<E> {
+    // Proves knowledge of the private part of `assignment` (the full R1CS assignment z).
+    // Returns None if the assignment does not satisfy the QAP
+    pub fn prove<R: RngCore>(&self, qap: &Qap<E::ScalarField>, assignment: &[E::ScalarField], rng: &mut R) -> Option<Proof<E>> {
+        if assignment.len() != self.a_query.len() {
+            return None;
+        }
+        let quotient = qap.quotient(assignment)?;
+
+        // fresh randomness makes the proof zero knowledge
+        let mut r = E::ScalarField::rand(rng);
+        let mut s = E::ScalarField::rand(rng);
+
+        let a = E::G1::msm_unchecked(&self.a_query, assignment) + self.alpha_g1 + self.delta_g1 * r;
+        let b_g1 = E::G1::msm_unchecked(&self.b_g1_query, assignment) + self.beta_g1 + self.delta_g1 * s;
+        let b = E::G2::msm_unchecked(&self.b_g2_query, assignment) + self.beta_g2 + self.delta_g2 * s;
+
+        let private_assignment = &assignment[self.verifying_key.ic.len()..];
+        let c = E::G1::msm_unchecked(&self.l_query, private_assignment)
+            + E::G1::msm_unchecked(&self.h_query[..quotient.coefficients.len()], &quotient.coefficients)
+            + a * s
+            + b_g1 * r
+            - self.delta_g1 * (r * s);
+
+        // r and s would let anyone strip the blinding from A, B and C
+        r.zeroize();
+        s.zeroize();
+
+        Some(Proof {
+            a: a.into_affine(),
+            b: b.into_affine(),
+            c: c.into_affine(),
+        })
+    }
+}
+
+impl <E: Pairing>VerifyingKey<E> {
+    // public_inputs are the public entries of z, without the leading 1
+    pub fn verify(&self, public_inputs: &[E::ScalarField], proof: &Proof<E>) -> bool {
+        if public_inputs.len() + 1 != self.ic.len() {
+            return false;
+        }
+
+        let public_combination = E::G1::msm_unchecked(&self.ic[1..], public_inputs) + self.ic[0];
+
+        E::pairing(proof.a, proof.b)
+            == self.alpha_beta + E::pairing(public_combination, self.gamma_g2) + E::pairing(proof.c, self.delta_g2)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arithmetization::r1cs::{ConstraintSystem, LinearCombination, Variable};
+    use ark_bn254::{Bn254, Fr};
+
+    // x^3 + x + 5 = out, with x private and out public
+    fn cubic() -> (Qap<Fr>, Vec<Fr>) {
+        let mut cs = ConstraintSystem::new();
+        let out = cs.alloc_public_input();
+        let x = cs.alloc_variable();
+        let x_squared = cs.alloc_variable();
+        let x_cubed = cs.alloc_variable();
+
+        cs.enforce(x.into(), x.into(), x_squared.into());
+        cs.enforce(x_squared.into(), x.into(), x_cubed.into());
+        cs.enforce(
+            LinearCombination::from(x_cubed) + x.into() + LinearCombination::constant(Fr::from(5)),
+            Variable::One.into(),
+            out.into(),
+        );
+
+        let assignment = cs.solve(&[(x, Fr::from(3))]).unwrap();
+        (Qap::from_r1cs(&cs).unwrap(), assignment)
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let rng = &mut ark_std::test_rng();
+        let (qap, assignment) = cubic();
+        let proving_key = setup::<Bn254, _>(&qap, rng);
+
+        let proof = proving_key.prove(&qap, &assignment, rng).unwrap();
+
+        assert!(proving_key.verifying_key.verify(&[Fr::from(35)], &proof));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_public_input_or_proof() {
+        let rng = &mut ark_std::test_rng();
+        let (qap, assignment) = cubic();
+        let proving_key = setup::<Bn254, _>(&qap, rng);
+        let verifying_key = &proving_key.verifying_key;
+
+        let proof = proving_key.prove(&qap, &assignment, rng).unwrap();
+
+        assert!(!verifying_key.verify(&[Fr::from(36)], &proof));
+        assert!(!verifying_key.verify(&[], &proof));
+
+        let mut tampered = proof;
+        tampered.c = (tampered.c + proving_key.delta_g1).into_affine();
+        assert!(!verifying_key.verify(&[Fr::from(35)], &tampered));
+    }
+
+    #[test]
+    fn test_proofs_are_randomized_and_unsatisfied_assignments_fail() {
+        let rng = &mut ark_std::test_rng();
+        let (qap, assignment) = cubic();
+        let proving_key = setup::<Bn254, _>(&qap, rng);
+
+        let first = proving_key.prove(&qap, &assignment, rng).unwrap();
+        let second = proving_key.prove(&qap, &assignment, rng).unwrap();
+        assert_ne!(first, second);
+        assert!(proving_key.verifying_key.verify(&[Fr::from(35)], &second));
+
+        let mut wrong = assignment;
+        wrong[2] = Fr::from(4);
+        assert_eq!(proving_key.prove(&qap, &wrong, rng), None);
+    }
+}
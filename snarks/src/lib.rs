@@ -0,0 +1,2 @@
+pub mod groth16;
+pub mod spartan;
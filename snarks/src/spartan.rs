@@ -0,0 +1,288 @@
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use arithmetization::r1cs::ConstraintSystem;
+use commitments::pcs::MultilinearPcs;
+use multilinear::multilinear::MultilinearPolynomial;
+use sumcheck::composed::{append_field_elements, ComposedSumcheckProof};
+use sumcheck::commitment_to_bytes;
+use sumcheck::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+
+// Spartan-style SNARK for R1CS, built from sumchecks and a multilinear commitment to the witness.
+//
+// The constraints are padded to 2^s rows and the assignment to 2^k columns laid out as z = ((1, public inputs), witness),
+// each half padded with zeros, so z(0, y) is public and z(1, y) = w(y) is the committed witness.
+// A, B and C become sparse multilinear polynomials A(x, y) that are 0 everywhere except on their non-zero entries.
+//
+// The R1CS holds exactly when (Az)(x) * (Bz)(x) - (Cz)(x) vanishes on the hypercube, which the verifier tests at a random tau:
+// phase 1: 0 = sum over x of eq(tau, x) * ((Az)(x) * (Bz)(x) - (Cz)(x)), ending at r_x with the claimed (Az)(r_x), (Bz)(r_x), (Cz)(r_x)
+// phase 2: the three claims are batched with random r_a, r_b, r_c into one sumcheck over the columns
+// r_a (Az)(r_x) + r_b (Bz)(r_x) + r_c (Cz)(r_x) = sum over y of (r_a A(r_x, y) + r_b B(r_x, y) + r_c C(r_x, y)) * z(y)
+// ending at r_y. The verifier evaluates the sparse A, B, C at (r_x, r_y) itself in time linear in the number of entries
+// (full Spartan commits to them with SPARK instead), computes the public half of z, and opens w at the rest of r_y
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpartanInstance<F: PrimeField> {
+    pub no_of_public_inputs: usize,
+    pub no_of_private_variables: usize,
+    // s and k, the number of row and column variables
+    pub no_of_row_vars: usize,
+    pub no_of_column_vars: usize,
+    // (row, column, value) entries in the padded layout
+    pub a: Vec<(usize, usize, F)>,
+    pub b: Vec<(usize, usize, F)>,
+    pub c: Vec<(usize, usize, F)>,
+}
+
+pub struct SpartanProof<F: PrimeField, S: MultilinearPcs<F>> {
+    pub witness_commitment: S::Commitment,
+    pub outer_sumcheck: ComposedSumcheckProof<F>,
+    // (Az)(r_x), (Bz)(r_x), (Cz)(r_x)
+    pub claimed_evaluations: [F; 3],
+    pub inner_sumcheck: ComposedSumcheckProof<F>,
+    pub witness_evaluation: F,
+    pub witness_opening: S::OpeningProof,
+}
+
+impl <F: PrimeField>SpartanInstance<F> {
+    pub fn from_r1cs(cs: &ConstraintSystem<F>) -> Self {
+        let no_of_row_vars = cs.constraints.len().max(1).next_power_of_two().ilog2() as usize;
+        let half = (1 + cs.no_of_public_inputs).max(cs.no_of_private_variables).next_power_of_two();
+        let no_of_column_vars = half.ilog2() as usize + 1;
+
+        // public columns keep their index, the private ones move to the second half
+        let column = |index: usize| if index <= cs.no_of_public_inputs { index } else { half + index - 1 - cs.no_of_public_inputs };
+
+        let (a, b, c) = cs.matrices();
+        let entries = |matrix: Vec<Vec<(usize, F)>>| -> Vec<(usize, usize, F)> {
+            matrix
+                .into_iter()
+                .enumerate()
+                .flat_map(|(row, entries)| entries.into_iter().map(move |(index, value)| (row, column(index), value)))
+                .collect()
+        };
+
+        Self {
+            no_of_public_inputs: cs.no_of_public_inputs,
+            no_of_private_variables: cs.no_of_private_variables,
+            no_of_row_vars,
+            no_of_column_vars,
+            a: entries(a),
+            b: entries(b),
+            c: entries(c),
+        }
+    }
+
+    // Number of variables of the committed witness polynomial, the size to set the commitment scheme up with
+    pub fn witness_no_of_vars(&self) -> usize {
+        self.no_of_column_vars - 1
+    }
+
+    // Proves knowledge of the private part of `assignment` (the full R1CS assignment z, in ConstraintSystem::index order).
+    // Returns None if the assignment does not satisfy the system
+    pub fn prove<S>(&self, params: &S::Params, assignment: &[F], transcript: &mut Transcript) -> Option<SpartanProof<F, S>>
+    where
+        S: MultilinearPcs<F>,
+        S::Commitment: CanonicalSerialize,
+    {
+        if assignment.len() != 1 + self.no_of_public_inputs + self.no_of_private_variables || assignment[0] != F::one() {
+            return None;
+        }
+
+        let half = 1 << self.witness_no_of_vars();
+        let mut z = vec![F::zero(); 2 * half];
+        z[..1 + self.no_of_public_inputs].copy_from_slice(&assignment[..1 + self.no_of_public_inputs]);
+        z[half..half + self.no_of_private_variables].copy_from_slice(&assignment[1 + self.no_of_public_inputs..]);
+
+        let witness = MultilinearPolynomial::new(&z[half..]);
+        let witness_commitment = S::commit(params, &witness);
+        let tau = self.bind_instance::<S>(transcript, &witness_commitment, &assignment[1..1 + self.no_of_public_inputs]);
+
+        let az = self.matrix_vector_product(&self.a, &z);
+        let bz = self.matrix_vector_product(&self.b, &z);
+        let cz = self.matrix_vector_product(&self.c, &z);
+        if az.iter().zip(bz.iter()).zip(cz.iter()).any(|((a, b), c)| *a * b != *c) {
+            return None;
+        }
+
+        // phase 1
+        let eq_tau = MultilinearPolynomial::new(&MultilinearPolynomial::eq_evaluations(&tau));
+        let (outer_sumcheck, r_x, evaluations) = ComposedSumcheckProof::prove(
+            &[eq_tau, MultilinearPolynomial::new(&az), MultilinearPolynomial::new(&bz), MultilinearPolynomial::new(&cz)],
+            3,
+            |values: &[F]| values[0] * (values[1] * values[2] - values[3]),
+            transcript,
+        )
+        .ok()?;
+        let claimed_evaluations = [evaluations[1], evaluations[2], evaluations[3]];
+        append_field_elements(transcript, &claimed_evaluations);
+
+        // phase 2
+        let (r_a, r_b, r_c): (F, F, F) = batching_challenges(transcript);
+        let eq_x = MultilinearPolynomial::eq_evaluations(&r_x);
+        let mut combined_row = vec![F::zero(); 2 * half];
+        for (matrix, scalar) in [(&self.a, r_a), (&self.b, r_b), (&self.c, r_c)] {
+            for (row, column, value) in matrix {
+                combined_row[*column] += scalar * eq_x[*row] * value;
+            }
+        }
+
+        let (inner_sumcheck, r_y, _) = ComposedSumcheckProof::prove(
+            &[MultilinearPolynomial::new(&combined_row), MultilinearPolynomial::new(&z)],
+            2,
+            |values: &[F]| values[0] * values[1],
+            transcript,
+        )
+        .ok()?;
+
+        let (witness_evaluation, witness_opening) = S::open(params, &witness, &r_y[1..], transcript);
+
+        Some(SpartanProof {
+            witness_commitment,
+            outer_sumcheck,
+            claimed_evaluations,
+            inner_sumcheck,
+            witness_evaluation,
+            witness_opening,
+        })
+    }
+
+    // public_inputs are the public entries of z, without the leading 1
+    pub fn verify<S>(&self, params: &S::Params, public_inputs: &[F], proof: &SpartanProof<F, S>, transcript: &mut Transcript) -> bool
+    where
+        S: MultilinearPcs<F>,
+        S::Commitment: CanonicalSerialize,
+    {
+        if public_inputs.len() != self.no_of_public_inputs {
+            return false;
+        }
+
+        let tau = self.bind_instance::<S>(transcript, &proof.witness_commitment, public_inputs);
+
+        // phase 1
+        let (r_x, outer_claim) = match proof.outer_sumcheck.verify(F::zero(), self.no_of_row_vars, 3, transcript) {
+            Some(result) => result,
+            None => return false,
+        };
+        let [a, b, c] = proof.claimed_evaluations;
+        if outer_claim != MultilinearPolynomial::eq_evaluate(&tau, &r_x) * (a * b - c) {
+            return false;
+        }
+        append_field_elements(transcript, &proof.claimed_evaluations);
+
+        // phase 2
+        let (r_a, r_b, r_c): (F, F, F) = batching_challenges(transcript);
+        let (r_y, inner_claim) =
+            match proof.inner_sumcheck.verify(r_a * a + r_b * b + r_c * c, self.no_of_column_vars, 2, transcript) {
+                Some(result) => result,
+                None => return false,
+            };
+
+        let eq_x = MultilinearPolynomial::eq_evaluations(&r_x);
+        let eq_y = MultilinearPolynomial::eq_evaluations(&r_y);
+        let sparse_evaluation = |matrix: &[(usize, usize, F)]| -> F {
+            matrix.iter().map(|(row, column, value)| eq_x[*row] * eq_y[*column] * value).sum()
+        };
+        let combined_row = r_a * sparse_evaluation(&self.a) + r_b * sparse_evaluation(&self.b) + r_c * sparse_evaluation(&self.c);
+
+        // z(r_y) = (1 - r_y[0]) * public(r_y[1..]) + r_y[0] * w(r_y[1..])
+        let mut public = vec![F::zero(); 1 << self.witness_no_of_vars()];
+        public[0] = F::one();
+        public[1..1 + public_inputs.len()].copy_from_slice(public_inputs);
+        let public_evaluation = MultilinearPolynomial::new(&public).evaluate(&r_y[1..]);
+        let z_evaluation = (F::one() - r_y[0]) * public_evaluation + r_y[0] * proof.witness_evaluation;
+
+        inner_claim == combined_row * z_evaluation
+            && S::verify(params, &proof.witness_commitment, &r_y[1..], proof.witness_evaluation, &proof.witness_opening, transcript)
+    }
+
+    // Appends the witness commitment and the public inputs and derives tau
+    fn bind_instance<S>(&self, transcript: &mut Transcript, witness_commitment: &S::Commitment, public_inputs: &[F]) -> Vec<F>
+    where
+        S: MultilinearPcs<F>,
+        S::Commitment: CanonicalSerialize,
+    {
+        transcript.append(&commitment_to_bytes(witness_commitment));
+        append_field_elements(transcript, public_inputs);
+
+        (0..self.no_of_row_vars).map(|_| transcript.random_challenge_as_field_element()).collect()
+    }
+
+    fn matrix_vector_product(&self, matrix: &[(usize, usize, F)], z: &[F]) -> Vec<F> {
+        let mut product = vec![F::zero(); 1 << self.no_of_row_vars];
+        for (row, column, value) in matrix {
+            product[*row] += *value * z[*column];
+        }
+        product
+    }
+}
+
+fn batching_challenges<F: PrimeField>(transcript: &mut Transcript) -> (F, F, F) {
+    (
+        transcript.random_challenge_as_field_element(),
+        transcript.random_challenge_as_field_element(),
+        transcript.random_challenge_as_field_element(),
+    )
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arithmetization::r1cs::{LinearCombination, Variable};
+    use ark_bn254::{Bn254, Fr};
+    use commitments::pcs::{PolynomialCommitmentScheme, PstPcs};
+
+    // x^3 + x + 5 = out, with x private and out public
+    fn cubic() -> (SpartanInstance<Fr>, Vec<Fr>) {
+        let mut cs = ConstraintSystem::new();
+        let out = cs.alloc_public_input();
+        let x = cs.alloc_variable();
+        let x_squared = cs.alloc_variable();
+        let x_cubed = cs.alloc_variable();
+
+        cs.enforce(x.into(), x.into(), x_squared.into());
+        cs.enforce(x_squared.into(), x.into(), x_cubed.into());
+        cs.enforce(
+            LinearCombination::from(x_cubed) + x.into() + LinearCombination::constant(Fr::from(5)),
+            Variable::One.into(),
+            out.into(),
+        );
+
+        let assignment = cs.solve(&[(x, Fr::from(3))]).unwrap();
+        (SpartanInstance::from_r1cs(&cs), assignment)
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let (instance, assignment) = cubic();
+        let params = PstPcs::<Bn254>::setup(instance.witness_no_of_vars(), &mut ark_std::test_rng());
+
+        // 3 constraints pad to 4 rows, z = (1, out, 0, 0, x, x^2, x^3, 0)
+        assert_eq!((instance.no_of_row_vars, instance.no_of_column_vars), (2, 3));
+
+        let proof = instance.prove::<PstPcs<Bn254>>(&params, &assignment, &mut Transcript::new()).unwrap();
+        assert!(instance.verify(&params, &[Fr::from(35)], &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_public_input_or_proof() {
+        let (instance, assignment) = cubic();
+        let params = PstPcs::<Bn254>::setup(instance.witness_no_of_vars(), &mut ark_std::test_rng());
+
+        let mut proof = instance.prove::<PstPcs<Bn254>>(&params, &assignment, &mut Transcript::new()).unwrap();
+        assert!(!instance.verify(&params, &[Fr::from(36)], &proof, &mut Transcript::new()));
+        assert!(!instance.verify(&params, &[], &proof, &mut Transcript::new()));
+
+        proof.claimed_evaluations[2] += Fr::from(1);
+        assert!(!instance.verify(&params, &[Fr::from(35)], &proof, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_unsatisfied_assignment_has_no_proof() {
+        let (instance, assignment) = cubic();
+        let params = PstPcs::<Bn254>::setup(instance.witness_no_of_vars(), &mut ark_std::test_rng());
+
+        let mut wrong = assignment;
+        wrong[3] = Fr::from(10);
+        assert!(instance.prove::<PstPcs<Bn254>>(&params, &wrong, &mut Transcript::new()).is_none());
+    }
+}
@@ -0,0 +1,54 @@
+use ark_bn254::Fr;
+use multilinear::multilinear::MultilinearPolynomial;
+use multilinear::virtual_poly::{Expression, VirtualPolynomial};
+use std::time::{Duration, Instant};
+use sumcheck::composed::ComposedSumcheckProof;
+use sumcheck::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+
+// Times the zerocheck of a·b = c, eq(r, x)·(a·b - c), with the plain composed prover and with the eq factor in closed
+// form, e.g.
+//   cargo run --release -p sumcheck --example eq_factored_zerocheck -- 18
+// The argument is the largest number of variables, 16 by default. Each prover runs three times and the fastest counts
+fn main() {
+    let max_vars: usize = std::env::args().nth(1).and_then(|arg| arg.parse().ok()).unwrap_or(16);
+
+    println!("{:>5} {:>12} {:>12}", "vars", "plain", "eq factored");
+    for no_of_vars in (10..=max_vars).step_by(2) {
+        let polynomial = zerocheck(no_of_vars);
+
+        let plain = fastest(|| {
+            let combine = |values: &[Fr]| polynomial.combine(values);
+            ComposedSumcheckProof::prove(&polynomial.tables(), polynomial.degree(), combine, &mut Transcript::new()).unwrap().0
+        });
+        let factored = fastest(|| ComposedSumcheckProof::prove_virtual(&polynomial, &mut Transcript::new()).unwrap().0);
+
+        println!("{:>5} {:>12?} {:>12?}", no_of_vars, plain, factored);
+    }
+}
+
+fn zerocheck(no_of_vars: usize) -> VirtualPolynomial<Fr> {
+    let table = |seed: u64| {
+        let values: Vec<Fr> = (0..1u64 << no_of_vars).map(|i| Fr::from(i * seed + 1)).collect();
+        MultilinearPolynomial::new(&values)
+    };
+    let r: Vec<Fr> = (0..no_of_vars as u64).map(|i| Fr::from(i * 7919 + 13)).collect();
+
+    let mut polynomial = VirtualPolynomial::new(no_of_vars);
+    let a = polynomial.add_mle("a", table(2)).unwrap();
+    let b = polynomial.add_mle("b", table(3)).unwrap();
+    let c = polynomial.add_mle("c", table(5)).unwrap();
+    let eq = polynomial.add_eq("eq", &r).unwrap();
+    polynomial.set_expression(eq * (a * b + Expression::Constant(-Fr::from(1)) * c));
+    polynomial
+}
+
+fn fastest(prove: impl Fn() -> ComposedSumcheckProof<Fr>) -> Duration {
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(prove());
+            start.elapsed()
+        })
+        .min()
+        .expect("three runs")
+}
@@ -0,0 +1,20 @@
+use ark_bn254::{Bn254, Fr};
+use commitments::pcs::{PolynomialCommitmentScheme, PstPcs};
+use sumcheck::sumcheck::{Prover, Verifier};
+
+// Proves the sum of a 3 variable polynomial over the boolean hypercube, once sending the whole polynomial
+// and once committing to it with PST and opening the commitment at the round challenges
+fn main() {
+    let evaluated_values: Vec<Fr> = [0u64, 0, 0, 3, 0, 0, 2, 5].iter().map(|value| Fr::from(*value)).collect();
+
+    let mut prover = Prover::new(&evaluated_values);
+    let proof = prover.prove();
+    println!("claimed sum: {}", proof.initial_claimed_sum);
+    println!("rounds: {}", proof.uni_poly_for_each_round.len());
+    println!("verified: {}", Verifier::new().verify(proof));
+
+    let params = PstPcs::<Bn254>::setup(3, &mut ark_std::test_rng());
+    let mut prover = Prover::new(&evaluated_values);
+    let proof = prover.prove_with_opening::<PstPcs<Bn254>>(&params);
+    println!("verified with opening: {}", Verifier::new().verify_with_opening(&params, proof));
+}
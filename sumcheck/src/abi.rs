@@ -0,0 +1,182 @@
+use crate::composed::ComposedSumcheckProof;
+use crate::encoding::field_to_bytes;
+use crate::grand_product::{GrandProductLayer, GrandProductProof};
+use alloc::vec;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+
+// Solidity ABI encoding of the proofs, the bytes abi.decode expects in a verifier contract:
+//   ComposedSumcheckProof  abi.encode(uint256[][] roundPolys)
+//   GrandProductProof      abi.encode(GrandProductProof proof) with
+//                          struct GrandProductProof { uint256 product; Layer[] layers; }
+//                          struct Layer { uint256[][] roundPolys; uint256 left; uint256 right; }
+// A field element is a uint256 holding its canonical value, so the field has to fit in 256 bits.
+// Prove with EvmTranscript for the contract to derive the same challenges
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbiValue {
+    Uint([u8; 32]),
+    // T[], a length followed by the elements
+    Array(Vec<AbiValue>),
+    // a struct, or the parameters of abi.encode
+    Tuple(Vec<AbiValue>),
+}
+
+pub trait AbiEncode {
+    fn to_abi_value(&self) -> AbiValue;
+
+    // abi.encode of the value as a single parameter
+    fn to_abi_bytes(&self) -> Vec<u8> {
+        encode(&[self.to_abi_value()])
+    }
+}
+
+impl AbiValue {
+    pub fn uint<F: PrimeField>(element: F) -> Self {
+        let bytes = field_to_bytes(element);
+        assert!(bytes.len() <= 32, "field elements must fit in a uint256");
+        let mut word = [0; 32];
+        word[32 - bytes.len()..].copy_from_slice(&bytes);
+        AbiValue::Uint(word)
+    }
+
+    pub fn uints<F: PrimeField>(elements: &[F]) -> Self {
+        AbiValue::Array(elements.iter().map(|element| AbiValue::uint(*element)).collect())
+    }
+
+    // Dynamic values are encoded in the tail and referenced by an offset from the head
+    fn is_dynamic(&self) -> bool {
+        match self {
+            AbiValue::Uint(_) => false,
+            AbiValue::Array(_) => true,
+            AbiValue::Tuple(values) => values.iter().any(AbiValue::is_dynamic),
+        }
+    }
+}
+
+// The head and tail encoding of a tuple: static values in place, dynamic ones as the offset of their encoding
+// counted from the start of the tuple
+pub fn encode(values: &[AbiValue]) -> Vec<u8> {
+    let head_size: usize = values.iter().map(|value| if value.is_dynamic() { 32 } else { static_size(value) }).sum();
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+
+    for value in values {
+        if value.is_dynamic() {
+            head.extend(word(head_size + tail.len()));
+            tail.extend(encode_value(value));
+        } else {
+            head.extend(encode_value(value));
+        }
+    }
+
+    head.extend(tail);
+    head
+}
+
+fn encode_value(value: &AbiValue) -> Vec<u8> {
+    match value {
+        AbiValue::Uint(bytes) => bytes.to_vec(),
+        AbiValue::Array(values) => {
+            let mut bytes = word(values.len()).to_vec();
+            bytes.extend(encode(values));
+            bytes
+        }
+        AbiValue::Tuple(values) => encode(values),
+    }
+}
+
+fn static_size(value: &AbiValue) -> usize {
+    match value {
+        AbiValue::Uint(_) => 32,
+        AbiValue::Array(_) => unreachable!("arrays are dynamic"),
+        AbiValue::Tuple(values) => values.iter().map(static_size).sum(),
+    }
+}
+
+fn word(value: usize) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+impl <F: PrimeField>AbiEncode for ComposedSumcheckProof<F> {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::Array(self.round_polys.iter().map(|round_poly| AbiValue::uints(round_poly)).collect())
+    }
+}
+
+impl <F: PrimeField>AbiEncode for GrandProductLayer<F> {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::Tuple(vec![self.sumcheck.to_abi_value(), AbiValue::uint(self.left), AbiValue::uint(self.right)])
+    }
+}
+
+impl <F: PrimeField>AbiEncode for GrandProductProof<F> {
+    fn to_abi_value(&self) -> AbiValue {
+        AbiValue::Tuple(vec![
+            AbiValue::uint(self.product),
+            AbiValue::Array(self.layers.iter().map(AbiEncode::to_abi_value).collect()),
+        ])
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fiat_shamir::FiatShamirTranscriptInterface;
+    use ark_bn254::Fr;
+    use multilinear::multilinear::MultilinearPolynomial;
+    use transcript::evm::EvmTranscript;
+
+    fn words(bytes: &[u8]) -> Vec<u64> {
+        bytes
+            .chunks(32)
+            .map(|word| {
+                assert!(word[..24].iter().all(|byte| *byte == 0));
+                u64::from_be_bytes(word[24..].try_into().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nested_array_encoding() {
+        let proof = ComposedSumcheckProof { round_polys: vec![vec![Fr::from(1), Fr::from(2)], vec![Fr::from(3)]] };
+
+        // offset of the outer array, its length, the offsets of the inner arrays after the length, then each of them
+        assert_eq!(words(&proof.to_abi_bytes()), vec![0x20, 2, 0x40, 0xa0, 2, 1, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_struct_encoding() {
+        let layer = GrandProductLayer {
+            sumcheck: ComposedSumcheckProof { round_polys: vec![vec![Fr::from(4)]] },
+            left: Fr::from(5),
+            right: Fr::from(6),
+        };
+        let proof = GrandProductProof { product: Fr::from(7), layers: vec![layer] };
+
+        assert_eq!(
+            words(&proof.to_abi_bytes()),
+            vec![
+                0x20, // the struct is dynamic, it sits behind an offset
+                7, 0x40, // product, offset of layers within the struct
+                1, 0x20, // one layer, at this offset after the length
+                0x60, 5, 6, // round polys behind an offset, left, right
+                1, 0x20, 1, 4, // one round with one value
+            ]
+        );
+    }
+
+    #[test]
+    fn test_proofs_over_the_evm_transcript() {
+        let values: Vec<Fr> = [3u64, 5, 2, 7].iter().map(|value| Fr::from(*value)).collect();
+        let (proof, _, _) = GrandProductProof::prove(&values, &mut EvmTranscript::new()).unwrap();
+        let (point, value) = proof.verify(2, &mut EvmTranscript::new()).unwrap();
+        assert_eq!(MultilinearPolynomial::new(&values).evaluate(&point), value);
+
+        // the challenges are the keccak chain's, the hashing transcript derives others
+        assert_eq!(proof.verify(2, &mut crate::fiat_shamir::Transcript::new()), None);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::fiat_shamir::FiatShamirTranscriptInterface;
+use alloc::vec::Vec;
+use multilinear::binary::{BinaryMultilinearPolynomial, Gf2_64};
+
+// Sumcheck of a multilinear polynomial over GF(2^64), the binary field counterpart of the plain prover.
+//
+// The protocol is unchanged: every round sends the round polynomial at 0 and 1, which must add up to the running
+// claim, and the challenge is drawn from the transcript; the round polynomial is linear, so its value at the
+// challenge is at_0 + r * (at_1 - at_0) and no evaluation at 2, which is 0 in this field, is ever needed. Challenges
+// are the first 8 bytes of the transcript's 32, which leaves the soundness error of a round at 1 / 2^64.
+// Like the composed sumcheck it stops at the final claim, which the caller checks, and the claimed sum is the
+// caller's to bind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinarySumcheckProof {
+    pub round_polys: Vec<[Gf2_64; 2]>,
+}
+
+impl BinarySumcheckProof {
+    // Returns the proof, the challenges and the polynomial's value at them
+    pub fn prove<T: FiatShamirTranscriptInterface>(
+        polynomial: &BinaryMultilinearPolynomial,
+        transcript: &mut T,
+    ) -> (Self, Vec<Gf2_64>, Gf2_64) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("binary_sumcheck_prove", no_of_vars = polynomial.no_of_vars).entered();
+
+        let mut current = polynomial.clone();
+        let mut round_polys = Vec::with_capacity(polynomial.no_of_vars);
+        let mut challenges = Vec::with_capacity(polynomial.no_of_vars);
+
+        for _ in 0..polynomial.no_of_vars {
+            let round_poly = current.round_evaluations();
+            let challenge = round_challenge(transcript, &round_poly);
+
+            current = current.fold(challenge);
+            round_polys.push(round_poly);
+            challenges.push(challenge);
+        }
+
+        (Self { round_polys }, challenges, current.evaluated_values[0])
+    }
+
+    // Checks the rounds against claimed_sum and returns the challenges and the final claim, or None if a round does
+    // not add up to the running claim
+    pub fn verify<T: FiatShamirTranscriptInterface>(
+        &self,
+        claimed_sum: Gf2_64,
+        no_of_vars: usize,
+        transcript: &mut T,
+    ) -> Option<(Vec<Gf2_64>, Gf2_64)> {
+        if self.round_polys.len() != no_of_vars {
+            return None;
+        }
+
+        let mut current_claim = claimed_sum;
+        let mut challenges = Vec::with_capacity(no_of_vars);
+
+        for round_poly in &self.round_polys {
+            let [at_0, at_1] = *round_poly;
+            if at_0 + at_1 != current_claim {
+                return None;
+            }
+
+            let challenge = round_challenge(transcript, round_poly);
+            current_claim = at_0 + challenge * (at_1 - at_0);
+            challenges.push(challenge);
+        }
+
+        Some((challenges, current_claim))
+    }
+}
+
+fn round_challenge<T: FiatShamirTranscriptInterface>(transcript: &mut T, round_poly: &[Gf2_64; 2]) -> Gf2_64 {
+    transcript.append(&round_poly[0].to_bytes());
+    transcript.append(&round_poly[1].to_bytes());
+    Gf2_64::from_bytes(&transcript.sample_random_challenge())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fiat_shamir::Transcript;
+
+    // a wire of a boolean circuit over 3 input bits: the majority of x_0, x_1, x_2
+    fn majority() -> BinaryMultilinearPolynomial {
+        let bits: Vec<bool> = (0..8u32).map(|x| x.count_ones() >= 2).collect();
+        BinaryMultilinearPolynomial::from_bits(&bits)
+    }
+
+    #[test]
+    fn test_binary_sumcheck() {
+        let polynomial = majority();
+        // four set bits sum to 0 in characteristic 2
+        assert_eq!(polynomial.sum(), Gf2_64(0));
+
+        let (proof, challenges, evaluation) = BinarySumcheckProof::prove(&polynomial, &mut Transcript::new());
+        let (verifier_challenges, final_claim) = proof.verify(polynomial.sum(), 3, &mut Transcript::new()).unwrap();
+
+        assert_eq!(verifier_challenges, challenges);
+        assert_eq!(final_claim, evaluation);
+        assert_eq!(polynomial.evaluate(&challenges), final_claim);
+    }
+
+    #[test]
+    fn test_binary_sumcheck_rejects_wrong_claims() {
+        let polynomial = majority();
+        let (proof, _, _) = BinarySumcheckProof::prove(&polynomial, &mut Transcript::new());
+
+        assert_eq!(proof.verify(Gf2_64(1), 3, &mut Transcript::new()), None);
+        assert_eq!(proof.verify(Gf2_64(0), 2, &mut Transcript::new()), None);
+
+        // a round shifted by the same amount at 0 and 1 still adds up, but leaves a claim the next round misses
+        let mut tampered = proof.clone();
+        tampered.round_polys[1][0] += Gf2_64(5);
+        tampered.round_polys[1][1] += Gf2_64(5);
+        assert_eq!(tampered.verify(Gf2_64(0), 3, &mut Transcript::new()), None);
+    }
+}
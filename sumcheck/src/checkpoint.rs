@@ -0,0 +1,214 @@
+use crate::deserialize_vec;
+use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use crate::sumcheck::{absorb_statement, round_challenge, split_and_reduce, SumcheckProof};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Validate, Write};
+use multilinear::multilinear::MultilinearPolynomial;
+
+pub const CHECKPOINT_VERSION: u8 = 1;
+
+// A plain sumcheck prover that can stop after any round, be saved, and carry on later or on another machine.
+//
+// The state is what the prover holds between rounds: the statement, the rounds sent so far and the table folded by
+// their challenges, which is the expensive part to recompute on a large instance. The hasher inside the transcript
+// cannot be serialized, so save leaves it out and load rebuilds it by replaying the statement and the sent rounds,
+// one hash per round; the challenges come out the same, and a resumed proof is the one an uninterrupted prover makes.
+//
+// save writes
+//   version (1 byte) | statement: flag, then label and statement as byte vectors | claimed sum | initial polynomial |
+//   rounds | folded table
+// with the canonical encodings of the serialization layer. load refuses another version, a folded table whose
+// number of variables does not match the rounds, or a state past its last round. A checkpoint holds the prover's whole
+// polynomial, so it is as secret as the witness
+pub struct ProverState<F: PrimeField> {
+    pub statement: Option<(Vec<u8>, Vec<u8>)>,
+    pub initial_claimed_sum: F,
+    pub initial_poly: MultilinearPolynomial<F>,
+    pub rounds: Vec<MultilinearPolynomial<F>>,
+    pub folded: MultilinearPolynomial<F>,
+    transcript: Transcript,
+}
+
+impl <F: PrimeField>ProverState<F> {
+    // The state of Prover::new before its first round
+    pub fn new(initial_poly_evaluation: &[F]) -> Self {
+        Self::start(initial_poly_evaluation, None)
+    }
+
+    // The state of Prover::new_with_statement before its first round
+    pub fn new_with_statement(initial_poly_evaluation: &[F], label: &[u8], statement: &[u8]) -> Self {
+        Self::start(initial_poly_evaluation, Some((label.to_vec(), statement.to_vec())))
+    }
+
+    fn start(initial_poly_evaluation: &[F], statement: Option<(Vec<u8>, Vec<u8>)>) -> Self {
+        let initial_poly = MultilinearPolynomial::padded(initial_poly_evaluation);
+        let initial_claimed_sum = initial_poly_evaluation.iter().sum();
+        let transcript = replay(&statement, &initial_poly, initial_claimed_sum, &[]);
+
+        Self { statement, initial_claimed_sum, folded: initial_poly.clone(), initial_poly, rounds: Vec::new(), transcript }
+    }
+
+    // The number of rounds sent so far
+    pub fn round(&self) -> usize {
+        self.rounds.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.rounds.len() == self.initial_poly.no_of_vars
+    }
+
+    // Runs up to no_of_rounds more rounds, fewer when the last one comes first
+    pub fn prove_rounds(&mut self, no_of_rounds: usize) {
+        for _ in 0..no_of_rounds {
+            if self.is_finished() {
+                return;
+            }
+
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("sumcheck_round", round = self.rounds.len()).entered();
+
+            let round = MultilinearPolynomial::new(&split_and_reduce(&self.folded.evaluated_values));
+            let challenge = round_challenge(&mut self.transcript, &round);
+
+            self.folded = MultilinearPolynomial::partial_evaluate(&self.folded.evaluated_values, 0, challenge);
+            self.rounds.push(round);
+        }
+    }
+
+    // Runs the remaining rounds and returns the proof
+    pub fn finish(mut self) -> SumcheckProof<F> {
+        self.prove_rounds(self.initial_poly.no_of_vars);
+
+        SumcheckProof {
+            initial_claimed_sum: self.initial_claimed_sum,
+            initial_poly: self.initial_poly,
+            uni_poly_for_each_round: self.rounds,
+        }
+    }
+
+    pub fn save<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        let compress = Compress::Yes;
+        CHECKPOINT_VERSION.serialize_with_mode(&mut writer, compress)?;
+        match &self.statement {
+            Some((label, statement)) => {
+                true.serialize_with_mode(&mut writer, compress)?;
+                label.serialize_with_mode(&mut writer, compress)?;
+                statement.serialize_with_mode(&mut writer, compress)?;
+            }
+            None => false.serialize_with_mode(&mut writer, compress)?,
+        }
+        self.initial_claimed_sum.serialize_with_mode(&mut writer, compress)?;
+        self.initial_poly.serialize_with_mode(&mut writer, compress)?;
+        self.rounds.serialize_with_mode(&mut writer, compress)?;
+        self.folded.serialize_with_mode(&mut writer, compress)
+    }
+
+    pub fn load<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let (compress, validate) = (Compress::Yes, Validate::Yes);
+        if u8::deserialize_with_mode(&mut reader, compress, validate)? != CHECKPOINT_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let bytes = |reader: &mut R| {
+            deserialize_vec(reader, compress, validate, |reader| u8::deserialize_with_mode(reader, compress, validate))
+        };
+        let statement = match bool::deserialize_with_mode(&mut reader, compress, validate)? {
+            true => Some((bytes(&mut reader)?, bytes(&mut reader)?)),
+            false => None,
+        };
+        let initial_claimed_sum = F::deserialize_with_mode(&mut reader, compress, validate)?;
+        let initial_poly = MultilinearPolynomial::deserialize_with_mode(&mut reader, compress, validate)?;
+        let rounds = deserialize_vec(&mut reader, compress, validate, |reader| {
+            MultilinearPolynomial::deserialize_with_mode(reader, compress, validate)
+        })?;
+        let folded = MultilinearPolynomial::<F>::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        if rounds.len() > initial_poly.no_of_vars || rounds.len() + folded.no_of_vars != initial_poly.no_of_vars {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let transcript = replay(&statement, &initial_poly, initial_claimed_sum, &rounds);
+        Ok(Self { statement, initial_claimed_sum, initial_poly, rounds, folded, transcript })
+    }
+}
+
+// The transcript of a prover that has sent these rounds
+fn replay<F: PrimeField>(
+    statement: &Option<(Vec<u8>, Vec<u8>)>,
+    initial_poly: &MultilinearPolynomial<F>,
+    initial_claimed_sum: F,
+    rounds: &[MultilinearPolynomial<F>],
+) -> Transcript {
+    let mut transcript = match statement {
+        Some((label, statement)) => Transcript::new_with_statement(label, statement),
+        None => Transcript::new(),
+    };
+    absorb_statement(&mut transcript, initial_poly, initial_claimed_sum);
+    for round in rounds {
+        round_challenge(&mut transcript, round);
+    }
+    transcript
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sumcheck::{Prover, Verifier};
+    use ark_bn254::Fr;
+
+    fn evaluations() -> Vec<Fr> {
+        (0..16u64).map(|i| Fr::from(i * 7 + 3)).collect()
+    }
+
+    fn round_trip(state: &ProverState<Fr>) -> ProverState<Fr> {
+        let mut bytes = Vec::new();
+        state.save(&mut bytes).unwrap();
+        ProverState::load(bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_resumed_proof_is_the_uninterrupted_one() {
+        for stop_after in 0..=4 {
+            let mut state = ProverState::new_with_statement(&evaluations(), b"sumcheck", b"instance");
+            state.prove_rounds(stop_after);
+            let resumed = round_trip(&state);
+            assert_eq!(resumed.round(), stop_after);
+
+            let proof = resumed.finish();
+            let expected = Prover::new_with_statement(&evaluations(), b"sumcheck", b"instance").prove();
+            assert_eq!(proof.uni_poly_for_each_round, expected.uni_poly_for_each_round);
+            assert!(Verifier::new_with_statement(b"sumcheck", b"instance").verify(proof));
+        }
+    }
+
+    #[test]
+    fn test_checkpoints_can_be_chained() {
+        // one round per machine, saved and loaded between every two
+        let mut state = ProverState::new(&evaluations());
+        while !state.is_finished() {
+            state.prove_rounds(1);
+            state = round_trip(&state);
+        }
+        assert!(Verifier::new().verify(state.finish()));
+    }
+
+    #[test]
+    fn test_bad_checkpoints_are_refused() {
+        let mut state = ProverState::new(&evaluations());
+        state.prove_rounds(2);
+        let mut bytes = Vec::new();
+        state.save(&mut bytes).unwrap();
+
+        let mut other_version = bytes.clone();
+        other_version[0] = 2;
+        assert!(ProverState::<Fr>::load(other_version.as_slice()).is_err());
+        assert!(ProverState::<Fr>::load(&bytes[..bytes.len() - 1]).is_err());
+
+        // a folded table that does not match the rounds sent
+        state.folded = state.initial_poly.clone();
+        let mut mismatched = Vec::new();
+        state.save(&mut mismatched).unwrap();
+        assert!(ProverState::<Fr>::load(mismatched.as_slice()).is_err());
+    }
+}
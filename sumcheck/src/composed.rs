@@ -0,0 +1,508 @@
+use crate::deserialize_vec;
+use crate::encoding::field_to_bytes;
+use crate::error::SumcheckError;
+use crate::fiat_shamir::FiatShamirTranscriptInterface;
+use alloc::vec;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate};
+use multilinear::multilinear::MultilinearPolynomial;
+
+// Sumcheck over a combination of several multilinear polynomials.
+//
+// Proves claimed_sum = sum over the boolean hypercube of combine(f_1(x), ..., f_k(x)), where combine has total degree
+// at most `degree`, e.g. eq(r, x) * a(x) * b(x) has degree 3. A round polynomial then has degree `degree` in the
+// bound variable and is sent as its evaluations at 0, 1, ..., degree. Variable 0 is bound first, like the plain prover.
+//
+// The protocol stops at the final claim: the verifier is left with combine(f_1(r), ..., f_k(r)) = final claim at the
+// challenge point r, and the caller decides how the f_i(r) are checked (computed directly, opened from a commitment,
+// or reduced further by another protocol). The claimed sum itself is not appended, the caller binds it to the transcript.
+// Any transcript works, Transcript or a HashTranscript over another hasher
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize)]
+pub struct ComposedSumcheckProof<F: PrimeField> {
+    pub round_polys: Vec<Vec<F>>,
+}
+
+// What a composed sumcheck proves: the sum over the hypercube of a combination of total degree at most max_degree.
+// The degree bound belongs to the statement, not to the proof. Sumcheck is only sound because a round polynomial of
+// degree d that is not the honest one agrees with it at the challenge with probability d / |F|, and a prover free to
+// send rounds of any degree can fit a polynomial through any claim it likes, so the verifier takes max_degree from
+// the claim and refuses every round with more evaluations than that degree needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SumcheckClaim<F: PrimeField> {
+    pub claimed_sum: F,
+    pub no_of_vars: usize,
+    pub max_degree: usize,
+}
+
+impl <F: PrimeField>SumcheckClaim<F> {
+    pub fn new(claimed_sum: F, no_of_vars: usize, max_degree: usize) -> Self {
+        Self { claimed_sum, no_of_vars, max_degree }
+    }
+}
+
+impl <F: PrimeField>ComposedSumcheckProof<F> {
+    // Returns the proof, the challenges and the evaluations of every polynomial at the challenges, or an error before
+    // anything goes into the transcript if there are no polynomials or they differ in their number of variables
+    pub fn prove<C, T>(
+        polynomials: &[MultilinearPolynomial<F>],
+        degree: usize,
+        combine: C,
+        transcript: &mut T,
+    ) -> Result<(Self, Vec<F>, Vec<F>), SumcheckError>
+    where
+        C: Fn(&[F]) -> F,
+        T: FiatShamirTranscriptInterface,
+    {
+        let no_of_vars = polynomials.first().ok_or(SumcheckError::NoPolynomials)?.no_of_vars;
+        if let Some(polynomial) = polynomials.iter().find(|polynomial| polynomial.no_of_vars != no_of_vars) {
+            return Err(SumcheckError::MismatchedVariables { expected: no_of_vars, provided: polynomial.no_of_vars });
+        }
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("composed_sumcheck_prove", no_of_vars, degree).entered();
+
+        let mut current: Vec<MultilinearPolynomial<F>> = polynomials.to_vec();
+        let mut round_polys = Vec::with_capacity(no_of_vars);
+        let mut challenges = Vec::with_capacity(no_of_vars);
+        let mut values = vec![F::zero(); polynomials.len()];
+
+        for _round in 0..no_of_vars {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("sumcheck_round", round = _round).entered();
+
+            let half = current[0].evaluated_values.len() / 2;
+
+            // evaluate the round polynomial at t = 0..=degree: with variable 0 fixed to t,
+            // every polynomial is low + t * (high - low) on the remaining hypercube
+            let round_poly: Vec<F> = (0..=degree)
+                .map(|t| {
+                    let t = F::from(t as u64);
+                    (0..half)
+                        .map(|j| {
+                            for (value, polynomial) in values.iter_mut().zip(current.iter()) {
+                                let low = polynomial.evaluated_values[j];
+                                let high = polynomial.evaluated_values[j + half];
+                                *value = low + t * (high - low);
+                            }
+                            combine(&values)
+                        })
+                        .sum()
+                })
+                .collect();
+
+            let challenge = round_challenge(transcript, &round_poly);
+
+            current = current
+                .iter()
+                .map(|polynomial| MultilinearPolynomial::partial_evaluate(&polynomial.evaluated_values, 0, challenge))
+                .collect();
+
+            round_polys.push(round_poly);
+            challenges.push(challenge);
+        }
+
+        let final_evaluations = current.iter().map(|polynomial| polynomial.evaluated_values[0]).collect();
+
+        Ok((Self { round_polys }, challenges, final_evaluations))
+    }
+
+    // Proves a claim with its own degree bound, checked against the polynomials the claim is about
+    pub fn prove_claim<C, T>(
+        polynomials: &[MultilinearPolynomial<F>],
+        claim: &SumcheckClaim<F>,
+        combine: C,
+        transcript: &mut T,
+    ) -> Result<(Self, Vec<F>, Vec<F>), SumcheckError>
+    where
+        C: Fn(&[F]) -> F,
+        T: FiatShamirTranscriptInterface,
+    {
+        let no_of_vars = polynomials.first().ok_or(SumcheckError::NoPolynomials)?.no_of_vars;
+        if no_of_vars != claim.no_of_vars {
+            return Err(SumcheckError::MismatchedVariables { expected: claim.no_of_vars, provided: no_of_vars });
+        }
+
+        Self::prove(polynomials, claim.max_degree, combine, transcript)
+    }
+
+    // Checks the rounds against the claim, with every round bounded by the claim's degree
+    pub fn verify_claim<T: FiatShamirTranscriptInterface>(&self, claim: &SumcheckClaim<F>, transcript: &mut T) -> Option<(Vec<F>, F)> {
+        self.verify(claim.claimed_sum, claim.no_of_vars, claim.max_degree, transcript)
+    }
+
+    // Checks the rounds against claimed_sum and returns the challenges and the final claim,
+    // or None if a round has the wrong shape or does not match the running claim.
+    // degree is the verifier's bound: a round is read as the polynomial through as many points as it has, so a round
+    // with more than degree + 1 evaluations is a polynomial of higher degree and is refused
+    pub fn verify<T: FiatShamirTranscriptInterface>(
+        &self,
+        claimed_sum: F,
+        no_of_vars: usize,
+        degree: usize,
+        transcript: &mut T,
+    ) -> Option<(Vec<F>, F)> {
+        if self.round_polys.len() != no_of_vars {
+            return None;
+        }
+
+        let mut current_claim = claimed_sum;
+        let mut challenges = Vec::with_capacity(no_of_vars);
+
+        for round_poly in &self.round_polys {
+            // a degree 0 round has no value at 1 to check, it is refused along with every other wrong shape.
+            // A round of lower degree than the bound is a polynomial through fewer points and is fine
+            match round_poly.as_slice() {
+                [at_0, at_1, ..] if round_poly.len() <= degree + 1 && *at_0 + at_1 == current_claim => {}
+                _ => return None,
+            }
+
+            let challenge = round_challenge(transcript, round_poly);
+
+            current_claim = evaluate_from_points(round_poly, challenge);
+            challenges.push(challenge);
+        }
+
+        Some((challenges, current_claim))
+    }
+
+    // The challenges verify draws for this proof, without checking anything, for tools that need to reproduce them
+    // such as a generated verifier or a debugger. The proof does not bind its statement, so transcript must be in the
+    // state the verifier's is in, with whatever the caller appended for the claim; it is left after the last round
+    pub fn derive_challenges<T: FiatShamirTranscriptInterface>(&self, transcript: &mut T) -> Vec<F> {
+        self.round_polys.iter().map(|round_poly| round_challenge(transcript, round_poly)).collect()
+    }
+}
+
+// One round of a composed sumcheck: the round polynomial's evaluations go into the transcript one element at a time,
+// and the challenge comes out
+pub fn round_challenge<F: PrimeField, T: FiatShamirTranscriptInterface>(transcript: &mut T, round_poly: &[F]) -> F {
+    append_field_elements(transcript, round_poly);
+    transcript.random_challenge_as_field_element()
+}
+
+// Sums over tables whose length n is not a power of two, e.g. 3 * 2^k.
+//
+// The tables are padded with zeros to 2^m and the sum runs over mask(x) * combine(f_1(x), ..., f_k(x)), where
+// mask is the indicator of the first n points of the hypercube. Padding alone is not enough: a combination with a
+// constant term is not zero on the padding, and a prover committing to the tables could put anything there. The mask
+// makes the claim the sum over the n real entries whatever the padding holds, for one more degree per round, and the
+// verifier evaluates it at the challenges itself in O(m), see MultilinearPolynomial::evaluate_prefix_indicator
+impl <F: PrimeField>ComposedSumcheckProof<F> {
+    // Returns the proof, the challenges and the evaluations of the padded tables at the challenges. The tables must
+    // have the same length, degree is that of combine
+    pub fn prove_padded<C, T>(
+        tables: &[Vec<F>],
+        degree: usize,
+        combine: C,
+        transcript: &mut T,
+    ) -> Result<(Self, Vec<F>, Vec<F>), SumcheckError>
+    where
+        C: Fn(&[F]) -> F,
+        T: FiatShamirTranscriptInterface,
+    {
+        let length = tables.first().ok_or(SumcheckError::NoPolynomials)?.len();
+        if let Some(table) = tables.iter().find(|table| table.len() != length) {
+            return Err(SumcheckError::MismatchedLengths { expected: length, provided: table.len() });
+        }
+
+        let mut polynomials: Vec<MultilinearPolynomial<F>> = tables.iter().map(|table| MultilinearPolynomial::padded(table)).collect();
+        let no_of_vars = polynomials[0].no_of_vars;
+        polynomials.push(MultilinearPolynomial::new(&MultilinearPolynomial::prefix_indicator_evaluations(length, no_of_vars)));
+
+        let no_of_tables = tables.len();
+        let masked = |values: &[F]| values[no_of_tables] * combine(&values[..no_of_tables]);
+        let (proof, challenges, mut evaluations) = Self::prove(&polynomials, degree + 1, masked, transcript)?;
+
+        // the mask's value is the verifier's to compute
+        evaluations.pop();
+        Ok((proof, challenges, evaluations))
+    }
+
+    // Checks a proof from prove_padded of the sum over the first `length` entries and returns the challenges and the
+    // final claim with the mask divided out, the value combine(f_1(r), ..., f_k(r)) must have. None if the rounds fail,
+    // or in the negligible case that the mask vanishes at the challenges and its claim cannot be divided
+    pub fn verify_padded<T: FiatShamirTranscriptInterface>(
+        &self,
+        claimed_sum: F,
+        length: usize,
+        degree: usize,
+        transcript: &mut T,
+    ) -> Option<(Vec<F>, F)> {
+        let no_of_vars = length.max(1).next_power_of_two().trailing_zeros() as usize;
+        let (challenges, final_claim) = self.verify(claimed_sum, no_of_vars, degree + 1, transcript)?;
+
+        let mask = MultilinearPolynomial::evaluate_prefix_indicator(length, &challenges);
+        Some((challenges, final_claim * mask.inverse()?))
+    }
+}
+
+impl <F: PrimeField>ComposedSumcheckProof<F> {
+    // The size of the compressed serialization: degree + 1 elements per round plus the length prefixes
+    pub fn size_in_bytes(&self) -> usize {
+        self.compressed_size()
+    }
+}
+
+// Same encoding as the derive, read through deserialize_vec so a forged length cannot allocate
+impl <F: PrimeField>CanonicalDeserialize for ComposedSumcheckProof<F> {
+    fn deserialize_with_mode<R: Read>(mut reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        let round_polys = deserialize_vec(&mut reader, compress, validate, |reader| {
+            deserialize_vec(reader, compress, validate, |reader| F::deserialize_with_mode(reader, compress, validate))
+        })?;
+        Ok(Self { round_polys })
+    }
+}
+
+impl <F: PrimeField>Valid for ComposedSumcheckProof<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.round_polys.check()
+    }
+}
+
+pub fn append_field_elements<F: PrimeField, T: FiatShamirTranscriptInterface>(transcript: &mut T, elements: &[F]) {
+    for element in elements {
+        transcript.append(&field_to_bytes(*element));
+    }
+}
+
+// Lagrange interpolation of the polynomial with the given evaluations at 0, 1, ..., n - 1, evaluated at x
+pub fn evaluate_from_points<F: PrimeField>(evaluations: &[F], x: F) -> F {
+    let points: Vec<F> = (0..evaluations.len()).map(|i| F::from(i as u64)).collect();
+
+    evaluations
+        .iter()
+        .enumerate()
+        .map(|(i, y)| {
+            let (numerator, denominator) = points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold((F::one(), F::one()), |(numerator, denominator), (_, point)| {
+                    (numerator * (x - point), denominator * (points[i] - point))
+                });
+            *y * numerator * denominator.inverse().expect("interpolation points are distinct")
+        })
+        .sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fiat_shamir::Transcript;
+    use ark_bn254::Fr;
+    use multilinear::arbitrary;
+    use proptest::prelude::*;
+
+    fn polynomial(values: &[u64]) -> MultilinearPolynomial<Fr> {
+        let values: Vec<Fr> = values.iter().map(|value| Fr::from(*value)).collect();
+        MultilinearPolynomial::new(&values)
+    }
+
+    #[test]
+    fn test_product_sumcheck() {
+        let a = polynomial(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let b = polynomial(&[2, 0, 1, 3, 0, 4, 2, 1]);
+        let c = polynomial(&[5, 1, 1, 2, 3, 0, 1, 1]);
+        let combine = |values: &[Fr]| values[0] * values[1] * values[2] + values[0];
+
+        let claimed_sum: Fr = (0..8)
+            .map(|i| combine(&[a.evaluated_values[i], b.evaluated_values[i], c.evaluated_values[i]]))
+            .sum();
+
+        let mut transcript = Transcript::new();
+        let (proof, challenges, evaluations) =
+            ComposedSumcheckProof::prove(&[a.clone(), b.clone(), c.clone()], 3, combine, &mut transcript).unwrap();
+
+        let mut transcript = Transcript::new();
+        let (verifier_challenges, final_claim) = proof.verify(claimed_sum, 3, 3, &mut transcript).unwrap();
+
+        assert_eq!(verifier_challenges, challenges);
+        assert_eq!(evaluations, vec![a.evaluate(&challenges), b.evaluate(&challenges), c.evaluate(&challenges)]);
+        assert_eq!(final_claim, combine(&evaluations));
+
+        // a wrong sum fails the first round, missing rounds or degrees fail the shape check
+        assert_eq!(proof.verify(claimed_sum + Fr::from(1), 3, 3, &mut Transcript::new()), None);
+        assert_eq!(proof.verify(claimed_sum, 2, 3, &mut Transcript::new()), None);
+        assert_eq!(proof.verify(claimed_sum, 3, 2, &mut Transcript::new()), None);
+
+        // one value per round is a degree 0 round, refused rather than read past
+        let constant = ComposedSumcheckProof { round_polys: vec![vec![claimed_sum]; 3] };
+        assert_eq!(constant.verify(claimed_sum, 3, 0, &mut Transcript::new()), None);
+    }
+
+    #[test]
+    fn test_derive_challenges_without_verifying() {
+        let a = polynomial(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let b = polynomial(&[2, 0, 1, 3, 0, 4, 2, 1]);
+        let product = |values: &[Fr]| values[0] * values[1];
+
+        let (proof, challenges, _) = ComposedSumcheckProof::prove(&[a, b], 2, product, &mut Transcript::new()).unwrap();
+        assert_eq!(proof.derive_challenges(&mut Transcript::new()), challenges);
+
+        // no check is made, so a proof the verifier refuses still has its challenges derived
+        let mut tampered = proof.clone();
+        tampered.round_polys[0][0] += Fr::from(1);
+        let derived = tampered.derive_challenges(&mut Transcript::new());
+        assert_eq!(derived.len(), 3);
+        assert_ne!(derived, challenges);
+    }
+
+    #[test]
+    fn test_rounds_above_the_claimed_degree_are_refused() {
+        let a = polynomial(&[1, 2, 3, 4]);
+        let b = polynomial(&[4, 0, 2, 5]);
+        let product = |values: &[Fr]| values[0] * values[1];
+        let claimed_sum = Fr::from(4 + 6 + 20);
+
+        let claim = SumcheckClaim::new(claimed_sum, 2, 2);
+        let (proof, _, _) = ComposedSumcheckProof::prove_claim(&[a, b], &claim, product, &mut Transcript::new()).unwrap();
+        assert!(proof.verify_claim(&claim, &mut Transcript::new()).is_some());
+
+        // the same round polynomials sent through one more point each are degree 3 rounds to the verifier, which a
+        // degree 2 claim refuses
+        let padded = ComposedSumcheckProof {
+            round_polys: proof
+                .round_polys
+                .iter()
+                .map(|round_poly| [&round_poly[..], &[evaluate_from_points(round_poly, Fr::from(3))]].concat())
+                .collect(),
+        };
+        assert_eq!(padded.verify_claim(&claim, &mut Transcript::new()), None);
+
+        // a bound above the degree of the rounds accepts them, one below refuses the honest rounds
+        assert!(proof.verify_claim(&SumcheckClaim::new(claimed_sum, 2, 4), &mut Transcript::new()).is_some());
+        assert_eq!(proof.verify_claim(&SumcheckClaim::new(claimed_sum, 2, 1), &mut Transcript::new()), None);
+    }
+
+    #[test]
+    fn test_padded_sumcheck() {
+        // six entries, and a combination that is 1 on the zero padding
+        let a: Vec<Fr> = (1..=6u64).map(Fr::from).collect();
+        let b: Vec<Fr> = (1..=6u64).map(|i| Fr::from(i + 2)).collect();
+        let combine = |values: &[Fr]| values[0] * values[1] + Fr::from(1);
+        let claimed_sum: Fr = a.iter().zip(&b).map(|(x, y)| combine(&[*x, *y])).sum();
+
+        let (proof, challenges, evaluations) =
+            ComposedSumcheckProof::prove_padded(&[a.clone(), b.clone()], 2, combine, &mut Transcript::new()).unwrap();
+        let (point, final_claim) = proof.verify_padded(claimed_sum, 6, 2, &mut Transcript::new()).unwrap();
+
+        assert_eq!(point, challenges);
+        assert_eq!(evaluations, vec![MultilinearPolynomial::padded(&a).evaluate(&point), MultilinearPolynomial::padded(&b).evaluate(&point)]);
+        assert_eq!(final_claim, combine(&evaluations));
+
+        // the sum over the padded tables counts the two padded entries, which the mask leaves out, and a verifier
+        // expecting fewer entries is left with a final claim the evaluations do not meet
+        assert_eq!(proof.verify_padded(claimed_sum + Fr::from(2), 6, 2, &mut Transcript::new()), None);
+        let (_, shorter_claim) = proof.verify_padded(claimed_sum, 5, 2, &mut Transcript::new()).unwrap();
+        assert_ne!(shorter_claim, combine(&evaluations));
+    }
+
+    #[test]
+    fn test_padding_cannot_carry_the_sum() {
+        // a table of three entries whose padding holds 100 instead of zero: summed without the mask of the
+        // verifier's length, the padding gets into the claim and the final check fails
+        let table = vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(100)];
+        let identity = |values: &[Fr]| values[0];
+
+        let (proof, _, evaluations) = ComposedSumcheckProof::prove_padded(&[table], 1, identity, &mut Transcript::new()).unwrap();
+        let (_, final_claim) = proof.verify_padded(Fr::from(106), 3, 1, &mut Transcript::new()).unwrap();
+
+        assert_ne!(final_claim, evaluations[0]);
+        assert!(proof.verify_padded(Fr::from(6), 3, 1, &mut Transcript::new()).is_none());
+    }
+
+    #[test]
+    fn test_evaluate_from_points() {
+        // 2x^2 + 3x + 1 at 0, 1, 2
+        let evaluations = vec![Fr::from(1), Fr::from(6), Fr::from(15)];
+
+        assert_eq!(evaluate_from_points(&evaluations, Fr::from(5)), Fr::from(66));
+        assert_eq!(evaluate_from_points(&evaluations, Fr::from(2)), Fr::from(15));
+    }
+
+    // Records the name of every span created while it is the default subscriber
+    #[cfg(feature = "tracing")]
+    struct SpanNames(std::sync::Mutex<Vec<&'static str>>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanNames {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            tracing::span::Id::from_u64(names.len() as u64)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, _: &tracing::Event<'_>) {}
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_prover_phases_are_traced() {
+        let subscriber = std::sync::Arc::new(SpanNames(std::sync::Mutex::new(Vec::new())));
+        let poly = polynomial(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            ComposedSumcheckProof::prove(&[poly], 1, |values: &[Fr]| values[0], &mut Transcript::new()).unwrap();
+        });
+
+        // one span for the proof, then each of the three rounds and the fold that ends it
+        let names = subscriber.0.lock().unwrap();
+        assert_eq!(names[0], "composed_sumcheck_prove");
+        assert_eq!(names.iter().filter(|name| **name == "sumcheck_round").count(), 3);
+        assert_eq!(names.iter().filter(|name| **name == "mle_fold").count(), 3);
+    }
+
+    // The product sumcheck over bn254's scalar field and over the 64-bit Goldilocks field, where the transcript's
+    // challenges are reduced modulo a much smaller p
+    multilinear::field_tests!(bn254: Fr, goldilocks: multilinear::goldilocks::Goldilocks => {
+        proptest! {
+            #[test]
+            fn prop_product_sumcheck_verifies(
+                (a, b) in (1..=4usize).prop_flat_map(|no_of_vars| (arbitrary::polynomial::<F>(no_of_vars), arbitrary::polynomial::<F>(no_of_vars)))
+            ) {
+                let no_of_vars = a.no_of_vars;
+                let claimed_sum: F = a.evaluated_values.iter().zip(&b.evaluated_values).map(|(x, y)| *x * y).sum();
+                let product = |values: &[F]| values[0] * values[1];
+
+                let (proof, challenges, evaluations) =
+                    ComposedSumcheckProof::prove(&[a.clone(), b.clone()], 2, product, &mut Transcript::new()).unwrap();
+                let (point, final_claim) = proof.verify(claimed_sum, no_of_vars, 2, &mut Transcript::new()).unwrap();
+
+                prop_assert_eq!(&point, &challenges);
+                prop_assert_eq!(final_claim, a.evaluate(&point) * b.evaluate(&point));
+                prop_assert_eq!(evaluations, vec![a.evaluate(&point), b.evaluate(&point)]);
+
+                // a wrong sum or a tampered round is refused
+                prop_assert_eq!(proof.verify(claimed_sum + F::from(1u64), no_of_vars, 2, &mut Transcript::new()), None);
+                let mut tampered = proof;
+                tampered.round_polys[no_of_vars - 1][0] += F::from(1u64);
+                prop_assert_eq!(tampered.verify(claimed_sum, no_of_vars, 2, &mut Transcript::new()), None);
+            }
+
+            #[test]
+            fn prop_proof_serialization_round_trips(a in any::<MultilinearPolynomial<F>>()) {
+                let (proof, _, _) = ComposedSumcheckProof::prove(&[a], 1, |values: &[F]| values[0], &mut Transcript::new()).unwrap();
+
+                let mut bytes = Vec::new();
+                proof.serialize_compressed(&mut bytes).unwrap();
+
+                prop_assert_eq!(bytes.len(), proof.size_in_bytes());
+                prop_assert_eq!(ComposedSumcheckProof::<F>::deserialize_compressed(&bytes[..]).unwrap(), proof);
+            }
+        }
+    });
+}
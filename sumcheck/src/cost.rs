@@ -0,0 +1,281 @@
+use core::fmt;
+use core::ops::Add;
+
+// What one run of a prover or verifier costs, counted from the loops of this implementation rather than
+// asymptotically, so a learner can line the numbers up against the code:
+//   rounds            one per variable
+//   field_ops         additions (subtractions included), multiplications and inversions
+//   hash_invocations  challenges squeezed from the transcript, each one a hash of everything absorbed so far
+// Turning a challenge's hash into a field element is transcript work and is not in field_ops.
+//
+// With N = 2^n evaluations the plain prover does 5N - 4 additions and N - 1 multiplications: the claimed sum,
+// then per round the two half sums and the fold, over tables of N, N/2, ..., 2 entries. Its verifier does a
+// constant amount per round and then the oracle check, which folds the whole table once more. The proof itself is
+// only 2n elements, so the prover's work grows with N and the verifier's rounds with n
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CostReport {
+    pub rounds: usize,
+    pub field_ops: FieldOps,
+    pub hash_invocations: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FieldOps {
+    pub additions: usize,
+    pub multiplications: usize,
+    pub inversions: usize,
+}
+
+impl FieldOps {
+    pub fn new(additions: usize, multiplications: usize, inversions: usize) -> Self {
+        Self { additions, multiplications, inversions }
+    }
+
+    fn times(self, count: usize) -> Self {
+        Self::new(self.additions * count, self.multiplications * count, self.inversions * count)
+    }
+}
+
+impl Add for FieldOps {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.additions + other.additions,
+            self.multiplications + other.multiplications,
+            self.inversions + other.inversions,
+        )
+    }
+}
+
+impl CostReport {
+    // Prover::new and Prover::prove for a polynomial in no_of_vars variables
+    pub fn sumcheck_prover(no_of_vars: usize) -> Self {
+        let size = 1 << no_of_vars;
+        // the claimed sum, then over the rounds the half sums and folds of 2N - 2 entries in all
+        let field_ops = FieldOps::new(size, 0, 0) + fold_table(size) + FieldOps::new(2 * size - 2, 0, 0);
+        Self { rounds: no_of_vars, field_ops, hash_invocations: no_of_vars }
+    }
+
+    // Verifier::verify, the oracle check included
+    pub fn sumcheck_verifier(no_of_vars: usize) -> Self {
+        // a round evaluates its linear polynomial at 0, 1 and the challenge, and adds the first two
+        let round = fold(2).times(3) + FieldOps::new(1, 0, 0);
+        let field_ops = round.times(no_of_vars) + fold_table(1 << no_of_vars);
+        Self { rounds: no_of_vars, field_ops, hash_invocations: no_of_vars }
+    }
+
+    // ComposedSumcheckProof::prove over no_of_polynomials polynomials, where combine costs `combine` per call
+    pub fn composed_prover(no_of_vars: usize, degree: usize, no_of_polynomials: usize, combine: FieldOps) -> Self {
+        let size = 1 << no_of_vars;
+        // at every one of the degree + 1 points and every entry of the lower half, each polynomial is interpolated
+        // as low + t * (high - low), combined and added to the round's sum; over all rounds the halves hold N - 1
+        let per_entry = fold(2).times(no_of_polynomials) + combine + FieldOps::new(1, 0, 0);
+        let evaluations = per_entry.times((degree + 1) * (size - 1));
+        let field_ops = evaluations + fold_table(size).times(no_of_polynomials);
+        Self { rounds: no_of_vars, field_ops, hash_invocations: no_of_vars }
+    }
+
+    // ComposedSumcheckProof::verify, up to the final claim
+    pub fn composed_verifier(no_of_vars: usize, degree: usize) -> Self {
+        // Lagrange interpolation at the challenge from degree + 1 points: for every point the products of
+        // (x - x_j) and (x_i - x_j) over the others, one inversion, two multiplications into the sum
+        let points = degree + 1;
+        let interpolation = (FieldOps::new(2, 2, 0).times(degree) + FieldOps::new(1, 2, 1)).times(points);
+        let round = interpolation + FieldOps::new(1, 0, 0);
+        Self { rounds: no_of_vars, field_ops: round.times(no_of_vars), hash_invocations: no_of_vars }
+    }
+}
+
+// One partial evaluation of a table of `size` entries: size / 2 times low + r * (high - low)
+fn fold(size: usize) -> FieldOps {
+    FieldOps::new(size, size / 2, 0)
+}
+
+// Folding a table of `size` entries down to one value, one variable at a time
+fn fold_table(size: usize) -> FieldOps {
+    FieldOps::new(2 * size - 2, size - 1, 0)
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} rounds, {} additions, {} multiplications, {} inversions, {} hashes",
+            self.rounds,
+            self.field_ops.additions,
+            self.field_ops.multiplications,
+            self.field_ops.inversions,
+            self.hash_invocations
+        )
+    }
+}
+
+
+// The counts are taken against the std provers
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::composed::ComposedSumcheckProof;
+    use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+    use crate::sumcheck::{Prover, Verifier};
+    use ark_ff::{BigInt, Fp, FpConfig, MontBackend, SqrtPrecomputation};
+    use core::cell::Cell;
+    use core::marker::PhantomData;
+
+    // BN254 Fr with every operation counted, to hold the formulas above against what the code really does
+    struct CountingConfig;
+    type Inner = MontBackend<ark_bn254::FrConfig, 4>;
+    type Counted = Fp<CountingConfig, 4>;
+
+    thread_local! {
+        static COUNTS: Cell<FieldOps> = Cell::new(FieldOps::default());
+    }
+
+    fn count(ops: FieldOps) {
+        COUNTS.with(|counts| counts.set(counts.get() + ops));
+    }
+
+    fn inner(a: &Counted) -> Fp<Inner, 4> {
+        Fp(a.0, PhantomData)
+    }
+
+    fn outer(a: Fp<Inner, 4>) -> Counted {
+        Fp(a.0, PhantomData)
+    }
+
+    impl FpConfig<4> for CountingConfig {
+        const MODULUS: BigInt<4> = <Inner as FpConfig<4>>::MODULUS;
+        const GENERATOR: Counted = Fp(<Inner as FpConfig<4>>::GENERATOR.0, PhantomData);
+        const ZERO: Counted = Fp(<Inner as FpConfig<4>>::ZERO.0, PhantomData);
+        const ONE: Counted = Fp(<Inner as FpConfig<4>>::ONE.0, PhantomData);
+        const TWO_ADICITY: u32 = <Inner as FpConfig<4>>::TWO_ADICITY;
+        const TWO_ADIC_ROOT_OF_UNITY: Counted = Fp(<Inner as FpConfig<4>>::TWO_ADIC_ROOT_OF_UNITY.0, PhantomData);
+        const SQRT_PRECOMP: Option<SqrtPrecomputation<Counted>> = None;
+
+        fn add_assign(a: &mut Counted, b: &Counted) {
+            count(FieldOps::new(1, 0, 0));
+            *a = outer(inner(a) + inner(b));
+        }
+
+        fn sub_assign(a: &mut Counted, b: &Counted) {
+            count(FieldOps::new(1, 0, 0));
+            *a = outer(inner(a) - inner(b));
+        }
+
+        fn double_in_place(a: &mut Counted) {
+            count(FieldOps::new(1, 0, 0));
+            *a = outer(inner(a) + inner(a));
+        }
+
+        fn neg_in_place(a: &mut Counted) {
+            count(FieldOps::new(1, 0, 0));
+            *a = outer(-inner(a));
+        }
+
+        fn mul_assign(a: &mut Counted, b: &Counted) {
+            count(FieldOps::new(0, 1, 0));
+            *a = outer(inner(a) * inner(b));
+        }
+
+        fn sum_of_products<const T: usize>(a: &[Counted; T], b: &[Counted; T]) -> Counted {
+            count(FieldOps::new(T, T, 0));
+            outer((0..T).map(|i| inner(&a[i]) * inner(&b[i])).sum())
+        }
+
+        fn square_in_place(a: &mut Counted) {
+            count(FieldOps::new(0, 1, 0));
+            *a = outer(inner(a) * inner(a));
+        }
+
+        fn inverse(a: &Counted) -> Option<Counted> {
+            count(FieldOps::new(0, 0, 1));
+            <Inner as FpConfig<4>>::inverse(&inner(a)).map(outer)
+        }
+
+        fn from_bigint(other: BigInt<4>) -> Option<Counted> {
+            <Inner as FpConfig<4>>::from_bigint(other).map(outer)
+        }
+
+        fn into_bigint(other: Counted) -> BigInt<4> {
+            <Inner as FpConfig<4>>::into_bigint(inner(&other))
+        }
+    }
+
+    // Runs f and returns the field operations it did, less the ones spent turning challenges into field elements
+    fn counted<T>(challenges: usize, f: impl FnOnce() -> T) -> (T, FieldOps) {
+        let per_challenge = {
+            let mut transcript = Transcript::new();
+            COUNTS.with(|counts| counts.set(FieldOps::default()));
+            let _: Counted = transcript.random_challenge_as_field_element();
+            COUNTS.with(|counts| counts.get())
+        };
+
+        COUNTS.with(|counts| counts.set(FieldOps::default()));
+        let result = f();
+        let ops = COUNTS.with(|counts| counts.get());
+        let challenge_ops = per_challenge.times(challenges);
+        (result, FieldOps::new(
+            ops.additions - challenge_ops.additions,
+            ops.multiplications - challenge_ops.multiplications,
+            ops.inversions - challenge_ops.inversions,
+        ))
+    }
+
+    fn evaluations(no_of_vars: usize) -> Vec<Counted> {
+        (0..1u64 << no_of_vars).map(|i| Counted::from(i * i + 3)).collect()
+    }
+
+    #[test]
+    fn test_sumcheck_costs_match_the_code() {
+        for no_of_vars in 1..=5 {
+            let evaluations = evaluations(no_of_vars);
+
+            let (proof, prover_ops) = counted(no_of_vars, || Prover::new(&evaluations).prove());
+            assert_eq!(prover_ops, CostReport::sumcheck_prover(no_of_vars).field_ops);
+
+            let (verified, verifier_ops) = counted(no_of_vars, || Verifier::new().verify(proof));
+            assert!(verified);
+            assert_eq!(verifier_ops, CostReport::sumcheck_verifier(no_of_vars).field_ops);
+        }
+    }
+
+    #[test]
+    fn test_composed_costs_match_the_code() {
+        let product = |values: &[Counted]| values[0] * values[1];
+
+        for no_of_vars in 1..=4 {
+            let a = multilinear::multilinear::MultilinearPolynomial::new(&evaluations(no_of_vars));
+            let b = a.scalar_mul(Counted::from(2u64));
+            let claimed_sum: Counted = a.evaluated_values.iter().zip(&b.evaluated_values).map(|(x, y)| *x * y).sum();
+
+            let ((proof, _, _), prover_ops) =
+                counted(no_of_vars, || ComposedSumcheckProof::prove(&[a.clone(), b.clone()], 2, product, &mut Transcript::new()).unwrap());
+            assert_eq!(prover_ops, CostReport::composed_prover(no_of_vars, 2, 2, FieldOps::new(0, 1, 0)).field_ops);
+
+            let (claim, verifier_ops) = counted(no_of_vars, || proof.verify(claimed_sum, no_of_vars, 2, &mut Transcript::new()));
+            assert!(claim.is_some());
+            assert_eq!(verifier_ops, CostReport::composed_verifier(no_of_vars, 2).field_ops);
+        }
+    }
+
+    #[test]
+    fn test_proof_sizes() {
+        let evaluations = evaluations(3);
+        // the claimed sum, 8 evaluations and 3 rounds of 2, at 32 bytes each
+        assert_eq!(Prover::new(&evaluations).prove().size_in_bytes(), 15 * 32);
+
+        let a = multilinear::multilinear::MultilinearPolynomial::new(&evaluations);
+        let (proof, _, _) = ComposedSumcheckProof::prove(&[a], 2, |values| values[0] * values[0], &mut Transcript::new()).unwrap();
+        // 3 rounds of 3 elements, and a length prefix for the rounds and for each round
+        assert_eq!(proof.size_in_bytes(), 9 * 32 + 4 * 8);
+    }
+
+    #[test]
+    fn test_report_display() {
+        assert_eq!(
+            CostReport::sumcheck_prover(3).to_string(),
+            "3 rounds, 36 additions, 7 multiplications, 0 inversions, 3 hashes"
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use crate::composed::{evaluate_from_points, ComposedSumcheckProof};
+use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use crate::sumcheck::SumcheckProof;
+use ark_ff::PrimeField;
+use std::fmt;
+
+// A round by round account of a sumcheck proof, for finding out why one was rejected.
+//
+// The inspector draws the verifier's challenges with derive_challenges, so the ones it prints are the verifier's, and
+// replays its checks, but where the verifier stops at the first bad round it carries on and checks every
+// round against the claim the previous one left. The report shows, per round, the claim coming in, the round
+// polynomial's evaluations, p(0) + p(1) against that claim, the challenge and the claim it leaves, and at the end the
+// final claim against the oracle's evaluation. Display prints it as text.
+//
+// A failing round usually breaks every round after it too, since its next claim is already off, so the first
+// failure is the one to look at
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumcheckReport<F: PrimeField> {
+    pub claimed_sum: F,
+    pub expected_rounds: usize,
+    pub max_degree: usize,
+    pub rounds: Vec<RoundReport<F>>,
+    pub final_claim: F,
+    // the oracle's value at the challenges, when the caller can compute it
+    pub final_evaluation: Option<F>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundReport<F: PrimeField> {
+    pub round: usize,
+    // the claim the round must add up to, the claimed sum in round 0
+    pub claim: F,
+    // the round polynomial at 0, 1, ...
+    pub evaluations: Vec<F>,
+    pub challenge: F,
+    // the round polynomial at the challenge, the claim for the next round
+    pub next_claim: F,
+    pub failure: Option<RoundFailure>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundFailure {
+    // fewer than 2 evaluations, or more than the degree bound allows
+    Shape { evaluations: usize, max_evaluations: usize },
+    // p(0) + p(1) is not the claim
+    Sum,
+}
+
+impl <F: PrimeField>RoundReport<F> {
+    // p(0) + p(1), or None for a round with fewer than two evaluations
+    pub fn sum(&self) -> Option<F> {
+        match self.evaluations.as_slice() {
+            [at_0, at_1, ..] => Some(*at_0 + at_1),
+            _ => None,
+        }
+    }
+}
+
+impl <F: PrimeField>SumcheckReport<F> {
+    // Replays the plain prover's proof the way Verifier::verify does, with the oracle evaluated from the polynomial
+    // the proof carries
+    pub fn inspect(proof: &SumcheckProof<F>) -> Self {
+        let challenges = proof.derive_challenges(&mut Transcript::new());
+        let rounds: Vec<Vec<F>> = proof.uni_poly_for_each_round.iter().map(|round| round.evaluated_values.clone()).collect();
+
+        let mut report = Self::replay(proof.initial_claimed_sum, proof.initial_poly.no_of_vars, 1, &rounds, &challenges);
+        if rounds.len() == report.expected_rounds {
+            report.final_evaluation = Some(proof.initial_poly.evaluate(&challenges));
+        }
+        report
+    }
+
+    // Replays a composed proof the way ComposedSumcheckProof::verify does on this transcript, which must be in the
+    // state the verifier's would be in. The final check is the caller's, see with_final_evaluation
+    pub fn inspect_composed<T: FiatShamirTranscriptInterface>(
+        proof: &ComposedSumcheckProof<F>,
+        claimed_sum: F,
+        no_of_vars: usize,
+        max_degree: usize,
+        transcript: &mut T,
+    ) -> Self {
+        let challenges = proof.derive_challenges(transcript);
+        Self::replay(claimed_sum, no_of_vars, max_degree, &proof.round_polys, &challenges)
+    }
+
+    pub fn with_final_evaluation(mut self, evaluation: F) -> Self {
+        self.final_evaluation = Some(evaluation);
+        self
+    }
+
+    pub fn challenges(&self) -> Vec<F> {
+        self.rounds.iter().map(|round| round.challenge).collect()
+    }
+
+    // The first round that fails its check
+    pub fn first_failure(&self) -> Option<usize> {
+        self.rounds.iter().position(|round| round.failure.is_some())
+    }
+
+    // What the verifier would have answered, given the final evaluation
+    pub fn verified(&self) -> bool {
+        self.rounds.len() == self.expected_rounds
+            && self.first_failure().is_none()
+            && self.final_evaluation == Some(self.final_claim)
+    }
+
+    fn replay(
+        claimed_sum: F,
+        expected_rounds: usize,
+        max_degree: usize,
+        rounds: &[Vec<F>],
+        challenges: &[F],
+    ) -> Self {
+        let mut claim = claimed_sum;
+        let mut reports = Vec::with_capacity(rounds.len());
+
+        for (round, (evaluations, challenge)) in rounds.iter().zip(challenges).enumerate() {
+            let failure = if evaluations.len() < 2 || evaluations.len() > max_degree + 1 {
+                Some(RoundFailure::Shape { evaluations: evaluations.len(), max_evaluations: max_degree + 1 })
+            } else if evaluations[0] + evaluations[1] != claim {
+                Some(RoundFailure::Sum)
+            } else {
+                None
+            };
+
+            let next_claim = evaluate_from_points(evaluations, *challenge);
+            reports.push(RoundReport { round, claim, evaluations: evaluations.clone(), challenge: *challenge, next_claim, failure });
+            claim = next_claim;
+        }
+
+        Self { claimed_sum, expected_rounds, max_degree, rounds: reports, final_claim: claim, final_evaluation: None }
+    }
+}
+
+impl <F: PrimeField>fmt::Display for SumcheckReport<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "sumcheck over {} variables, degree <= {}, claimed sum {}", self.expected_rounds, self.max_degree, self.claimed_sum)?;
+        if self.rounds.len() != self.expected_rounds {
+            writeln!(f, "  wrong number of rounds: {} sent, {} expected", self.rounds.len(), self.expected_rounds)?;
+        }
+
+        for round in &self.rounds {
+            writeln!(f, "round {}", round.round)?;
+            writeln!(f, "  claim          {}", round.claim)?;
+            for (t, evaluation) in round.evaluations.iter().enumerate() {
+                writeln!(f, "  p({})           {}", t, evaluation)?;
+            }
+            match (round.failure, round.sum()) {
+                (Some(RoundFailure::Shape { evaluations, max_evaluations }), _) => {
+                    writeln!(f, "  FAILED: {} evaluations, between 2 and {} expected", evaluations, max_evaluations)?
+                }
+                (Some(RoundFailure::Sum), Some(sum)) => writeln!(f, "  FAILED: p(0) + p(1) = {} is not the claim", sum)?,
+                _ => writeln!(f, "  p(0) + p(1) matches the claim")?,
+            }
+            writeln!(f, "  challenge      {}", round.challenge)?;
+            writeln!(f, "  next claim     {}", round.next_claim)?;
+        }
+
+        writeln!(f, "final claim      {}", self.final_claim)?;
+        match self.final_evaluation {
+            Some(evaluation) if evaluation == self.final_claim => writeln!(f, "oracle           {} matches", evaluation)?,
+            Some(evaluation) => writeln!(f, "oracle           {} FAILED: not the final claim", evaluation)?,
+            None => writeln!(f, "oracle           not checked")?,
+        }
+
+        match self.first_failure() {
+            Some(round) => write!(f, "rejected at round {}", round),
+            None if self.verified() => write!(f, "verified"),
+            None => write!(f, "rejected"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sumcheck::{Prover, Verifier};
+    use ark_bn254::Fr;
+    use multilinear::multilinear::MultilinearPolynomial;
+
+    fn evaluations() -> Vec<Fr> {
+        [0u64, 0, 0, 3, 0, 0, 2, 5].iter().map(|value| Fr::from(*value)).collect()
+    }
+
+    fn proof() -> SumcheckProof<Fr> {
+        Prover::new(&evaluations()).prove()
+    }
+
+    // p(1) of one round moved by one
+    fn tampered(round: usize) -> SumcheckProof<Fr> {
+        let mut proof = proof();
+        proof.uni_poly_for_each_round[round].evaluated_values[1] += Fr::from(1);
+        proof
+    }
+
+    #[test]
+    fn test_honest_proof_reads_as_verified() {
+        let report = SumcheckReport::inspect(&proof());
+
+        assert!(report.verified());
+        assert_eq!(report.first_failure(), None);
+        assert_eq!(report.rounds.len(), 3);
+        assert_eq!(report.rounds[0].claim, Fr::from(10));
+        assert_eq!(report.rounds[1].claim, report.rounds[0].next_claim);
+
+        // the challenges are the verifier's: the oracle agrees at them
+        let polynomial = MultilinearPolynomial::new(&evaluations());
+        assert_eq!(polynomial.evaluate(&report.challenges()), report.final_claim);
+        assert!(report.to_string().ends_with("verified"));
+    }
+
+    #[test]
+    fn test_report_points_at_the_tampered_round() {
+        for round in 0..3 {
+            let report = SumcheckReport::inspect(&tampered(round));
+            assert!(!Verifier::new().verify(tampered(round)));
+            assert!(!report.verified());
+            assert_eq!(report.first_failure(), Some(round));
+            assert_eq!(report.rounds[round].failure, Some(RoundFailure::Sum));
+            assert!(report.to_string().contains(&format!("rejected at round {}", round)));
+        }
+
+        // a wrong claimed sum fails round 0, and a round with a third evaluation fails on its shape
+        let mut wrong_sum = proof();
+        wrong_sum.initial_claimed_sum += Fr::from(1);
+        assert_eq!(SumcheckReport::inspect(&wrong_sum).first_failure(), Some(0));
+        let mut too_long = proof();
+        too_long.uni_poly_for_each_round[2].evaluated_values.push(Fr::from(0));
+        assert_eq!(
+            SumcheckReport::inspect(&too_long).rounds[2].failure,
+            Some(RoundFailure::Shape { evaluations: 3, max_evaluations: 2 })
+        );
+    }
+
+    #[test]
+    fn test_composed_report_with_the_caller_final_check() {
+        let a = MultilinearPolynomial::new(&evaluations());
+        let b = MultilinearPolynomial::new(&evaluations().iter().rev().copied().collect::<Vec<Fr>>());
+        let product = |values: &[Fr]| values[0] * values[1];
+        let claimed_sum: Fr = a.evaluated_values.iter().zip(&b.evaluated_values).map(|(x, y)| *x * y).sum();
+
+        let (proof, challenges, evaluations) = ComposedSumcheckProof::prove(&[a, b], 2, product, &mut Transcript::new()).unwrap();
+        let report = SumcheckReport::inspect_composed(&proof, claimed_sum, 3, 2, &mut Transcript::new());
+
+        assert_eq!(report.challenges(), challenges);
+        assert_eq!(report.final_evaluation, None);
+        assert!(!report.verified());
+        let report = report.with_final_evaluation(product(&evaluations));
+        assert!(report.verified());
+
+        let wrong = SumcheckReport::inspect_composed(&proof, claimed_sum, 3, 1, &mut Transcript::new());
+        assert_eq!(wrong.first_failure(), Some(0));
+        assert!(wrong.to_string().contains("FAILED: 3 evaluations, between 2 and 2 expected"));
+    }
+}
@@ -0,0 +1,41 @@
+use core::fmt;
+
+// What the provers refuse to prove: inputs of the wrong shape, which the caller got wrong rather than the protocol.
+// The verifiers never return these, a proof that does not check out is just rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SumcheckError {
+    // a composed sumcheck of no polynomials, or a padded one of no tables
+    NoPolynomials,
+    // a polynomial in `provided` variables next to ones in `expected`, or against a claim about `expected`
+    MismatchedVariables { expected: usize, provided: usize },
+    // a table of `provided` entries next to ones of `expected`
+    MismatchedLengths { expected: usize, provided: usize },
+    // a grand product or a lookup over a number of values that is not a power of two
+    NotPowerOfTwo { len: usize },
+    // sigma has to map every one of the `expected` points of the hypercube
+    WrongPermutationLength { expected: usize, provided: usize },
+    // witness[position] is not in the lookup table
+    NotInTable { position: usize },
+    // the trace does not have one row per gate of the circuit
+    WrongTraceLength,
+}
+
+impl fmt::Display for SumcheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SumcheckError::NoPolynomials => write!(f, "no polynomials to prove a sum of"),
+            SumcheckError::MismatchedVariables { expected, provided } => {
+                write!(f, "polynomial in {} variables, {} expected", provided, expected)
+            }
+            SumcheckError::MismatchedLengths { expected, provided } => write!(f, "table of {} entries, {} expected", provided, expected),
+            SumcheckError::NotPowerOfTwo { len } => write!(f, "{} values, a power of two is needed", len),
+            SumcheckError::WrongPermutationLength { expected, provided } => {
+                write!(f, "sigma maps {} points of a hypercube of {}", provided, expected)
+            }
+            SumcheckError::NotInTable { position } => write!(f, "witness value {} is not in the table", position),
+            SumcheckError::WrongTraceLength => write!(f, "the trace does not have one row per gate"),
+        }
+    }
+}
+
+impl core::error::Error for SumcheckError {}
@@ -0,0 +1,207 @@
+use crate::composed::{append_field_elements, ComposedSumcheckProof};
+use crate::deserialize_vec;
+use crate::encoding::field_to_bytes;
+use crate::error::SumcheckError;
+use crate::fiat_shamir::FiatShamirTranscriptInterface;
+use alloc::vec;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate};
+use multilinear::multilinear::MultilinearPolynomial;
+
+// Grand product argument: proves that the 2^k evaluations of a multilinear polynomial v multiply to `product`.
+//
+// The product is computed by a binary tree: layer k is v itself and layer i has 2^i entries
+// v_i[x] = v_{i+1}[x] * v_{i+1}[x + 2^i], so in multilinear form v_i(x) = v_{i+1}(0, x) * v_{i+1}(1, x), and v_0 = product.
+// Going down from the root, a claim v_i(r) = c is reduced with the degree 3 sumcheck
+// c = sum over x of eq(r, x) * v_{i+1}(0, x) * v_{i+1}(1, x)
+// which ends at a point r' where the prover sends left = v_{i+1}(0, r') and right = v_{i+1}(1, r'). The verifier folds
+// the two into a single claim at a random t: v_{i+1}(t, r') = left + t * (right - left).
+// After the last layer the verifier holds a claim v(point) = value about the input polynomial, which the caller must check
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize)]
+pub struct GrandProductProof<F: PrimeField> {
+    pub product: F,
+    pub layers: Vec<GrandProductLayer<F>>,
+}
+
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GrandProductLayer<F: PrimeField> {
+    pub sumcheck: ComposedSumcheckProof<F>,
+    pub left: F,
+    pub right: F,
+}
+
+impl <F: PrimeField>GrandProductProof<F> {
+    // Returns the proof and the point and value of the final claim about `values`, whose number must be a power of two
+    pub fn prove<T: FiatShamirTranscriptInterface>(values: &[F], transcript: &mut T) -> Result<(Self, Vec<F>, F), SumcheckError> {
+        if !values.len().is_power_of_two() {
+            return Err(SumcheckError::NotPowerOfTwo { len: values.len() });
+        }
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("grand_product_prove", size = values.len()).entered();
+
+        // tree[i] is layer i, tree[k] the input values
+        let mut tree = vec![values.to_vec()];
+        while tree[0].len() > 1 {
+            let half = tree[0].len() / 2;
+            let parent: Vec<F> = (0..half).map(|x| tree[0][x] * tree[0][x + half]).collect();
+            tree.insert(0, parent);
+        }
+
+        let product = tree[0][0];
+        transcript.append(&field_to_bytes(product));
+
+        let mut point: Vec<F> = Vec::new();
+        let mut claim = product;
+        let mut layers = Vec::with_capacity(tree.len() - 1);
+
+        for child in &tree[1..] {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("grand_product_layer", size = child.len()).entered();
+
+            let half = child.len() / 2;
+            let eq = MultilinearPolynomial::new(&MultilinearPolynomial::eq_evaluations(&point));
+            let low = MultilinearPolynomial::new(&child[..half]);
+            let high = MultilinearPolynomial::new(&child[half..]);
+
+            let (sumcheck, challenges, evaluations) =
+                ComposedSumcheckProof::prove(&[eq, low, high], 3, |values: &[F]| values[0] * values[1] * values[2], transcript)?;
+            let (left, right) = (evaluations[1], evaluations[2]);
+
+            append_field_elements(transcript, &[left, right]);
+            let t: F = transcript.random_challenge_as_field_element();
+
+            claim = left + t * (right - left);
+            point = core::iter::once(t).chain(challenges).collect();
+            layers.push(GrandProductLayer { sumcheck, left, right });
+        }
+
+        Ok((Self { product, layers }, point, claim))
+    }
+
+    // Checks every layer for a polynomial in no_of_vars variables.
+    // Returns the point and value of the final claim about the input polynomial, or None if a layer fails
+    pub fn verify<T: FiatShamirTranscriptInterface>(&self, no_of_vars: usize, transcript: &mut T) -> Option<(Vec<F>, F)> {
+        if self.layers.len() != no_of_vars {
+            return None;
+        }
+
+        transcript.append(&field_to_bytes(self.product));
+
+        let mut point: Vec<F> = Vec::new();
+        let mut claim = self.product;
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            let (challenges, final_claim) = layer.sumcheck.verify(claim, i, 3, transcript)?;
+
+            if final_claim != MultilinearPolynomial::eq_evaluate(&point, &challenges) * layer.left * layer.right {
+                return None;
+            }
+
+            let t = fold_challenge(transcript, layer);
+
+            claim = layer.left + t * (layer.right - layer.left);
+            point = core::iter::once(t).chain(challenges).collect();
+        }
+
+        Some((point, claim))
+    }
+
+    // The challenges verify draws for this proof, without checking anything: for every layer the challenges of its
+    // sumcheck followed by the challenge t that folds left and right. transcript must be in the state the
+    // verifier's is in
+    pub fn derive_challenges<T: FiatShamirTranscriptInterface>(&self, transcript: &mut T) -> Vec<Vec<F>> {
+        transcript.append(&field_to_bytes(self.product));
+
+        self.layers
+            .iter()
+            .map(|layer| {
+                let mut challenges = layer.sumcheck.derive_challenges(transcript);
+                challenges.push(fold_challenge(transcript, layer));
+                challenges
+            })
+            .collect()
+    }
+}
+
+// The challenge t folding a layer's two claims into one
+fn fold_challenge<F: PrimeField, T: FiatShamirTranscriptInterface>(transcript: &mut T, layer: &GrandProductLayer<F>) -> F {
+    append_field_elements(transcript, &[layer.left, layer.right]);
+    transcript.random_challenge_as_field_element()
+}
+
+// Same encoding as the derive, read through deserialize_vec so a forged number of layers cannot allocate
+impl <F: PrimeField>CanonicalDeserialize for GrandProductProof<F> {
+    fn deserialize_with_mode<R: Read>(mut reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        let product = F::deserialize_with_mode(&mut reader, compress, validate)?;
+        let layers = deserialize_vec(&mut reader, compress, validate, |reader| {
+            GrandProductLayer::deserialize_with_mode(reader, compress, validate)
+        })?;
+        Ok(Self { product, layers })
+    }
+}
+
+impl <F: PrimeField>Valid for GrandProductProof<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.product.check()?;
+        self.layers.check()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fiat_shamir::Transcript;
+    use ark_bn254::Fr;
+    use proptest::prelude::*;
+
+    fn values() -> Vec<Fr> {
+        [3u64, 5, 2, 7, 1, 4, 6, 9].iter().map(|value| Fr::from(*value)).collect()
+    }
+
+    #[test]
+    fn test_grand_product() {
+        let values = values();
+
+        let (proof, point, value) = GrandProductProof::prove(&values, &mut Transcript::new()).unwrap();
+        assert_eq!(proof.product, Fr::from(3 * 5 * 2 * 7 * 4 * 6 * 9));
+
+        let (verifier_point, verifier_value) = proof.verify(3, &mut Transcript::new()).unwrap();
+        assert_eq!((verifier_point.clone(), verifier_value), (point, value));
+
+        // the final claim is about the input polynomial, checked here directly
+        assert_eq!(MultilinearPolynomial::new(&values).evaluate(&verifier_point), verifier_value);
+    }
+
+    #[test]
+    fn test_wrong_product_is_caught() {
+        let values = values();
+
+        let (mut proof, _, _) = GrandProductProof::prove(&values, &mut Transcript::new()).unwrap();
+        proof.product += Fr::from(1);
+        assert_eq!(proof.verify(3, &mut Transcript::new()), None);
+
+        // a wrong layer evaluation no longer matches the end of that layer's sumcheck
+        let (mut proof, _, _) = GrandProductProof::prove(&values, &mut Transcript::new()).unwrap();
+        proof.layers[2].right += Fr::from(1);
+        assert_eq!(proof.verify(3, &mut Transcript::new()), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_grand_product_verifies(poly in any::<MultilinearPolynomial<Fr>>()) {
+            let values = poly.evaluated_values.clone();
+            let (proof, _, _) = GrandProductProof::prove(&values, &mut Transcript::new()).unwrap();
+            prop_assert_eq!(proof.product, values.iter().product::<Fr>());
+
+            let (point, value) = proof.verify(poly.no_of_vars, &mut Transcript::new()).unwrap();
+            prop_assert_eq!(poly.evaluate(&point), value);
+
+            let mut tampered = proof;
+            tampered.product += Fr::from(1);
+            prop_assert_eq!(tampered.verify(poly.no_of_vars, &mut Transcript::new()), None);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::commitment_to_bytes;
+use crate::composed::{append_field_elements, ComposedSumcheckProof};
+use crate::error::SumcheckError;
+use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use crate::permutation::PermutationProof;
+use arithmetization::plonkish::{gate_constraint, MultilinearColumns, PlonkishCircuit, Trace, GATE_CONSTRAINT_DEGREE};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use commitments::pcs::MultilinearPcs;
+use multilinear::multilinear::MultilinearPolynomial;
+
+// A small HyperPlonk-style SNARK for Plonkish circuits, built from the pieces of this crate.
+//
+// The trace is exported as the multilinear columns of arithmetization::plonkish::MultilinearColumns, and the prover
+// commits to the witness w = a || b || c || 0 alone: the selectors and the wiring are the circuit's, which the
+// verifier knows. The proof is three arguments on one transcript:
+//   - the wiring, a permutation argument of w against itself under the copy constraint permutation, whose commitment
+//     to f is the commitment to w
+//   - the gates, a zerocheck: after drawing r the prover runs the sumcheck of eq(r, x) * gate_constraint(...) against
+//     0, degree 4, which ends at a point z where it sends a(z), b(z), c(z)
+//   - the openings of w at (0, 0, z), (0, 1, z) and (1, 0, z), the points where w takes the values of a, b and c
+// The verifier evaluates the selectors at z itself, O(n) work a preprocessed commitment to them would remove, the
+// same trade the permutation argument makes for sigma.
+//
+// Nothing here fixes the circuit's outputs: public values are pinned with rows of Selectors::constant, the way
+// Circuit::to_plonkish pins the input constants. Start the transcript with new_with_statement to bind the proof to
+// its instance
+pub struct HyperPlonkProof<F: PrimeField, S: MultilinearPcs<F>> {
+    pub wiring: PermutationProof<F, S>,
+    pub zerocheck: ComposedSumcheckProof<F>,
+    // a(z), b(z), c(z)
+    pub evaluations: [F; 3],
+    pub openings: Vec<S::OpeningProof>,
+}
+
+impl <F: PrimeField, S>HyperPlonkProof<F, S>
+where
+    S: MultilinearPcs<F>,
+    S::Commitment: CanonicalSerialize,
+{
+    // params must support no_of_vars + 2 variables, where 2^no_of_vars is the number of rows padded to a power of two
+    pub fn prove(
+        params: &S::Params,
+        circuit: &PlonkishCircuit<F>,
+        trace: &Trace<F>,
+        transcript: &mut Transcript,
+    ) -> Result<Self, SumcheckError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("hyperplonk_prove", rows = circuit.no_of_rows()).entered();
+
+        let columns = MultilinearColumns::new(circuit, trace).ok_or(SumcheckError::WrongTraceLength)?;
+        let witness = MultilinearPolynomial::new(&columns.witness());
+        let sigma = columns.wiring(circuit);
+
+        let wiring = PermutationProof::prove(params, &witness, &witness, Some(&sigma), transcript)?;
+
+        let r = draw_point(transcript, columns.no_of_vars);
+        let mut polynomials = vec![MultilinearPolynomial::new(&MultilinearPolynomial::eq_evaluations(&r))];
+        polynomials.extend(columns.columns().map(MultilinearPolynomial::new));
+
+        let (zerocheck, z, values) = ComposedSumcheckProof::prove(
+            &polynomials,
+            GATE_CONSTRAINT_DEGREE + 1,
+            |values: &[F]| values[0] * gate_constraint(&core::array::from_fn(|column| values[column + 1])),
+            transcript,
+        )?;
+
+        let evaluations = [values[6], values[7], values[8]];
+        append_field_elements(transcript, &evaluations);
+        let openings = witness_points(&z).iter().map(|point| S::open(params, &witness, point, transcript).1).collect();
+
+        Ok(Self { wiring, zerocheck, evaluations, openings })
+    }
+
+    pub fn verify(&self, params: &S::Params, circuit: &PlonkishCircuit<F>, transcript: &mut Transcript) -> bool {
+        // the columns with an all-zero witness: the selectors and the wiring are all the verifier reads
+        let no_of_rows = circuit.no_of_rows();
+        let empty = Trace { a: vec![F::zero(); no_of_rows], b: vec![F::zero(); no_of_rows], c: vec![F::zero(); no_of_rows] };
+        let preprocessed = MultilinearColumns::new(circuit, &empty).expect("the empty trace has one row per gate");
+        let no_of_vars = preprocessed.no_of_vars;
+
+        // the permutation is of w against itself, so both of its commitments are the commitment to w
+        let commitment = &self.wiring.f_commitment;
+        if commitment_to_bytes(commitment) != commitment_to_bytes(&self.wiring.g_commitment) || self.openings.len() != 3 {
+            return false;
+        }
+        if !self.wiring.verify(params, no_of_vars + 2, Some(&preprocessed.wiring(circuit)), transcript) {
+            return false;
+        }
+
+        let r = draw_point(transcript, no_of_vars);
+        let (z, final_claim) = match self.zerocheck.verify(F::zero(), no_of_vars, GATE_CONSTRAINT_DEGREE + 1, transcript) {
+            Some(claim) => claim,
+            None => return false,
+        };
+
+        let selectors = preprocessed.columns();
+        let values: [F; 8] = core::array::from_fn(|column| match column {
+            0..5 => MultilinearPolynomial::new(selectors[column]).evaluate(&z),
+            _ => self.evaluations[column - 5],
+        });
+        if final_claim != MultilinearPolynomial::eq_evaluate(&r, &z) * gate_constraint(&values) {
+            return false;
+        }
+
+        append_field_elements(transcript, &self.evaluations);
+        witness_points(&z)
+            .iter()
+            .zip(self.evaluations.iter().zip(&self.openings))
+            .all(|(point, (value, opening))| S::verify(params, commitment, point, *value, opening, transcript))
+    }
+}
+
+fn draw_point<F: PrimeField>(transcript: &mut Transcript, no_of_vars: usize) -> Vec<F> {
+    (0..no_of_vars).map(|_| transcript.random_challenge_as_field_element()).collect()
+}
+
+// The points of w holding a(z), b(z) and c(z): the two leading variables pick the column
+fn witness_points<F: PrimeField>(z: &[F]) -> [Vec<F>; 3] {
+    [(F::zero(), F::zero()), (F::zero(), F::one()), (F::one(), F::zero())]
+        .map(|(high, low)| [high, low].into_iter().chain(z.iter().copied()).collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arithmetization::plonkish::{Cell, Column, Selectors};
+    use ark_bn254::{Bn254, Fr};
+    use commitments::pcs::{PolynomialCommitmentScheme, PstPcs};
+
+    type Proof = HyperPlonkProof<Fr, PstPcs<Bn254>>;
+
+    // (x + y) * y = out with out pinned to 35, three rows padded to four
+    fn circuit() -> PlonkishCircuit<Fr> {
+        let mut circuit = PlonkishCircuit::new();
+        let sum = circuit.add_gate(Selectors::addition());
+        let product = circuit.add_gate(Selectors::multiplication());
+        let out = circuit.add_gate(Selectors::constant(Fr::from(35)));
+
+        circuit.copy(Cell::new(Column::C, sum), Cell::new(Column::A, product));
+        circuit.copy(Cell::new(Column::B, sum), Cell::new(Column::B, product));
+        circuit.copy(Cell::new(Column::C, product), Cell::new(Column::A, out));
+        circuit
+    }
+
+    fn trace(x: u64, y: u64) -> Trace<Fr> {
+        let mut trace = Trace::new();
+        trace.push_row(Fr::from(x), Fr::from(y), Fr::from(x + y));
+        trace.push_row(Fr::from(x + y), Fr::from(y), Fr::from((x + y) * y));
+        trace.push_row(Fr::from((x + y) * y), Fr::from(0), Fr::from(0));
+        trace
+    }
+
+    fn prove(trace: &Trace<Fr>) -> (<PstPcs<Bn254> as PolynomialCommitmentScheme<Fr>>::Params, Proof) {
+        let params = PstPcs::<Bn254>::setup(4, &mut ark_std::test_rng());
+        let proof = Proof::prove(&params, &circuit(), trace, &mut Transcript::new_with_statement(b"hyperplonk", b"out = 35")).unwrap();
+        (params, proof)
+    }
+
+    #[test]
+    fn test_satisfying_trace_verifies() {
+        // (2 + 5) * 5 = 35
+        assert!(circuit().is_satisfied(&trace(2, 5)));
+
+        let (params, proof) = prove(&trace(2, 5));
+        assert!(proof.verify(&params, &circuit(), &mut Transcript::new_with_statement(b"hyperplonk", b"out = 35")));
+        assert!(!proof.verify(&params, &circuit(), &mut Transcript::new_with_statement(b"hyperplonk", b"out = 36")));
+    }
+
+    #[test]
+    fn test_broken_gate_or_wiring_is_rejected() {
+        let verify = |trace: &Trace<Fr>| {
+            let (params, proof) = prove(trace);
+            proof.verify(&params, &circuit(), &mut Transcript::new_with_statement(b"hyperplonk", b"out = 35"))
+        };
+
+        // the multiplication is off by one
+        let mut wrong_gate = trace(2, 5);
+        wrong_gate.c[1] += Fr::from(1);
+        assert!(!verify(&wrong_gate));
+
+        // every gate holds, but the addition is 3 + 4 and the multiplication reads y = 5
+        let mut wrong_wiring = trace(2, 5);
+        wrong_wiring.a[0] = Fr::from(3);
+        wrong_wiring.b[0] = Fr::from(4);
+        assert_eq!(circuit().first_unsatisfied_gate(&wrong_wiring), None);
+        assert!(!verify(&wrong_wiring));
+    }
+
+    #[test]
+    fn test_tampered_proof_is_rejected() {
+        let statement = || Transcript::new_with_statement(b"hyperplonk", b"out = 35");
+        let (params, mut proof) = prove(&trace(2, 5));
+        proof.evaluations[2] += Fr::from(1);
+        assert!(!proof.verify(&params, &circuit(), &mut statement()));
+
+        // a commitment to another witness in the g slot no longer proves w is wired to itself
+        let (params, mut proof) = prove(&trace(2, 5));
+        let other = MultilinearPolynomial::new(&vec![Fr::from(1); 16]);
+        proof.wiring.g_commitment = PstPcs::<Bn254>::commit(&params, &other);
+        assert!(!proof.verify(&params, &circuit(), &mut statement()));
+    }
+}
@@ -1,2 +1,69 @@
+// Without the default std feature only the plain, composed and grand product sumchecks are built, on alloc,
+// so their verifiers can run where there is no std. The arguments over commitments stay behind std
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 // Define your module structure
-pub mod fiat_shamir; 
\ No newline at end of file
+// The Fiat-Shamir transcript lives in its own crate so commitment schemes can share it
+pub use transcript::fiat_shamir;
+pub use transcript::encoding;
+pub mod abi;
+pub mod binary;
+#[cfg(feature = "std")]
+pub mod checkpoint;
+pub mod composed;
+pub mod cost;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod debug;
+pub mod grand_product;
+#[cfg(feature = "std")]
+pub mod hyperplonk;
+#[cfg(feature = "std")]
+pub mod lookup;
+// provers that cheat in one place each, for negative tests of verifiers
+#[cfg(all(feature = "std", any(test, feature = "malicious")))]
+pub mod malicious;
+#[cfg(feature = "std")]
+pub mod permutation;
+#[cfg(feature = "std")]
+pub mod proof_format;
+// the composed sumcheck of a VirtualPolynomial, with closed-form eq factors
+pub mod structured;
+pub mod sumcheck;
+#[cfg(feature = "std")]
+pub mod univariate;
+
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Validate};
+
+#[deprecated(note = "use encoding::field_to_bytes, the same big-endian bytes")]
+pub fn f_to_bytes<F: PrimeField>(field_element: F) -> Vec<u8> {
+    encoding::field_to_bytes(field_element)
+}
+
+pub fn commitment_to_bytes<C: CanonicalSerialize>(commitment: &C) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    commitment
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec cannot fail");
+    bytes
+}
+
+// Reads a length prefixed vector the way Vec's CanonicalDeserialize does, without reserving the length up front:
+// eight bytes claiming 2^60 elements would abort the process there, here they fail at the end of the input.
+// Proofs come from untrusted bytes, so their deserializers read vectors with this
+pub fn deserialize_vec<T, R, E>(reader: &mut R, compress: Compress, validate: Validate, mut element: E) -> Result<Vec<T>, SerializationError>
+where
+    R: Read,
+    E: FnMut(&mut R) -> Result<T, SerializationError>,
+{
+    let len = u64::deserialize_with_mode(&mut *reader, compress, validate)?;
+    let mut elements = Vec::new();
+    for _ in 0..len {
+        elements.push(element(reader)?);
+    }
+    Ok(elements)
+}
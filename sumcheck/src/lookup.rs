@@ -0,0 +1,256 @@
+use crate::commitment_to_bytes;
+use crate::composed::ComposedSumcheckProof;
+use crate::encoding::field_to_bytes;
+use crate::error::SumcheckError;
+use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use commitments::pcs::MultilinearPcs;
+use multilinear::multilinear::MultilinearPolynomial;
+
+// LogUp lookup argument: proves that every value of a committed witness column w appears in a public table t.
+//
+// With m(y) the number of times t(y) occurs in w, the lookup holds exactly when, as rational functions of X,
+// sum over x of 1 / (X - w(x)) = sum over y of m(y) / (X - t(y))
+// and after committing to w and m the verifier checks it at a random gamma. The prover commits to the fractions
+// h_w(x) = 1 / (gamma - w(x)) and h_t(y) = m(y) / (gamma - t(y)) and claims both sum to the same S.
+// One sumcheck per side proves the sum and that the fraction is well formed, batched with a random lambda
+// and a zero check at a random point r:
+// S = sum over x of h_w(x) + lambda * eq(r, x) * (h_w(x) * (gamma - w(x)) - 1)
+// S = sum over y of h_t(y) + lambda * eq(r', y) * (h_t(y) * (gamma - t(y)) - m(y))
+// Both have degree 3. The verifier evaluates eq and the public table itself and checks everything else against
+// openings of the commitments at the points the sumchecks end at
+pub struct LookupProof<F: PrimeField, S: MultilinearPcs<F>> {
+    pub witness_commitment: S::Commitment,
+    pub multiplicity_commitment: S::Commitment,
+    pub witness_inverse_commitment: S::Commitment,
+    pub table_inverse_commitment: S::Commitment,
+    pub sum: F,
+    pub witness_sumcheck: ComposedSumcheckProof<F>,
+    pub table_sumcheck: ComposedSumcheckProof<F>,
+    pub witness_opening: Opening<F, S>,
+    pub witness_inverse_opening: Opening<F, S>,
+    pub multiplicity_opening: Opening<F, S>,
+    pub table_inverse_opening: Opening<F, S>,
+}
+
+pub struct Opening<F: PrimeField, S: MultilinearPcs<F>> {
+    pub value: F,
+    pub proof: S::OpeningProof,
+}
+
+impl <F: PrimeField, S>LookupProof<F, S>
+where
+    S: MultilinearPcs<F>,
+    S::Commitment: CanonicalSerialize,
+{
+    // Both the witness and the table need a power of two length, and every witness value has to be in the table
+    pub fn prove(params: &S::Params, witness: &[F], table: &[F], transcript: &mut Transcript) -> Result<Self, SumcheckError> {
+        if let Some(len) = [witness.len(), table.len()].into_iter().find(|len| !len.is_power_of_two()) {
+            return Err(SumcheckError::NotPowerOfTwo { len });
+        }
+
+        // every witness value counts towards the first table entry holding it
+        let mut multiplicities = vec![F::zero(); table.len()];
+        for (position, value) in witness.iter().enumerate() {
+            let entry = table.iter().position(|entry| entry == value).ok_or(SumcheckError::NotInTable { position })?;
+            multiplicities[entry] += F::one();
+        }
+
+        let witness = MultilinearPolynomial::new(witness);
+        let multiplicities = MultilinearPolynomial::new(&multiplicities);
+
+        let witness_commitment = S::commit(params, &witness);
+        let multiplicity_commitment = S::commit(params, &multiplicities);
+        let gamma = bind_commitments::<F, S>(transcript, &[&witness_commitment, &multiplicity_commitment]);
+
+        let witness_inverse: Vec<F> = witness
+            .evaluated_values
+            .iter()
+            .map(|value| (gamma - value).inverse().expect("gamma hits a witness value with negligible probability"))
+            .collect();
+        let table_inverse: Vec<F> = table
+            .iter()
+            .zip(multiplicities.evaluated_values.iter())
+            .map(|(entry, multiplicity)| *multiplicity * (gamma - entry).inverse().expect("gamma hits a table value with negligible probability"))
+            .collect();
+        let sum: F = witness_inverse.iter().sum();
+
+        let witness_inverse = MultilinearPolynomial::new(&witness_inverse);
+        let table_inverse = MultilinearPolynomial::new(&table_inverse);
+
+        let witness_inverse_commitment = S::commit(params, &witness_inverse);
+        let table_inverse_commitment = S::commit(params, &table_inverse);
+        bind_commitments::<F, S>(transcript, &[&witness_inverse_commitment, &table_inverse_commitment]);
+        transcript.append(&field_to_bytes(sum));
+
+        let lambda: F = transcript.random_challenge_as_field_element();
+        let witness_r = challenges(transcript, witness.no_of_vars);
+        let table_r = challenges(transcript, table_inverse.no_of_vars);
+
+        let witness_eq = MultilinearPolynomial::new(&MultilinearPolynomial::eq_evaluations(&witness_r));
+        let (witness_sumcheck, witness_point, _) = ComposedSumcheckProof::prove(
+            &[witness_inverse.clone(), witness_eq, witness.clone()],
+            3,
+            |values: &[F]| values[0] + lambda * values[1] * (values[0] * (gamma - values[2]) - F::one()),
+            transcript,
+        )?;
+
+        let table_polynomial = MultilinearPolynomial::new(table);
+        let table_eq = MultilinearPolynomial::new(&MultilinearPolynomial::eq_evaluations(&table_r));
+        let (table_sumcheck, table_point, _) = ComposedSumcheckProof::prove(
+            &[table_inverse.clone(), table_eq, table_polynomial, multiplicities.clone()],
+            3,
+            |values: &[F]| values[0] + lambda * values[1] * (values[0] * (gamma - values[2]) - values[3]),
+            transcript,
+        )?;
+
+        let mut open = |polynomial: &MultilinearPolynomial<F>, point: &[F]| {
+            let (value, proof) = S::open(params, polynomial, point, transcript);
+            Opening { value, proof }
+        };
+        let witness_opening = open(&witness, &witness_point);
+        let witness_inverse_opening = open(&witness_inverse, &witness_point);
+        let multiplicity_opening = open(&multiplicities, &table_point);
+        let table_inverse_opening = open(&table_inverse, &table_point);
+
+        Ok(Self {
+            witness_commitment,
+            multiplicity_commitment,
+            witness_inverse_commitment,
+            table_inverse_commitment,
+            sum,
+            witness_sumcheck,
+            table_sumcheck,
+            witness_opening,
+            witness_inverse_opening,
+            multiplicity_opening,
+            table_inverse_opening,
+        })
+    }
+
+    pub fn verify(&self, params: &S::Params, witness_no_of_vars: usize, table: &[F], transcript: &mut Transcript) -> bool {
+        if !table.len().is_power_of_two() {
+            return false;
+        }
+        let table_polynomial = MultilinearPolynomial::new(table);
+
+        let gamma = bind_commitments::<F, S>(transcript, &[&self.witness_commitment, &self.multiplicity_commitment]);
+        bind_commitments::<F, S>(transcript, &[&self.witness_inverse_commitment, &self.table_inverse_commitment]);
+        transcript.append(&field_to_bytes(self.sum));
+
+        let lambda: F = transcript.random_challenge_as_field_element();
+        let witness_r = challenges(transcript, witness_no_of_vars);
+        let table_r = challenges(transcript, table_polynomial.no_of_vars);
+
+        let (witness_point, witness_claim) = match self.witness_sumcheck.verify(self.sum, witness_no_of_vars, 3, transcript) {
+            Some(result) => result,
+            None => return false,
+        };
+        let (table_point, table_claim) = match self.table_sumcheck.verify(self.sum, table_polynomial.no_of_vars, 3, transcript) {
+            Some(result) => result,
+            None => return false,
+        };
+
+        // the final claims, from the openings and the values the verifier computes itself
+        let witness_inverse = self.witness_inverse_opening.value;
+        let witness_expected = witness_inverse
+            + lambda * MultilinearPolynomial::eq_evaluate(&witness_r, &witness_point)
+                * (witness_inverse * (gamma - self.witness_opening.value) - F::one());
+
+        let table_inverse = self.table_inverse_opening.value;
+        let table_expected = table_inverse
+            + lambda * MultilinearPolynomial::eq_evaluate(&table_r, &table_point)
+                * (table_inverse * (gamma - table_polynomial.evaluate(&table_point)) - self.multiplicity_opening.value);
+
+        if witness_claim != witness_expected || table_claim != table_expected {
+            return false;
+        }
+
+        let mut check = |commitment: &S::Commitment, point: &[F], opening: &Opening<F, S>| {
+            S::verify(params, commitment, point, opening.value, &opening.proof, transcript)
+        };
+        check(&self.witness_commitment, &witness_point, &self.witness_opening)
+            && check(&self.witness_inverse_commitment, &witness_point, &self.witness_inverse_opening)
+            && check(&self.multiplicity_commitment, &table_point, &self.multiplicity_opening)
+            && check(&self.table_inverse_commitment, &table_point, &self.table_inverse_opening)
+    }
+}
+
+// Appends the commitments and returns a challenge derived from them
+fn bind_commitments<F: PrimeField, S>(transcript: &mut Transcript, commitments: &[&S::Commitment]) -> F
+where
+    S: MultilinearPcs<F>,
+    S::Commitment: CanonicalSerialize,
+{
+    for commitment in commitments {
+        transcript.append(&commitment_to_bytes(*commitment));
+    }
+    transcript.random_challenge_as_field_element()
+}
+
+fn challenges<F: PrimeField>(transcript: &mut Transcript, count: usize) -> Vec<F> {
+    (0..count).map(|_| transcript.random_challenge_as_field_element()).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arithmetization::plonkish::{Cell, Column, PlonkishCircuit, Selectors, Trace};
+    use ark_bn254::{Bn254, Fr};
+    use commitments::pcs::{PolynomialCommitmentScheme, PstPcs};
+
+    fn range_table(bits: usize) -> Vec<Fr> {
+        (0..1u64 << bits).map(Fr::from).collect()
+    }
+
+    #[test]
+    fn test_lookup_into_range_table() {
+        let params = PstPcs::<Bn254>::setup(4, &mut ark_std::test_rng());
+        let table = range_table(4);
+        let witness: Vec<Fr> = [3u64, 15, 0, 3].iter().map(|value| Fr::from(*value)).collect();
+
+        let proof = LookupProof::<Fr, PstPcs<Bn254>>::prove(&params, &witness, &table, &mut Transcript::new()).unwrap();
+        assert!(proof.verify(&params, 2, &table, &mut Transcript::new()));
+
+        // the same proof does not verify against another table
+        let shifted: Vec<Fr> = (1..17u64).map(Fr::from).collect();
+        assert!(!proof.verify(&params, 2, &shifted, &mut Transcript::new()));
+
+        // a value outside the range has no proof
+        let out_of_range = vec![Fr::from(3), Fr::from(16)];
+        assert_eq!(
+            LookupProof::<Fr, PstPcs<Bn254>>::prove(&params, &out_of_range, &table, &mut Transcript::new()).err(),
+            Some(SumcheckError::NotInTable { position: 1 })
+        );
+    }
+
+    #[test]
+    fn test_range_check_of_a_plonkish_trace() {
+        let params = PstPcs::<Bn254>::setup(3, &mut ark_std::test_rng());
+
+        // a + b = c with all three cells range checked against 0..8
+        let mut circuit = PlonkishCircuit::new();
+        circuit.lookup_table = range_table(3);
+        let row = circuit.add_gate(Selectors::addition());
+        for column in [Column::A, Column::B, Column::C] {
+            circuit.add_lookup(Cell::new(column, row));
+        }
+
+        let mut trace = Trace::new();
+        trace.push_row(Fr::from(2), Fr::from(5), Fr::from(7));
+        assert!(circuit.is_satisfied(&trace));
+
+        let column = circuit.lookup_column(&trace);
+        let proof = LookupProof::<Fr, PstPcs<Bn254>>::prove(&params, &column, &circuit.lookup_table, &mut Transcript::new()).unwrap();
+        assert!(proof.verify(&params, 2, &circuit.lookup_table, &mut Transcript::new()));
+
+        // 4 + 5 = 9 satisfies the gate but not the range check
+        let mut overflow = Trace::new();
+        overflow.push_row(Fr::from(4), Fr::from(5), Fr::from(9));
+        assert!(!circuit.is_satisfied(&overflow));
+        let column = circuit.lookup_column(&overflow);
+        assert!(LookupProof::<Fr, PstPcs<Bn254>>::prove(&params, &column, &circuit.lookup_table, &mut Transcript::new()).is_err());
+    }
+}
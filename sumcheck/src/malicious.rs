@@ -0,0 +1,128 @@
+use crate::encoding::field_to_bytes;
+use crate::fiat_shamir::FiatShamirTranscriptInterface;
+use crate::sumcheck::{absorb_statement, Prover, SumcheckProof};
+use ark_ff::PrimeField;
+use multilinear::multilinear::MultilinearPolynomial;
+use sha3::{Digest, Keccak256};
+
+// Ways for a sumcheck prover to cheat, one at a time, for negative tests of the verifier.
+//
+// Each is a prover that runs the honest protocol except at the one place it deviates, through the round hook of
+// Prover, so a verifier that accepts any of them has a hole exactly there. The module is built for the crate's own
+// tests and, with the malicious feature, for other crates that want to test a verifier wrapped around this one
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fault<F: PrimeField> {
+    // claims the sum plus delta and sends the honest rounds, which do not add up to the claim
+    WrongClaimedSum(F),
+    // claims the sum plus delta and moves both values of the first round polynomial by delta / 2 so that they add up
+    // to it; the polynomial then misses the table at the challenge and a later round or the oracle check catches it
+    ConsistentWrongClaimedSum(F),
+    // adds delta to the value at 1 of the polynomial of this round before sending it
+    TamperedRound { round: usize, delta: F },
+    // absorbs the claimed sum before the polynomial's digest, so the prover's challenges are not the verifier's
+    ReorderedAppends,
+    // leaves this round out of the proof and the transcript, binding its variable to a challenge nothing was sent for
+    SkippedRound(usize),
+}
+
+// A proof of the sum of the table by a prover committing fault, for Verifier::new
+pub fn prove_with_fault<F: PrimeField>(initial_poly_evaluation: &[F], fault: &Fault<F>) -> SumcheckProof<F> {
+    let mut prover = Prover::new(initial_poly_evaluation);
+    let two_inverse = F::from(2u64).inverse().expect("2 is invertible in an odd prime field");
+
+    match fault {
+        Fault::WrongClaimedSum(delta) | Fault::ConsistentWrongClaimedSum(delta) => prover.initial_claimed_sum += delta,
+        _ => {}
+    }
+
+    if let Fault::ReorderedAppends = fault {
+        prover.transcript.append(&field_to_bytes(prover.initial_claimed_sum));
+        prover.transcript.append(&prover.initial_poly.digest(Keccak256::new()));
+    } else {
+        absorb_statement(&mut prover.transcript, &prover.initial_poly, prover.initial_claimed_sum);
+    }
+
+    prover.prove_rounds_with(prover.initial_poly.clone(), &mut |round, round_poly| {
+        let shift = |round_poly: MultilinearPolynomial<F>, at_0: F, at_1: F| {
+            let values = &round_poly.evaluated_values;
+            Some(MultilinearPolynomial::new(&[values[0] + at_0, values[1] + at_1]))
+        };
+
+        match fault {
+            Fault::ConsistentWrongClaimedSum(delta) if round == 0 => shift(round_poly, *delta * two_inverse, *delta * two_inverse),
+            Fault::TamperedRound { round: tampered, delta } if round == *tampered => shift(round_poly, F::zero(), *delta),
+            Fault::SkippedRound(skipped) if round == *skipped => None,
+            _ => Some(round_poly),
+        }
+    });
+
+    SumcheckProof {
+        initial_claimed_sum: prover.initial_claimed_sum,
+        initial_poly: prover.initial_poly.clone(),
+        uni_poly_for_each_round: prover.uni_poly_for_each_round,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sumcheck::Verifier;
+    use ark_bn254::Fr;
+
+    fn evaluations() -> Vec<Fr> {
+        (0..16u64).map(|i| Fr::from(i * i + 2)).collect()
+    }
+
+    fn faults() -> Vec<Fault<Fr>> {
+        let mut faults = vec![
+            Fault::WrongClaimedSum(Fr::from(1)),
+            Fault::ConsistentWrongClaimedSum(Fr::from(1)),
+            Fault::ConsistentWrongClaimedSum(-Fr::from(1000)),
+            Fault::ReorderedAppends,
+        ];
+        for round in 0..4 {
+            faults.push(Fault::TamperedRound { round, delta: Fr::from(3) });
+            faults.push(Fault::SkippedRound(round));
+        }
+        faults
+    }
+
+    #[test]
+    fn test_every_fault_is_rejected() {
+        for fault in faults() {
+            let proof = prove_with_fault(&evaluations(), &fault);
+            assert!(!Verifier::new().verify(proof), "{:?} was accepted", fault);
+        }
+    }
+
+    #[test]
+    fn test_faulty_provers_are_honest_elsewhere() {
+        // a delta of zero is no fault at all, so the provers deviate only where they say
+        for fault in [
+            Fault::WrongClaimedSum(Fr::from(0)),
+            Fault::ConsistentWrongClaimedSum(Fr::from(0)),
+            Fault::TamperedRound { round: 2, delta: Fr::from(0) },
+        ] {
+            let proof = prove_with_fault(&evaluations(), &fault);
+            let honest = Prover::new(&evaluations()).prove();
+            assert_eq!(proof.uni_poly_for_each_round, honest.uni_poly_for_each_round);
+            assert!(Verifier::new().verify(proof));
+        }
+    }
+
+    #[test]
+    fn test_consistent_cheat_passes_its_own_round() {
+        // the first round adds up to the false claim, it is the second round or the oracle check that fails
+        let delta = Fr::from(10);
+        let proof = prove_with_fault(&evaluations(), &Fault::ConsistentWrongClaimedSum(delta));
+        let first = &proof.uni_poly_for_each_round[0].evaluated_values;
+        assert_eq!(first[0] + first[1], proof.initial_claimed_sum);
+        assert_eq!(proof.initial_claimed_sum, evaluations().iter().sum::<Fr>() + delta);
+        assert!(!Verifier::new().verify(proof));
+
+        // a skipped round leaves a proof one round short
+        let proof = prove_with_fault(&evaluations(), &Fault::SkippedRound(1));
+        assert_eq!(proof.uni_poly_for_each_round.len(), 3);
+    }
+}
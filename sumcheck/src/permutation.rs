@@ -0,0 +1,200 @@
+use crate::commitment_to_bytes;
+use crate::error::SumcheckError;
+use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use crate::grand_product::GrandProductProof;
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use commitments::pcs::MultilinearPcs;
+use multilinear::multilinear::MultilinearPolynomial;
+
+// Permutation argument for two committed multilinear polynomials f and g over the same hypercube.
+//
+// With a public permutation sigma of the hypercube it proves g(x) = f(sigma(x)) for every x, i.e. that the multisets
+// {(f(x), x)} and {(g(x), sigma(x))} are equal. After committing to f and g the verifier draws beta and gamma and
+// the prover shows, with one grand product each, that
+// product of (f(x) + beta * x + gamma) = product of (g(x) + beta * sigma(x) + gamma)
+// Taking f = g = w with sigma the wiring of a circuit turns this into the copy constraint check w(x) = w(sigma(x)).
+// Without sigma the beta terms are dropped and only f and g being permutations of each other is proven.
+//
+// Each grand product ends in a claim about its leaves at a random point p. The verifier evaluates the index
+// and sigma polynomials at p itself (sigma is public, a preprocessed commitment would avoid this O(n) step),
+// which turns the claim into a value of f(p) or g(p) that is checked against the commitment
+pub struct PermutationProof<F: PrimeField, S: MultilinearPcs<F>> {
+    pub f_commitment: S::Commitment,
+    pub g_commitment: S::Commitment,
+    pub f_product: GrandProductProof<F>,
+    pub g_product: GrandProductProof<F>,
+    pub f_opening: S::OpeningProof,
+    pub g_opening: S::OpeningProof,
+}
+
+impl <F: PrimeField, S>PermutationProof<F, S>
+where
+    S: MultilinearPcs<F>,
+    S::Commitment: CanonicalSerialize,
+{
+    // sigma[x] is the hypercube index g(x) takes its value from, None for a plain multiset check. The shapes are
+    // checked before anything is committed to, so an error leaves the transcript as it was
+    pub fn prove(
+        params: &S::Params,
+        f: &MultilinearPolynomial<F>,
+        g: &MultilinearPolynomial<F>,
+        sigma: Option<&[usize]>,
+        transcript: &mut Transcript,
+    ) -> Result<Self, SumcheckError> {
+        if f.no_of_vars != g.no_of_vars {
+            return Err(SumcheckError::MismatchedVariables { expected: f.no_of_vars, provided: g.no_of_vars });
+        }
+        let size = f.evaluated_values.len();
+        if let Some(sigma) = sigma.filter(|sigma| sigma.len() != size) {
+            return Err(SumcheckError::WrongPermutationLength { expected: size, provided: sigma.len() });
+        }
+
+        let f_commitment = S::commit(params, f);
+        let g_commitment = S::commit(params, g);
+        let (beta, gamma) = bind_commitments::<F, S>(transcript, &f_commitment, &g_commitment, sigma.is_some());
+
+        let index: Vec<F> = (0..size).map(|x| F::from(x as u64)).collect();
+        let permuted_index: Vec<F> = match sigma {
+            Some(sigma) => sigma.iter().map(|x| F::from(*x as u64)).collect(),
+            None => index.clone(),
+        };
+
+        let f_leaves = leaves(&f.evaluated_values, &index, beta, gamma);
+        let g_leaves = leaves(&g.evaluated_values, &permuted_index, beta, gamma);
+
+        let (f_product, f_point, _) = GrandProductProof::prove(&f_leaves, transcript)?;
+        let (g_product, g_point, _) = GrandProductProof::prove(&g_leaves, transcript)?;
+
+        let (_, f_opening) = S::open(params, f, &f_point, transcript);
+        let (_, g_opening) = S::open(params, g, &g_point, transcript);
+
+        Ok(Self { f_commitment, g_commitment, f_product, g_product, f_opening, g_opening })
+    }
+
+    pub fn verify(&self, params: &S::Params, no_of_vars: usize, sigma: Option<&[usize]>, transcript: &mut Transcript) -> bool {
+        if let Some(sigma) = sigma {
+            if sigma.len() != 1 << no_of_vars {
+                return false;
+            }
+        }
+
+        let (beta, gamma) = bind_commitments::<F, S>(transcript, &self.f_commitment, &self.g_commitment, sigma.is_some());
+
+        if self.f_product.product != self.g_product.product {
+            return false;
+        }
+
+        let (f_point, f_leaf) = match self.f_product.verify(no_of_vars, transcript) {
+            Some(claim) => claim,
+            None => return false,
+        };
+        let (g_point, g_leaf) = match self.g_product.verify(no_of_vars, transcript) {
+            Some(claim) => claim,
+            None => return false,
+        };
+
+        let f_index = index_evaluation(&f_point);
+        let g_index = match sigma {
+            Some(sigma) => {
+                let sigma: Vec<F> = sigma.iter().map(|x| F::from(*x as u64)).collect();
+                MultilinearPolynomial::new(&sigma).evaluate(&g_point)
+            }
+            None => index_evaluation(&g_point),
+        };
+
+        // leaf(p) = polynomial(p) + beta * index(p) + gamma
+        let f_value = f_leaf - beta * f_index - gamma;
+        let g_value = g_leaf - beta * g_index - gamma;
+
+        S::verify(params, &self.f_commitment, &f_point, f_value, &self.f_opening, transcript)
+            && S::verify(params, &self.g_commitment, &g_point, g_value, &self.g_opening, transcript)
+    }
+}
+
+// Appends both commitments and derives beta and gamma. Without sigma beta is zero
+fn bind_commitments<F: PrimeField, S>(
+    transcript: &mut Transcript,
+    f_commitment: &S::Commitment,
+    g_commitment: &S::Commitment,
+    uses_sigma: bool,
+) -> (F, F)
+where
+    S: MultilinearPcs<F>,
+    S::Commitment: CanonicalSerialize,
+{
+    transcript.append(&commitment_to_bytes(f_commitment));
+    transcript.append(&commitment_to_bytes(g_commitment));
+
+    let beta: F = transcript.random_challenge_as_field_element();
+    let gamma: F = transcript.random_challenge_as_field_element();
+
+    (if uses_sigma { beta } else { F::zero() }, gamma)
+}
+
+fn leaves<F: PrimeField>(values: &[F], index: &[F], beta: F, gamma: F) -> Vec<F> {
+    values.iter().zip(index.iter()).map(|(value, index)| *value + beta * index + gamma).collect()
+}
+
+// The multilinear extension of x -> x, with variable 0 as the most significant bit
+fn index_evaluation<F: PrimeField>(point: &[F]) -> F {
+    point.iter().fold(F::zero(), |index, bit| index.double() + bit)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+    use commitments::pcs::{PolynomialCommitmentScheme, PstPcs};
+
+    fn polynomial(values: &[u64]) -> MultilinearPolynomial<Fr> {
+        let values: Vec<Fr> = values.iter().map(|value| Fr::from(*value)).collect();
+        MultilinearPolynomial::new(&values)
+    }
+
+    #[test]
+    fn test_permutation_with_sigma() {
+        let params = PstPcs::<Bn254>::setup(3, &mut ark_std::test_rng());
+        let f = polynomial(&[10, 11, 12, 13, 14, 15, 16, 17]);
+        let sigma = [3, 0, 7, 1, 2, 6, 4, 5];
+        // g(x) = f(sigma(x))
+        let g = polynomial(&[13, 10, 17, 11, 12, 16, 14, 15]);
+
+        let proof = PermutationProof::<Fr, PstPcs<Bn254>>::prove(&params, &f, &g, Some(&sigma), &mut Transcript::new()).unwrap();
+        assert!(proof.verify(&params, 3, Some(&sigma), &mut Transcript::new()));
+
+        // the same values under a different permutation are rejected
+        let other_sigma = [0, 3, 7, 1, 2, 6, 4, 5];
+        assert!(!proof.verify(&params, 3, Some(&other_sigma), &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_copy_constraints() {
+        let params = PstPcs::<Bn254>::setup(2, &mut ark_std::test_rng());
+        // cells 0, 2 and 3 are wired together
+        let sigma = [2, 1, 3, 0];
+
+        let wired = polynomial(&[7, 1, 7, 7]);
+        let proof = PermutationProof::<Fr, PstPcs<Bn254>>::prove(&params, &wired, &wired, Some(&sigma), &mut Transcript::new()).unwrap();
+        assert!(proof.verify(&params, 2, Some(&sigma), &mut Transcript::new()));
+
+        let broken = polynomial(&[7, 1, 7, 8]);
+        let proof = PermutationProof::<Fr, PstPcs<Bn254>>::prove(&params, &broken, &broken, Some(&sigma), &mut Transcript::new()).unwrap();
+        assert!(!proof.verify(&params, 2, Some(&sigma), &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_multiset_equality() {
+        let params = PstPcs::<Bn254>::setup(2, &mut ark_std::test_rng());
+        let f = polynomial(&[4, 9, 2, 9]);
+
+        let shuffled = polynomial(&[9, 2, 9, 4]);
+        let proof = PermutationProof::<Fr, PstPcs<Bn254>>::prove(&params, &f, &shuffled, None, &mut Transcript::new()).unwrap();
+        assert!(proof.verify(&params, 2, None, &mut Transcript::new()));
+
+        let different = polynomial(&[9, 2, 4, 4]);
+        let proof = PermutationProof::<Fr, PstPcs<Bn254>>::prove(&params, &f, &different, None, &mut Transcript::new()).unwrap();
+        assert!(!proof.verify(&params, 2, None, &mut Transcript::new()));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::composed::ComposedSumcheckProof;
+use crate::encoding::{bytes_to_field, field_to_bytes};
+use crate::grand_product::{GrandProductLayer, GrandProductProof};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde_json::{json, Value};
+
+pub const PROOF_FORMAT_VERSION: u8 = 1;
+
+// A versioned wire format for the composed sumcheck and grand product proofs, so a proof made in one process can be
+// checked in another. Each kind of proof implements ProofFormat with its own KIND byte and name.
+//
+// to_bytes writes
+//   version (1 byte) | kind (1 byte) | proof (compressed canonical serialization)
+// and to_json writes {"version": 1, "kind": "...", "proof": {...}} where field elements are big endian hex strings,
+// so a product, a layer's round polynomials or its final evaluations can be read off by eye.
+// Decoding returns None for a different version or kind, trailing bytes, or a field element above the modulus
+pub trait ProofFormat: Sized + CanonicalSerialize + CanonicalDeserialize {
+    const KIND: u8;
+    const KIND_NAME: &'static str;
+
+    fn to_json_value(&self) -> Value;
+
+    fn from_json_value(value: &Value) -> Option<Self>;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![PROOF_FORMAT_VERSION, Self::KIND];
+        self.serialize_compressed(&mut bytes).expect("serializing into a Vec cannot fail");
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [version, kind, rest @ ..] if *version == PROOF_FORMAT_VERSION && *kind == Self::KIND => {
+                let mut rest = rest;
+                let proof = Self::deserialize_compressed(&mut rest).ok()?;
+                rest.is_empty().then_some(proof)
+            }
+            _ => None,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        json!({ "version": PROOF_FORMAT_VERSION, "kind": Self::KIND_NAME, "proof": self.to_json_value() }).to_string()
+    }
+
+    fn from_json(text: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(text).ok()?;
+        if value["version"].as_u64()? != PROOF_FORMAT_VERSION as u64 || value["kind"].as_str()? != Self::KIND_NAME {
+            return None;
+        }
+        Self::from_json_value(&value["proof"])
+    }
+}
+
+impl <F: PrimeField>ProofFormat for ComposedSumcheckProof<F> {
+    const KIND: u8 = 1;
+    const KIND_NAME: &'static str = "composed_sumcheck";
+
+    fn to_json_value(&self) -> Value {
+        let round_polys: Vec<Value> = self.round_polys.iter().map(|poly| field_elements_to_json(poly)).collect();
+        json!({ "round_polys": round_polys })
+    }
+
+    fn from_json_value(value: &Value) -> Option<Self> {
+        let round_polys = value["round_polys"]
+            .as_array()?
+            .iter()
+            .map(field_elements_from_json)
+            .collect::<Option<Vec<Vec<F>>>>()?;
+        Some(Self { round_polys })
+    }
+}
+
+impl <F: PrimeField>ProofFormat for GrandProductProof<F> {
+    const KIND: u8 = 2;
+    const KIND_NAME: &'static str = "grand_product";
+
+    fn to_json_value(&self) -> Value {
+        let layers: Vec<Value> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                json!({
+                    "sumcheck": layer.sumcheck.to_json_value(),
+                    "left": field_element_to_json(layer.left),
+                    "right": field_element_to_json(layer.right),
+                })
+            })
+            .collect();
+        json!({ "product": field_element_to_json(self.product), "layers": layers })
+    }
+
+    fn from_json_value(value: &Value) -> Option<Self> {
+        let layers = value["layers"]
+            .as_array()?
+            .iter()
+            .map(|layer| {
+                Some(GrandProductLayer {
+                    sumcheck: ComposedSumcheckProof::from_json_value(&layer["sumcheck"])?,
+                    left: field_element_from_json(&layer["left"])?,
+                    right: field_element_from_json(&layer["right"])?,
+                })
+            })
+            .collect::<Option<Vec<GrandProductLayer<F>>>>()?;
+        Some(Self { product: field_element_from_json(&value["product"])?, layers })
+    }
+}
+
+// A field element as a 0x prefixed big endian hex string, the form every element takes in the JSON format
+pub fn field_element_to_json<F: PrimeField>(element: F) -> Value {
+    let hex: String = field_to_bytes(element).iter().map(|byte| format!("{:02x}", byte)).collect();
+    Value::String(format!("0x{}", hex))
+}
+
+fn field_elements_to_json<F: PrimeField>(elements: &[F]) -> Value {
+    Value::Array(elements.iter().map(|element| field_element_to_json(*element)).collect())
+}
+
+// The inverse of field_element_to_json.
+// Only the canonical encoding is accepted: reducing a value above the modulus would let two strings name one proof
+pub fn field_element_from_json<F: PrimeField>(value: &Value) -> Option<F> {
+    let hex = value.as_str()?.strip_prefix("0x")?;
+    if hex.len() != 2 * field_to_bytes(F::zero()).len() || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    bytes_to_field(&bytes)
+}
+
+fn field_elements_from_json<F: PrimeField>(value: &Value) -> Option<Vec<F>> {
+    value.as_array()?.iter().map(field_element_from_json).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+    use ark_bn254::Fr;
+    use ark_ff::BigInteger;
+
+    fn proof() -> GrandProductProof<Fr> {
+        let values: Vec<Fr> = [3u64, 5, 2, 7, 4, 6, 9, 1].iter().map(|value| Fr::from(*value)).collect();
+        GrandProductProof::prove(&values, &mut Transcript::new()).unwrap().0
+    }
+
+    #[test]
+    fn test_binary_round_trip_still_verifies() {
+        let proof = proof();
+        let bytes = proof.to_bytes();
+        assert_eq!(&bytes[..2], &[PROOF_FORMAT_VERSION, GrandProductProof::<Fr>::KIND]);
+
+        // a verifier that only ever sees the bytes reaches the same final claim
+        let decoded = GrandProductProof::<Fr>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        assert_eq!(decoded.verify(3, &mut Transcript::new()), proof.verify(3, &mut Transcript::new()));
+        assert!(decoded.verify(3, &mut Transcript::new()).is_some());
+
+        let layer = &proof.layers[1].sumcheck;
+        assert_eq!(ComposedSumcheckProof::<Fr>::from_bytes(&layer.to_bytes()).as_ref(), Some(layer));
+    }
+
+    #[test]
+    fn test_json_round_trip_still_verifies() {
+        let proof = proof();
+        let json = proof.to_json();
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["kind"], "grand_product");
+        assert_eq!(value["proof"]["layers"].as_array().unwrap().len(), 3);
+        assert_eq!(value["proof"]["product"], field_element_to_json(Fr::from(3 * 5 * 2 * 7 * 4 * 6 * 9)));
+
+        let decoded = GrandProductProof::<Fr>::from_json(&json).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify(3, &mut Transcript::new()).is_some());
+    }
+
+    #[test]
+    fn test_malformed_proofs_are_rejected() {
+        let proof = proof();
+        let bytes = proof.to_bytes();
+
+        let mut future = bytes.clone();
+        future[0] = PROOF_FORMAT_VERSION + 1;
+        assert_eq!(GrandProductProof::<Fr>::from_bytes(&future), None);
+
+        // the kind byte keeps a sumcheck proof from being read as a grand product proof
+        assert_eq!(GrandProductProof::<Fr>::from_bytes(&proof.layers[0].sumcheck.to_bytes()), None);
+        assert_eq!(GrandProductProof::<Fr>::from_bytes(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(GrandProductProof::<Fr>::from_bytes(&[bytes.clone(), vec![0]].concat()), None);
+        assert_eq!(GrandProductProof::<Fr>::from_bytes(&[]), None);
+
+        // a length claiming far more layers or rounds than the input holds fails instead of allocating them
+        let forged = [&bytes[..2 + 32], &u64::MAX.to_le_bytes()[..]].concat();
+        assert_eq!(GrandProductProof::<Fr>::from_bytes(&forged), None);
+        let forged = [&[PROOF_FORMAT_VERSION, ComposedSumcheckProof::<Fr>::KIND][..], &(1u64 << 60).to_le_bytes()].concat();
+        assert_eq!(ComposedSumcheckProof::<Fr>::from_bytes(&forged), None);
+
+        let json = proof.to_json().replace("\"version\":1", "\"version\":2");
+        assert_eq!(GrandProductProof::<Fr>::from_json(&json), None);
+
+        // the modulus itself is above every canonical field element
+        let modulus: String = Fr::MODULUS.to_bytes_be().iter().map(|byte| format!("{:02x}", byte)).collect();
+        let mut value: Value = serde_json::from_str(&proof.to_json()).unwrap();
+        value["proof"]["product"] = Value::String(format!("0x{}", modulus));
+        assert_eq!(GrandProductProof::<Fr>::from_json(&value.to_string()), None);
+
+        // a signed byte would be a second spelling of the same element
+        let product = field_element_to_json(proof.product).as_str().unwrap().replacen("0x0", "0x+", 1);
+        value["proof"]["product"] = Value::String(product);
+        assert_eq!(GrandProductProof::<Fr>::from_json(&value.to_string()), None);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::composed::{evaluate_from_points, round_challenge, ComposedSumcheckProof};
+use crate::error::SumcheckError;
+use crate::fiat_shamir::FiatShamirTranscriptInterface;
+use alloc::vec;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use multilinear::multilinear::MultilinearPolynomial;
+use multilinear::virtual_poly::{eq1, ClosedForm, VirtualPolynomial};
+
+// Sumcheck of a VirtualPolynomial, with the rounds of a closed-form eq factor computed rather than summed.
+//
+// For eq(r, x)·g(x), e.g. a zerocheck, the round polynomial after binding the first i variables to ρ is
+//   s_i(t) = scale_i · eq1(r_i, t) · h_i(t),   h_i(t) = sum over x' of eq(r_{>i}, x')·g(ρ, t, x')
+// with scale_i the product of eq1(r_j, ρ_j) over the bound variables. h_i has the degree of g, one less than the
+// product, so the prover evaluates g at one point fewer per entry, against the eq table of the remaining coordinates
+// built once per round at half the size, and never folds the eq table itself. The rounds are the same polynomials as
+// ComposedSumcheckProof::prove sends for the same tables and expression, the proof is byte for byte the same, and the
+// verifier does not know which prover ran. Without an eq factor, or with one the rest also reads, the plain prover
+// runs
+impl <F: PrimeField>ComposedSumcheckProof<F> {
+    // Returns the proof, the challenges and the evaluations of every polynomial at the challenges, as prove does
+    pub fn prove_virtual<T: FiatShamirTranscriptInterface>(
+        polynomial: &VirtualPolynomial<F>,
+        transcript: &mut T,
+    ) -> Result<(Self, Vec<F>, Vec<F>), SumcheckError> {
+        let Some((eq_index, ClosedForm::Eq { point, scale }, rest)) = polynomial.eq_factor() else {
+            return Self::prove(&polynomial.tables(), polynomial.degree(), |values| polynomial.combine(values), transcript);
+        };
+
+        let no_of_vars = point.len();
+        let degree = polynomial.degree();
+        let rest_degree = rest.degree();
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("eq_factored_sumcheck_prove", no_of_vars, degree).entered();
+
+        // the eq slot stays an empty table, rest never reads it
+        let mut current: Vec<Vec<F>> = polynomial
+            .mles
+            .iter()
+            .enumerate()
+            .map(|(index, (_, mle))| if index == eq_index { Vec::new() } else { mle.evaluated_values.clone() })
+            .collect();
+        let mut scale = *scale;
+        let mut round_polys = Vec::with_capacity(no_of_vars);
+        let mut challenges = Vec::with_capacity(no_of_vars);
+        let mut values = vec![F::zero(); current.len()];
+
+        for round in 0..no_of_vars {
+            let half = 1usize << (no_of_vars - round - 1);
+            let eq_rest = MultilinearPolynomial::eq_evaluations(&point[round + 1..]);
+
+            let h: Vec<F> = (0..=rest_degree)
+                .map(|t| {
+                    let t = F::from(t as u64);
+                    (0..half)
+                        .map(|j| {
+                            for (value, table) in values.iter_mut().zip(current.iter()) {
+                                if !table.is_empty() {
+                                    *value = table[j] + t * (table[j + half] - table[j]);
+                                }
+                            }
+                            eq_rest[j] * rest.evaluate(&values)
+                        })
+                        .sum()
+                })
+                .collect();
+
+            let round_poly: Vec<F> = (0..=degree)
+                .map(|t| {
+                    let h_t = if t <= rest_degree { h[t] } else { evaluate_from_points(&h, F::from(t as u64)) };
+                    scale * eq1(point[round], F::from(t as u64)) * h_t
+                })
+                .collect();
+
+            let challenge = round_challenge(transcript, &round_poly);
+
+            for table in current.iter_mut().filter(|table| !table.is_empty()) {
+                *table = MultilinearPolynomial::partial_evaluate(table, 0, challenge).evaluated_values;
+            }
+            scale *= eq1(point[round], challenge);
+
+            round_polys.push(round_poly);
+            challenges.push(challenge);
+        }
+
+        let final_evaluations = current
+            .iter()
+            .map(|table| if table.is_empty() { scale } else { table[0] })
+            .collect();
+
+        Ok((Self { round_polys }, challenges, final_evaluations))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composed::SumcheckClaim;
+    use crate::fiat_shamir::Transcript;
+    use ark_bn254::Fr;
+    use multilinear::virtual_poly::Expression;
+
+    fn table(no_of_vars: usize, seed: u64) -> MultilinearPolynomial<Fr> {
+        let values: Vec<Fr> = (0..1u64 << no_of_vars).map(|i| Fr::from(i * i * seed + seed * 7 + 1)).collect();
+        MultilinearPolynomial::new(&values)
+    }
+
+    // eq(r, x)·(a(x)·b(x) - c(x)), a zerocheck of the gate a·b = c
+    fn zerocheck(no_of_vars: usize) -> VirtualPolynomial<Fr> {
+        let r: Vec<Fr> = (0..no_of_vars as u64).map(|i| Fr::from(i * 31 + 4)).collect();
+        let mut polynomial = VirtualPolynomial::new(no_of_vars);
+        let a = polynomial.add_mle("a", table(no_of_vars, 2)).unwrap();
+        let b = polynomial.add_mle("b", table(no_of_vars, 3)).unwrap();
+        let c = polynomial.add_mle("c", table(no_of_vars, 5)).unwrap();
+        let eq = polynomial.add_eq("eq", &r).unwrap();
+        polynomial.set_expression(eq * (a * b + Expression::Constant(-Fr::from(1)) * c));
+        polynomial
+    }
+
+    #[test]
+    fn test_eq_factored_rounds_match_the_plain_prover() {
+        for no_of_vars in 1..6 {
+            let polynomial = zerocheck(no_of_vars);
+            let (fast, fast_challenges, fast_values) =
+                ComposedSumcheckProof::prove_virtual(&polynomial, &mut Transcript::new()).unwrap();
+            let (plain, challenges, values) = ComposedSumcheckProof::prove(
+                &polynomial.tables(),
+                polynomial.degree(),
+                |values| polynomial.combine(values),
+                &mut Transcript::new(),
+            ).unwrap();
+
+            assert_eq!(fast, plain);
+            assert_eq!(fast_challenges, challenges);
+            assert_eq!(fast_values, values);
+        }
+    }
+
+    #[test]
+    fn test_eq_factored_proof_verifies() {
+        let polynomial = zerocheck(4);
+        let claim = SumcheckClaim::new(polynomial.sum(), 4, polynomial.degree());
+        let (proof, challenges, values) = ComposedSumcheckProof::prove_virtual(&polynomial, &mut Transcript::new()).unwrap();
+
+        let (verifier_challenges, final_claim) = proof.verify_claim(&claim, &mut Transcript::new()).unwrap();
+        assert_eq!(verifier_challenges, challenges);
+        assert_eq!(final_claim, polynomial.combine(&values));
+    }
+
+    #[test]
+    fn test_without_an_eq_factor_the_plain_prover_runs() {
+        let mut polynomial = VirtualPolynomial::new(3);
+        let a = polynomial.add_mle("a", table(3, 2)).unwrap();
+        let b = polynomial.add_mle("b", table(3, 3)).unwrap();
+        polynomial.set_expression(a.clone() * b + a);
+
+        let (proof, _, _) = ComposedSumcheckProof::prove_virtual(&polynomial, &mut Transcript::new()).unwrap();
+        let (plain, _, _) =
+            ComposedSumcheckProof::prove(&polynomial.tables(), 2, |values| polynomial.combine(values), &mut Transcript::new()).unwrap();
+        assert_eq!(proof, plain);
+    }
+
+    #[test]
+    fn test_eq_read_beyond_its_factor_runs_the_plain_prover() {
+        // eq·(eq + g) is degree 2 in eq, which the closed form rounds would take as degree 1
+        let r = [Fr::from(4), Fr::from(35), Fr::from(66)];
+        let mut polynomial = VirtualPolynomial::new(3);
+        let g = polynomial.add_mle("g", table(3, 2)).unwrap();
+        let eq = polynomial.add_eq("eq", &r).unwrap();
+        polynomial.set_expression(eq.clone() * (eq + g));
+
+        let claim = SumcheckClaim::new(polynomial.sum(), 3, polynomial.degree());
+        let (proof, challenges, values) = ComposedSumcheckProof::prove_virtual(&polynomial, &mut Transcript::new()).unwrap();
+        let (plain, _, _) =
+            ComposedSumcheckProof::prove(&polynomial.tables(), 2, |values| polynomial.combine(values), &mut Transcript::new()).unwrap();
+        assert_eq!(proof, plain);
+
+        let (verifier_challenges, final_claim) = proof.verify_claim(&claim, &mut Transcript::new()).unwrap();
+        assert_eq!(verifier_challenges, challenges);
+        assert_eq!(final_claim, polynomial.combine(&values));
+    }
+}
@@ -0,0 +1,686 @@
+// The plain prover and Verifier::verify only need alloc. The variants over a commitment scheme need std, as the
+// commitments crate does
+use multilinear::multilinear::MultilinearPolynomial;
+#[cfg(feature = "std")]
+use commitments::pcs::{MultilinearPcs, PolynomialCommitmentScheme};
+use crate::fiat_shamir::{
+    Transcript,
+    FiatShamirTranscriptInterface
+};
+#[cfg(feature = "std")]
+use crate::commitment_to_bytes;
+use crate::encoding::field_to_bytes;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+#[cfg(feature = "std")]
+use ark_serialize::CanonicalSerialize;
+#[cfg(feature = "std")]
+use ark_std::rand::RngCore;
+use core::marker::PhantomData;
+use core::mem;
+use sha3::{Digest, Keccak256};
+
+// What the prover does with the polynomial of a round before sending it, given the round's index: an honest prover
+// sends it as it is, a hook returning None leaves the round out of the proof and the transcript
+pub(crate) type RoundHook<'a, F> = dyn FnMut(usize, MultilinearPolynomial<F>) -> Option<MultilinearPolynomial<F>> + 'a;
+
+// Define a struct to represent a sumcheck prover that is generating the proof
+pub struct Prover<F: PrimeField> {
+    pub initial_poly: MultilinearPolynomial<F>,
+    pub initial_claimed_sum: F,
+    pub transcript: Transcript,
+    pub uni_poly_for_each_round: Vec<MultilinearPolynomial<F>>
+}
+
+// A struct that represents a sumcheck proof
+pub struct SumcheckProof<F: PrimeField> {
+    pub initial_claimed_sum: F, // type of F
+    pub initial_poly: MultilinearPolynomial<F>, // type of Multilinear poly
+    pub uni_poly_for_each_round: Vec<MultilinearPolynomial<F>>, // vector of univariate polynomials to store reduced poly at each round
+}
+
+// A sumcheck proof for a committed polynomial: instead of shipping the whole polynomial for the final oracle check,
+// the prover sends a commitment up front and, at the end, an opening of it at the verifier's challenges.
+// Any multilinear commitment scheme can be used, e.g. PstPcs, or MultilinearIpaPcs for one without pairings
+#[cfg(feature = "std")]
+pub struct SumcheckProofWithOpening<F: PrimeField, S: MultilinearPcs<F>> {
+    pub initial_claimed_sum: F,
+    pub commitment: S::Commitment,
+    pub uni_poly_for_each_round: Vec<MultilinearPolynomial<F>>,
+    pub final_evaluation: F, // claimed value of the polynomial at the challenge point
+    pub opening_proof: S::OpeningProof,
+}
+
+// What a verifier starts from when it holds a commitment instead of the polynomial: the commitment and the sum the
+// committed polynomial is claimed to have over the hypercube. C is the commitment type of the scheme, S::Commitment
+#[cfg(feature = "std")]
+pub struct Claim<F: PrimeField, C> {
+    pub commitment: C,
+    pub claimed_sum: F,
+}
+
+// The committed polynomial's value at the round challenges and the scheme's proof of it, which stands in for the
+// verifier evaluating the polynomial itself
+#[cfg(feature = "std")]
+pub struct Opening<F: PrimeField, S: MultilinearPcs<F>> {
+    pub evaluation: F,
+    pub proof: S::OpeningProof,
+}
+
+// A SumcheckProofWithOpening as the claim it is about, the round polynomials and the opening, for verify_committed
+#[cfg(feature = "std")]
+pub type CommittedParts<F, S> = (Claim<F, <S as PolynomialCommitmentScheme<F>>::Commitment>, Vec<MultilinearPolynomial<F>>, Opening<F, S>);
+
+#[cfg(feature = "std")]
+impl <F: PrimeField, S: MultilinearPcs<F>>SumcheckProofWithOpening<F, S> {
+    pub fn into_parts(self) -> CommittedParts<F, S> {
+        (
+            Claim { commitment: self.commitment, claimed_sum: self.initial_claimed_sum },
+            self.uni_poly_for_each_round,
+            Opening { evaluation: self.final_evaluation, proof: self.opening_proof },
+        )
+    }
+}
+
+// A zero-knowledge sumcheck proof for a committed polynomial f, masked the way Chiesa, Forbes and Spooner do it.
+// Before the rounds the prover commits to a random multilinear polynomial g and sends its sum G, the verifier
+// answers with rho, and the rounds prove that f + rho * g sums to H + rho * G. Since g is uniform over all the
+// polynomials with sum G, the round polynomials are uniform too apart from what the claim fixes, so they say nothing
+// about f. The proof ends with openings of f and g at the challenges.
+//
+// The value f(r) the opening reveals is the one leak left: the full construction removes it by also masking f with a
+// random low degree extension, which is what a zk GKR does with its wire polynomials. A hiding commitment is needed
+// for the commitments themselves to hide f and g
+#[cfg(feature = "std")]
+pub struct ZkSumcheckProof<F: PrimeField, S: MultilinearPcs<F>> {
+    pub initial_claimed_sum: F,
+    pub commitment: S::Commitment,
+    pub mask_commitment: S::Commitment,
+    pub mask_sum: F, // G, the sum of the masking polynomial over the hypercube
+    pub uni_poly_for_each_round: Vec<MultilinearPolynomial<F>>, // round polynomials of f + rho * g
+    pub final_evaluation: F,
+    pub opening_proof: S::OpeningProof,
+    pub mask_evaluation: F,
+    pub mask_opening_proof: S::OpeningProof,
+}
+
+// Define a struct to represent a sumcheck verifier
+pub struct Verifier<F: PrimeField> {
+    pub transcript: Transcript,
+    _phantom: PhantomData<F>
+}
+
+impl <F: PrimeField>SumcheckProof<F> {
+    // The field elements the proof carries, at their compressed size: the claimed sum, the 2^n evaluations of the
+    // initial polynomial the verifier needs for the oracle check, and two per round polynomial.
+    // Shipping the polynomial dominates; SumcheckProofWithOpening swaps it for a commitment and an opening
+    pub fn size_in_bytes(&self) -> usize {
+        let no_of_elements = 1
+            + self.initial_poly.evaluated_values.len()
+            + self.uni_poly_for_each_round.iter().map(|poly| poly.evaluated_values.len()).sum::<usize>();
+        no_of_elements * F::zero().compressed_size()
+    }
+
+    // The challenges Verifier::verify draws for this proof, without checking anything, for tools that need to
+    // reproduce them such as a generated verifier or a debugger. transcript is the one the verifier starts from:
+    // Transcript::new(), Transcript::new_with_statement(label, statement) for a proof bound to an instance, or a
+    // running session for one made by Prover::prove_with_transcript, which it is left at the end of, as the
+    // verifier leaves it
+    pub fn derive_challenges(&self, transcript: &mut Transcript) -> Vec<F> {
+        absorb_statement(transcript, &self.initial_poly, self.initial_claimed_sum);
+        self.uni_poly_for_each_round.iter().map(|round| round_challenge(transcript, round)).collect()
+    }
+}
+
+// What the plain prover binds before its rounds: the initial polynomial through its digest, 32 bytes however large
+// the table is, then the claimed sum
+pub(crate) fn absorb_statement<F: PrimeField>(transcript: &mut Transcript, polynomial: &MultilinearPolynomial<F>, claimed_sum: F) {
+    transcript.append(&polynomial.digest(Keccak256::new()));
+    transcript.append(&field_to_bytes(claimed_sum));
+}
+
+// One round of the plain protocol: the round polynomial goes into the transcript as bytes, and the challenge comes out
+pub(crate) fn round_challenge<F: PrimeField>(transcript: &mut Transcript, round: &MultilinearPolynomial<F>) -> F {
+    transcript.append(&round.convert_to_bytes());
+    transcript.random_challenge_as_field_element()
+}
+
+impl<F: PrimeField>Prover<F> {
+    pub fn new(initial_poly_evaluation: &[F]) -> Self {
+        Self::with_transcript(initial_poly_evaluation, Transcript::new())
+    }
+
+    // A prover whose transcript is bound to the instance, see FiatShamirTranscriptInterface::new_with_statement.
+    // The verifier has to start from the same label and statement
+    pub fn new_with_statement(initial_poly_evaluation: &[F], label: &[u8], statement: &[u8]) -> Self {
+        Self::with_transcript(initial_poly_evaluation, Transcript::new_with_statement(label, statement))
+    }
+
+    // Proves the sum of the polynomial inside a transcript that is already running, e.g. one sumcheck of many in a
+    // layer by layer protocol. The proof's challenges then depend on everything the session absorbed before it, and
+    // every later step depends on this proof, so the sub-protocols cannot be proved or replayed apart from each other.
+    // The transcript is handed back in the state a fresh prover would leave its own one
+    pub fn prove_with_transcript(transcript: &mut Transcript, initial_poly_evaluation: &[F]) -> SumcheckProof<F> {
+        let mut prover = Self::with_transcript(initial_poly_evaluation, mem::replace(transcript, Transcript::new()));
+        let proof = prover.prove();
+        *transcript = prover.transcript;
+        proof
+    }
+
+    // A table whose length is not a power of two is padded with zeros, which leaves the sum as it is, see
+    // MultilinearPolynomial::padded. The proof carries the padded polynomial, so the verifier sees the zeros
+    fn with_transcript(initial_poly_evaluation: &[F], transcript: Transcript) -> Self {
+        let polynomial = MultilinearPolynomial::padded(initial_poly_evaluation);
+
+        Prover {
+            initial_poly: polynomial,
+            initial_claimed_sum: initial_poly_evaluation.iter().sum(),
+            transcript,
+            uni_poly_for_each_round: Vec::new(),
+        }
+    }
+
+    pub fn prove(&mut self) -> SumcheckProof<F> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("sumcheck_prove", no_of_vars = self.initial_poly.no_of_vars).entered();
+
+        absorb_statement(&mut self.transcript, &self.initial_poly, self.initial_claimed_sum);
+
+        self.prove_rounds(self.initial_poly.clone());
+
+        SumcheckProof {
+            initial_claimed_sum: self.initial_claimed_sum,
+            initial_poly: self.initial_poly.clone(),
+            uni_poly_for_each_round: self.uni_poly_for_each_round.clone(),
+        }
+    }
+
+    // Same protocol as prove(), but the transcript absorbs a commitment instead of the whole polynomial,
+    // and the proof ends with an opening of the commitment at the round challenges
+    #[cfg(feature = "std")]
+    pub fn prove_with_opening<S>(&mut self, params: &S::Params) -> SumcheckProofWithOpening<F, S>
+    where
+        S: MultilinearPcs<F>,
+        S::Commitment: CanonicalSerialize,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("sumcheck_prove_with_opening", no_of_vars = self.initial_poly.no_of_vars).entered();
+
+        let commitment = S::commit(params, &self.initial_poly);
+
+        // commit the polynomial commitment and the initial claimed sum to the transcript
+        self.transcript.append(&commitment_to_bytes(&commitment));
+        self.transcript.append(&field_to_bytes(self.initial_claimed_sum));
+
+        let challenges = self.prove_rounds(self.initial_poly.clone());
+
+        let (final_evaluation, opening_proof) = S::open(params, &self.initial_poly, &challenges, &mut self.transcript);
+
+        SumcheckProofWithOpening {
+            initial_claimed_sum: self.initial_claimed_sum,
+            commitment,
+            uni_poly_for_each_round: self.uni_poly_for_each_round.clone(),
+            final_evaluation,
+            opening_proof,
+        }
+    }
+
+    // Same protocol as prove_with_opening(), with the rounds run on f + rho * g for a masking polynomial g drawn
+    // from the transcript's RNG with entropy from rng, see ZkSumcheckProof and Transcript::rng
+    #[cfg(feature = "std")]
+    pub fn prove_zk<S, R>(&mut self, params: &S::Params, rng: &mut R) -> ZkSumcheckProof<F, S>
+    where
+        S: MultilinearPcs<F>,
+        S::Commitment: CanonicalSerialize,
+        R: RngCore,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("sumcheck_prove_zk", no_of_vars = self.initial_poly.no_of_vars).entered();
+
+        let commitment = S::commit(params, &self.initial_poly);
+        self.transcript.append(&commitment_to_bytes(&commitment));
+        self.transcript.append(&field_to_bytes(self.initial_claimed_sum));
+
+        // the mask is drawn from the transcript's RNG, seeded from the commitment and the claim as well as rng
+        let mut mask_rng = self.transcript.rng(rng);
+        let mask_values: Vec<F> = (0..self.initial_poly.evaluated_values.len()).map(|_| F::rand(&mut mask_rng)).collect();
+        let mask = MultilinearPolynomial::new(&mask_values);
+        let mask_commitment = S::commit(params, &mask);
+        let mask_sum: F = mask_values.iter().sum();
+        let rho = bind_mask::<F, S>(&mut self.transcript, &mask_commitment, mask_sum);
+
+        let masked: Vec<F> = self.initial_poly.evaluated_values.iter().zip(&mask_values).map(|(f, g)| *f + rho * g).collect();
+        let challenges = self.prove_rounds(MultilinearPolynomial::new(&masked));
+
+        let (final_evaluation, opening_proof) = S::open(params, &self.initial_poly, &challenges, &mut self.transcript);
+        let (mask_evaluation, mask_opening_proof) = S::open(params, &mask, &challenges, &mut self.transcript);
+
+        ZkSumcheckProof {
+            initial_claimed_sum: self.initial_claimed_sum,
+            commitment,
+            mask_commitment,
+            mask_sum,
+            uni_poly_for_each_round: self.uni_poly_for_each_round.clone(),
+            final_evaluation,
+            opening_proof,
+            mask_evaluation,
+            mask_opening_proof,
+        }
+    }
+
+    // Runs one round per variable: sends the round's univariate polynomial to the transcript,
+    // then binds the first variable to the transcript's challenge. Returns the challenges in order
+    fn prove_rounds(&mut self, polynomial: MultilinearPolynomial<F>) -> Vec<F> {
+        self.prove_rounds_with(polynomial, &mut |_, round_poly| Some(round_poly))
+    }
+
+    // prove_rounds with every round polynomial passed through hook before it is sent, the place where the provers of
+    // the malicious module inject their faults
+    pub(crate) fn prove_rounds_with(&mut self, polynomial: MultilinearPolynomial<F>, hook: &mut RoundHook<F>) -> Vec<F> {
+        let no_of_vars = polynomial.no_of_vars;
+        let mut current_polynomial = polynomial;
+        let mut challenges = Vec::with_capacity(no_of_vars);
+
+        for round in 0..no_of_vars {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("sumcheck_round", round).entered();
+            
+            let univariate_poly_values = split_and_reduce(&current_polynomial.evaluated_values);
+
+            // defined a univariate polynomial for this round
+            let univariate_polynomial = MultilinearPolynomial::new(&univariate_poly_values);
+
+            let random_challenge = match hook(round, univariate_polynomial) {
+                Some(sent) => {
+                    // commit the univariate polynomial to the transcript and get the round's challenge from it
+                    let random_challenge = round_challenge(&mut self.transcript, &sent);
+
+                    // add the univariate polynomial for this round to the vector in sumcheck proof
+                    self.uni_poly_for_each_round.push(sent);
+                    random_challenge
+                }
+                // a round left out sends nothing, and the variable is bound to a challenge nothing was sent for
+                None => self.transcript.random_challenge_as_field_element(),
+            };
+            challenges.push(random_challenge);
+
+            // Partial evaluate current polynomial using the random_challenge
+            current_polynomial = MultilinearPolynomial::partial_evaluate(&current_polynomial.evaluated_values.clone(), 0, random_challenge);
+        }
+
+        challenges
+    }
+}
+
+
+impl <F: PrimeField>Verifier<F> {
+    pub fn new() -> Self {
+        Verifier {
+            transcript: Transcript::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn new_with_statement(label: &[u8], statement: &[u8]) -> Self {
+        Verifier {
+            transcript: Transcript::new_with_statement(label, statement),
+            _phantom: PhantomData,
+        }
+    }
+
+    // Verifies a proof made by Prover::prove_with_transcript, continuing the session the verifier has replayed so far
+    pub fn verify_with_transcript(transcript: &mut Transcript, proof: SumcheckProof<F>) -> bool {
+        let mut verifier = Verifier { transcript: mem::replace(transcript, Transcript::new()), _phantom: PhantomData };
+        let verified = verifier.verify(proof);
+        *transcript = verifier.transcript;
+        verified
+    }
+
+    pub fn verify(&mut self, proof: SumcheckProof<F>) -> bool {
+
+        // Check if the number of univariate polynomials in the proof is equal to the number of variables in the initial polynomial
+        if proof.uni_poly_for_each_round.len() != proof.initial_poly.no_of_vars {
+            return false;
+        }
+
+        // commit the initial polynomial and the initial claimed sum to the transcript, as the prover did
+        absorb_statement(&mut self.transcript, &proof.initial_poly, proof.initial_claimed_sum);
+
+        let (challenges, final_claim_sum) = match self.verify_rounds(proof.initial_claimed_sum, &proof.uni_poly_for_each_round) {
+            Some(result) => result,
+            None => return false,
+        };
+
+        let final_evaluation = proof.initial_poly.evaluate(&challenges);
+
+        // Oracle Check
+        final_evaluation == final_claim_sum
+    }
+
+    // Verifies a proof about a committed polynomial, see verify_committed
+    #[cfg(feature = "std")]
+    pub fn verify_with_opening<S>(&mut self, params: &S::Params, proof: SumcheckProofWithOpening<F, S>) -> bool
+    where
+        S: MultilinearPcs<F>,
+        S::Commitment: CanonicalSerialize,
+    {
+        let (claim, rounds, opening) = proof.into_parts();
+        self.verify_committed(params, &claim, &rounds, &opening)
+    }
+
+    // Verifies that the committed polynomial sums to the claimed sum with the verifier never holding the polynomial.
+    // The oracle check is replaced by checking that the opened evaluation matches the last round and is a valid
+    // opening of the commitment at the challenges. A proof with the wrong number of rounds is rejected by the
+    // opening, which needs one challenge per committed variable
+    #[cfg(feature = "std")]
+    pub fn verify_committed<S>(
+        &mut self,
+        params: &S::Params,
+        claim: &Claim<F, S::Commitment>,
+        rounds: &[MultilinearPolynomial<F>],
+        opening: &Opening<F, S>,
+    ) -> bool
+    where
+        S: MultilinearPcs<F>,
+        S::Commitment: CanonicalSerialize,
+    {
+        // commit the polynomial commitment and the initial claimed sum to the transcript
+        self.transcript.append(&commitment_to_bytes(&claim.commitment));
+        self.transcript.append(&field_to_bytes(claim.claimed_sum));
+
+        let (challenges, final_claim_sum) = match self.verify_rounds(claim.claimed_sum, rounds) {
+            Some(result) => result,
+            None => return false,
+        };
+
+        // Oracle Check, done through the commitment
+        opening.evaluation == final_claim_sum
+            && S::verify(params, &claim.commitment, &challenges, opening.evaluation, &opening.proof, &mut self.transcript)
+    }
+
+    // Verifies a zero-knowledge proof: the rounds are checked against H + rho * G, and the last round against
+    // f(r) + rho * g(r) with both evaluations opened from their commitments
+    #[cfg(feature = "std")]
+    pub fn verify_zk<S>(&mut self, params: &S::Params, proof: ZkSumcheckProof<F, S>) -> bool
+    where
+        S: MultilinearPcs<F>,
+        S::Commitment: CanonicalSerialize,
+    {
+        self.transcript.append(&commitment_to_bytes(&proof.commitment));
+        self.transcript.append(&field_to_bytes(proof.initial_claimed_sum));
+        let rho = bind_mask::<F, S>(&mut self.transcript, &proof.mask_commitment, proof.mask_sum);
+
+        let masked_sum = proof.initial_claimed_sum + rho * proof.mask_sum;
+        let (challenges, final_claim_sum) = match self.verify_rounds(masked_sum, &proof.uni_poly_for_each_round) {
+            Some(result) => result,
+            None => return false,
+        };
+
+        proof.final_evaluation + rho * proof.mask_evaluation == final_claim_sum
+            && S::verify(params, &proof.commitment, &challenges, proof.final_evaluation, &proof.opening_proof, &mut self.transcript)
+            && S::verify(params, &proof.mask_commitment, &challenges, proof.mask_evaluation, &proof.mask_opening_proof, &mut self.transcript)
+    }
+
+    // Checks every round polynomial against the running claim and derives the round challenges.
+    // Returns the challenges and the claim left after the last round, or None if a round check fails
+    fn verify_rounds(&mut self, initial_claimed_sum: F, uni_poly_for_each_round: &[MultilinearPolynomial<F>]) -> Option<(Vec<F>, F)> {
+        // let the current_sum be the initial claimed sum from the sent proof
+        let mut current_claim_sum = initial_claimed_sum;
+
+        // creates a new mutable vector called challenges that will store field elements of type F
+        // pre-allocates space for a vector that will space equal to the number of univariate polynomials in the proof
+        let mut challenges: Vec<F> = Vec::with_capacity(uni_poly_for_each_round.len());
+
+        // Loop through the vector of univariate polynomials
+        for uni_poly in uni_poly_for_each_round {
+            // a round polynomial must be linear: exactly 2 evaluations
+            if uni_poly.evaluated_values.len() != 2 {
+                return None;
+            }
+
+            // the sum of the univariate polynomial evaluated at 0 and 1 should equal the current claimed sum.
+            if uni_poly.evaluate(&[F::zero()]) + uni_poly.evaluate(&[F::one()]) != current_claim_sum {
+                return None;
+            }
+
+            // commit the univariate polynomial to the transcript and get the round's challenge from it
+            let challenge = round_challenge(&mut self.transcript, uni_poly);
+            challenges.push(challenge);
+
+            // update the current claimed sum
+            current_claim_sum = uni_poly.evaluate(&[challenge])
+        }
+
+        Some((challenges, current_claim_sum))
+    }
+}
+
+impl <F: PrimeField>Default for Verifier<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Appends the masking polynomial's commitment and sum and derives rho, the same on both sides
+#[cfg(feature = "std")]
+fn bind_mask<F, S>(transcript: &mut Transcript, mask_commitment: &S::Commitment, mask_sum: F) -> F
+where
+    F: PrimeField,
+    S: MultilinearPcs<F>,
+    S::Commitment: CanonicalSerialize,
+{
+    transcript.append(&commitment_to_bytes(mask_commitment));
+    transcript.append(&field_to_bytes(mask_sum));
+    transcript.random_challenge_as_field_element()
+}
+
+pub fn split_and_reduce<F: PrimeField>(polynomial_evaluated_values: &[F]) -> Vec<F> {
+    let mut univariate_polynomial: Vec<F> = Vec::with_capacity(2);
+
+    let mid = polynomial_evaluated_values.len() / 2;
+    let (left, right) = polynomial_evaluated_values.split_at(mid);
+
+    let left_sum: F = left.iter().sum();
+    let right_sum: F = right.iter().sum();
+
+    univariate_polynomial.push(left_sum);
+    univariate_polynomial.push(right_sum);
+
+    univariate_polynomial
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ark_bn254::{Bn254, Fq, Fr, G1Projective};
+    use commitments::pcs::{MultilinearIpaPcs, PolynomialCommitmentScheme, PstPcs};
+
+    #[test]
+    fn test_prover_init() {
+        let evaluated_values = vec![Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(8)];
+        let prover = Prover::new(&evaluated_values);
+
+        assert_eq!(prover.initial_claimed_sum, Fq::from(11));
+        assert_eq!(prover.initial_poly.evaluated_values, evaluated_values);
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let evaluated_values = vec![Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(0), Fq::from(0), Fq::from(2), Fq::from(5)];
+        let mut prover = Prover::new(&evaluated_values);
+        let proof = prover.prove();
+
+        let mut verifier = Verifier::new();
+        assert!(verifier.verify(proof));
+    }
+
+    #[test]
+    fn test_tables_of_any_length_are_padded() {
+        let evaluated_values: Vec<Fq> = (1..=6u64).map(Fq::from).collect();
+        let mut prover = Prover::new(&evaluated_values);
+        let proof = prover.prove();
+
+        assert_eq!(proof.initial_claimed_sum, Fq::from(21));
+        assert_eq!(proof.uni_poly_for_each_round.len(), 3);
+        assert!(Verifier::new().verify(proof));
+    }
+
+    #[test]
+    fn test_sumchecks_share_one_transcript() {
+        let first = vec![Fq::from(1), Fq::from(4), Fq::from(1), Fq::from(5)];
+        let second = vec![Fq::from(9), Fq::from(2), Fq::from(6), Fq::from(5), Fq::from(3), Fq::from(5), Fq::from(8), Fq::from(9)];
+        let prove_session = || {
+            let mut transcript = Transcript::new();
+            let first_proof = Prover::prove_with_transcript(&mut transcript, &first);
+            (first_proof, Prover::prove_with_transcript(&mut transcript, &second))
+        };
+
+        // the first proof is what a prover with its own transcript makes, the second depends on the first
+        let (first_proof, second_proof) = prove_session();
+        assert_eq!(first_proof.uni_poly_for_each_round, Prover::new(&first).prove().uni_poly_for_each_round);
+        assert_ne!(second_proof.uni_poly_for_each_round, Prover::new(&second).prove().uni_poly_for_each_round);
+
+        let mut transcript = Transcript::new();
+        assert!(Verifier::verify_with_transcript(&mut transcript, first_proof));
+        assert!(Verifier::verify_with_transcript(&mut transcript, second_proof));
+
+        // the second proof taken out of its session does not verify on its own
+        let (_, second_proof) = prove_session();
+        assert!(!Verifier::new().verify(second_proof));
+    }
+
+    #[test]
+    fn test_prove_and_verify_with_opening() {
+        let params = PstPcs::<Bn254>::setup(3, &mut ark_std::test_rng());
+        let evaluated_values = vec![Fr::from(0), Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(0), Fr::from(0), Fr::from(2), Fr::from(5)];
+
+        let mut prover = Prover::new(&evaluated_values);
+        let proof = prover.prove_with_opening::<PstPcs<Bn254>>(&params);
+
+        let mut verifier = Verifier::new();
+        assert!(verifier.verify_with_opening(&params, proof));
+    }
+
+    #[test]
+    fn test_verify_committed_against_a_claim_the_verifier_holds() {
+        let params = PstPcs::<Bn254>::setup(2, &mut ark_std::test_rng());
+        let evaluated_values = vec![Fr::from(1), Fr::from(4), Fr::from(1), Fr::from(5)];
+        let (_, rounds, opening) = Prover::new(&evaluated_values).prove_with_opening::<PstPcs<Bn254>>(&params).into_parts();
+
+        // the verifier got the commitment from somewhere it trusts, not from the proof
+        let claim = Claim {
+            commitment: PstPcs::<Bn254>::commit(&params, &MultilinearPolynomial::new(&evaluated_values)),
+            claimed_sum: Fr::from(11),
+        };
+        assert!(Verifier::new().verify_committed(&params, &claim, &rounds, &opening));
+
+        // the same rounds and opening against a commitment to another polynomial with the same sum
+        let other = Claim {
+            commitment: PstPcs::<Bn254>::commit(&params, &MultilinearPolynomial::new(&[Fr::from(4), Fr::from(1), Fr::from(1), Fr::from(5)])),
+            claimed_sum: Fr::from(11),
+        };
+        assert!(!Verifier::new().verify_committed(&params, &other, &rounds, &opening));
+    }
+
+    #[test]
+    #[should_panic(expected = "point must have one value per setup variable")]
+    fn test_opening_with_fewer_rounds_than_the_setup_is_refused() {
+        // the setup has 3 variables and the sumcheck over 4 entries ends on a 2 coordinate point, which
+        // MultilinearIpaPcs::verify would never accept, so the prover stops rather than hand out the proof
+        let params = MultilinearIpaPcs::<G1Projective>::setup(3, &mut ark_std::test_rng());
+        let prefix = vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)];
+        Prover::new(&prefix).prove_with_opening::<MultilinearIpaPcs<G1Projective>>(&params);
+    }
+
+    #[test]
+    fn test_verify_with_opening_rejects_wrong_sum() {
+        let params = PstPcs::<Bn254>::setup(3, &mut ark_std::test_rng());
+        let evaluated_values = vec![Fr::from(0), Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(0), Fr::from(0), Fr::from(2), Fr::from(5)];
+
+        let mut prover = Prover::new(&evaluated_values);
+        let mut proof = prover.prove_with_opening::<PstPcs<Bn254>>(&params);
+        proof.initial_claimed_sum += Fr::from(1);
+
+        let mut verifier = Verifier::new();
+        assert!(!verifier.verify_with_opening(&params, proof));
+    }
+
+    #[test]
+    fn test_proofs_are_bound_to_their_statement() {
+        let params = PstPcs::<Bn254>::setup(3, &mut ark_std::test_rng());
+        let evaluated_values = vec![Fr::from(0), Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(0), Fr::from(0), Fr::from(2), Fr::from(5)];
+        let prove = || Prover::new_with_statement(&evaluated_values, b"sumcheck", b"instance 1").prove_with_opening::<PstPcs<Bn254>>(&params);
+
+        assert!(Verifier::new_with_statement(b"sumcheck", b"instance 1").verify_with_opening(&params, prove()));
+        // the same proof replayed against another instance, or a blank transcript, draws other challenges
+        assert!(!Verifier::new_with_statement(b"sumcheck", b"instance 2").verify_with_opening(&params, prove()));
+        assert!(!Verifier::new().verify_with_opening(&params, prove()));
+    }
+
+    #[test]
+    fn test_derived_challenges_are_the_verifier_ones() {
+        let evaluated_values = vec![Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(0), Fq::from(0), Fq::from(2), Fq::from(5)];
+        let prove = || Prover::new_with_statement(&evaluated_values, b"sumcheck", b"instance 1").prove();
+        let proof = prove();
+        let challenges = proof.derive_challenges(&mut Transcript::new_with_statement(b"sumcheck", b"instance 1"));
+
+        // the last round polynomial at the last challenge is the oracle's value at all of them
+        let last = proof.uni_poly_for_each_round.last().unwrap().evaluate(&[challenges[2]]);
+        assert_eq!(proof.initial_poly.evaluate(&challenges), last);
+
+        // another statement, or a changed round, gives other challenges
+        assert_ne!(proof.derive_challenges(&mut Transcript::new_with_statement(b"sumcheck", b"instance 2")), challenges);
+        let mut tampered = prove();
+        tampered.uni_poly_for_each_round[1].evaluated_values[0] += Fq::from(1);
+        let tampered = tampered.derive_challenges(&mut Transcript::new_with_statement(b"sumcheck", b"instance 1"));
+        assert_eq!(tampered[..1], challenges[..1]);
+        assert_ne!(tampered[1..], challenges[1..]);
+    }
+
+    fn zk_proof(evaluated_values: &[Fr], seed: u64) -> (<PstPcs<Bn254> as PolynomialCommitmentScheme<Fr>>::Params, ZkSumcheckProof<Fr, PstPcs<Bn254>>) {
+        use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+        let params = PstPcs::<Bn254>::setup(3, &mut ark_std::test_rng());
+        let proof = Prover::new(evaluated_values).prove_zk::<PstPcs<Bn254>, _>(&params, &mut StdRng::seed_from_u64(seed));
+        (params, proof)
+    }
+
+    #[test]
+    fn test_prove_and_verify_zk() {
+        let evaluated_values = vec![Fr::from(0), Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(0), Fr::from(0), Fr::from(2), Fr::from(5)];
+        let (params, proof) = zk_proof(&evaluated_values, 0);
+
+        assert_eq!(proof.initial_claimed_sum, Fr::from(10));
+        assert!(Verifier::new().verify_zk(&params, proof));
+    }
+
+    #[test]
+    fn test_zk_rounds_do_not_repeat_the_transparent_ones() {
+        let evaluated_values = vec![Fr::from(0), Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(0), Fr::from(0), Fr::from(2), Fr::from(5)];
+        let (_, first) = zk_proof(&evaluated_values, 0);
+        let (_, second) = zk_proof(&evaluated_values, 1);
+
+        // the first transparent round is (f's sum with x_0 = 0, with x_0 = 1) = (3, 7), the masked ones are fresh per mask
+        let first_round = |proof: &ZkSumcheckProof<Fr, PstPcs<Bn254>>| proof.uni_poly_for_each_round[0].evaluated_values.clone();
+        assert_ne!(first_round(&first), vec![Fr::from(3), Fr::from(7)]);
+        assert_ne!(first_round(&first), first_round(&second));
+    }
+
+    #[test]
+    fn test_verify_zk_rejects_tampering() {
+        let evaluated_values = vec![Fr::from(0), Fr::from(0), Fr::from(0), Fr::from(3), Fr::from(0), Fr::from(0), Fr::from(2), Fr::from(5)];
+
+        let (params, mut proof) = zk_proof(&evaluated_values, 0);
+        proof.initial_claimed_sum += Fr::from(1);
+        assert!(!Verifier::new().verify_zk(&params, proof));
+
+        let (params, mut proof) = zk_proof(&evaluated_values, 0);
+        proof.mask_sum += Fr::from(1);
+        assert!(!Verifier::new().verify_zk(&params, proof));
+
+        let (params, mut proof) = zk_proof(&evaluated_values, 0);
+        proof.mask_evaluation += Fr::from(1);
+        assert!(!Verifier::new().verify_zk(&params, proof));
+    }
+}
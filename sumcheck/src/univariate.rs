@@ -0,0 +1,203 @@
+use crate::commitment_to_bytes;
+use crate::encoding::field_to_bytes;
+use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use ark_ec::pairing::Pairing;
+use ark_ff::{Field, PrimeField};
+use commitments::kzg::{KzgCommitment, KzgOpeningProof, KzgParams};
+use univariate_poly::domain::EvaluationDomain;
+use univariate_poly::univariate::UnivariatePoly;
+
+// Univariate sumcheck, Aurora style: proves that a committed polynomial p sums to S over an evaluation domain H of
+// size n.
+//
+// Dividing p by the vanishing polynomial Z_H leaves a remainder r of degree < n with the same values on H, and the sum
+// of h^k over H is 0 for every 0 < k < n, so the sum over H is n * r_0. The claim is therefore that
+//   p = g * Z_H + X * h + S / n
+// for some g and some h of degree < n - 1. The prover commits to g and h, the verifier draws z, and one batched KZG
+// opening gives p(z), g(z), h(z) for the check at z.
+//
+// The degree bound on h is what makes it sound: without it any S fits, the x * h term absorbing the difference. KZG
+// does not bound degrees by itself, so the prover also commits to X^(D - n + 2) * h, D being the setup's maximum
+// degree, which only exists when deg h <= n - 2, and the verifier checks it opens to z^(D - n + 2) * h(z)
+pub struct UnivariateSumcheckProof<E: Pairing> {
+    pub g_commitment: KzgCommitment<E>,
+    pub h_commitment: KzgCommitment<E>,
+    pub shifted_h_commitment: KzgCommitment<E>,
+    // p(z), g(z), h(z) and the shifted h at z
+    pub values: Vec<E::ScalarField>,
+    pub opening: KzgOpeningProof<E>,
+}
+
+impl <E: Pairing>UnivariateSumcheckProof<E> {
+    // Returns the proof and the sum of polynomial over domain. The domain needs at least 2 elements, and the setup
+    // must hold polynomials of the degree of polynomial
+    pub fn prove(
+        params: &KzgParams<E>,
+        domain: &EvaluationDomain<E::ScalarField>,
+        polynomial: &UnivariatePoly<E::ScalarField>,
+        transcript: &mut Transcript,
+    ) -> (Self, E::ScalarField) {
+        assert!(domain.size >= 2, "the domain needs at least 2 elements");
+        let max_degree = params.max_degree();
+        assert!(max_degree + 2 >= domain.size, "the setup is too small for the domain");
+
+        let (g, remainder) = polynomial
+            .div_rem(&domain.vanishing_poly().to_dense())
+            .expect("the vanishing polynomial is not the zero polynomial");
+        let constant = remainder.coefficients.first().copied().unwrap_or_default();
+        let sum = constant * E::ScalarField::from(domain.size as u64);
+        let h = UnivariatePoly::new(remainder.coefficients.iter().skip(1).copied().collect());
+        let shifted_h = UnivariatePoly::new(
+            core::iter::repeat_n(E::ScalarField::from(0u64), shift(max_degree, domain.size))
+                .chain(h.coefficients.iter().copied())
+                .collect(),
+        );
+
+        let commitment = params.commit(polynomial);
+        let polynomials = [polynomial.clone(), g, h, shifted_h];
+        let [g_commitment, h_commitment, shifted_h_commitment] = [1, 2, 3].map(|i| params.commit(&polynomials[i]));
+
+        let z = challenge(transcript, &commitment, sum, [&g_commitment, &h_commitment, &shifted_h_commitment]);
+        let commitments = [commitment, g_commitment, h_commitment, shifted_h_commitment];
+        let (values, opening) = params.open_batch(&polynomials, &commitments, z, transcript).expect("one commitment per polynomial");
+
+        (Self { g_commitment, h_commitment, shifted_h_commitment, values, opening }, sum)
+    }
+
+    pub fn verify(
+        &self,
+        params: &KzgParams<E>,
+        domain: &EvaluationDomain<E::ScalarField>,
+        commitment: &KzgCommitment<E>,
+        claimed_sum: E::ScalarField,
+        transcript: &mut Transcript,
+    ) -> bool {
+        let max_degree = params.max_degree();
+        if domain.size < 2 || max_degree + 2 < domain.size || self.values.len() != 4 {
+            return false;
+        }
+
+        let z = challenge(transcript, commitment, claimed_sum, [&self.g_commitment, &self.h_commitment, &self.shifted_h_commitment]);
+        let [p, g, h, shifted_h] = [self.values[0], self.values[1], self.values[2], self.values[3]];
+
+        let size = E::ScalarField::from(domain.size as u64);
+        let decomposes = p == g * domain.evaluate_vanishing(z) + z * h + claimed_sum * size.inverse().expect("the domain size is nonzero");
+        let bounded = shifted_h == z.pow([shift(max_degree, domain.size) as u64]) * h;
+
+        let commitments = [*commitment, self.g_commitment, self.h_commitment, self.shifted_h_commitment];
+        decomposes && bounded && params.verify_batch(&commitments, z, &self.values, &self.opening, transcript)
+    }
+}
+
+// The power of X that lifts a polynomial of degree n - 2 to the setup's maximum degree
+fn shift(max_degree: usize, domain_size: usize) -> usize {
+    max_degree + 2 - domain_size
+}
+
+// Binds the statement and the prover's commitments, and draws the point z
+fn challenge<E: Pairing>(
+    transcript: &mut Transcript,
+    commitment: &KzgCommitment<E>,
+    claimed_sum: E::ScalarField,
+    commitments: [&KzgCommitment<E>; 3],
+) -> E::ScalarField {
+    transcript.append(&commitment_to_bytes(commitment));
+    transcript.append(&field_to_bytes(claimed_sum));
+    for commitment in commitments {
+        transcript.append(&commitment_to_bytes(commitment));
+    }
+    transcript.random_challenge_as_field_element()
+}
+
+// The sum of polynomial over domain, directly, for tests and callers that want to check a claim themselves
+pub fn sum_over_domain<F: PrimeField>(domain: &EvaluationDomain<F>, polynomial: &UnivariatePoly<F>) -> F {
+    domain.elements().iter().map(|x| polynomial.evaluate(*x)).sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, Fr};
+
+    // degree 12 over a domain of 8 elements, so g is not zero
+    fn polynomial() -> UnivariatePoly<Fr> {
+        UnivariatePoly::new((1..=13u64).map(|i| Fr::from(i * i + 3)).collect())
+    }
+
+    fn setup() -> (KzgParams<Bn254>, EvaluationDomain<Fr>) {
+        (KzgParams::setup(16, &mut ark_std::test_rng()), EvaluationDomain::new(8).unwrap())
+    }
+
+    #[test]
+    fn test_univariate_sumcheck() {
+        let (params, domain) = setup();
+        let polynomial = polynomial();
+
+        let (proof, sum) = UnivariateSumcheckProof::prove(&params, &domain, &polynomial, &mut Transcript::new());
+        assert_eq!(sum, sum_over_domain(&domain, &polynomial));
+        assert!(proof.verify(&params, &domain, &params.commit(&polynomial), sum, &mut Transcript::new()));
+
+        // the same works over a coset of the domain
+        let coset = domain.get_coset(Fr::from(5)).unwrap();
+        let (proof, sum) = UnivariateSumcheckProof::prove(&params, &coset, &polynomial, &mut Transcript::new());
+        assert_eq!(sum, sum_over_domain(&coset, &polynomial));
+        assert!(proof.verify(&params, &coset, &params.commit(&polynomial), sum, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_wrong_sum_or_polynomial_is_rejected() {
+        let (params, domain) = setup();
+        let polynomial = polynomial();
+        let commitment = params.commit(&polynomial);
+
+        let (proof, sum) = UnivariateSumcheckProof::prove(&params, &domain, &polynomial, &mut Transcript::new());
+        assert!(!proof.verify(&params, &domain, &commitment, sum + Fr::from(1), &mut Transcript::new()));
+
+        let other = &polynomial + &UnivariatePoly::new(vec![Fr::from(1)]);
+        assert!(!proof.verify(&params, &domain, &params.commit(&other), sum, &mut Transcript::new()));
+
+        let mut tampered = UnivariateSumcheckProof::prove(&params, &domain, &polynomial, &mut Transcript::new()).0;
+        tampered.values[1] += Fr::from(1);
+        assert!(!tampered.verify(&params, &domain, &commitment, sum, &mut Transcript::new()));
+    }
+
+    #[test]
+    fn test_h_of_too_high_degree_is_caught() {
+        // a prover claiming sum + 8 moves the difference into h: p = g' * Z_H + X * h' + (sum + 8) / 8 with
+        // g' = g + 1 cancelling the constant term, Z_H(0) being -1, and h' = (remainder - Z_H - (sum + 8) / 8) / X of
+        // degree n - 1
+        let (params, domain) = setup();
+        let polynomial = polynomial();
+        let commitment = params.commit(&polynomial);
+        let (honest, sum) = UnivariateSumcheckProof::prove(&params, &domain, &polynomial, &mut Transcript::new());
+
+        let z_h = domain.vanishing_poly().to_dense();
+        let (g, remainder) = polynomial.div_rem(&z_h).unwrap();
+        let lie = sum + Fr::from(8);
+        let g = &g + &UnivariatePoly::new(vec![Fr::from(1)]);
+        let numerator = &(&remainder - &z_h) - &UnivariatePoly::new(vec![lie * Fr::from(8).inverse().unwrap()]);
+        assert_eq!(numerator.coefficients[0], Fr::from(0));
+        let h = UnivariatePoly::new(numerator.coefficients[1..].to_vec());
+        assert_eq!(h.degree(), 7);
+
+        // the decomposition holds at every point, but X^(D - n + 2) * h does not fit in the setup, and h in the
+        // shifted slot fails the shift check
+        let forged_commitments = [params.commit(&g), params.commit(&h), params.commit(&h)];
+        let mut transcript = Transcript::new();
+        let z = challenge(&mut transcript, &commitment, lie, forged_commitments.each_ref());
+        let polynomials = [polynomial.clone(), g, h.clone(), h];
+        let commitments = [commitment, forged_commitments[0], forged_commitments[1], forged_commitments[2]];
+        let (values, opening) = params.open_batch(&polynomials, &commitments, z, &mut transcript).unwrap();
+        let forged = UnivariateSumcheckProof {
+            g_commitment: forged_commitments[0],
+            h_commitment: forged_commitments[1],
+            shifted_h_commitment: forged_commitments[2],
+            values,
+            opening,
+        };
+
+        assert!(honest.verify(&params, &domain, &commitment, sum, &mut Transcript::new()));
+        assert!(!forged.verify(&params, &domain, &commitment, lie, &mut Transcript::new()));
+    }
+}
@@ -0,0 +1,119 @@
+use alloc::vec::Vec;
+use ark_ff::{BigInteger, LegendreSymbol, PrimeField};
+
+// Conversions between field elements and bytes or bits, shared by every crate that hashes, serializes or decomposes
+// field elements, so they all agree on one encoding.
+//
+// The byte encoding is the canonical integer in big-endian, as wide as the field's BigInt, 32 bytes for a 254 bit
+// field: the bytes the transcripts absorb, the ones abi.encode writes for a uint256, and the ones proofs serialize
+// to JSON as hex. bytes_to_field is its exact inverse and refuses anything that is not such an encoding
+pub fn field_to_bytes<F: PrimeField>(value: F) -> Vec<u8> {
+    value.into_bigint().to_bytes_be()
+}
+
+// The element whose encoding is bytes, or None if the length is wrong or the integer is not below the modulus
+pub fn bytes_to_field<F: PrimeField>(bytes: &[u8]) -> Option<F> {
+    if bytes.len() != field_to_bytes(F::zero()).len() {
+        return None;
+    }
+    let value = F::from_be_bytes_mod_order(bytes);
+    (field_to_bytes(value) == bytes).then_some(value)
+}
+
+// The MODULUS_BIT_SIZE bits of the canonical integer, least significant first, e.g. for a range check gadget
+pub fn bits_le<F: PrimeField>(value: F) -> Vec<bool> {
+    let mut bits = value.into_bigint().to_bits_le();
+    bits.truncate(F::MODULUS_BIT_SIZE as usize);
+    bits
+}
+
+// The same bits, most significant first
+pub fn bits_be<F: PrimeField>(value: F) -> Vec<bool> {
+    let mut bits = bits_le(value);
+    bits.reverse();
+    bits
+}
+
+// The sum of 2^i over the set bits, reduced modulo p, so it inverts bits_le and recomposes a decomposition in-circuit
+pub fn from_bits_le<F: PrimeField>(bits: &[bool]) -> F {
+    bits.iter().rev().fold(F::zero(), |accumulator, bit| accumulator.double() + F::from(*bit))
+}
+
+// Euler's criterion: value^((p - 1) / 2) is 1 for a nonzero square, -1 for a non-square and 0 for zero, since the
+// nonzero elements form a cyclic group of even order p - 1 in which the squares are exactly the even powers
+pub fn legendre<F: PrimeField>(value: F) -> LegendreSymbol {
+    let power = value.pow(F::MODULUS_MINUS_ONE_DIV_TWO);
+    if power.is_zero() {
+        LegendreSymbol::Zero
+    } else if power.is_one() {
+        LegendreSymbol::QuadraticResidue
+    } else {
+        LegendreSymbol::QuadraticNonResidue
+    }
+}
+
+// A square root of value picked the same way every time, the one whose canonical integer is at most (p - 1) / 2, so
+// encodings that store a root and its sign, like compressed points, agree. None when value is not a square
+pub fn canonical_sqrt<F: PrimeField>(value: F) -> Option<F> {
+    let root = value.sqrt()?;
+    if root.into_bigint() <= F::MODULUS_MINUS_ONE_DIV_TWO {
+        Some(root)
+    } else {
+        Some(-root)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::Field;
+
+    #[test]
+    fn test_bytes_round_trip_and_reject_non_canonical() {
+        for value in [Fr::from(0), Fr::from(1), -Fr::from(1), Fr::from(u64::MAX)] {
+            let bytes = field_to_bytes(value);
+            assert_eq!(bytes.len(), 32);
+            assert_eq!(bytes_to_field::<Fr>(&bytes), Some(value));
+        }
+        assert_eq!(field_to_bytes(Fr::from(258))[30..], [1, 2]);
+
+        // the modulus itself reduces to zero, and is not an encoding
+        let modulus = Fr::MODULUS.to_bytes_be();
+        assert_eq!(bytes_to_field::<Fr>(&modulus), None);
+        assert_eq!(bytes_to_field::<Fr>(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_bit_decomposition() {
+        let value = Fr::from(0b1011u64);
+        let bits = bits_le(value);
+        assert_eq!(bits.len(), 254);
+        assert_eq!(bits[..5], [true, true, false, true, false]);
+        assert_eq!(bits_be(value)[250..], [true, false, true, true]);
+
+        for value in [Fr::from(0), Fr::from(12345), -Fr::from(7)] {
+            assert_eq!(from_bits_le::<Fr>(&bits_le(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_legendre_and_canonical_sqrt() {
+        assert_eq!(legendre(Fr::from(0)), LegendreSymbol::Zero);
+        for i in 1..50u64 {
+            let value = Fr::from(i);
+            assert_eq!(legendre(value), value.legendre());
+            assert_eq!(legendre(value * value), LegendreSymbol::QuadraticResidue);
+
+            match canonical_sqrt(value) {
+                Some(root) => {
+                    assert_eq!(root * root, value);
+                    assert_eq!(canonical_sqrt(value), canonical_sqrt((-root) * (-root)));
+                    assert!(root.into_bigint() <= Fr::MODULUS_MINUS_ONE_DIV_TWO);
+                }
+                None => assert_eq!(legendre(value), LegendreSymbol::QuadraticNonResidue),
+            }
+        }
+    }
+}
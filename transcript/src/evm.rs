@@ -0,0 +1,61 @@
+use crate::fiat_shamir::FiatShamirTranscriptInterface;
+use ark_ff::PrimeField;
+use sha3::{Digest, Keccak256};
+
+// A transcript a Solidity verifier can replay with one keccak256 per step. The state is a single bytes32:
+//   append(data)  state = keccak256(abi.encodePacked(state, data))
+//   challenge     state = keccak256(abi.encodePacked(state)), and the challenge is the new state
+// and a field challenge is uint256(challenge) % p, reading the hash big endian like the EVM does.
+//
+// HashTranscript keeps every appended byte in a running hasher instead, which a contract could only mirror by
+// copying the whole transcript into memory for every challenge. Field elements go in as the 32 byte big endian
+// words that field_to_bytes writes for a 254 bit field, the same words abi.encode gives a uint256
+pub struct EvmTranscript {
+    state: [u8; 32],
+}
+
+impl FiatShamirTranscriptInterface for EvmTranscript {
+    fn new() -> Self {
+        Self { state: [0; 32] }
+    }
+
+    fn append(&mut self, incoming_data: &[u8]) {
+        let mut hasher = Keccak256::new();
+        hasher.update(self.state);
+        hasher.update(incoming_data);
+        self.state = hasher.finalize().into();
+    }
+
+    fn sample_random_challenge(&mut self) -> [u8; 32] {
+        self.state = Keccak256::digest(self.state).into();
+        self.state
+    }
+
+    fn random_challenge_as_field_element<F: PrimeField>(&mut self) -> F {
+        F::from_be_bytes_mod_order(&self.sample_random_challenge())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::BigInteger;
+
+    #[test]
+    fn test_state_is_a_keccak_chain() {
+        let mut transcript = EvmTranscript::new();
+        transcript.append(b"boy");
+
+        // the steps a contract takes
+        let appended = Keccak256::digest([[0u8; 32].as_slice(), b"boy"].concat());
+        let challenge = Keccak256::digest(appended);
+        assert_eq!(transcript.sample_random_challenge().as_slice(), challenge.as_slice());
+
+        let next: Fr = transcript.random_challenge_as_field_element();
+        let word = Keccak256::digest(challenge);
+        assert_eq!(next, Fr::from_be_bytes_mod_order(&word));
+        assert_eq!(next.into_bigint().to_bytes_be().len(), 32);
+    }
+}
@@ -0,0 +1,307 @@
+use sha3::digest::consts::U32;
+use sha3::digest::OutputSizeUser;
+use sha3::{Keccak256, Digest};
+use ark_ff::PrimeField;
+use ark_std::rand::RngCore;
+use core::num::NonZeroUsize;
+use crate::rng::TranscriptRng;
+
+// A transcript over any hash with a 32 byte output, e.g. HashTranscript<sha3::Sha3_256>.
+// The hasher only has to implement Digest, so picking one that builds without std keeps the transcript no_std
+pub struct HashTranscript<H: Digest + OutputSizeUser<OutputSize = U32> + Clone> {
+    hasher: H,
+}
+
+// The transcript every protocol in the repository uses
+pub type Transcript = HashTranscript<Keccak256>;
+
+// How a challenge in a prime field is read out of the transcript's 32 byte outputs.
+//
+// Reducing k uniform bytes mod p leaves the values below 2^(8k) mod p one preimage more likely than the rest, a
+// statistical distance from uniform of at most p / 2^(8k). random_challenge_as_field_element reduces 32 bytes, where
+// for BN254's 254 bit fields that bound is about 1/5: harmless for a sumcheck, whose soundness only needs the
+// challenge to be unpredictable, but not a uniform element. WideReduction with 16 bytes more than the field's brings
+// it below 2^-128. Rejection takes as many bits as the modulus has and draws again until they are below it, exactly
+// uniform at the cost of a variable number of hashes, at most two on average whatever the field.
+// WideReduction with fewer bytes than the field's gives short challenges, below 2^(8k), for when the protocol only
+// needs 8k bits of soundness from them. A challenge of no bytes would be the constant 0, so bytes is a NonZeroUsize
+// and such a sampling cannot be built, see wide_reduction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeSampling {
+    WideReduction { bytes: NonZeroUsize },
+    Rejection,
+}
+
+impl ChallengeSampling {
+    // WideReduction over the given number of bytes, None for 0
+    pub const fn wide_reduction(bytes: usize) -> Option<Self> {
+        match NonZeroUsize::new(bytes) {
+            Some(bytes) => Some(ChallengeSampling::WideReduction { bytes }),
+            None => None,
+        }
+    }
+}
+
+impl Default for ChallengeSampling {
+    // 64 bytes, within 2^-128 of uniform for fields of up to 384 bits
+    fn default() -> Self {
+        ChallengeSampling::WideReduction { bytes: NonZeroUsize::new(64).expect("64 is not zero") }
+    }
+}
+
+pub trait FiatShamirTranscriptInterface {
+    fn new() -> Self;
+    fn append(&mut self, incoming_data: &[u8]);
+    fn sample_random_challenge(&mut self) -> [u8; 32];
+    fn random_challenge_as_field_element<F: PrimeField>(&mut self) -> F;
+
+    // A challenge in F sampled with the default strategy, see ChallengeSampling
+    fn challenge_scalar<F: PrimeField>(&mut self) -> F
+    where
+        Self: Sized,
+    {
+        self.challenge_scalar_with(ChallengeSampling::default())
+    }
+
+    // A challenge in F sampled with the given strategy. Both squeeze as many 32 byte outputs as they need, so
+    // WideReduction { bytes: 32 } is random_challenge_as_field_element of the hash transcript
+    fn challenge_scalar_with<F: PrimeField>(&mut self, sampling: ChallengeSampling) -> F
+    where
+        Self: Sized,
+    {
+        match sampling {
+            ChallengeSampling::WideReduction { bytes } => {
+                // the little endian number of all the bytes, 32 at a time: chunk i weighs 2^(256 i)
+                let shift = F::from(2u64).pow([256]);
+                let (mut value, mut weight) = (F::zero(), F::one());
+                let mut remaining = bytes.get();
+                while remaining > 0 {
+                    let chunk = self.sample_random_challenge();
+                    let taken = remaining.min(32);
+                    value += F::from_le_bytes_mod_order(&chunk[..taken]) * weight;
+                    weight *= shift;
+                    remaining -= taken;
+                }
+                value
+            }
+            ChallengeSampling::Rejection => {
+                let bits = F::MODULUS_BIT_SIZE as usize;
+                let no_of_limbs = bits.div_ceil(64);
+                loop {
+                    let mut candidate = F::BigInt::default();
+                    let limbs = candidate.as_mut();
+                    let mut chunk = [0u8; 32];
+                    for (i, limb) in limbs.iter_mut().take(no_of_limbs).enumerate() {
+                        if i.is_multiple_of(4) {
+                            chunk = self.sample_random_challenge();
+                        }
+                        let mut limb_bytes = [0u8; 8];
+                        limb_bytes.copy_from_slice(&chunk[8 * (i % 4)..8 * (i % 4) + 8]);
+                        *limb = u64::from_le_bytes(limb_bytes);
+                    }
+                    if !bits.is_multiple_of(64) {
+                        limbs[no_of_limbs - 1] &= (1u64 << (bits % 64)) - 1;
+                    }
+
+                    // None when the candidate is not below the modulus
+                    if let Some(challenge) = F::from_bigint(candidate) {
+                        return challenge;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Starts a transcript bound to one protocol instance: label names the protocol and statement is everything the
+    /// proof is about, e.g. a circuit digest, the public inputs or an SRS hash. A proof made under one statement
+    /// draws different challenges under any other, so it cannot be replayed there.
+    /// Both are length prefixed, so moving bytes from the label into the statement changes the transcript too
+    fn new_with_statement(label: &[u8], statement: &[u8]) -> Self
+    where
+        Self: Sized,
+    {
+        let mut transcript = Self::new();
+        transcript.append(&(label.len() as u64).to_be_bytes());
+        transcript.append(label);
+        transcript.append(&(statement.len() as u64).to_be_bytes());
+        transcript.append(statement);
+        transcript
+    }
+}
+
+impl <H: Digest + OutputSizeUser<OutputSize = U32> + Clone>HashTranscript<H> {
+    /// A CSPRNG seeded from everything absorbed so far and 32 bytes drawn from rng, for the prover's blinding
+    /// factors and nonces, see TranscriptRng. The transcript itself is left as it is
+    pub fn rng<R: RngCore>(&self, rng: &mut R) -> TranscriptRng<H> {
+        TranscriptRng::new(self.hasher.clone(), rng)
+    }
+}
+
+impl <H: Digest + OutputSizeUser<OutputSize = U32> + Clone>FiatShamirTranscriptInterface for HashTranscript<H> {
+    /// This function uses the new() associated function from the hasher (Keccak256 by default) to create a hash function which starts an initial state.
+    /// It is in this initial state that the append() function will append data to.
+    fn new() -> Self {
+        Self {
+            hasher: H::new()
+        }
+    }
+
+    /// Takes in incoming_data as argument and uses the update() method from the hasher to incrementally update the state with the incoming_data
+    /// The new data (incoming_data) is appended to the existing data in the state, and a new state is computed based on the combined data
+    /// Note: The order of appending/updating/absorbing data matters: Hash(data1, data2) is different from Hash(data2, data1)
+    /// incoming_data => sum of polynomial evaluated values (y_values) |OR| univariate polynomial in evaluated form
+    fn append(&mut self, incoming_data: &[u8]) {
+        self.hasher.update(incoming_data);
+    }
+
+
+    /// Generates and returns 32-byte random challenge by calling finalize_reset()
+    /// Random challenge generated by computing current state of appended data
+    /// Resets the state, then it appends the computed hash back to the hash function state so that future operation rounds can build on it
+    fn sample_random_challenge(&mut self) -> [u8; 32] {
+        let mut output_hash = [0; 32]; // fixed sized array of 32-bytes initially filled with zeros
+        output_hash.copy_from_slice(&self.hasher.clone().finalize());
+
+        self.hasher.update(output_hash);
+
+        output_hash
+    }
+
+    fn random_challenge_as_field_element<F: PrimeField>(&mut self) -> F {
+        let random_challenge = self.sample_random_challenge();
+
+        // convert bytes into field element using: from_bytes_mod_order()
+        F::from_le_bytes_mod_order(&random_challenge)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ark_bn254::Fq;
+    use ark_ff::BigInteger;
+    use ark_ff::fields::{Fp64, MontBackend, MontConfig};
+
+    // a field of 193 elements, where 256 mod p = 63 makes the bias of reducing one byte easy to see
+    #[derive(MontConfig)]
+    #[modulus = "193"]
+    #[generator = "5"]
+    pub struct SmallConfig;
+    type Small = Fp64<MontBackend<SmallConfig, 1>>;
+
+    // the fraction of many challenges that fall below 63, 63 / 193 ~ 0.326 for uniform ones
+    fn fraction_below_63(sampling: ChallengeSampling) -> f64 {
+        let mut transcript = Transcript::new_with_statement(b"uniformity", b"");
+        let samples = 20_000;
+        let below = (0..samples)
+            .filter(|_| transcript.challenge_scalar_with::<Small>(sampling).into_bigint().0[0] < 63)
+            .count();
+        below as f64 / samples as f64
+    }
+
+    #[test]
+    fn test_hash() {
+        let mut transcript = Transcript::new();
+        transcript.append("boy".as_bytes());
+
+        let random_challenge_vec = transcript.sample_random_challenge();
+        let random_challenge: Fq = transcript.random_challenge_as_field_element();
+
+        // Run this test using: cargo test -- --nocapture
+        dbg!(random_challenge_vec);
+        dbg!(random_challenge);
+    }
+
+    #[test]
+    fn test_statement_separates_transcripts() {
+        let challenge = |label: &[u8], statement: &[u8]| Transcript::new_with_statement(label, statement).sample_random_challenge();
+
+        assert_eq!(challenge(b"sumcheck", b"instance 1"), challenge(b"sumcheck", b"instance 1"));
+        assert_ne!(challenge(b"sumcheck", b"instance 1"), challenge(b"sumcheck", b"instance 2"));
+        assert_ne!(challenge(b"sumcheck", b"instance 1"), challenge(b"gkr", b"instance 1"));
+        // the length prefixes keep the boundary between label and statement
+        assert_ne!(challenge(b"sumcheck ", b"instance 1"), challenge(b"sumcheck", b" instance 1"));
+        assert_ne!(challenge(b"", b""), Transcript::new().sample_random_challenge());
+    }
+
+    #[test]
+    fn test_selectable_hasher() {
+        let mut keccak = Transcript::new();
+        let mut sha3 = HashTranscript::<sha3::Sha3_256>::new();
+        keccak.append(b"boy");
+        sha3.append(b"boy");
+
+        let mut again = HashTranscript::<sha3::Sha3_256>::new();
+        again.append(b"boy");
+
+        // the same data gives the same challenge under one hasher and a different one under another
+        let challenge = sha3.sample_random_challenge();
+        assert_eq!(challenge, again.sample_random_challenge());
+        assert_ne!(challenge, keccak.sample_random_challenge());
+    }
+
+    #[test]
+    fn test_wide_reduction_is_the_number_of_the_bytes() {
+        let mut plain = Transcript::new();
+        let mut wide = Transcript::new();
+        plain.append(b"boy");
+        wide.append(b"boy");
+        assert_eq!(wide.challenge_scalar_with::<Fq>(ChallengeSampling::wide_reduction(32).unwrap()), plain.random_challenge_as_field_element::<Fq>());
+
+        // 64 bytes are two outputs read as one little endian number
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&plain.sample_random_challenge());
+        bytes[32..].copy_from_slice(&plain.sample_random_challenge());
+        assert_eq!(wide.challenge_scalar::<Fq>(), Fq::from_le_bytes_mod_order(&bytes));
+
+        // short challenges stay below 2^(8 * bytes)
+        let short: Fq = wide.challenge_scalar_with(ChallengeSampling::wide_reduction(2).unwrap());
+        assert!(short.into_bigint() < Fq::from(1u64 << 16).into_bigint());
+        // and a challenge of no bytes is not a sampling at all
+        assert_eq!(ChallengeSampling::wide_reduction(0), None);
+    }
+
+    #[test]
+    fn test_challenge_uniformity_bounds() {
+        let uniform = 63.0 / 193.0;
+
+        // one byte reduced mod 193 hits 0..63 twice as often: 126 / 256 ~ 0.49
+        assert!(fraction_below_63(ChallengeSampling::wide_reduction(1).unwrap()) > 0.45);
+        for sampling in [ChallengeSampling::wide_reduction(16).unwrap(), ChallengeSampling::Rejection] {
+            assert!((fraction_below_63(sampling) - uniform).abs() < 0.02, "{:?} is biased", sampling);
+        }
+
+        // over all 193 values at once: with 20000 draws a chi-square statistic above 267, p < 0.001 at 192 degrees of
+        // freedom, means some value comes up too often or too rarely
+        let mut transcript = Transcript::new_with_statement(b"uniformity", b"");
+        let mut counts = [0u32; 193];
+        for _ in 0..20_000 {
+            counts[transcript.challenge_scalar_with::<Small>(ChallengeSampling::Rejection).into_bigint().0[0] as usize] += 1;
+        }
+        let expected = 20_000.0 / 193.0;
+        let chi_square: f64 = counts.iter().map(|count| (*count as f64 - expected).powi(2) / expected).sum();
+        assert!(chi_square < 267.0, "chi-square {}", chi_square);
+    }
+
+    #[test]
+    fn test_rejection_keeps_the_first_candidate_below_the_modulus() {
+        // over BN254 rejection draws 254 bits, the low 254 of one 32 byte output read little endian, and returns the
+        // first such number below the modulus, drawing again otherwise
+        let mut sampled = Transcript::new();
+        let mut expected = Transcript::new();
+        sampled.append(b"boy");
+        expected.append(b"boy");
+
+        for _ in 0..20 {
+            let value = loop {
+                let mut bytes = expected.sample_random_challenge();
+                bytes[31] &= 0x3f;
+                let value = Fq::from_le_bytes_mod_order(&bytes);
+                if value.into_bigint().to_bytes_le() == bytes {
+                    break value;
+                }
+            };
+            assert_eq!(sampled.challenge_scalar_with::<Fq>(ChallengeSampling::Rejection), value);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+// Builds without std when the default std feature is off, the transcript only hashes bytes
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+// encoding rather than field_utils, the name univariate_poly already gives its batch inversion helpers
+pub mod encoding;
+pub mod fiat_shamir;
+pub mod evm;
+pub mod rng;
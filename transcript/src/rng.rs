@@ -0,0 +1,123 @@
+use core::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Error, RngCore};
+use sha3::digest::consts::U32;
+use sha3::digest::OutputSizeUser;
+use sha3::Digest;
+
+// Prover randomness bound to the transcript, Merlin's transcript RNG.
+//
+// A prover that draws its blinding factors from its own RNG alone trusts that RNG completely: a weak or backdoored
+// one can pick blinding that correlates with the challenges to come and leaks the witness through the proof. The
+// seed here is the hash of everything the transcript absorbed so far together with 32 bytes of local entropy, so the
+// output is unpredictable as long as either the entropy is good or the statement is one the adversary did not see
+// coming, and two proofs of different statements never share blinding even under a repeating RNG.
+//
+// The output stream is H(seed || 0), H(seed || 1), ... in counter mode. Deriving it does not touch the transcript, so
+// prover and verifier stay in step
+pub struct TranscriptRng<H: Digest + OutputSizeUser<OutputSize = U32>> {
+    seed: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    position: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl <H: Digest + OutputSizeUser<OutputSize = U32>>TranscriptRng<H> {
+    // transcript_state is a copy of the transcript's hasher, absorbed up to the point the randomness is needed
+    pub(crate) fn new<R: RngCore>(mut transcript_state: H, rng: &mut R) -> Self {
+        let mut entropy = [0u8; 32];
+        rng.fill_bytes(&mut entropy);
+
+        transcript_state.update(b"transcript rng");
+        transcript_state.update(entropy);
+
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&transcript_state.finalize());
+
+        Self { seed, counter: 0, block: [0; 32], position: 32, _hasher: PhantomData }
+    }
+
+    fn next_block(&mut self) {
+        let mut hasher = H::new();
+        hasher.update(self.seed);
+        hasher.update(self.counter.to_le_bytes());
+        self.block.copy_from_slice(&hasher.finalize());
+
+        self.counter += 1;
+        self.position = 0;
+    }
+}
+
+impl <H: Digest + OutputSizeUser<OutputSize = U32>>RngCore for TranscriptRng<H> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest.iter_mut() {
+            if self.position == self.block.len() {
+                self.next_block();
+            }
+            *byte = self.block[self.position];
+            self.position += 1;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl <H: Digest + OutputSizeUser<OutputSize = U32>>CryptoRng for TranscriptRng<H> {}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+    use ark_std::rand::rngs::StdRng;
+    use ark_std::rand::{RngCore, SeedableRng};
+
+    fn output(transcript: &Transcript, entropy_seed: u64) -> [u8; 80] {
+        let mut bytes = [0u8; 80];
+        transcript.rng(&mut StdRng::seed_from_u64(entropy_seed)).fill_bytes(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn test_rng_depends_on_transcript_and_entropy() {
+        let mut transcript = Transcript::new();
+        transcript.append(b"statement 1");
+        let mut other = Transcript::new();
+        other.append(b"statement 2");
+
+        assert_eq!(output(&transcript, 1), output(&transcript, 1));
+        assert_ne!(output(&transcript, 1), output(&transcript, 2));
+        // the same local entropy gives unrelated output under another statement
+        assert_ne!(output(&transcript, 1), output(&other, 1));
+
+        // blocks do not repeat across the counter
+        let bytes = output(&transcript, 1);
+        assert_ne!(bytes[..32], bytes[32..64]);
+    }
+
+    #[test]
+    fn test_rng_leaves_the_transcript_alone() {
+        let mut transcript = Transcript::new();
+        transcript.append(b"statement");
+        let mut untouched = Transcript::new();
+        untouched.append(b"statement");
+
+        transcript.rng(&mut StdRng::seed_from_u64(1)).next_u64();
+
+        assert_eq!(transcript.sample_random_challenge(), untouched.sample_random_challenge());
+    }
+}
@@ -0,0 +1,144 @@
+use ark_bn254::{Fq, Fr};
+use multilinear::multilinear::MultilinearPolynomial;
+use serde_json::{json, Value};
+use shamir_secret::shamir::{generate_byte_shares_with_rng, reconstruct_bytes, ByteShare};
+use shamir_secret::share::{decode_hex, encode_hex, SecretBytesShare};
+use sumcheck::composed::{append_field_elements, ComposedSumcheckProof};
+use sumcheck::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+use sumcheck::proof_format::{field_element_from_json, field_element_to_json, ProofFormat};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+// Browser bindings for the training material: a sumcheck prover and verifier over BN254 Fr and Shamir split and
+// combine over Fq, the field the shamir CLI uses so share files move between the two.
+//
+// Everything crosses the boundary as strings or bytes. Field elements are the 0x prefixed hex of the proof format,
+// and a sumcheck proof is
+//   {"claimed_sum": "0x..", "evaluations": ["0x..", ...], "proof": <ComposedSumcheckProof::to_json>}
+// The polynomial travels with the proof so verify_sumcheck can do the final oracle check itself.
+// Errors come back as messages, which the bindings turn into thrown JavaScript exceptions
+
+// Proves the sum over the hypercube of the multilinear polynomial with the given evaluations, a JSON array of
+// hex strings or unsigned integers whose length is a power of two
+#[wasm_bindgen]
+pub fn prove_sumcheck(evals_json: &str) -> Result<String, String> {
+    let value: Value = serde_json::from_str(evals_json).map_err(|e| e.to_string())?;
+    let evaluations = parse_evaluations(&value)?;
+    let claimed_sum: Fr = evaluations.iter().sum();
+
+    let mut transcript = bind_claim(&evaluations, claimed_sum);
+    let polynomial = MultilinearPolynomial::new(&evaluations);
+    let (proof, _, _) =
+        ComposedSumcheckProof::prove(&[polynomial], 1, |values: &[Fr]| values[0], &mut transcript).map_err(|e| e.to_string())?;
+
+    let proof: Value = serde_json::from_str(&proof.to_json()).map_err(|e| e.to_string())?;
+    let evaluations: Vec<Value> = evaluations.into_iter().map(field_element_to_json).collect();
+    Ok(json!({ "claimed_sum": field_element_to_json(claimed_sum), "evaluations": evaluations, "proof": proof }).to_string())
+}
+
+// Checks a proof made by prove_sumcheck. Returns whether it verifies, or an error if it is not a proof at all
+#[wasm_bindgen]
+pub fn verify_sumcheck(proof_json: &str) -> Result<bool, String> {
+    let value: Value = serde_json::from_str(proof_json).map_err(|e| e.to_string())?;
+    let evaluations = parse_evaluations(&value["evaluations"])?;
+    let claimed_sum: Fr = field_element_from_json(&value["claimed_sum"]).ok_or("claimed_sum is not a field element")?;
+    let proof = ComposedSumcheckProof::<Fr>::from_json(&value["proof"].to_string()).ok_or("proof is not a sumcheck proof")?;
+
+    let polynomial = MultilinearPolynomial::new(&evaluations);
+    let mut transcript = bind_claim(&evaluations, claimed_sum);
+
+    Ok(match proof.verify(claimed_sum, polynomial.no_of_vars, 1, &mut transcript) {
+        Some((point, final_claim)) => polynomial.evaluate(&point) == final_claim,
+        None => false,
+    })
+}
+
+// Splits secret into total_shares shares, any threshold of which recover it together with the password.
+// Returns a JSON array of the shares in the hex format of the shamir CLI share files
+#[wasm_bindgen]
+pub fn shamir_split(secret: &[u8], password: i32, threshold: usize, total_shares: usize) -> Result<String, String> {
+    let shares = generate_byte_shares_with_rng::<Fq, _>(secret, password, threshold, total_shares, &mut rand::rngs::OsRng)
+        .map_err(|e| e.to_string())?;
+
+    let shares = shares
+        .into_iter()
+        .map(|share| SecretBytesShare::new(share, threshold).to_bytes().map(|bytes| encode_hex(&bytes)))
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(json!(shares).to_string())
+}
+
+// Recovers the secret from a JSON array of hex shares made by shamir_split or the shamir CLI
+#[wasm_bindgen]
+pub fn shamir_combine(shares_json: &str, password: i32) -> Result<Vec<u8>, String> {
+    let hexes: Vec<String> = serde_json::from_str(shares_json).map_err(|e| e.to_string())?;
+    let shares = hexes
+        .iter()
+        .map(|hex| SecretBytesShare::<Fq>::from_bytes(&decode_hex(hex)?))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let threshold = shares.first().ok_or("no shares")?.threshold;
+    let byte_shares: Vec<ByteShare<Fq>> = shares.iter().map(|share| share.share.clone()).collect();
+    reconstruct_bytes(&byte_shares, password, threshold).map_err(|e| e.to_string())
+}
+
+fn parse_evaluations(value: &Value) -> Result<Vec<Fr>, String> {
+    let evaluations = value
+        .as_array()
+        .ok_or("evaluations must be a JSON array")?
+        .iter()
+        .map(|element| match element.as_u64() {
+            Some(integer) => Some(Fr::from(integer)),
+            None => field_element_from_json(element),
+        })
+        .collect::<Option<Vec<Fr>>>()
+        .ok_or("evaluations must be hex field elements or unsigned integers")?;
+
+    if evaluations.len() < 2 || !evaluations.len().is_power_of_two() {
+        return Err(format!("{} evaluations, a power of two of at least 2 is needed", evaluations.len()));
+    }
+    Ok(evaluations)
+}
+
+// The proof is about this polynomial and sum, both go into the transcript before the first round
+fn bind_claim(evaluations: &[Fr], claimed_sum: Fr) -> Transcript {
+    let mut transcript = Transcript::new();
+    append_field_elements(&mut transcript, evaluations);
+    append_field_elements(&mut transcript, &[claimed_sum]);
+    transcript
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sumcheck_round_trip() {
+        let proof = prove_sumcheck("[0, 0, 0, 3, 0, 0, 2, 5]").unwrap();
+        assert_eq!(verify_sumcheck(&proof), Ok(true));
+
+        let mut value: Value = serde_json::from_str(&proof).unwrap();
+        assert_eq!(value["claimed_sum"], field_element_to_json(Fr::from(10)));
+
+        // the claimed sum and the polynomial are bound to the transcript, changing either fails
+        value["claimed_sum"] = field_element_to_json(Fr::from(11));
+        assert_eq!(verify_sumcheck(&value.to_string()), Ok(false));
+        let mut value: Value = serde_json::from_str(&proof).unwrap();
+        value["evaluations"][0] = field_element_to_json(Fr::from(1));
+        assert_eq!(verify_sumcheck(&value.to_string()), Ok(false));
+
+        assert!(prove_sumcheck("[1, 2, 3]").is_err());
+        assert!(verify_sumcheck("{}").is_err());
+    }
+
+    #[test]
+    fn test_shamir_round_trip() {
+        let shares = shamir_split(b"playground secret", 7, 2, 3).unwrap();
+        let hexes: Vec<String> = serde_json::from_str(&shares).unwrap();
+
+        assert_eq!(shamir_combine(&json!(hexes[1..]).to_string(), 7), Ok(b"playground secret".to_vec()));
+        assert!(shamir_combine(&json!(hexes[..1]).to_string(), 7).is_err());
+        assert!(shamir_split(b"secret", 0, 4, 3).is_err());
+    }
+}
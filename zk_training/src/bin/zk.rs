@@ -0,0 +1,313 @@
+use ark_bn254::Fr;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use zk_training::circuit::{Circuit, Gate, Layer, Operator};
+use zk_training::polynomial::multilinear::multilinear::MultilinearPolynomial;
+use zk_training::polynomial::univariate_poly::univariate::UnivariatePoly;
+use zk_training::sumcheck::composed::{append_field_elements, ComposedSumcheckProof};
+use zk_training::sumcheck::debug::SumcheckReport;
+use zk_training::sumcheck::proof_format::{field_element_from_json, field_element_to_json, ProofFormat};
+use zk_training::transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+
+// The protocol stack from the command line, everything over BN254 Fr.
+//
+// Field elements in files are JSON numbers (unsigned 64 bit) or the 0x prefixed big endian hex of the proof format.
+// A circuit file lists its layers from the inputs up, every gate writing one wire of its layer from two of the layer
+// below:
+//   {"layers": [[{"op": "add", "left": 0, "right": 1, "output": 0}, ...], ...]}
+// with "add", "mul" or "affine" gates, an affine gate also listing its constants: "constants": [a, b, c] for
+// a * left + b * right + c. An optional "input_constants": [...] declares the constants region of the input layer,
+// wires 0..k ahead of the inputs from the input file.
+// An input or evaluation file is a JSON array of field elements. Sumcheck proofs are ComposedSumcheckProof in the
+// proof format, JSON or binary. Univariate polynomials are written like 4x^3 + 8x^2 + 2
+#[derive(Parser)]
+#[command(name = "zk", about = "Circuits and sumcheck proofs from files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Layered arithmetic circuits
+    #[command(subcommand)]
+    Circuit(CircuitCommand),
+    /// Sumcheck over the hypercube of a multilinear polynomial
+    #[command(subcommand)]
+    Sumcheck(SumcheckCommand),
+    /// Univariate polynomials written like "4x^3 + 8x^2 + 2"
+    #[command(subcommand)]
+    Poly(PolyCommand),
+}
+
+#[derive(Subcommand)]
+enum PolyCommand {
+    /// Evaluate a polynomial at a point
+    Eval {
+        poly: String,
+        /// A decimal number or 0x prefixed hex
+        #[arg(long)]
+        at: String,
+    },
+    /// Multiply polynomials
+    Mul {
+        #[arg(num_args = 2.., required = true)]
+        polys: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CircuitCommand {
+    /// Evaluate a circuit, printing the values of every layer as JSON, output layer first
+    Eval {
+        #[arg(long)]
+        circuit: PathBuf,
+        #[arg(long)]
+        input: PathBuf,
+        /// Print every gate with the values it read and wrote instead
+        #[arg(long)]
+        report: bool,
+        /// Also write every gate as a row of a CSV table to this file
+        #[arg(long)]
+        csv: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SumcheckCommand {
+    /// Prove the sum of a polynomial given by its evaluations, printing the sum
+    Prove {
+        /// JSON array of the evaluations, a power of two of them
+        #[arg(long)]
+        evaluations: PathBuf,
+        #[arg(long)]
+        output: PathBuf,
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+    },
+    /// Verify a proof that the polynomial sums to claimed_sum, exiting with failure if it does not
+    Verify {
+        #[arg(long)]
+        evaluations: PathBuf,
+        /// The proof in either format, told apart by its first byte
+        #[arg(long)]
+        proof: PathBuf,
+        /// A decimal number or 0x prefixed hex
+        #[arg(long)]
+        claimed_sum: String,
+    },
+    /// Print a proof round by round with the verifier's checks, to see where a rejected one goes wrong
+    Inspect {
+        #[arg(long)]
+        evaluations: PathBuf,
+        #[arg(long)]
+        proof: PathBuf,
+        /// A decimal number or 0x prefixed hex
+        #[arg(long)]
+        claimed_sum: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Binary,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Ok(false) is a proof that was read but does not verify
+fn run(command: Command) -> Result<bool, String> {
+    match command {
+        Command::Circuit(CircuitCommand::Eval { circuit, input, report, csv }) => {
+            let mut circuit = parse_circuit(&read_json(&circuit)?).map_err(|e| format!("{}: {}", circuit.display(), e))?;
+            let input = read_field_elements(&input)?;
+
+            if let Some(csv) = csv {
+                let table = circuit.evaluate_with_report(input.clone()).map_err(|e| e.to_string())?.to_csv();
+                fs::write(&csv, table).map_err(|e| format!("writing {}: {}", csv.display(), e))?;
+            }
+
+            if report {
+                println!("{}", circuit.evaluate_with_report(input).map_err(|e| e.to_string())?);
+                return Ok(true);
+            }
+
+            circuit.evaluate(input).map_err(|e| e.to_string())?;
+            let layers: Vec<Value> = circuit
+                .round_poly
+                .iter()
+                .map(|values| Value::Array(values.iter().copied().map(field_element_to_json).collect()))
+                .collect();
+            println!("{}", Value::Array(layers));
+            Ok(true)
+        }
+        Command::Sumcheck(SumcheckCommand::Prove { evaluations, output, format }) => {
+            let evaluations = read_evaluations(&evaluations)?;
+            let claimed_sum: Fr = evaluations.iter().sum();
+
+            let polynomial = MultilinearPolynomial::new(&evaluations);
+            let mut transcript = bind_claim(&evaluations, claimed_sum);
+            let (proof, _, _) = ComposedSumcheckProof::prove(&[polynomial], 1, |values: &[Fr]| values[0], &mut transcript)
+                .map_err(|e| e.to_string())?;
+
+            let bytes = match format {
+                Format::Json => proof.to_json().into_bytes(),
+                Format::Binary => proof.to_bytes(),
+            };
+            fs::write(&output, bytes).map_err(|e| format!("writing {}: {}", output.display(), e))?;
+            // printed the way --claimed-sum of verify reads it back
+            println!("{}", field_element_to_json(claimed_sum).as_str().expect("field elements are hex strings"));
+            Ok(true)
+        }
+        Command::Poly(PolyCommand::Eval { poly, at }) => {
+            let poly = parse_poly(&poly)?;
+            let at = parse_field_element(&at).ok_or("the point is not a field element")?;
+            println!("{}", poly.evaluate(at));
+            Ok(true)
+        }
+        Command::Poly(PolyCommand::Mul { polys }) => {
+            let product = polys
+                .iter()
+                .map(|poly| parse_poly(poly))
+                .try_fold(UnivariatePoly::new(vec![Fr::from(1)]), |product, poly| poly.map(|poly| &product * &poly))?;
+            println!("{}", product);
+            Ok(true)
+        }
+        Command::Sumcheck(SumcheckCommand::Verify { evaluations, proof, claimed_sum }) => {
+            let evaluations = read_evaluations(&evaluations)?;
+            let claimed_sum = parse_field_element(&claimed_sum).ok_or("claimed sum is not a field element")?;
+            let bytes = fs::read(&proof).map_err(|e| format!("reading {}: {}", proof.display(), e))?;
+            let proof = read_proof(&bytes).ok_or_else(|| format!("{} is not a sumcheck proof", proof.display()))?;
+
+            let polynomial = MultilinearPolynomial::new(&evaluations);
+            let mut transcript = bind_claim(&evaluations, claimed_sum);
+            let verified = match proof.verify(claimed_sum, polynomial.no_of_vars, 1, &mut transcript) {
+                Some((point, final_claim)) => polynomial.evaluate(&point) == final_claim,
+                None => false,
+            };
+            println!("{}", if verified { "verified" } else { "rejected" });
+            Ok(verified)
+        }
+        Command::Sumcheck(SumcheckCommand::Inspect { evaluations, proof, claimed_sum }) => {
+            let evaluations = read_evaluations(&evaluations)?;
+            let claimed_sum = parse_field_element(&claimed_sum).ok_or("claimed sum is not a field element")?;
+            let bytes = fs::read(&proof).map_err(|e| format!("reading {}: {}", proof.display(), e))?;
+            let proof = read_proof(&bytes).ok_or_else(|| format!("{} is not a sumcheck proof", proof.display()))?;
+
+            let polynomial = MultilinearPolynomial::new(&evaluations);
+            let mut transcript = bind_claim(&evaluations, claimed_sum);
+            let mut report = SumcheckReport::inspect_composed(&proof, claimed_sum, polynomial.no_of_vars, 1, &mut transcript);
+            if report.rounds.len() == polynomial.no_of_vars {
+                let final_evaluation = polynomial.evaluate(&report.challenges());
+                report = report.with_final_evaluation(final_evaluation);
+            }
+            println!("{}", report);
+            Ok(report.verified())
+        }
+    }
+}
+
+fn read_json(path: &Path) -> Result<Value, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("reading {}: {}", path.display(), e))?;
+    serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn read_field_elements(path: &Path) -> Result<Vec<Fr>, String> {
+    read_json(path)?
+        .as_array()
+        .and_then(|elements| elements.iter().map(json_field_element).collect())
+        .ok_or_else(|| format!("{}: expected a JSON array of field elements", path.display()))
+}
+
+fn read_evaluations(path: &Path) -> Result<Vec<Fr>, String> {
+    let evaluations = read_field_elements(path)?;
+    if evaluations.len() < 2 || !evaluations.len().is_power_of_two() {
+        return Err(format!("{}: {} evaluations, a power of two of at least 2 is needed", path.display(), evaluations.len()));
+    }
+    Ok(evaluations)
+}
+
+fn json_field_element(value: &Value) -> Option<Fr> {
+    match value.as_u64() {
+        Some(integer) => Some(Fr::from(integer)),
+        None => field_element_from_json(value),
+    }
+}
+
+fn parse_field_element(text: &str) -> Option<Fr> {
+    match text.parse::<u64>() {
+        Ok(integer) => Some(Fr::from(integer)),
+        Err(_) => field_element_from_json(&json!(text)),
+    }
+}
+
+fn parse_poly(text: &str) -> Result<UnivariatePoly<Fr>, String> {
+    text.parse().map_err(|e| format!("{}: {}", text, e))
+}
+
+// Both encodings of the proof format: a binary proof starts with its version byte, a JSON one with a brace
+fn read_proof(bytes: &[u8]) -> Option<ComposedSumcheckProof<Fr>> {
+    match bytes.first() {
+        Some(b'{') => ComposedSumcheckProof::from_json(std::str::from_utf8(bytes).ok()?),
+        _ => ComposedSumcheckProof::from_bytes(bytes),
+    }
+}
+
+fn parse_circuit(value: &Value) -> Result<Circuit<Fr>, String> {
+    let layers = value["layers"].as_array().ok_or("expected {\"layers\": [...]}")?;
+    let layers = layers
+        .iter()
+        .enumerate()
+        .map(|(layer_index, gates)| {
+            let gates = gates.as_array().ok_or(format!("layer {} is not an array of gates", layer_index))?;
+            let gates = gates
+                .iter()
+                .map(|gate| parse_gate(gate).ok_or(format!("layer {} has a malformed gate {}", layer_index, gate)))
+                .collect::<Result<Vec<Gate<Fr>>, String>>()?;
+            Ok(Layer::new(gates))
+        })
+        .collect::<Result<Vec<Layer<Fr>>, String>>()?;
+    let input_constants = match value.get("input_constants") {
+        Some(values) => values
+            .as_array()
+            .and_then(|values| values.iter().map(json_field_element).collect::<Option<Vec<Fr>>>())
+            .ok_or("input_constants is not an array of field elements")?,
+        None => Vec::new(),
+    };
+    Ok(Circuit::new(layers).with_input_constants(input_constants))
+}
+
+fn parse_gate(gate: &Value) -> Option<Gate<Fr>> {
+    let operator = match gate["op"].as_str()? {
+        "add" => Operator::Add,
+        "mul" => Operator::Mul,
+        "affine" => match gate["constants"].as_array()?.as_slice() {
+            [a, b, c] => Operator::affine(json_field_element(a)?, json_field_element(b)?, json_field_element(c)?),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let wire = |name: &str| gate[name].as_u64().and_then(|index| usize::try_from(index).ok());
+    Some(Gate::new(wire("left")?, wire("right")?, wire("output")?, operator))
+}
+
+// Same as the wasm bindings: the polynomial and the sum go into the transcript before the first round
+fn bind_claim(evaluations: &[Fr], claimed_sum: Fr) -> Transcript {
+    let mut transcript = Transcript::new();
+    append_field_elements(&mut transcript, evaluations);
+    append_field_elements(&mut transcript, &[claimed_sum]);
+    transcript
+}
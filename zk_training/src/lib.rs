@@ -0,0 +1,58 @@
+// One crate for the whole protocol stack, so a user can depend on zk_training instead of every crate of the workspace.
+// Each layer sits behind a feature, all of them on by default, and re-exports its crates whole:
+//   circuit     circuits: layered circuits, their R1CS and PLONK-ish exports and the Poseidon gadget
+//   polynomial  multilinear and univariate_poly: multilinear, virtual, binary field and Goldilocks polynomials,
+//               univariate polynomials, domains and Reed-Solomon codes
+//   transcript  transcript: the Fiat-Shamir transcripts, including the one a Solidity verifier replays, and the RNG
+//   sumcheck    sumcheck: sumcheck, grand product, permutation and lookup arguments and their proof format
+//   pcs         commitments: polynomial commitment schemes
+//   shamir      shamir_secret: Shamir secret sharing, verifiable sharing and distributed key generation
+// mmap, off by default, adds the disk backed tables of multilinear::disk.
+// There is no GKR crate yet; when it lands it goes behind its own feature next to sumcheck
+#[cfg(feature = "circuit")]
+pub use circuits as circuit;
+
+#[cfg(feature = "polynomial")]
+pub mod polynomial {
+    pub use multilinear;
+    pub use univariate_poly;
+}
+
+#[cfg(feature = "transcript")]
+pub use transcript;
+
+#[cfg(feature = "sumcheck")]
+pub use sumcheck;
+
+#[cfg(feature = "pcs")]
+pub use commitments as pcs;
+
+#[cfg(feature = "shamir")]
+pub use shamir_secret as shamir;
+
+
+#[cfg(all(test, feature = "circuit", feature = "sumcheck"))]
+mod tests {
+    use crate::circuit::{Circuit, Gate, Layer, Operator};
+    use crate::polynomial::multilinear::multilinear::MultilinearPolynomial;
+    use crate::sumcheck::composed::ComposedSumcheckProof;
+    use crate::transcript::fiat_shamir::{FiatShamirTranscriptInterface, Transcript};
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_stack_through_the_prelude() {
+        // (a + b) * (b * c) = 7 * 20
+        let layer1 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Add), Gate::new(1, 2, 1, Operator::Mul)]);
+        let layer2 = Layer::new(vec![Gate::new(0, 1, 0, Operator::Mul)]);
+        let mut circuit = Circuit::new(vec![layer1, layer2]);
+        assert_eq!(circuit.evaluate(vec![Fr::from(3), Fr::from(4), Fr::from(5)]), Ok(vec![Fr::from(140)]));
+
+        // the sum over the hypercube of the first layer's values, proven and checked with sumcheck
+        let values = circuit.get_round_poly(1).unwrap();
+        let poly = MultilinearPolynomial::new(&values);
+        let (proof, _, _) = ComposedSumcheckProof::prove(&[poly], 1, |values: &[Fr]| values[0], &mut Transcript::new()).unwrap();
+
+        assert!(proof.verify(Fr::from(27), 1, 1, &mut Transcript::new()).is_some());
+        assert!(proof.verify(Fr::from(28), 1, 1, &mut Transcript::new()).is_none());
+    }
+}